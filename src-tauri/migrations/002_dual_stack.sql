@@ -0,0 +1,9 @@
+-- 双栈域名: 一条域名配置同时维护 A 和 AAAA 记录
+ALTER TABLE domains ADD COLUMN dual_stack INTEGER NOT NULL DEFAULT 0; -- 0 or 1
+ALTER TABLE domains ADD COLUMN remove_aaaa_on_ipv6_loss INTEGER NOT NULL DEFAULT 0; -- 0 or 1
+ALTER TABLE domains ADD COLUMN current_ipv4 TEXT;
+ALTER TABLE domains ADD COLUMN current_ipv6 TEXT;
+
+-- 按记录类型迁移已有的当前 IP
+UPDATE domains SET current_ipv4 = current_ip WHERE record_type = 'A';
+UPDATE domains SET current_ipv6 = current_ip WHERE record_type = 'AAAA';
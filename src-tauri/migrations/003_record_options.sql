@@ -0,0 +1,4 @@
+-- 记录选项: 创建/更新记录时提交给提供商, NULL 表示使用提供商默认值
+ALTER TABLE domains ADD COLUMN ttl INTEGER;
+ALTER TABLE domains ADD COLUMN proxied INTEGER; -- 0 or 1
+ALTER TABLE domains ADD COLUMN comment TEXT;
@@ -1,4 +1,6 @@
 use crate::services::scheduler::{SchedulerService, SchedulerStatus};
+use crate::services::DNSUpdaterService;
+use crate::storage::secure_store::CredentialManager;
 use crate::storage::Database;
 use std::sync::Arc;
 use tokio::sync::RwLock;
@@ -7,13 +9,23 @@ use tokio::sync::RwLock;
 pub struct AppState {
     pub db: Arc<Database>,
     pub scheduler: Arc<RwLock<Option<SchedulerService>>>,
+    pub credentials: Arc<CredentialManager>,
+    /// 调度器、手动更新与区域编辑共用, 复用已初始化的提供商
+    pub dns_updater: Arc<DNSUpdaterService>,
 }
 
 impl AppState {
-    pub fn new(db: Database) -> Self {
+    pub fn new(db: Database, credentials: CredentialManager) -> Self {
+        let db = Arc::new(db);
+        let credentials = Arc::new(credentials);
         Self {
-            db: Arc::new(db),
+            dns_updater: Arc::new(DNSUpdaterService::new(
+                Arc::clone(&db),
+                Arc::clone(&credentials),
+            )),
+            db,
             scheduler: Arc::new(RwLock::new(None)),
+            credentials,
         }
     }
 
@@ -26,11 +38,13 @@ impl AppState {
             drop(scheduler_guard);
             self.stop_scheduler().await?;
             let mut scheduler_guard = self.scheduler.write().await;
-            let scheduler = SchedulerService::new(Arc::clone(&self.db));
+            let scheduler =
+                SchedulerService::new(Arc::clone(&self.db), Arc::clone(&self.dns_updater));
             scheduler.start().await.map_err(|e| e.to_string())?;
             *scheduler_guard = Some(scheduler);
         } else {
-            let scheduler = SchedulerService::new(Arc::clone(&self.db));
+            let scheduler =
+                SchedulerService::new(Arc::clone(&self.db), Arc::clone(&self.dns_updater));
             scheduler.start().await.map_err(|e| e.to_string())?;
             *scheduler_guard = Some(scheduler);
         }
@@ -0,0 +1,92 @@
+use crate::app_state::AppState;
+use crate::models::Credentials;
+use crate::providers::ProviderFactory;
+
+/// 保存提供商凭证 (指定 domain_id 时保存为域名专属凭证)
+#[tauri::command]
+pub async fn set_credentials(
+    state: tauri::State<'_, AppState>,
+    provider_id: String,
+    domain_id: Option<String>,
+    credentials: Credentials,
+) -> std::result::Result<(), String> {
+    // 确认提供商存在
+    ProviderFactory::create(&provider_id).map_err(|e| e.to_string())?;
+
+    match domain_id {
+        Some(domain_id) => {
+            state
+                .credentials
+                .store_credentials_for_domain(&provider_id, &domain_id, &credentials)
+        }
+        None => state
+            .credentials
+            .store_credentials(&provider_id, &credentials),
+    }
+    .map_err(|e| e.to_string())
+}
+
+/// 删除提供商凭证
+#[tauri::command]
+pub async fn delete_credentials(
+    state: tauri::State<'_, AppState>,
+    provider_id: String,
+    domain_id: Option<String>,
+) -> std::result::Result<(), String> {
+    match domain_id {
+        Some(domain_id) => state
+            .credentials
+            .delete_credentials_for_domain(&provider_id, &domain_id),
+        None => state.credentials.delete_credentials(&provider_id),
+    }
+    .map_err(|e| e.to_string())
+}
+
+/// 检查是否已保存凭证 (不返回凭证内容)
+#[tauri::command]
+pub async fn has_credentials(
+    state: tauri::State<'_, AppState>,
+    provider_id: String,
+    domain_id: Option<String>,
+) -> std::result::Result<bool, String> {
+    let credentials = match domain_id {
+        Some(domain_id) => state
+            .credentials
+            .get_credentials_for_domain(&provider_id, &domain_id),
+        None => state.credentials.get_credentials(&provider_id),
+    }
+    .map_err(|e| e.to_string())?;
+
+    Ok(credentials.is_some())
+}
+
+/// 测试凭证是否可用 (未传入凭证时测试已保存的凭证)
+#[tauri::command]
+pub async fn test_credentials(
+    state: tauri::State<'_, AppState>,
+    provider_id: String,
+    domain_id: Option<String>,
+    credentials: Option<Credentials>,
+) -> std::result::Result<bool, String> {
+    let credentials = match (credentials, domain_id) {
+        (Some(credentials), _) => credentials,
+        (None, Some(domain_id)) => state
+            .credentials
+            .get_credentials_for_domain(&provider_id, &domain_id)
+            .map_err(|e| e.to_string())?
+            .ok_or_else(|| "未找到域名凭证".to_string())?,
+        (None, None) => state
+            .credentials
+            .get_credentials(&provider_id)
+            .map_err(|e| e.to_string())?
+            .ok_or_else(|| "未找到提供商凭证".to_string())?,
+    };
+
+    let mut provider = ProviderFactory::create(&provider_id).map_err(|e| e.to_string())?;
+    provider
+        .initialize(&credentials.to_provider_credentials())
+        .await
+        .map_err(|e| e.to_string())?;
+
+    provider.test_connection().await.map_err(|e| e.to_string())
+}
@@ -1,5 +1,7 @@
 use crate::app_state::AppState;
+use crate::error::{AppError, Result};
 use crate::models::{CreateDomain, UpdateDomain};
+use crate::providers::{registry, RecordSpec};
 
 /// 获取所有域名
 #[tauri::command]
@@ -15,6 +17,14 @@ pub async fn add_domain(
     state: tauri::State<'_, AppState>,
     domain: CreateDomain,
 ) -> std::result::Result<crate::models::Domain, String> {
+    let spec = RecordSpec {
+        ttl: domain.ttl,
+        proxied: domain.proxied,
+        priority: None,
+        comment: domain.comment.clone(),
+    };
+    check_record_options(&domain.provider, &spec).map_err(|e| e.to_string())?;
+
     state
         .db
         .create_domain(domain)
@@ -29,6 +39,11 @@ pub async fn update_domain(
     id: String,
     updates: UpdateDomain,
 ) -> std::result::Result<crate::models::Domain, String> {
+    // 先在副本上应用更新, 校验合并后的记录选项
+    let mut preview = state.db.get_domain(&id).await.map_err(|e| e.to_string())?;
+    preview.apply(updates.clone());
+    check_record_options(&preview.provider, &preview.record_spec()).map_err(|e| e.to_string())?;
+
     state
         .db
         .update_domain(&id, updates)
@@ -42,5 +57,27 @@ pub async fn delete_domain(
     state: tauri::State<'_, AppState>,
     id: String,
 ) -> std::result::Result<(), String> {
-    state.db.delete_domain(&id).await.map_err(|e| e.to_string())
+    let domain = state.db.get_domain(&id).await.map_err(|e| e.to_string())?;
+    state
+        .db
+        .delete_domain(&id)
+        .await
+        .map_err(|e| e.to_string())?;
+
+    // 同时删除域名专属凭证
+    state
+        .credentials
+        .delete_credentials_for_domain(&domain.provider, &id)
+        .map_err(|e| e.to_string())
+}
+
+/// 校验记录选项是否被域名的提供商支持
+fn check_record_options(provider: &str, spec: &RecordSpec) -> Result<()> {
+    if spec.ttl == Some(0) {
+        return Err(AppError::Validation("TTL 必须大于 0".to_string()));
+    }
+    match registry::find(provider) {
+        Some(descriptor) => spec.ensure_supported(&descriptor.capabilities),
+        None => Ok(()),
+    }
 }
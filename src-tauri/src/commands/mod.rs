@@ -1,11 +1,17 @@
 pub mod config;
+pub mod credentials;
 pub mod domain;
 pub mod ip_detection;
 pub mod logging;
+pub mod provider;
 pub mod scheduler;
+pub mod zone;
 
 pub use config::*;
+pub use credentials::*;
 pub use domain::*;
 pub use ip_detection::*;
 pub use logging::*;
+pub use provider::*;
 pub use scheduler::*;
+pub use zone::*;
@@ -0,0 +1,8 @@
+use crate::providers::registry::ProviderDescriptor;
+use crate::providers::ProviderFactory;
+
+/// 列出所有提供商的描述, 供前端生成凭证表单
+#[tauri::command]
+pub async fn list_provider_descriptors() -> std::result::Result<Vec<ProviderDescriptor>, String> {
+    Ok(ProviderFactory::descriptors().to_vec())
+}
@@ -1,19 +1,16 @@
 use crate::app_state::AppState;
-use crate::services::{DNSUpdaterService, IPDetectorService};
+use crate::services::scheduler::{detect_for_domains, domain_families, sync_record};
+use crate::services::IPDetectorService;
 
 /// 启动调度器
 #[tauri::command]
-pub async fn start_scheduler(
-    state: tauri::State<'_, AppState>,
-) -> std::result::Result<(), String> {
+pub async fn start_scheduler(state: tauri::State<'_, AppState>) -> std::result::Result<(), String> {
     state.start_scheduler().await.map_err(|e| e.to_string())
 }
 
 /// 停止调度器
 #[tauri::command]
-pub async fn stop_scheduler(
-    state: tauri::State<'_, AppState>,
-) -> std::result::Result<(), String> {
+pub async fn stop_scheduler(state: tauri::State<'_, AppState>) -> std::result::Result<(), String> {
     state.stop_scheduler().await.map_err(|e| e.to_string())
 }
 
@@ -41,29 +38,41 @@ pub async fn force_update_domain(
         .await
         .map_err(|e| e.to_string())?;
 
-    // 检测 IP
-    let ip_detector = IPDetectorService::new();
-    let ip_info = ip_detector
-        .detect_ipv4()
-        .await
-        .map_err(|e| e.to_string())?;
-    let new_ip = ip_info
-        .ipv4
-        .ok_or_else(|| "未检测到 IP".to_string())?;
+    let families = domain_families(&domain);
+    if families.is_empty() {
+        return Err(format!("{} 记录不支持自动更新", domain.record_type));
+    }
 
-    // 更新 DNS
-    let dns_updater = DNSUpdaterService::new();
-    dns_updater
-        .update_domain(&domain_id, &new_ip)
+    // 按记录类型检测 IPv4 和/或 IPv6
+    let ip_detector = IPDetectorService::new();
+    let ip_info = detect_for_domains(&ip_detector, std::slice::from_ref(&domain))
         .await
         .map_err(|e| e.to_string())?;
 
-    // 更新数据库
-    state
-        .db
-        .update_domain_ip(&domain_id, &new_ip)
-        .await
-        .map_err(|e| e.to_string())?;
+    // 更新 DNS 并记录历史, 某个地址族失败时仍更新其余地址族
+    let mut messages = Vec::new();
+    let mut errors = Vec::new();
+    let updater = &state.dns_updater;
+    for family in families {
+        let result = sync_record(&state.db, updater, &domain, family, &ip_info, true).await;
+        match result {
+            Ok(Some(result)) if result.new_ip.is_empty() => messages.push(result.message),
+            Ok(Some(result)) => messages.push(format!(
+                "成功更新 {} 到 {}",
+                domain.full_domain(),
+                result.new_ip
+            )),
+            Ok(None) => {}
+            Err(e) => errors.push(format!("{} 记录更新失败: {}", family.record_type(), e)),
+        }
+    }
 
-    Ok(format!("成功更新 {} 到 {}", domain.full_domain(), new_ip))
+    if !errors.is_empty() {
+        messages.extend(errors);
+        return Err(messages.join("; "));
+    }
+    if messages.is_empty() {
+        return Err("未检测到 IP".to_string());
+    }
+    Ok(messages.join("; "))
 }
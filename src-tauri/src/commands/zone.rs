@@ -0,0 +1,61 @@
+use crate::app_state::AppState;
+use crate::models::{CreateZoneRecord, UpdateZoneRecord};
+use crate::providers::{DNSRecord, UpdateResult};
+use crate::services::ZoneEditorService;
+use std::sync::Arc;
+
+fn zone_editor(state: &AppState) -> ZoneEditorService {
+    ZoneEditorService::new(Arc::clone(&state.db), Arc::clone(&state.dns_updater))
+}
+
+/// 列出域名所在区域的全部记录
+#[tauri::command]
+pub async fn list_zone_records(
+    state: tauri::State<'_, AppState>,
+    domain_id: String,
+) -> std::result::Result<Vec<DNSRecord>, String> {
+    zone_editor(&state)
+        .list_records(&domain_id)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// 在域名所在区域中创建记录
+#[tauri::command]
+pub async fn create_zone_record(
+    state: tauri::State<'_, AppState>,
+    domain_id: String,
+    record: CreateZoneRecord,
+) -> std::result::Result<DNSRecord, String> {
+    zone_editor(&state)
+        .create_record(&domain_id, record)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// 更新区域中的记录
+#[tauri::command]
+pub async fn update_zone_record(
+    state: tauri::State<'_, AppState>,
+    domain_id: String,
+    record_id: String,
+    record: UpdateZoneRecord,
+) -> std::result::Result<UpdateResult, String> {
+    zone_editor(&state)
+        .update_record(&domain_id, &record_id, record)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// 删除区域中的记录
+#[tauri::command]
+pub async fn delete_zone_record(
+    state: tauri::State<'_, AppState>,
+    domain_id: String,
+    record_id: String,
+) -> std::result::Result<DNSRecord, String> {
+    zone_editor(&state)
+        .delete_record(&domain_id, &record_id)
+        .await
+        .map_err(|e| e.to_string())
+}
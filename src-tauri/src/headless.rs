@@ -0,0 +1,119 @@
+// 无界面守护进程模式 (用于 Docker 等没有显示环境的服务器)
+use crate::app_state::AppState;
+use crate::error::{AppError, Result};
+use crate::storage::secure_store::CredentialManager;
+use crate::storage::yaml_config::YamlConfig;
+use crate::storage::Database;
+use std::path::PathBuf;
+
+/// 是否以无界面模式运行 (`DDNS_HEADLESS=true` 或 `--headless` 参数)
+pub fn is_enabled() -> bool {
+    let from_env = std::env::var("DDNS_HEADLESS")
+        .map(|v| parse_flag(&v))
+        .unwrap_or(false);
+
+    from_env || std::env::args().any(|arg| arg == "--headless")
+}
+
+/// 从 `DDNS_CONFIG_PATH` 读取配置目录
+pub fn config_dir_from_env() -> Option<PathBuf> {
+    std::env::var_os("DDNS_CONFIG_PATH")
+        .filter(|v| !v.is_empty())
+        .map(PathBuf::from)
+}
+
+/// 获取 YAML 配置文件路径 (`--config <path>` 参数, 默认为配置目录下的 ddns.yml)
+pub fn config_file(config_dir: &std::path::Path) -> PathBuf {
+    let args: Vec<String> = std::env::args().collect();
+    args.iter()
+        .position(|arg| arg == "--config")
+        .and_then(|index| args.get(index + 1))
+        .map(PathBuf::from)
+        .unwrap_or_else(|| config_dir.join("ddns.yml"))
+}
+
+/// 运行守护进程, 直到收到 SIGTERM / Ctrl+C
+pub async fn run(config_dir: PathBuf) -> Result<()> {
+    tracing::info!("以无界面模式启动, 配置目录: {}", config_dir.display());
+    std::fs::create_dir_all(&config_dir)?;
+
+    let db = Database::open(&config_dir.join("data.db")).await?;
+    let credentials = CredentialManager::file(&config_dir)?;
+    let app_state = AppState::new(db, credentials);
+
+    // 同步 YAML 配置文件中的域名和凭证
+    let config_file = config_file(&config_dir);
+    if config_file.exists() {
+        tracing::info!("加载配置文件: {}", config_file.display());
+        let config = YamlConfig::load(&config_file)?;
+        config
+            .reconcile(&app_state.db, &app_state.credentials)
+            .await?;
+    } else {
+        tracing::warn!(
+            "配置文件 {} 不存在, 使用数据库中的域名",
+            config_file.display()
+        );
+    }
+
+    app_state
+        .start_scheduler()
+        .await
+        .map_err(AppError::Custom)?;
+
+    wait_for_shutdown().await?;
+
+    tracing::info!("正在停止 DDNS 服务");
+    app_state.stop_scheduler().await.map_err(AppError::Custom)?;
+    app_state.db.pool().close().await;
+    tracing::info!("DDNS 服务已停止");
+
+    Ok(())
+}
+
+/// 等待终止信号
+async fn wait_for_shutdown() -> Result<()> {
+    #[cfg(unix)]
+    {
+        use tokio::signal::unix::{signal, SignalKind};
+
+        let mut sigterm = signal(SignalKind::terminate())?;
+        tokio::select! {
+            _ = sigterm.recv() => tracing::info!("收到 SIGTERM"),
+            result = tokio::signal::ctrl_c() => {
+                result?;
+                tracing::info!("收到 SIGINT");
+            }
+        }
+    }
+
+    #[cfg(not(unix))]
+    {
+        tokio::signal::ctrl_c().await?;
+        tracing::info!("收到 Ctrl+C");
+    }
+
+    Ok(())
+}
+
+/// 解析布尔型环境变量
+fn parse_flag(value: &str) -> bool {
+    matches!(
+        value.trim().to_lowercase().as_str(),
+        "1" | "true" | "yes" | "on"
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_flag() {
+        assert!(parse_flag("true"));
+        assert!(parse_flag("TRUE"));
+        assert!(parse_flag("1"));
+        assert!(!parse_flag("false"));
+        assert!(!parse_flag(""));
+    }
+}
@@ -1,4 +1,18 @@
-// Library exports for testing
+// 后端逻辑库, 供桌面程序、无界面守护进程和集成测试共用
+// 允许未使用的代码 - 这些是为将来功能预留的
+#![allow(dead_code)]
+#![allow(clippy::upper_case_acronyms)]
+
+pub mod app_state;
+#[cfg(feature = "gui")]
+pub mod commands;
+pub mod error;
+pub mod headless;
+pub mod models;
+pub mod providers;
+pub mod services;
+pub mod storage;
+
 pub fn greet(name: &str) -> String {
     format!("你好, {}! 欢迎使用 DDNS 工具!", name)
 }
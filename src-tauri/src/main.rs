@@ -1,62 +1,60 @@
 // Prevents additional console window on Windows in release builds
 #![cfg_attr(not(debug_assertions), windows_subsystem = "windows")]
-// 允许未使用的代码 - 这些是为将来功能预留的
-#![allow(dead_code)]
-#![allow(unused_imports)]
 
-mod app_state;
-mod commands;
-mod error;
-mod models;
-mod providers;
-mod services;
-mod storage;
-
-use app_state::AppState;
-use services::Logger;
-use storage::Database;
-use tauri::Listener;
+use ddns_lib::headless;
+use ddns_lib::services::Logger;
 
 #[tokio::main]
 async fn main() {
+    if std::env::args().any(|arg| arg == "--version" || arg == "-V") {
+        println!("ddns {}", env!("CARGO_PKG_VERSION"));
+        return;
+    }
+
     // 初始化日志系统
     Logger::init().expect("无法初始化日志系统");
 
-    // 获取数据库路径
-    let config_dir = if cfg!(target_os = "linux") {
-        dirs::home_dir()
-            .map(|p| p.join(".config"))
-            .unwrap_or_else(|| std::path::PathBuf::from("/tmp/config"))
-    } else if cfg!(target_os = "macos") {
-        dirs::home_dir()
-            .map(|p| p.join("Library").join("Application Support"))
-            .unwrap_or_else(|| std::path::PathBuf::from("/tmp/config"))
-    } else {
-        dirs::config_dir()
-            .unwrap_or_else(|| std::path::PathBuf::from("./config"))
-    };
-
-    let db_path = config_dir.join("ddns/data.db");
+    // 获取配置目录
+    let config_dir = default_config_dir().join("ddns");
 
-    // 确保数据库目录存在
-    if let Some(parent) = db_path.parent() {
-        std::fs::create_dir_all(parent).expect("无法创建数据库目录");
+    // 无界面模式: 不启动 webview, 仅运行调度器 (无界面构建始终如此)
+    if !cfg!(feature = "gui") || headless::is_enabled() {
+        let config_dir = headless::config_dir_from_env().unwrap_or(config_dir);
+        if let Err(e) = headless::run(config_dir).await {
+            tracing::error!("无界面模式运行失败: {}", e);
+            std::process::exit(1);
+        }
+        return;
     }
 
-    // SQLx SQLite 需要使用 file:// URI 格式并指定 mode=rwc
-    // mode=rwc: read-write-create mode
-    let database_url = format!(
-        "file://{}?mode=rwc",
-        db_path.to_str().expect("无效的数据库路径")
-    );
-    tracing::info!("使用数据库: {}", database_url);
+    #[cfg(feature = "gui")]
+    run_gui(config_dir).await;
+}
+
+/// 启动桌面界面
+#[cfg(feature = "gui")]
+async fn run_gui(config_dir: std::path::PathBuf) {
+    use ddns_lib::app_state::AppState;
+    use ddns_lib::commands;
+    use ddns_lib::storage::secure_store::{passphrase_from_env, CredentialManager};
+    use ddns_lib::storage::Database;
+    use tauri::Listener;
 
-    let db = Database::new(&database_url)
+    let db = Database::open(&config_dir.join("data.db"))
         .await
         .expect("无法初始化数据库");
 
+    // 加密凭证存储, 未设置口令时凭证只保存在内存中
+    let credentials = match passphrase_from_env().expect("无法读取凭证加密口令") {
+        Some(_) => CredentialManager::file(&config_dir).expect("无法打开凭证存储"),
+        None => {
+            tracing::warn!("未设置凭证加密口令, 凭证不会持久化");
+            CredentialManager::memory()
+        }
+    };
+
     // 创建应用状态
-    let app_state = AppState::new(db);
+    let app_state = AppState::new(db, credentials);
 
     tauri::Builder::default()
         .plugin(tauri_plugin_shell::init())
@@ -77,6 +75,13 @@ async fn main() {
             commands::add_domain,
             commands::update_domain,
             commands::delete_domain,
+            // 凭证命令
+            commands::set_credentials,
+            commands::delete_credentials,
+            commands::has_credentials,
+            commands::test_credentials,
+            // 提供商命令
+            commands::list_provider_descriptors,
             // IP 检测命令
             commands::detect_ip,
             commands::get_current_ip,
@@ -92,12 +97,33 @@ async fn main() {
             commands::stop_scheduler,
             commands::get_scheduler_status,
             commands::force_update_domain,
+            // 区域记录命令
+            commands::list_zone_records,
+            commands::create_zone_record,
+            commands::update_zone_record,
+            commands::delete_zone_record,
         ])
         .run(tauri::generate_context!())
         .expect("error while running tauri application");
 }
 
+/// 获取平台默认配置目录
+fn default_config_dir() -> std::path::PathBuf {
+    if cfg!(target_os = "linux") {
+        dirs::home_dir()
+            .map(|p| p.join(".config"))
+            .unwrap_or_else(|| std::path::PathBuf::from("/tmp/config"))
+    } else if cfg!(target_os = "macos") {
+        dirs::home_dir()
+            .map(|p| p.join("Library").join("Application Support"))
+            .unwrap_or_else(|| std::path::PathBuf::from("/tmp/config"))
+    } else {
+        dirs::config_dir().unwrap_or_else(|| std::path::PathBuf::from("./config"))
+    }
+}
+
+#[cfg(feature = "gui")]
 #[tauri::command]
 fn greet(name: &str) -> String {
-    format!("你好, {}! 欢迎使用 DDNS 工具!", name)
+    ddns_lib::greet(name)
 }
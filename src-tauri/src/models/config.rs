@@ -12,6 +12,21 @@ pub enum ProviderType {
     Huawei,
     Baidu,
     Jdcloud,
+    Rfc2136,
+    Webhook,
+    Dyndns2,
+    Duckdns,
+    Dynu,
+    Freedns,
+    Gandi,
+    Digitalocean,
+    Linode,
+    Hetzner,
+    Porkbun,
+    Namecheap,
+    Namecom,
+    Azure,
+    Gcp,
 }
 
 impl ProviderType {
@@ -25,10 +40,26 @@ impl ProviderType {
             ProviderType::Huawei => "huawei",
             ProviderType::Baidu => "baidu",
             ProviderType::Jdcloud => "jdcloud",
+            ProviderType::Rfc2136 => "rfc2136",
+            ProviderType::Webhook => "webhook",
+            ProviderType::Dyndns2 => "dyndns2",
+            ProviderType::Duckdns => "duckdns",
+            ProviderType::Dynu => "dynu",
+            ProviderType::Freedns => "freedns",
+            ProviderType::Gandi => "gandi",
+            ProviderType::Digitalocean => "digitalocean",
+            ProviderType::Linode => "linode",
+            ProviderType::Hetzner => "hetzner",
+            ProviderType::Porkbun => "porkbun",
+            ProviderType::Namecheap => "namecheap",
+            ProviderType::Namecom => "namecom",
+            ProviderType::Azure => "azure",
+            ProviderType::Gcp => "gcp",
         }
     }
 
     /// 从字符串解析
+    #[allow(clippy::should_implement_trait)]
     pub fn from_str(s: &str) -> Option<Self> {
         match s.to_lowercase().as_str() {
             "aliyun" => Some(ProviderType::Aliyun),
@@ -38,6 +69,21 @@ impl ProviderType {
             "huawei" => Some(ProviderType::Huawei),
             "baidu" => Some(ProviderType::Baidu),
             "jdcloud" => Some(ProviderType::Jdcloud),
+            "rfc2136" => Some(ProviderType::Rfc2136),
+            "webhook" => Some(ProviderType::Webhook),
+            "dyndns2" => Some(ProviderType::Dyndns2),
+            "duckdns" => Some(ProviderType::Duckdns),
+            "dynu" => Some(ProviderType::Dynu),
+            "freedns" => Some(ProviderType::Freedns),
+            "gandi" => Some(ProviderType::Gandi),
+            "digitalocean" => Some(ProviderType::Digitalocean),
+            "linode" => Some(ProviderType::Linode),
+            "hetzner" => Some(ProviderType::Hetzner),
+            "porkbun" => Some(ProviderType::Porkbun),
+            "namecheap" => Some(ProviderType::Namecheap),
+            "namecom" => Some(ProviderType::Namecom),
+            "azure" => Some(ProviderType::Azure),
+            "gcp" => Some(ProviderType::Gcp),
             _ => None,
         }
     }
@@ -55,6 +101,25 @@ pub struct Credentials {
     pub extra: HashMap<String, String>,
 }
 
+impl Credentials {
+    /// 转换为提供商使用的凭证格式
+    pub fn to_provider_credentials(&self) -> crate::providers::Credentials {
+        let extra = self
+            .extra
+            .iter()
+            .map(|(k, v)| (k.clone(), serde_json::Value::String(v.clone())))
+            .collect::<serde_json::Map<_, _>>();
+
+        crate::providers::Credentials {
+            api_key: self.api_key.clone(),
+            api_secret: self.api_secret.clone(),
+            access_key: self.access_key.clone(),
+            region: self.region.clone(),
+            extra: serde_json::Value::Object(extra),
+        }
+    }
+}
+
 /// 应用配置
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct AppConfig {
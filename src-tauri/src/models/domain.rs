@@ -1,9 +1,13 @@
+use crate::error::{self, AppError};
+use crate::providers::{DNSRecordType, RecordSpec};
 use chrono::Utc;
-use serde::{Deserialize, Serialize};
+use serde::{Deserialize, Deserializer, Serialize};
 use sqlx::FromRow;
 
-/// DNS 记录类型
-#[derive(Debug, Clone, Serialize, Deserialize, sqlx::Type)]
+/// 域名可以自动维护的记录类型 (与 domains 表的 CHECK 约束一致)
+///
+/// 区域编辑器支持的其他类型见 [`DNSRecordType`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, sqlx::Type)]
 #[sqlx(type_name = "record_type", rename_all = "UPPERCASE")]
 pub enum RecordType {
     A,
@@ -11,6 +15,52 @@ pub enum RecordType {
     CNAME,
 }
 
+impl RecordType {
+    pub const ALL: [RecordType; 3] = [RecordType::A, RecordType::AAAA, RecordType::CNAME];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            RecordType::A => "A",
+            RecordType::AAAA => "AAAA",
+            RecordType::CNAME => "CNAME",
+        }
+    }
+}
+
+impl std::fmt::Display for RecordType {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl std::str::FromStr for RecordType {
+    type Err = AppError;
+
+    /// 解析类型名称 (不区分大小写), 其他 DNS 记录类型返回验证错误
+    fn from_str(s: &str) -> error::Result<Self> {
+        match s.parse::<DNSRecordType>() {
+            Ok(DNSRecordType::A) => Ok(RecordType::A),
+            Ok(DNSRecordType::AAAA) => Ok(RecordType::AAAA),
+            Ok(DNSRecordType::CNAME) => Ok(RecordType::CNAME),
+            _ => Err(AppError::Validation(format!(
+                "不支持的记录类型 '{}', 可选值: {}",
+                s.trim(),
+                RecordType::ALL.map(|t| t.as_str()).join(", ")
+            ))),
+        }
+    }
+}
+
+impl From<RecordType> for DNSRecordType {
+    fn from(record_type: RecordType) -> Self {
+        match record_type {
+            RecordType::A => DNSRecordType::A,
+            RecordType::AAAA => DNSRecordType::AAAA,
+            RecordType::CNAME => DNSRecordType::CNAME,
+        }
+    }
+}
+
 /// 域名配置
 #[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
 pub struct Domain {
@@ -18,11 +68,23 @@ pub struct Domain {
     pub name: String,
     pub provider: String,
     pub subdomain: String,
-    pub record_type: String, // 存储为字符串,使用 RecordType enum 进行验证
+    pub record_type: String, // 存储为字符串, 写入前使用 RecordType enum 进行验证
     pub current_ip: Option<String>,
     pub last_updated: Option<i64>, // Unix 时间戳
     pub update_interval: i64,      // 秒
     pub enabled: bool,
+    /// 双栈模式: 同时维护 A 和 AAAA 记录 (此时忽略 record_type)
+    pub dual_stack: bool,
+    /// 双栈模式下 IPv6 不可用时删除 AAAA 记录
+    pub remove_aaaa_on_ipv6_loss: bool,
+    pub current_ipv4: Option<String>,
+    pub current_ipv6: Option<String>,
+    /// 记录 TTL (秒), None 表示使用提供商默认值
+    pub ttl: Option<u32>,
+    /// 是否开启代理 (Cloudflare), None 表示不指定
+    pub proxied: Option<bool>,
+    /// 记录备注
+    pub comment: Option<String>,
     pub created_at: i64,
     pub updated_at: i64,
 }
@@ -36,15 +98,46 @@ pub struct CreateDomain {
     pub record_type: String,
     pub update_interval: i64,
     pub enabled: bool,
+    #[serde(default)]
+    pub dual_stack: bool,
+    #[serde(default)]
+    pub remove_aaaa_on_ipv6_loss: bool,
+    #[serde(default)]
+    pub ttl: Option<u32>,
+    #[serde(default)]
+    pub proxied: Option<bool>,
+    #[serde(default)]
+    pub comment: Option<String>,
 }
 
 /// 更新域名的输入
+///
+/// 记录选项 (`ttl`/`proxied`/`comment`) 省略时保持不变, 显式传入 `null` 时清除
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct UpdateDomain {
     pub name: Option<String>,
     pub subdomain: Option<String>,
     pub update_interval: Option<i64>,
     pub enabled: Option<bool>,
+    #[serde(default)]
+    pub dual_stack: Option<bool>,
+    #[serde(default)]
+    pub remove_aaaa_on_ipv6_loss: Option<bool>,
+    #[serde(default, deserialize_with = "explicit_null")]
+    pub ttl: Option<Option<u32>>,
+    #[serde(default, deserialize_with = "explicit_null")]
+    pub proxied: Option<Option<bool>>,
+    #[serde(default, deserialize_with = "explicit_null")]
+    pub comment: Option<Option<String>>,
+}
+
+/// 区分字段缺失 (`None`) 与显式的 `null` (`Some(None)`)
+fn explicit_null<'de, T, D>(deserializer: D) -> Result<Option<Option<T>>, D::Error>
+where
+    T: Deserialize<'de>,
+    D: Deserializer<'de>,
+{
+    Option::<T>::deserialize(deserializer).map(Some)
 }
 
 impl Domain {
@@ -61,11 +154,77 @@ impl Domain {
             last_updated: None,
             update_interval: create.update_interval,
             enabled: create.enabled,
+            dual_stack: create.dual_stack,
+            remove_aaaa_on_ipv6_loss: create.remove_aaaa_on_ipv6_loss,
+            current_ipv4: None,
+            current_ipv6: None,
+            ttl: create.ttl,
+            proxied: create.proxied,
+            comment: create.comment,
             created_at: now,
             updated_at: now,
         }
     }
 
+    /// 应用更新输入, 未提供的字段保持不变
+    pub fn apply(&mut self, update: UpdateDomain) {
+        if let Some(name) = update.name {
+            self.name = name;
+        }
+        if let Some(subdomain) = update.subdomain {
+            self.subdomain = subdomain;
+        }
+        if let Some(interval) = update.update_interval {
+            self.update_interval = interval;
+        }
+        if let Some(enabled) = update.enabled {
+            self.enabled = enabled;
+        }
+        if let Some(dual_stack) = update.dual_stack {
+            self.dual_stack = dual_stack;
+        }
+        if let Some(remove) = update.remove_aaaa_on_ipv6_loss {
+            self.remove_aaaa_on_ipv6_loss = remove;
+        }
+        if let Some(ttl) = update.ttl {
+            self.ttl = ttl;
+        }
+        if let Some(proxied) = update.proxied {
+            self.proxied = proxied;
+        }
+        if let Some(comment) = update.comment {
+            self.comment = comment;
+        }
+        self.updated_at = Utc::now().timestamp();
+    }
+
+    /// 校验记录类型并规范为大写, 双栈模式下必须为 A
+    pub fn validate(&mut self) -> error::Result<()> {
+        let record_type: RecordType = self.record_type.parse()?;
+        if self.dual_stack && record_type != RecordType::A {
+            return Err(AppError::Validation(
+                "双栈模式下记录类型必须为 A".to_string(),
+            ));
+        }
+        self.record_type = record_type.to_string();
+        Ok(())
+    }
+
+    /// 创建/更新记录时提交给提供商的选项
+    pub fn record_spec(&self) -> RecordSpec {
+        RecordSpec {
+            ttl: self.ttl,
+            proxied: self.proxied,
+            priority: None,
+            comment: self.comment.clone(),
+        }
+    }
+
+    /// 是否配置了需要与远端保持一致的记录选项 (TTL、代理或备注)
+    pub fn has_record_options(&self) -> bool {
+        self.ttl.is_some() || self.proxied.is_some() || self.comment.is_some()
+    }
+
     /// 获取完整域名 (子域名 + 主域名)
     pub fn full_domain(&self) -> String {
         if self.subdomain.is_empty() || self.subdomain == "@" {
@@ -75,6 +234,12 @@ impl Domain {
         }
     }
 
+    /// 上次同步之后域名配置是否被修改过 (需要重新提交记录选项)
+    pub fn modified_since_sync(&self) -> bool {
+        self.last_updated
+            .is_none_or(|last_updated| last_updated <= self.updated_at)
+    }
+
     /// 检查是否需要更新
     pub fn should_update(&self) -> bool {
         if !self.enabled {
@@ -107,6 +272,13 @@ mod tests {
             last_updated: None,
             update_interval: 300,
             enabled: true,
+            dual_stack: false,
+            remove_aaaa_on_ipv6_loss: false,
+            current_ipv4: None,
+            current_ipv6: None,
+            ttl: None,
+            proxied: None,
+            comment: None,
             created_at: 0,
             updated_at: 0,
         };
@@ -126,10 +298,92 @@ mod tests {
             last_updated: None,
             update_interval: 300,
             enabled: true,
+            dual_stack: false,
+            remove_aaaa_on_ipv6_loss: false,
+            current_ipv4: None,
+            current_ipv6: None,
+            ttl: None,
+            proxied: None,
+            comment: None,
             created_at: 0,
             updated_at: 0,
         };
 
         assert_eq!(domain.full_domain(), "example.com");
     }
+
+    #[test]
+    fn test_update_record_options() {
+        let mut domain = Domain::new(CreateDomain {
+            name: "example.com".to_string(),
+            provider: "cloudflare".to_string(),
+            subdomain: "www".to_string(),
+            record_type: "A".to_string(),
+            update_interval: 300,
+            enabled: true,
+            dual_stack: false,
+            remove_aaaa_on_ipv6_loss: false,
+            ttl: Some(600),
+            proxied: Some(true),
+            comment: Some("home".to_string()),
+        });
+
+        // 省略的字段保持不变, null 清除
+        let update: UpdateDomain =
+            serde_json::from_str(r#"{"name": null, "ttl": 120, "comment": null}"#).unwrap();
+        assert_eq!(update.ttl, Some(Some(120)));
+        assert_eq!(update.proxied, None);
+        assert_eq!(update.comment, Some(None));
+        domain.apply(update);
+
+        assert_eq!(domain.name, "example.com");
+        assert_eq!(domain.ttl, Some(120));
+        assert_eq!(domain.proxied, Some(true));
+        assert_eq!(domain.comment, None);
+
+        // 修改配置后需要重新同步
+        assert!(domain.modified_since_sync());
+        domain.last_updated = Some(domain.updated_at + 60);
+        assert!(!domain.modified_since_sync());
+
+        let spec = domain.record_spec();
+        assert_eq!(spec.ttl, Some(120));
+        assert_eq!(spec.proxied, Some(true));
+        assert_eq!(spec.priority, None);
+    }
+
+    #[test]
+    fn test_validate_record_type() {
+        let mut domain = Domain::new(CreateDomain {
+            name: "example.com".to_string(),
+            provider: "cloudflare".to_string(),
+            subdomain: "www".to_string(),
+            record_type: "aaaa".to_string(),
+            update_interval: 300,
+            enabled: true,
+            dual_stack: false,
+            remove_aaaa_on_ipv6_loss: false,
+            ttl: None,
+            proxied: None,
+            comment: None,
+        });
+        domain.validate().unwrap();
+        assert_eq!(domain.record_type, "AAAA");
+
+        // 双栈模式只接受 A
+        domain.apply(serde_json::from_str(r#"{"dual_stack": true}"#).unwrap());
+        assert!(domain.validate().is_err());
+
+        // 区域编辑器支持的其他类型不能用于自动更新
+        domain.dual_stack = false;
+        domain.record_type = "TXT".to_string();
+        assert!(matches!(domain.validate(), Err(AppError::Validation(_))));
+        domain.record_type = "".to_string();
+        assert!(domain.validate().is_err());
+
+        assert_eq!(
+            DNSRecordType::from("cname".parse::<RecordType>().unwrap()),
+            DNSRecordType::CNAME
+        );
+    }
 }
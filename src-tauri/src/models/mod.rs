@@ -1,7 +1,9 @@
 pub mod config;
 pub mod domain;
 pub mod ip_info;
+pub mod zone;
 
 pub use config::*;
 pub use domain::*;
 pub use ip_info::*;
+pub use zone::*;
@@ -0,0 +1,58 @@
+use crate::providers::RecordSpec;
+use serde::{Deserialize, Serialize};
+
+/// 在域名所在区域中创建记录的输入
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CreateZoneRecord {
+    /// 相对于区域的记录名称, 根域名为 `@`
+    pub name: String,
+    pub record_type: String,
+    /// 区域文件格式的记录数据, 如 MX `10 mail.example.com`、SRV `10 5 5060 sip.example.com`
+    pub content: String,
+    #[serde(default)]
+    pub ttl: Option<u32>,
+    #[serde(default)]
+    pub proxied: Option<bool>,
+    #[serde(default)]
+    pub priority: Option<u16>,
+    #[serde(default)]
+    pub comment: Option<String>,
+}
+
+/// 更新区域记录的输入, 记录名称和类型不可修改
+///
+/// 记录选项省略时保持记录原值
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct UpdateZoneRecord {
+    pub content: String,
+    #[serde(default)]
+    pub ttl: Option<u32>,
+    #[serde(default)]
+    pub proxied: Option<bool>,
+    #[serde(default)]
+    pub priority: Option<u16>,
+    #[serde(default)]
+    pub comment: Option<String>,
+}
+
+impl CreateZoneRecord {
+    pub fn record_spec(&self) -> RecordSpec {
+        RecordSpec {
+            ttl: self.ttl,
+            proxied: self.proxied,
+            priority: self.priority,
+            comment: self.comment.clone(),
+        }
+    }
+}
+
+impl UpdateZoneRecord {
+    pub fn record_spec(&self) -> RecordSpec {
+        RecordSpec {
+            ttl: self.ttl,
+            proxied: self.proxied,
+            priority: self.priority,
+            comment: self.comment.clone(),
+        }
+    }
+}
@@ -1,30 +1,82 @@
 use crate::error::{AppError, Result};
 use crate::providers::provider_trait::{
-    Credentials, DNSProvider, DNSRecord, DNSRecordType, ProviderError, UpdateResult,
+    Credentials, DNSProvider, DNSRecord, DNSRecordType, ProviderCapabilities, ProviderError,
+    RecordContent, RecordSpec, UpdateResult,
 };
 use async_trait::async_trait;
 use reqwest::Client;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 
+/// DescribeDomainRecords 每页记录数 (阿里云允许的最大值)
+const PAGE_SIZE: usize = 500;
+
+/// 新建记录的默认 TTL (免费版允许的最小值)
+const DEFAULT_TTL: u32 = 600;
+
 /// 阿里云 DNS 提供商
 pub struct AliyunProvider {
     initialized: bool,
     access_key_id: Option<String>,
     access_key_secret: Option<String>,
     region: Option<String>,
+    endpoint: String,
     client: Client,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
 struct AliyunDNSRecord {
+    #[serde(rename = "RecordId")]
     record_id: String,
+    #[serde(rename = "DomainName")]
     domain_name: String,
+    #[serde(rename = "RR")]
     rr: String,
-    #[serde(rename = "type")]
+    #[serde(rename = "Type")]
     record_type: String,
+    #[serde(rename = "Value")]
     value: String,
+    #[serde(rename = "TTL")]
     ttl: i64,
+    #[serde(rename = "Priority", default)]
+    priority: Option<u16>,
+    #[serde(rename = "Remark", default)]
+    remark: Option<String>,
+}
+
+impl AliyunDNSRecord {
+    fn into_dns_record(self) -> DNSRecord {
+        let record_type = self
+            .record_type
+            .parse()
+            .unwrap_or_else(|_| DNSRecordType::Other(self.record_type.clone()));
+        // SRV 记录值为 "优先级 权重 端口 目标", MX 的优先级在 Priority 字段中
+        let content = RecordContent::parse(&record_type, &self.value);
+
+        DNSRecord {
+            id: self.record_id,
+            name: self.rr,
+            priority: content
+                .priority
+                .or(self.priority)
+                .filter(|_| record_type.has_priority()),
+            record_type,
+            content: content.content,
+            ttl: self.ttl as u32,
+            proxied: None,
+            weight: content.weight,
+            port: content.port,
+            comment: self.remark.filter(|remark| !remark.is_empty()),
+        }
+    }
+}
+
+/// 阿里云的记录值: MX 的优先级单独传递, 其他类型使用区域文件格式
+fn record_value(record_type: &DNSRecordType, content: &RecordContent) -> String {
+    match record_type {
+        DNSRecordType::MX => content.content.clone(),
+        _ => content.to_rdata(record_type),
+    }
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -53,29 +105,38 @@ struct UpdateDomainRecordRequest {
 
 #[derive(Debug, Serialize, Deserialize)]
 struct UpdateDomainRecordResponse {
+    #[serde(rename = "RecordId")]
     record_id: String,
 }
 
 impl AliyunProvider {
     pub fn new() -> Self {
+        Self::with_endpoint("https://alidns.aliyuncs.com")
+    }
+
+    /// 使用自定义 API 地址创建提供商 (例如测试服务器)
+    pub fn with_endpoint(endpoint: impl Into<String>) -> Self {
         Self {
             initialized: false,
             access_key_id: None,
             access_key_secret: None,
             region: Some("cn-hangzhou".to_string()),
+            endpoint: endpoint.into().trim_end_matches('/').to_string(),
             client: Client::new(),
         }
     }
 
+    /// 当前使用的地域
+    pub fn region(&self) -> Option<&str> {
+        self.region.as_deref()
+    }
+
     /// 构造阿里云 API 签名
     fn build_signature(
         &self,
         method: &str,
         params: &mut HashMap<String, String>,
     ) -> Result<String> {
-        
-        
-
         // 公共参数
         params.insert("Format".to_string(), "JSON".to_string());
         params.insert("Version".to_string(), "2015-01-09".to_string());
@@ -149,7 +210,7 @@ impl AliyunProvider {
             .collect::<Vec<String>>()
             .join("&");
 
-        let url = format!("https://alidns.aliyuncs.com/?{}", query_string);
+        let url = format!("{}/?{}", self.endpoint, query_string);
 
         let response = self
             .client
@@ -172,6 +233,17 @@ impl AliyunProvider {
             ))))
         }
     }
+
+    /// 设置记录备注 (阿里云通过单独的接口修改备注)
+    async fn update_remark(&self, record_id: &str, remark: &str) -> Result<()> {
+        let mut params = HashMap::new();
+        params.insert("RecordId".to_string(), record_id.to_string());
+        params.insert("Remark".to_string(), remark.to_string());
+
+        self.send_request("UpdateDomainRecordRemark", params)
+            .await?;
+        Ok(())
+    }
 }
 
 impl Default for AliyunProvider {
@@ -197,8 +269,16 @@ impl DNSProvider for AliyunProvider {
         if let Some(key_secret) = credentials.extra.get("access_key_secret") {
             self.access_key_secret = Some(key_secret.as_str().unwrap_or_default().to_string());
         }
-        if let Some(region) = credentials.extra.get("region") {
-            self.region = Some(region.as_str().unwrap_or_default().to_string());
+        // 注册表的 region 字段落入 Credentials::region, 旧版凭证放在 extra 中
+        let region = credentials.region.clone().or_else(|| {
+            credentials
+                .extra
+                .get("region")
+                .and_then(|v| v.as_str())
+                .map(|v| v.to_string())
+        });
+        if let Some(region) = region.filter(|r| !r.trim().is_empty()) {
+            self.region = Some(region.trim().to_string());
         }
 
         if self.access_key_id.is_none() || self.access_key_secret.is_none() {
@@ -215,39 +295,55 @@ impl DNSProvider for AliyunProvider {
     }
 
     async fn list_records(&self, domain: &str) -> Result<Vec<DNSRecord>> {
-        let mut params = HashMap::new();
-        params.insert("DomainName".to_string(), domain.to_string());
-
-        let response = self.send_request("DescribeDomain", params).await?;
-        let domain_records: Vec<AliyunDNSRecord> =
-            serde_json::from_value(response["DomainRecords"]["Record"].clone())
-                .map_err(|e| AppError::Provider(ProviderError::ParseError(e.to_string())))?;
+        let mut domain_records: Vec<AliyunDNSRecord> = Vec::new();
+        let mut page_number = 1;
+
+        // 逐页读取, 直到读完 TotalCount 条记录
+        loop {
+            let mut params = HashMap::new();
+            params.insert("DomainName".to_string(), domain.to_string());
+            params.insert("PageNumber".to_string(), page_number.to_string());
+            params.insert("PageSize".to_string(), PAGE_SIZE.to_string());
+
+            let response = self.send_request("DescribeDomainRecords", params).await?;
+            let page: Vec<AliyunDNSRecord> =
+                serde_json::from_value(response["DomainRecords"]["Record"].clone())
+                    .map_err(|e| AppError::Provider(ProviderError::ParseError(e.to_string())))?;
+            let total_count = response["TotalCount"].as_u64().unwrap_or(0) as usize;
+
+            let count = page.len();
+            domain_records.extend(page);
+            if count == 0 || domain_records.len() >= total_count {
+                break;
+            }
+            page_number += 1;
+        }
 
         Ok(domain_records
             .into_iter()
-            .map(|r| DNSRecord {
-                id: r.record_id,
-                name: r.rr,
-                record_type: match r.record_type.as_str() {
-                    "A" => DNSRecordType::A,
-                    "AAAA" => DNSRecordType::AAAA,
-                    "CNAME" => DNSRecordType::CNAME,
-                    _ => DNSRecordType::A,
-                },
-                content: r.value,
-                ttl: r.ttl as u32,
-                proxied: None,
-                priority: None,
-            })
+            .map(AliyunDNSRecord::into_dns_record)
             .collect())
     }
 
     async fn update_record(
+        &self,
+        domain: &str,
+        record_id: &str,
+        new_content: &str,
+    ) -> Result<UpdateResult> {
+        self.update_record_with(domain, record_id, new_content, &RecordSpec::default())
+            .await
+    }
+
+    async fn update_record_with(
         &self,
         _domain: &str,
         record_id: &str,
         new_content: &str,
+        spec: &RecordSpec,
     ) -> Result<UpdateResult> {
+        spec.ensure_supported(&self.capabilities())?;
+
         // 先获取记录详情
         let mut params = HashMap::new();
         params.insert("RecordId".to_string(), record_id.to_string());
@@ -255,24 +351,46 @@ impl DNSProvider for AliyunProvider {
         let response = self
             .send_request("DescribeDomainRecordInfo", params)
             .await?;
-        let record: AliyunDNSRecord = serde_json::from_value(response.clone())
-            .map_err(|e| AppError::Provider(ProviderError::ParseError(e.to_string())))?;
+        let record = serde_json::from_value::<AliyunDNSRecord>(response)
+            .map_err(|e| AppError::Provider(ProviderError::ParseError(e.to_string())))?
+            .into_dns_record();
+        let mut content = RecordContent::from_input(&record.record_type, new_content, spec)?;
+        content.priority = content.priority.or(record.priority);
 
-        // 更新记录
+        // 更新记录 (不传 TTL 时阿里云会重置为默认值, 因此总是带上)
         let mut params = HashMap::new();
         params.insert("RecordId".to_string(), record_id.to_string());
-        params.insert("RR".to_string(), record.rr);
-        params.insert("Type".to_string(), record.record_type);
-        params.insert("Value".to_string(), new_content.to_string());
+        params.insert("RR".to_string(), record.name.clone());
+        params.insert("Type".to_string(), record.record_type.to_string());
+        params.insert(
+            "Value".to_string(),
+            record_value(&record.record_type, &content),
+        );
+        params.insert(
+            "TTL".to_string(),
+            spec.ttl.unwrap_or(record.ttl).to_string(),
+        );
+        if let Some(priority) = content
+            .priority
+            .filter(|_| record.record_type == DNSRecordType::MX)
+        {
+            params.insert("Priority".to_string(), priority.to_string());
+        }
 
         let response = self.send_request("UpdateDomainRecord", params).await?;
         let update_result: UpdateDomainRecordResponse = serde_json::from_value(response)
             .map_err(|e| AppError::Provider(ProviderError::ParseError(e.to_string())))?;
 
+        if let Some(comment) = &spec.comment {
+            if record.comment.as_deref().unwrap_or_default() != comment {
+                self.update_remark(record_id, comment).await?;
+            }
+        }
+
         Ok(UpdateResult {
             success: true,
             record_id: update_result.record_id,
-            old_ip: record.value.clone(),
+            old_ip: record.rdata(),
             new_ip: new_content.to_string(),
             message: "更新成功".to_string(),
         })
@@ -285,24 +403,61 @@ impl DNSProvider for AliyunProvider {
         record_type: DNSRecordType,
         content: &str,
     ) -> Result<DNSRecord> {
+        self.create_record_with(
+            domain,
+            record_name,
+            record_type,
+            content,
+            &RecordSpec::default(),
+        )
+        .await
+    }
+
+    async fn create_record_with(
+        &self,
+        domain: &str,
+        record_name: &str,
+        record_type: DNSRecordType,
+        content: &str,
+        spec: &RecordSpec,
+    ) -> Result<DNSRecord> {
+        spec.ensure_supported(&self.capabilities())?;
+        let content = RecordContent::from_input(&record_type, content, spec)?;
+
         let mut params = HashMap::new();
         params.insert("DomainName".to_string(), domain.to_string());
         params.insert("RR".to_string(), record_name.to_string());
-        params.insert("Type".to_string(), format!("{:?}", record_type));
-        params.insert("Value".to_string(), content.to_string());
+        params.insert("Type".to_string(), record_type.to_string());
+        params.insert("Value".to_string(), record_value(&record_type, &content));
+        let ttl = spec.ttl.unwrap_or(DEFAULT_TTL);
+        params.insert("TTL".to_string(), ttl.to_string());
+        if let Some(priority) = content
+            .priority
+            .filter(|_| record_type == DNSRecordType::MX)
+        {
+            params.insert("Priority".to_string(), priority.to_string());
+        }
 
+        // AddDomainRecord 只返回 RecordId
         let response = self.send_request("AddDomainRecord", params).await?;
-        let record: AliyunDNSRecord = serde_json::from_value(response)
+        let created: UpdateDomainRecordResponse = serde_json::from_value(response)
             .map_err(|e| AppError::Provider(ProviderError::ParseError(e.to_string())))?;
 
+        if let Some(comment) = &spec.comment {
+            self.update_remark(&created.record_id, comment).await?;
+        }
+
         Ok(DNSRecord {
-            id: record.record_id,
-            name: record.rr,
+            id: created.record_id,
+            name: record_name.to_string(),
             record_type,
-            content: record.value,
-            ttl: record.ttl as u32,
+            content: content.content,
+            ttl,
             proxied: None,
-            priority: None,
+            priority: content.priority,
+            weight: content.weight,
+            port: content.port,
+            comment: spec.comment.clone(),
         })
     }
 
@@ -320,6 +475,29 @@ impl DNSProvider for AliyunProvider {
         let response = self.send_request("DescribeDomains", params).await?;
         Ok(response["TotalCount"].as_i64().unwrap_or(0) >= 0)
     }
+
+    fn supported_record_types(&self) -> Vec<DNSRecordType> {
+        vec![
+            DNSRecordType::A,
+            DNSRecordType::AAAA,
+            DNSRecordType::CNAME,
+            DNSRecordType::MX,
+            DNSRecordType::TXT,
+            DNSRecordType::NS,
+            DNSRecordType::SRV,
+            DNSRecordType::CAA,
+        ]
+    }
+
+    fn capabilities(&self) -> ProviderCapabilities {
+        ProviderCapabilities {
+            proxy: false,
+            ttl: true,
+            line: true,
+            priority: true,
+            comment: true,
+        }
+    }
 }
 
 // 简化的 HMAC-SHA1 实现
@@ -338,3 +516,269 @@ fn base64_encode(data: &[u8]) -> String {
     use base64::Engine;
     base64::engine::general_purpose::STANDARD.encode(data)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::providers::test_support::{paginate, PagedResponder};
+    use wiremock::matchers::{method, query_param};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    #[tokio::test]
+    async fn test_list_records_reads_all_pages() {
+        let server = MockServer::start().await;
+
+        let pages = paginate(1200, PAGE_SIZE, |i| {
+            serde_json::json!({
+                "RecordId": i.to_string(),
+                "DomainName": "example.com",
+                "RR": format!("host{}", i),
+                "Type": "A",
+                "Value": "1.1.1.1",
+                "TTL": 600
+            })
+        });
+        let pages = pages
+            .into_iter()
+            .enumerate()
+            .map(|(index, records)| {
+                serde_json::json!({
+                    "TotalCount": 1200,
+                    "PageNumber": index + 1,
+                    "PageSize": PAGE_SIZE,
+                    "DomainRecords": { "Record": records }
+                })
+            })
+            .collect();
+        Mock::given(method("GET"))
+            .and(query_param("Action", "DescribeDomainRecords"))
+            .and(query_param("DomainName", "example.com"))
+            .respond_with(PagedResponder::page_number("PageNumber", 1, pages))
+            .expect(3)
+            .mount(&server)
+            .await;
+
+        let mut provider = AliyunProvider::with_endpoint(server.uri());
+        provider.access_key_id = Some("id".to_string());
+        provider.access_key_secret = Some("secret".to_string());
+
+        let records = provider.list_records("example.com").await.unwrap();
+        assert_eq!(records.len(), 1200);
+        assert_eq!(records[1199].name, "host1199");
+    }
+
+    #[tokio::test]
+    async fn test_region_from_registry_field() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(query_param("Action", "DescribeDomains"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "RequestId": "req",
+                "TotalCount": 0
+            })))
+            .mount(&server)
+            .await;
+
+        // 按注册表声明的字段填写凭证, region 落入 Credentials::region
+        let descriptor = crate::providers::registry::find("aliyun").unwrap();
+        let mut form = serde_json::Map::new();
+        form.insert("provider_id".to_string(), "aliyun".into());
+        for field in &descriptor.credential_fields {
+            let value = match field.key {
+                "region" => "cn-shanghai",
+                key => key,
+            };
+            form.insert(field.key.to_string(), value.into());
+        }
+        let credentials: crate::models::Credentials =
+            serde_json::from_value(serde_json::Value::Object(form)).unwrap();
+        assert_eq!(credentials.region.as_deref(), Some("cn-shanghai"));
+
+        let mut provider = AliyunProvider::with_endpoint(server.uri());
+        provider
+            .initialize(&credentials.to_provider_credentials())
+            .await
+            .unwrap();
+        assert_eq!(provider.region.as_deref(), Some("cn-shanghai"));
+
+        // 旧版凭证中 extra 里的 region 仍然有效
+        let mut provider = AliyunProvider::with_endpoint(server.uri());
+        provider
+            .initialize(&Credentials {
+                extra: serde_json::json!({
+                    "access_key_id": "id",
+                    "access_key_secret": "secret",
+                    "region": "cn-beijing"
+                }),
+                ..Default::default()
+            })
+            .await
+            .unwrap();
+        assert_eq!(provider.region.as_deref(), Some("cn-beijing"));
+    }
+
+    fn mock_provider(server: &MockServer) -> AliyunProvider {
+        let mut provider = AliyunProvider::with_endpoint(server.uri());
+        provider.access_key_id = Some("id".to_string());
+        provider.access_key_secret = Some("secret".to_string());
+        provider
+    }
+
+    #[tokio::test]
+    async fn test_create_record_with_spec() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(query_param("Action", "AddDomainRecord"))
+            .and(query_param("Type", "MX"))
+            .and(query_param("TTL", "300"))
+            .and(query_param("Priority", "5"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "RequestId": "req",
+                "RecordId": "9999"
+            })))
+            .expect(1)
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(query_param("Action", "UpdateDomainRecordRemark"))
+            .and(query_param("RecordId", "9999"))
+            .and(query_param("Remark", "mail"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "RequestId": "req"
+            })))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let provider = mock_provider(&server);
+        let spec = RecordSpec {
+            ttl: Some(300),
+            priority: Some(5),
+            comment: Some("mail".to_string()),
+            ..Default::default()
+        };
+        let record = provider
+            .create_record_with(
+                "example.com",
+                "@",
+                DNSRecordType::MX,
+                "mx.example.com",
+                &spec,
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(record.id, "9999");
+        assert_eq!(record.ttl, 300);
+        assert_eq!(record.priority, Some(5));
+        assert_eq!(record.comment.as_deref(), Some("mail"));
+    }
+
+    #[tokio::test]
+    async fn test_update_record_keeps_ttl() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(query_param("Action", "DescribeDomainRecordInfo"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "RecordId": "1",
+                "DomainName": "example.com",
+                "RR": "www",
+                "Type": "A",
+                "Value": "1.1.1.1",
+                "TTL": 60,
+                "Remark": "home"
+            })))
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(query_param("Action", "UpdateDomainRecord"))
+            .and(query_param("TTL", "60"))
+            .and(query_param("Value", "2.2.2.2"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "RequestId": "req",
+                "RecordId": "1"
+            })))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        // 备注未变化时不调用 UpdateDomainRecordRemark
+        let provider = mock_provider(&server);
+        let spec = RecordSpec {
+            comment: Some("home".to_string()),
+            ..Default::default()
+        };
+        let result = provider
+            .update_record_with("example.com", "1", "2.2.2.2", &spec)
+            .await
+            .unwrap();
+        assert_eq!(result.old_ip, "1.1.1.1");
+        assert_eq!(result.record_id, "1");
+    }
+
+    #[tokio::test]
+    async fn test_srv_record_round_trip() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(query_param("Action", "DescribeDomainRecords"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "TotalCount": 2,
+                "DomainRecords": { "Record": [
+                    {
+                        "RecordId": "1",
+                        "DomainName": "example.com",
+                        "RR": "_sip._tcp",
+                        "Type": "SRV",
+                        "Value": "10 5 5060 sip.example.com",
+                        "TTL": 600
+                    },
+                    {
+                        "RecordId": "2",
+                        "DomainName": "example.com",
+                        "RR": "@",
+                        "Type": "PTR",
+                        "Value": "host.example.com",
+                        "TTL": 600
+                    }
+                ]}
+            })))
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(query_param("Action", "AddDomainRecord"))
+            .and(query_param("Type", "SRV"))
+            .and(query_param("Value", "10 5 5060 sip.example.com"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "RequestId": "req",
+                "RecordId": "3"
+            })))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let provider = mock_provider(&server);
+        let records = provider.list_records("example.com").await.unwrap();
+        assert_eq!(records[0].record_type, DNSRecordType::SRV);
+        assert_eq!(records[0].content, "sip.example.com");
+        assert_eq!(
+            (records[0].priority, records[0].weight, records[0].port),
+            (Some(10), Some(5), Some(5060))
+        );
+        // 未内置的类型不再被当作 A 记录
+        assert_eq!(
+            records[1].record_type,
+            DNSRecordType::Other("PTR".to_string())
+        );
+
+        let record = provider
+            .create_record(
+                "example.com",
+                "_sip._tcp",
+                DNSRecordType::SRV,
+                &records[0].rdata(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(record.rdata(), "10 5 5060 sip.example.com");
+    }
+}
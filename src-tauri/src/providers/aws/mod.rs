@@ -1,18 +1,349 @@
-// AWS Route53 提供商 - Phase 4 后续实现
 use crate::error::{AppError, Result};
 use crate::providers::provider_trait::{
-    Credentials, DNSProvider, DNSRecord, DNSRecordType, ProviderError, UpdateResult,
+    full_record_name, Credentials, DNSProvider, DNSRecord, DNSRecordType, ProviderCapabilities,
+    ProviderError, RecordContent, RecordSpec, UpdateResult,
 };
+use crate::providers::rrset::{remove_value, replace_value, split_value_id, value_id};
+use crate::providers::signing::{canonical_query_string, hex_encode, hmac_sha256, sha256_hex};
 use async_trait::async_trait;
+use reqwest::{Client, Method};
+use std::time::Duration;
+
+/// Route53 API 版本
+const API_VERSION: &str = "2013-04-01";
+
+/// Route53 为全局服务, 签名区域固定为 us-east-1
+const SIGNING_REGION: &str = "us-east-1";
+
+/// 新建记录的默认 TTL
+const DEFAULT_TTL: u32 = 300;
+
+/// 未指定优先级时 MX 记录使用的优先级
+const DEFAULT_MX_PRIORITY: u16 = 10;
+
+/// 轮询 GetChange 的默认间隔
+const DEFAULT_CHANGE_POLL_INTERVAL: Duration = Duration::from_secs(5);
+
+/// 等待变更同步 (INSYNC) 的默认超时
+const DEFAULT_CHANGE_TIMEOUT: Duration = Duration::from_secs(120);
 
 /// AWS Route53 提供商
+///
+/// 记录集中的每个值对应一条记录, 记录 ID 为 `<完整名称>.|<类型>#<值>`
 pub struct AwsProvider {
-    initialized: bool,
+    access_key_id: Option<String>,
+    secret_access_key: Option<String>,
+    endpoint: String,
+    change_poll_interval: Duration,
+    change_timeout: Duration,
+    client: Client,
 }
 
+/// Route53 记录集
+#[derive(Debug, Clone)]
+struct ResourceRecordSet {
+    name: String,
+    record_type: String,
+    ttl: Option<u32>,
+    values: Vec<String>,
+    set_identifier: Option<String>,
+    alias: bool,
+}
+
+/// 记录集分页游标 (NextRecordName, NextRecordType, NextRecordIdentifier)
+type RecordSetCursor = (String, String, Option<String>);
+
 impl AwsProvider {
     pub fn new() -> Self {
-        Self { initialized: false }
+        Self::with_endpoint("https://route53.amazonaws.com")
+    }
+
+    /// 使用自定义 API 地址创建提供商 (例如 LocalStack 或测试服务器)
+    pub fn with_endpoint(endpoint: impl Into<String>) -> Self {
+        Self {
+            access_key_id: None,
+            secret_access_key: None,
+            endpoint: endpoint.into().trim_end_matches('/').to_string(),
+            change_poll_interval: DEFAULT_CHANGE_POLL_INTERVAL,
+            change_timeout: DEFAULT_CHANGE_TIMEOUT,
+            client: Client::builder()
+                .timeout(Duration::from_secs(30))
+                .build()
+                .unwrap(),
+        }
+    }
+
+    /// 设置轮询 GetChange 的间隔和等待变更同步的超时
+    pub fn with_change_polling(mut self, interval: Duration, timeout: Duration) -> Self {
+        self.change_poll_interval = interval;
+        self.change_timeout = timeout;
+        self
+    }
+
+    /// 发送签名后的 API 请求, 返回响应正文 (XML)
+    async fn send_request(
+        &self,
+        method: Method,
+        path: &str,
+        query: &[(&str, String)],
+        body: Option<String>,
+    ) -> Result<String> {
+        let (access_key_id, secret_access_key) =
+            match (&self.access_key_id, &self.secret_access_key) {
+                (Some(id), Some(secret)) => (id, secret),
+                _ => {
+                    return Err(AppError::Provider(ProviderError::AuthenticationFailed(
+                        "未设置 AWS 访问密钥".to_string(),
+                    )))
+                }
+            };
+
+        let url = reqwest::Url::parse(&format!("{}{}", self.endpoint, path))
+            .map_err(|e| AppError::Provider(ProviderError::InvalidConfig(e.to_string())))?;
+        let host = match (url.host_str(), url.port()) {
+            (Some(host), Some(port)) => format!("{}:{}", host, port),
+            (Some(host), None) => host.to_string(),
+            _ => {
+                return Err(AppError::Provider(ProviderError::InvalidConfig(format!(
+                    "无效的 API 地址: {}",
+                    self.endpoint
+                ))))
+            }
+        };
+
+        let canonical_query = canonical_query_string(query);
+        let payload = body.clone().unwrap_or_default();
+        let amz_date = chrono::Utc::now().format("%Y%m%dT%H%M%SZ").to_string();
+
+        let authorization = sign_v4(&SigningParams {
+            access_key_id,
+            secret_access_key,
+            region: SIGNING_REGION,
+            service: "route53",
+            method: method.as_str(),
+            host: &host,
+            path,
+            canonical_query: &canonical_query,
+            payload: &payload,
+            amz_date: &amz_date,
+        });
+
+        let full_url = if canonical_query.is_empty() {
+            format!("{}{}", self.endpoint, path)
+        } else {
+            format!("{}{}?{}", self.endpoint, path, canonical_query)
+        };
+
+        let mut request = self
+            .client
+            .request(method, &full_url)
+            .header("X-Amz-Date", &amz_date)
+            .header("Authorization", authorization);
+        if let Some(body) = body {
+            request = request.header("Content-Type", "text/xml").body(body);
+        }
+
+        let response = request.send().await.map_err(|e| {
+            AppError::Provider(ProviderError::NetworkError(format!("请求失败: {}", e)))
+        })?;
+
+        let status = response.status();
+        let text = response.text().await.map_err(|e| {
+            AppError::Provider(ProviderError::NetworkError(format!("读取响应失败: {}", e)))
+        })?;
+
+        if !status.is_success() {
+            return Err(parse_error(status.as_u16(), &text));
+        }
+
+        Ok(text)
+    }
+
+    /// 根据域名查找托管区域 ID
+    async fn get_hosted_zone_id(&self, domain: &str) -> Result<String> {
+        let dns_name = format!("{}.", domain.trim_end_matches('.'));
+        let text = self
+            .send_request(
+                Method::GET,
+                &format!("/{}/hostedzonesbyname", API_VERSION),
+                &[("dnsname", dns_name.clone()), ("maxitems", "1".to_string())],
+                None,
+            )
+            .await?;
+
+        let doc = parse_xml(&text)?;
+        doc.descendants()
+            .filter(|n| n.has_tag_name("HostedZone"))
+            .find(|zone| child_text(*zone, "Name").as_deref() == Some(dns_name.as_str()))
+            .and_then(|zone| child_text(zone, "Id"))
+            .map(|id| id.trim_start_matches("/hostedzone/").to_string())
+            .ok_or_else(|| {
+                AppError::Provider(ProviderError::DomainNotFound(format!(
+                    "找不到托管区域: {}",
+                    domain
+                )))
+            })
+    }
+
+    /// 获取一页记录集
+    async fn list_record_sets_page(
+        &self,
+        zone_id: &str,
+        start: Option<&RecordSetCursor>,
+        max_items: Option<u32>,
+    ) -> Result<(Vec<ResourceRecordSet>, Option<RecordSetCursor>)> {
+        let mut query = Vec::new();
+        if let Some((name, record_type, identifier)) = start {
+            query.push(("name", name.clone()));
+            query.push(("type", record_type.clone()));
+            if let Some(identifier) = identifier {
+                query.push(("identifier", identifier.clone()));
+            }
+        }
+        if let Some(max_items) = max_items {
+            query.push(("maxitems", max_items.to_string()));
+        }
+
+        let text = self
+            .send_request(
+                Method::GET,
+                &format!("/{}/hostedzone/{}/rrset", API_VERSION, zone_id),
+                &query,
+                None,
+            )
+            .await?;
+
+        parse_record_sets(&text)
+    }
+
+    /// 获取托管区域的所有记录集 (自动翻页)
+    async fn list_all_record_sets(&self, zone_id: &str) -> Result<Vec<ResourceRecordSet>> {
+        let mut record_sets = Vec::new();
+        let mut cursor = None;
+
+        loop {
+            let (page, next) = self
+                .list_record_sets_page(zone_id, cursor.as_ref(), None)
+                .await?;
+            record_sets.extend(page);
+
+            match next {
+                Some(next) => cursor = Some(next),
+                None => break,
+            }
+        }
+
+        Ok(record_sets)
+    }
+
+    /// 精确查找记录集
+    async fn find_record_set(
+        &self,
+        zone_id: &str,
+        name: &str,
+        record_type: &str,
+    ) -> Result<Option<ResourceRecordSet>> {
+        let start = (name.to_string(), record_type.to_string(), None);
+        let (record_sets, _) = self
+            .list_record_sets_page(zone_id, Some(&start), Some(1))
+            .await?;
+
+        Ok(record_sets
+            .into_iter()
+            .find(|r| same_name(&r.name, name) && r.record_type == record_type))
+    }
+
+    /// 提交记录集变更, 返回变更 ID 和状态
+    async fn change_record_set(
+        &self,
+        zone_id: &str,
+        action: &str,
+        record_set: &ResourceRecordSet,
+    ) -> Result<(String, String)> {
+        let values = record_set
+            .values
+            .iter()
+            .map(|v| {
+                format!(
+                    "<ResourceRecord><Value>{}</Value></ResourceRecord>",
+                    xml_escape(v)
+                )
+            })
+            .collect::<String>();
+
+        let body = format!(
+            r#"<?xml version="1.0" encoding="UTF-8"?>
+<ChangeResourceRecordSetsRequest xmlns="https://route53.amazonaws.com/doc/{version}/">
+  <ChangeBatch>
+    <Comment>ddns</Comment>
+    <Changes>
+      <Change>
+        <Action>{action}</Action>
+        <ResourceRecordSet>
+          <Name>{name}</Name>
+          <Type>{record_type}</Type>
+          <TTL>{ttl}</TTL>
+          <ResourceRecords>{values}</ResourceRecords>
+        </ResourceRecordSet>
+      </Change>
+    </Changes>
+  </ChangeBatch>
+</ChangeResourceRecordSetsRequest>"#,
+            version = API_VERSION,
+            action = action,
+            name = xml_escape(&record_set.name),
+            record_type = record_set.record_type,
+            ttl = record_set.ttl.unwrap_or(DEFAULT_TTL),
+            values = values,
+        );
+
+        let text = self
+            .send_request(
+                Method::POST,
+                &format!("/{}/hostedzone/{}/rrset/", API_VERSION, zone_id),
+                &[],
+                Some(body),
+            )
+            .await?;
+
+        parse_change_info(&text)
+    }
+
+    /// 提交记录集变更并轮询 GetChange 直到变更同步 (INSYNC), 超时返回错误
+    async fn apply_change(
+        &self,
+        zone_id: &str,
+        action: &str,
+        record_set: &ResourceRecordSet,
+    ) -> Result<()> {
+        let (change_id, mut status) = self.change_record_set(zone_id, action, record_set).await?;
+
+        let started = tokio::time::Instant::now();
+        while status != "INSYNC" {
+            if started.elapsed() >= self.change_timeout {
+                return Err(AppError::Provider(ProviderError::ApiError(format!(
+                    "等待 Route53 变更 {} 同步超时 ({} 秒), 当前状态: {}",
+                    change_id,
+                    self.change_timeout.as_secs(),
+                    status
+                ))));
+            }
+            tracing::debug!("Route53 变更 {} 状态: {}", change_id, status);
+            tokio::time::sleep(self.change_poll_interval).await;
+
+            let text = self
+                .send_request(
+                    Method::GET,
+                    &format!("/{}/change/{}", API_VERSION, change_id),
+                    &[],
+                    None,
+                )
+                .await?;
+            status = parse_change_info(&text)?.1;
+        }
+
+        tracing::debug!("Route53 变更 {} 已同步", change_id);
+        Ok(())
     }
 }
 
@@ -32,47 +363,1014 @@ impl DNSProvider for AwsProvider {
         "AWS Route53"
     }
 
-    async fn initialize(&mut self, _credentials: &Credentials) -> Result<()> {
-        self.initialized = true;
+    async fn initialize(&mut self, credentials: &Credentials) -> Result<()> {
+        self.access_key_id = credentials.access_key.clone().or_else(|| {
+            credentials
+                .extra
+                .get("access_key_id")
+                .and_then(|v| v.as_str())
+                .map(|v| v.to_string())
+        });
+        self.secret_access_key = credentials.api_secret.clone().or_else(|| {
+            credentials
+                .extra
+                .get("secret_access_key")
+                .and_then(|v| v.as_str())
+                .map(|v| v.to_string())
+        });
+        if let Some(timeout) = credentials.extra.get("change_timeout") {
+            let seconds = timeout
+                .as_u64()
+                .or_else(|| timeout.as_str().and_then(|v| v.trim().parse().ok()))
+                .ok_or_else(|| {
+                    AppError::Provider(ProviderError::InvalidConfig(format!(
+                        "change_timeout 必须为秒数: {}",
+                        timeout
+                    )))
+                })?;
+            self.change_timeout = Duration::from_secs(seconds);
+        }
+
+        if self.access_key_id.is_none() || self.secret_access_key.is_none() {
+            return Err(AppError::Provider(ProviderError::InvalidConfig(
+                "AWS Route53 需要提供 access_key (Access Key ID) 和 api_secret (Secret Access Key)"
+                    .to_string(),
+            )));
+        }
+
+        // 测试连接
+        if !self.test_connection().await? {
+            return Err(AppError::Provider(ProviderError::AuthenticationFailed(
+                "AWS 凭证验证失败".to_string(),
+            )));
+        }
+
         Ok(())
     }
 
-    async fn list_records(&self, _domain: &str) -> Result<Vec<DNSRecord>> {
-        Err(AppError::Provider(ProviderError::Unknown(
-            "AWS Route53 提供商尚未实现".to_string(),
-        )))
+    async fn list_records(&self, domain: &str) -> Result<Vec<DNSRecord>> {
+        let zone_id = self.get_hosted_zone_id(domain).await?;
+        let record_sets = self.list_all_record_sets(&zone_id).await?;
+
+        Ok(record_sets
+            .into_iter()
+            // 别名记录和带路由策略的记录不适用于 DDNS
+            .filter(|r| !r.alias && r.set_identifier.is_none())
+            .flat_map(|r| to_dns_records(&r))
+            .collect())
     }
 
     async fn update_record(
         &self,
-        _domain: &str,
-        _record_id: &str,
-        _new_content: &str,
+        domain: &str,
+        record_id: &str,
+        new_content: &str,
+    ) -> Result<UpdateResult> {
+        self.update_record_with(domain, record_id, new_content, &RecordSpec::default())
+            .await
+    }
+
+    async fn update_record_with(
+        &self,
+        domain: &str,
+        record_id: &str,
+        new_content: &str,
+        spec: &RecordSpec,
     ) -> Result<UpdateResult> {
-        Err(AppError::Provider(ProviderError::Unknown(
-            "AWS Route53 提供商尚未实现".to_string(),
-        )))
+        spec.ensure_supported(&self.capabilities())?;
+        let not_found = || AppError::Provider(ProviderError::RecordNotFound(record_id.to_string()));
+        let (rrset_id, value) = split_value_id(record_id);
+        let (name, record_type) = parse_record_id(rrset_id)?;
+        let zone_id = self.get_hosted_zone_id(domain).await?;
+
+        let current = self
+            .find_record_set(&zone_id, &name, &record_type)
+            .await?
+            .ok_or_else(not_found)?;
+        let old = to_dns_records(&current)
+            .into_iter()
+            .find(|r| value.is_none_or(|value| r.id == value_id(rrset_id, value)))
+            .ok_or_else(not_found)?;
+        let mut content = RecordContent::from_input(&old.record_type, new_content, spec)?;
+        content.priority = content.priority.or(old.priority);
+
+        // UPSERT 替换整个记录集, 因此只替换这一个值, 其余值原样提交
+        let new_value = content.to_rdata(&old.record_type);
+        let values =
+            replace_value(&current.values, value, new_value.clone()).ok_or_else(not_found)?;
+        let updated = ResourceRecordSet {
+            ttl: spec.ttl.or(current.ttl),
+            values,
+            ..current
+        };
+        self.apply_change(&zone_id, "UPSERT", &updated).await?;
+
+        Ok(UpdateResult {
+            success: true,
+            record_id: value_id(rrset_id, &new_value),
+            old_ip: old.rdata(),
+            new_ip: new_content.to_string(),
+            message: "更新成功".to_string(),
+        })
     }
 
     async fn create_record(
         &self,
-        _domain: &str,
-        _record_name: &str,
-        _record_type: DNSRecordType,
-        _content: &str,
+        domain: &str,
+        record_name: &str,
+        record_type: DNSRecordType,
+        content: &str,
+    ) -> Result<DNSRecord> {
+        self.create_record_with(
+            domain,
+            record_name,
+            record_type,
+            content,
+            &RecordSpec::default(),
+        )
+        .await
+    }
+
+    async fn create_record_with(
+        &self,
+        domain: &str,
+        record_name: &str,
+        record_type: DNSRecordType,
+        content: &str,
+        spec: &RecordSpec,
     ) -> Result<DNSRecord> {
-        Err(AppError::Provider(ProviderError::Unknown(
-            "AWS Route53 提供商尚未实现".to_string(),
-        )))
+        spec.ensure_supported(&self.capabilities())?;
+        let mut content = RecordContent::from_input(&record_type, content, spec)?;
+        if record_type == DNSRecordType::MX {
+            content.priority = content.priority.or(Some(DEFAULT_MX_PRIORITY));
+        }
+        let zone_id = self.get_hosted_zone_id(domain).await?;
+        let name = format!("{}.", full_record_name(domain, record_name));
+        let value = content.to_rdata(&record_type);
+
+        // UPSERT 会替换整个记录集, 同名同类型的记录集已存在时把新值追加到其中
+        let existing = self
+            .find_record_set(&zone_id, &name, &record_type.to_string())
+            .await?
+            .filter(|r| !r.alias && r.set_identifier.is_none());
+        let record_set = match existing {
+            Some(existing) => {
+                let mut values = existing.values.clone();
+                if !values.contains(&value) {
+                    values.push(value.clone());
+                }
+                ResourceRecordSet {
+                    ttl: spec.ttl.or(existing.ttl),
+                    values,
+                    ..existing
+                }
+            }
+            None => ResourceRecordSet {
+                name,
+                record_type: record_type.to_string(),
+                ttl: Some(spec.ttl.unwrap_or(DEFAULT_TTL)),
+                values: vec![value.clone()],
+                set_identifier: None,
+                alias: false,
+            },
+        };
+        self.apply_change(&zone_id, "UPSERT", &record_set).await?;
+
+        to_dns_records(&record_set)
+            .into_iter()
+            .find(|r| r.id == value_id(&record_id(&record_set), &value))
+            .ok_or_else(|| {
+                AppError::Provider(ProviderError::ParseError(
+                    "创建的记录集中没有记录值".to_string(),
+                ))
+            })
     }
 
-    async fn delete_record(&self, _domain: &str, _record_id: &str) -> Result<()> {
-        Err(AppError::Provider(ProviderError::Unknown(
-            "AWS Route53 提供商尚未实现".to_string(),
-        )))
+    async fn delete_record(&self, domain: &str, record_id: &str) -> Result<()> {
+        let not_found = || AppError::Provider(ProviderError::RecordNotFound(record_id.to_string()));
+        let (rrset_id, value) = split_value_id(record_id);
+        let (name, record_type) = parse_record_id(rrset_id)?;
+        let zone_id = self.get_hosted_zone_id(domain).await?;
+
+        let current = self
+            .find_record_set(&zone_id, &name, &record_type)
+            .await?
+            .ok_or_else(not_found)?;
+        let remaining = remove_value(&current.values, value).ok_or_else(not_found)?;
+
+        // 记录集中还有其他值时只移除这一个值;
+        // 否则删除整个记录集, DELETE 需要提交与当前记录集完全一致的内容
+        let (action, record_set) = if remaining.is_empty() {
+            ("DELETE", current)
+        } else {
+            (
+                "UPSERT",
+                ResourceRecordSet {
+                    values: remaining,
+                    ..current
+                },
+            )
+        };
+        self.apply_change(&zone_id, action, &record_set).await?;
+
+        Ok(())
     }
 
     async fn test_connection(&self) -> Result<bool> {
-        Ok(true)
+        match self
+            .send_request(
+                Method::GET,
+                &format!("/{}/hostedzonecount", API_VERSION),
+                &[],
+                None,
+            )
+            .await
+        {
+            Ok(_) => Ok(true),
+            Err(AppError::Provider(ProviderError::AuthenticationFailed(_))) => Ok(false),
+            Err(e) => Err(e),
+        }
+    }
+
+    fn supported_record_types(&self) -> Vec<DNSRecordType> {
+        vec![
+            DNSRecordType::A,
+            DNSRecordType::AAAA,
+            DNSRecordType::CNAME,
+            DNSRecordType::MX,
+            DNSRecordType::TXT,
+            DNSRecordType::SRV,
+            DNSRecordType::CAA,
+        ]
+    }
+
+    fn capabilities(&self) -> ProviderCapabilities {
+        ProviderCapabilities {
+            proxy: false,
+            ttl: true,
+            line: false,
+            priority: true,
+            comment: false,
+        }
+    }
+}
+
+// ============ SigV4 签名 ============
+
+/// SigV4 签名参数
+struct SigningParams<'a> {
+    access_key_id: &'a str,
+    secret_access_key: &'a str,
+    region: &'a str,
+    service: &'a str,
+    method: &'a str,
+    host: &'a str,
+    path: &'a str,
+    canonical_query: &'a str,
+    payload: &'a str,
+    amz_date: &'a str,
+}
+
+/// 构造规范请求 (签名头为 host 和 x-amz-date)
+fn canonical_request(params: &SigningParams) -> String {
+    format!(
+        "{}\n{}\n{}\nhost:{}\nx-amz-date:{}\n\nhost;x-amz-date\n{}",
+        params.method,
+        params.path,
+        params.canonical_query,
+        params.host,
+        params.amz_date,
+        sha256_hex(params.payload.as_bytes())
+    )
+}
+
+/// 计算 SigV4 Authorization 头
+fn sign_v4(params: &SigningParams) -> String {
+    let date = &params.amz_date[..8];
+    let scope = format!("{}/{}/{}/aws4_request", date, params.region, params.service);
+
+    let string_to_sign = format!(
+        "AWS4-HMAC-SHA256\n{}\n{}\n{}",
+        params.amz_date,
+        scope,
+        sha256_hex(canonical_request(params).as_bytes())
+    );
+
+    let key = signing_key(
+        params.secret_access_key,
+        date,
+        params.region,
+        params.service,
+    );
+    let signature = hex_encode(&hmac_sha256(&key, string_to_sign.as_bytes()));
+
+    format!(
+        "AWS4-HMAC-SHA256 Credential={}/{}, SignedHeaders=host;x-amz-date, Signature={}",
+        params.access_key_id, scope, signature
+    )
+}
+
+/// 派生签名密钥
+fn signing_key(secret: &str, date: &str, region: &str, service: &str) -> Vec<u8> {
+    let k_date = hmac_sha256(format!("AWS4{}", secret).as_bytes(), date.as_bytes());
+    let k_region = hmac_sha256(&k_date, region.as_bytes());
+    let k_service = hmac_sha256(&k_region, service.as_bytes());
+    hmac_sha256(&k_service, b"aws4_request")
+}
+
+// ============ XML 解析 ============
+
+fn parse_xml(text: &str) -> Result<roxmltree::Document<'_>> {
+    roxmltree::Document::parse(text)
+        .map_err(|e| AppError::Provider(ProviderError::ParseError(format!("解析 XML 失败: {}", e))))
+}
+
+/// 读取子元素文本
+fn child_text(node: roxmltree::Node, tag: &str) -> Option<String> {
+    node.children()
+        .find(|n| n.has_tag_name(tag))
+        .and_then(|n| n.text())
+        .map(|t| t.to_string())
+}
+
+/// 解析 ListResourceRecordSets 响应
+fn parse_record_sets(text: &str) -> Result<(Vec<ResourceRecordSet>, Option<RecordSetCursor>)> {
+    let doc = parse_xml(text)?;
+    let root = doc.root_element();
+
+    let record_sets = root
+        .descendants()
+        .filter(|n| n.has_tag_name("ResourceRecordSet"))
+        .map(|node| ResourceRecordSet {
+            name: child_text(node, "Name").unwrap_or_default(),
+            record_type: child_text(node, "Type").unwrap_or_default(),
+            ttl: child_text(node, "TTL").and_then(|t| t.parse().ok()),
+            values: node
+                .descendants()
+                .filter(|n| n.has_tag_name("Value"))
+                .filter_map(|n| n.text())
+                .map(|t| t.to_string())
+                .collect(),
+            set_identifier: child_text(node, "SetIdentifier"),
+            alias: node.children().any(|n| n.has_tag_name("AliasTarget")),
+        })
+        .collect();
+
+    let next = if child_text(root, "IsTruncated").as_deref() == Some("true") {
+        match (
+            child_text(root, "NextRecordName"),
+            child_text(root, "NextRecordType"),
+        ) {
+            (Some(name), Some(record_type)) => {
+                Some((name, record_type, child_text(root, "NextRecordIdentifier")))
+            }
+            _ => None,
+        }
+    } else {
+        None
+    };
+
+    Ok((record_sets, next))
+}
+
+/// 解析 ChangeInfo (返回变更 ID 和状态)
+fn parse_change_info(text: &str) -> Result<(String, String)> {
+    let doc = parse_xml(text)?;
+    let info = doc
+        .descendants()
+        .find(|n| n.has_tag_name("ChangeInfo"))
+        .ok_or_else(|| {
+            AppError::Provider(ProviderError::ParseError(
+                "响应中缺少 ChangeInfo".to_string(),
+            ))
+        })?;
+
+    let id = child_text(info, "Id").unwrap_or_default();
+    let status = child_text(info, "Status").unwrap_or_default();
+    Ok((id.trim_start_matches("/change/").to_string(), status))
+}
+
+/// 将 Route53 错误响应映射为 ProviderError
+fn parse_error(status: u16, text: &str) -> AppError {
+    let (code, message) = match roxmltree::Document::parse(text) {
+        Ok(doc) => {
+            let root = doc.root_element();
+            let code = doc
+                .descendants()
+                .find(|n| n.has_tag_name("Code"))
+                .and_then(|n| n.text())
+                .unwrap_or_else(|| root.tag_name().name())
+                .to_string();
+            let message = doc
+                .descendants()
+                .find(|n| n.has_tag_name("Message"))
+                .and_then(|n| n.text())
+                .unwrap_or_default()
+                .to_string();
+            (code, message)
+        }
+        Err(_) => (String::new(), text.to_string()),
+    };
+
+    let detail = format!("HTTP {} {}: {}", status, code, message);
+    let error = match code.as_str() {
+        "InvalidClientTokenId"
+        | "SignatureDoesNotMatch"
+        | "IncompleteSignature"
+        | "AccessDenied"
+        | "AccessDeniedException"
+        | "MissingAuthenticationToken" => ProviderError::AuthenticationFailed(detail),
+        "Throttling" | "ThrottlingException" | "PriorRequestNotComplete" => {
+            ProviderError::RateLimitExceeded
+        }
+        "NoSuchHostedZone" => ProviderError::DomainNotFound(detail),
+        _ if status == 403 => ProviderError::AuthenticationFailed(detail),
+        _ => ProviderError::ApiError(detail),
+    };
+
+    AppError::Provider(error)
+}
+
+// ============ 辅助函数 ============
+
+/// 记录集 ID 格式: `<完整名称>.|<类型>`
+fn record_id(record_set: &ResourceRecordSet) -> String {
+    format!("{}|{}", record_set.name, record_set.record_type)
+}
+
+fn parse_record_id(record_id: &str) -> Result<(String, String)> {
+    record_id
+        .rsplit_once('|')
+        .map(|(name, record_type)| (name.to_string(), record_type.to_string()))
+        .ok_or_else(|| {
+            AppError::Provider(ProviderError::InvalidConfig(format!(
+                "无效的 Route53 记录 ID: {}",
+                record_id
+            )))
+        })
+}
+
+/// 转换为通用记录 (记录集中的每个值各一条), 未内置的类型以 `Other` 保留
+fn to_dns_records(record_set: &ResourceRecordSet) -> Vec<DNSRecord> {
+    let record_type: DNSRecordType = record_set
+        .record_type
+        .parse()
+        .unwrap_or_else(|_| DNSRecordType::Other(record_set.record_type.clone()));
+    let id = record_id(record_set);
+
+    record_set
+        .values
+        .iter()
+        .map(|value| {
+            let content = RecordContent::parse(&record_type, value);
+            DNSRecord {
+                id: value_id(&id, value),
+                name: unescape_name(record_set.name.trim_end_matches('.')),
+                record_type: record_type.clone(),
+                content: content.content,
+                ttl: record_set.ttl.unwrap_or(DEFAULT_TTL),
+                proxied: None,
+                priority: content.priority,
+                weight: content.weight,
+                port: content.port,
+                comment: None,
+            }
+        })
+        .collect()
+}
+
+/// Route53 以 `\052` 形式转义通配符等特殊字符
+fn unescape_name(name: &str) -> String {
+    let mut result = String::with_capacity(name.len());
+    let mut chars = name.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        if c == '\\' {
+            let digits: String = chars.by_ref().take(3).collect();
+            match u8::from_str_radix(&digits, 8) {
+                Ok(byte) => result.push(byte as char),
+                Err(_) => {
+                    result.push(c);
+                    result.push_str(&digits);
+                }
+            }
+        } else {
+            result.push(c);
+        }
+    }
+
+    result
+}
+
+fn same_name(a: &str, b: &str) -> bool {
+    a.trim_end_matches('.')
+        .eq_ignore_ascii_case(b.trim_end_matches('.'))
+}
+
+fn xml_escape(s: &str) -> String {
+    s.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use wiremock::matchers::{body_string_contains, method, path, query_param};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    const ZONES: &str = r#"<?xml version="1.0"?>
+<ListHostedZonesByNameResponse xmlns="https://route53.amazonaws.com/doc/2013-04-01/">
+  <HostedZones>
+    <HostedZone><Id>/hostedzone/Z123</Id><Name>example.com.</Name></HostedZone>
+  </HostedZones>
+  <IsTruncated>false</IsTruncated>
+</ListHostedZonesByNameResponse>"#;
+
+    fn record_sets_page(records: &str, next: Option<(&str, &str)>) -> String {
+        let truncated = match next {
+            Some((name, record_type)) => format!(
+                "<IsTruncated>true</IsTruncated><NextRecordName>{}</NextRecordName><NextRecordType>{}</NextRecordType>",
+                name, record_type
+            ),
+            None => "<IsTruncated>false</IsTruncated>".to_string(),
+        };
+        format!(
+            r#"<?xml version="1.0"?>
+<ListResourceRecordSetsResponse xmlns="https://route53.amazonaws.com/doc/2013-04-01/">
+  <ResourceRecordSets>{}</ResourceRecordSets>{}<MaxItems>100</MaxItems>
+</ListResourceRecordSetsResponse>"#,
+            records, truncated
+        )
+    }
+
+    fn record_set(name: &str, record_type: &str, value: &str) -> String {
+        multi_value_record_set(name, record_type, &[value])
+    }
+
+    fn multi_value_record_set(name: &str, record_type: &str, values: &[&str]) -> String {
+        let values: String = values
+            .iter()
+            .map(|v| format!("<ResourceRecord><Value>{}</Value></ResourceRecord>", v))
+            .collect();
+        format!(
+            "<ResourceRecordSet><Name>{}</Name><Type>{}</Type><TTL>300</TTL><ResourceRecords>{}</ResourceRecords></ResourceRecordSet>",
+            name, record_type, values
+        )
+    }
+
+    fn change_info(status: &str) -> String {
+        format!(
+            r#"<?xml version="1.0"?>
+<ChangeResourceRecordSetsResponse xmlns="https://route53.amazonaws.com/doc/2013-04-01/">
+  <ChangeInfo><Id>/change/C42</Id><Status>{}</Status><SubmittedAt>2024-01-01T00:00:00Z</SubmittedAt></ChangeInfo>
+</ChangeResourceRecordSetsResponse>"#,
+            status
+        )
+    }
+
+    async fn mock_provider(server: &MockServer) -> AwsProvider {
+        Mock::given(method("GET"))
+            .and(path("/2013-04-01/hostedzonecount"))
+            .respond_with(ResponseTemplate::new(200).set_body_string(
+                "<GetHostedZoneCountResponse><HostedZoneCount>1</HostedZoneCount></GetHostedZoneCountResponse>",
+            ))
+            .mount(server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/2013-04-01/hostedzonesbyname"))
+            .and(query_param("dnsname", "example.com."))
+            .respond_with(ResponseTemplate::new(200).set_body_string(ZONES))
+            .mount(server)
+            .await;
+
+        let mut provider = AwsProvider::with_endpoint(server.uri())
+            .with_change_polling(Duration::from_millis(10), Duration::from_secs(5));
+        provider
+            .initialize(&Credentials {
+                access_key: Some("AKIDEXAMPLE".to_string()),
+                api_secret: Some("wJalrXUtnFEMI/K7MDENG+bPxRfiCYEXAMPLEKEY".to_string()),
+                ..Default::default()
+            })
+            .await
+            .unwrap();
+        provider
+    }
+
+    #[test]
+    fn test_signing_key() {
+        // AWS 文档中的派生密钥示例
+        let key = signing_key(
+            "wJalrXUtnFEMI/K7MDENG+bPxRfiCYEXAMPLEKEY",
+            "20120215",
+            "us-east-1",
+            "iam",
+        );
+        assert_eq!(
+            hex_encode(&key),
+            "f4780e2d9f65fa895f9c67b32ce1baf0b0d8a43505a000a1a9e090d414db404d"
+        );
+    }
+
+    #[test]
+    fn test_sign_v4_get_vanilla() {
+        // AWS SigV4 测试套件 get-vanilla 用例
+        let authorization = sign_v4(&SigningParams {
+            access_key_id: "AKIDEXAMPLE",
+            secret_access_key: "wJalrXUtnFEMI/K7MDENG+bPxRfiCYEXAMPLEKEY",
+            region: "us-east-1",
+            service: "service",
+            method: "GET",
+            host: "example.amazonaws.com",
+            path: "/",
+            canonical_query: "",
+            payload: "",
+            amz_date: "20150830T123600Z",
+        });
+        assert_eq!(
+            authorization,
+            "AWS4-HMAC-SHA256 Credential=AKIDEXAMPLE/20150830/us-east-1/service/aws4_request, \
+             SignedHeaders=host;x-amz-date, \
+             Signature=5fa00fa31553b73ebf1942676e86291e8372ff2a2260956d9b8aae1d763fbf31"
+        );
+    }
+
+    #[test]
+    fn test_canonical_query_string() {
+        let query = [
+            ("type", "A".to_string()),
+            ("name", "*.example.com.".to_string()),
+        ];
+        assert_eq!(
+            canonical_query_string(&query),
+            "name=%2A.example.com.&type=A"
+        );
+    }
+
+    #[test]
+    fn test_unescape_name() {
+        assert_eq!(unescape_name("\\052.example.com"), "*.example.com");
+        assert_eq!(unescape_name("www.example.com"), "www.example.com");
+    }
+
+    #[test]
+    fn test_parse_error() {
+        let body = r#"<ErrorResponse><Error><Type>Sender</Type><Code>SignatureDoesNotMatch</Code><Message>bad signature</Message></Error></ErrorResponse>"#;
+        assert!(matches!(
+            parse_error(403, body),
+            AppError::Provider(ProviderError::AuthenticationFailed(_))
+        ));
+
+        let body = r#"<ErrorResponse><Error><Code>Throttling</Code><Message>Rate exceeded</Message></Error></ErrorResponse>"#;
+        assert!(matches!(
+            parse_error(400, body),
+            AppError::Provider(ProviderError::RateLimitExceeded)
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_list_records_paging() {
+        let server = MockServer::start().await;
+
+        Mock::given(method("GET"))
+            .and(path("/2013-04-01/hostedzone/Z123/rrset"))
+            .and(query_param("name", "www.example.com."))
+            .respond_with(ResponseTemplate::new(200).set_body_string(record_sets_page(
+                &record_set("www.example.com.", "A", "1.1.1.1"),
+                None,
+            )))
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/2013-04-01/hostedzone/Z123/rrset"))
+            .respond_with(ResponseTemplate::new(200).set_body_string(record_sets_page(
+                &format!(
+                    "{}{}",
+                    record_set("example.com.", "A", "2.2.2.2"),
+                    record_set("example.com.", "SPF", "\"v=spf1 -all\"")
+                ),
+                Some(("www.example.com.", "A")),
+            )))
+            .mount(&server)
+            .await;
+
+        let provider = mock_provider(&server).await;
+        let records = provider.list_records("example.com").await.unwrap();
+
+        assert_eq!(records.len(), 3);
+        assert_eq!(records[0].name, "example.com");
+        // 未内置的类型以 Other 保留
+        assert_eq!(
+            records[1].record_type,
+            DNSRecordType::Other("SPF".to_string())
+        );
+        assert_eq!(records[2].name, "www.example.com");
+        assert_eq!(records[2].id, "www.example.com.|A#1.1.1.1");
+        assert_eq!(records[2].content, "1.1.1.1");
+    }
+
+    #[tokio::test]
+    async fn test_update_record_waits_for_insync() {
+        let server = MockServer::start().await;
+
+        Mock::given(method("GET"))
+            .and(path("/2013-04-01/hostedzone/Z123/rrset"))
+            .and(query_param("name", "www.example.com."))
+            .and(query_param("type", "A"))
+            .respond_with(ResponseTemplate::new(200).set_body_string(record_sets_page(
+                &record_set("www.example.com.", "A", "1.1.1.1"),
+                None,
+            )))
+            .mount(&server)
+            .await;
+        Mock::given(method("POST"))
+            .and(path("/2013-04-01/hostedzone/Z123/rrset/"))
+            .and(body_string_contains("<Action>UPSERT</Action>"))
+            .and(body_string_contains("<Value>3.3.3.3</Value>"))
+            .respond_with(ResponseTemplate::new(200).set_body_string(change_info("PENDING")))
+            .expect(1)
+            .mount(&server)
+            .await;
+        // 第一次 GetChange 仍为 PENDING, 第二次同步完成
+        Mock::given(method("GET"))
+            .and(path("/2013-04-01/change/C42"))
+            .respond_with(ResponseTemplate::new(200).set_body_string(change_info("PENDING")))
+            .up_to_n_times(1)
+            .expect(1)
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/2013-04-01/change/C42"))
+            .respond_with(ResponseTemplate::new(200).set_body_string(change_info("INSYNC")))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let provider = mock_provider(&server).await;
+        let result = provider
+            .update_record("example.com", "www.example.com.|A#1.1.1.1", "3.3.3.3")
+            .await
+            .unwrap();
+
+        assert!(result.success);
+        assert_eq!(result.record_id, "www.example.com.|A#3.3.3.3");
+        assert_eq!(result.old_ip, "1.1.1.1");
+        assert_eq!(result.new_ip, "3.3.3.3");
+        assert_eq!(result.message, "更新成功");
+    }
+
+    #[tokio::test]
+    async fn test_change_not_in_sync_times_out() {
+        let server = MockServer::start().await;
+
+        Mock::given(method("GET"))
+            .and(path("/2013-04-01/hostedzone/Z123/rrset"))
+            .respond_with(ResponseTemplate::new(200).set_body_string(record_sets_page(
+                &record_set("www.example.com.", "A", "1.1.1.1"),
+                None,
+            )))
+            .mount(&server)
+            .await;
+        Mock::given(method("POST"))
+            .and(path("/2013-04-01/hostedzone/Z123/rrset/"))
+            .respond_with(ResponseTemplate::new(200).set_body_string(change_info("PENDING")))
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/2013-04-01/change/C42"))
+            .respond_with(ResponseTemplate::new(200).set_body_string(change_info("PENDING")))
+            .mount(&server)
+            .await;
+
+        let provider = mock_provider(&server)
+            .await
+            .with_change_polling(Duration::from_millis(10), Duration::from_millis(50));
+        let err = provider
+            .update_record("example.com", "www.example.com.|A#1.1.1.1", "3.3.3.3")
+            .await
+            .unwrap_err();
+
+        assert!(err.to_string().contains("同步超时"), "{}", err);
+    }
+
+    #[tokio::test]
+    async fn test_change_timeout_from_credentials() {
+        let server = MockServer::start().await;
+        let mut provider = mock_provider(&server).await;
+        provider
+            .initialize(&Credentials {
+                access_key: Some("AKIDEXAMPLE".to_string()),
+                api_secret: Some("secret".to_string()),
+                extra: serde_json::json!({ "change_timeout": "30" }),
+                ..Default::default()
+            })
+            .await
+            .unwrap();
+        assert_eq!(provider.change_timeout, Duration::from_secs(30));
+
+        let err = provider
+            .initialize(&Credentials {
+                access_key: Some("AKIDEXAMPLE".to_string()),
+                api_secret: Some("secret".to_string()),
+                extra: serde_json::json!({ "change_timeout": "soon" }),
+                ..Default::default()
+            })
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains("change_timeout"), "{}", err);
+    }
+
+    #[tokio::test]
+    async fn test_update_record_with_ttl() {
+        let server = MockServer::start().await;
+
+        Mock::given(method("GET"))
+            .and(path("/2013-04-01/hostedzone/Z123/rrset"))
+            .respond_with(ResponseTemplate::new(200).set_body_string(record_sets_page(
+                &record_set("www.example.com.", "A", "1.1.1.1"),
+                None,
+            )))
+            .mount(&server)
+            .await;
+        Mock::given(method("POST"))
+            .and(path("/2013-04-01/hostedzone/Z123/rrset/"))
+            .and(body_string_contains("<TTL>60</TTL>"))
+            .and(body_string_contains("<Value>3.3.3.3</Value>"))
+            .respond_with(ResponseTemplate::new(200).set_body_string(change_info("INSYNC")))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let provider = mock_provider(&server).await;
+        let spec = RecordSpec {
+            ttl: Some(60),
+            ..Default::default()
+        };
+        provider
+            .update_record_with("example.com", "www.example.com.|A", "3.3.3.3", &spec)
+            .await
+            .unwrap();
+
+        // Route53 没有独立的备注字段
+        let spec = RecordSpec {
+            comment: Some("home".to_string()),
+            ..Default::default()
+        };
+        assert!(provider
+            .update_record_with("example.com", "www.example.com.|A", "3.3.3.3", &spec)
+            .await
+            .is_err());
+    }
+
+    #[tokio::test]
+    async fn test_delete_record_sends_current_values() {
+        let server = MockServer::start().await;
+
+        Mock::given(method("GET"))
+            .and(path("/2013-04-01/hostedzone/Z123/rrset"))
+            .respond_with(ResponseTemplate::new(200).set_body_string(record_sets_page(
+                &record_set("www.example.com.", "A", "1.1.1.1"),
+                None,
+            )))
+            .mount(&server)
+            .await;
+        Mock::given(method("POST"))
+            .and(path("/2013-04-01/hostedzone/Z123/rrset/"))
+            .and(body_string_contains("<Action>DELETE</Action>"))
+            .and(body_string_contains("<Value>1.1.1.1</Value>"))
+            .respond_with(ResponseTemplate::new(200).set_body_string(change_info("INSYNC")))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let provider = mock_provider(&server).await;
+        provider
+            .delete_record("example.com", "www.example.com.|A")
+            .await
+            .unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_multi_value_record_set_edits_one_value() {
+        let server = MockServer::start().await;
+
+        Mock::given(method("GET"))
+            .and(path("/2013-04-01/hostedzone/Z123/rrset"))
+            .respond_with(ResponseTemplate::new(200).set_body_string(record_sets_page(
+                &multi_value_record_set("www.example.com.", "A", &["1.1.1.1", "2.2.2.2"]),
+                None,
+            )))
+            .mount(&server)
+            .await;
+        Mock::given(method("POST"))
+            .and(path("/2013-04-01/hostedzone/Z123/rrset/"))
+            .and(body_string_contains("<Action>UPSERT</Action>"))
+            .and(body_string_contains(
+                "<ResourceRecords><ResourceRecord><Value>3.3.3.3</Value></ResourceRecord>\
+                 <ResourceRecord><Value>2.2.2.2</Value></ResourceRecord></ResourceRecords>",
+            ))
+            .respond_with(ResponseTemplate::new(200).set_body_string(change_info("INSYNC")))
+            .expect(1)
+            .mount(&server)
+            .await;
+        Mock::given(method("POST"))
+            .and(path("/2013-04-01/hostedzone/Z123/rrset/"))
+            .and(body_string_contains("<Action>UPSERT</Action>"))
+            .and(body_string_contains(
+                "<ResourceRecords><ResourceRecord><Value>1.1.1.1</Value></ResourceRecord></ResourceRecords>",
+            ))
+            .respond_with(ResponseTemplate::new(200).set_body_string(change_info("INSYNC")))
+            .expect(1)
+            .mount(&server)
+            .await;
+        Mock::given(method("POST"))
+            .and(path("/2013-04-01/hostedzone/Z123/rrset/"))
+            .and(body_string_contains(
+                "<ResourceRecords><ResourceRecord><Value>1.1.1.1</Value></ResourceRecord>\
+                 <ResourceRecord><Value>2.2.2.2</Value></ResourceRecord>\
+                 <ResourceRecord><Value>4.4.4.4</Value></ResourceRecord></ResourceRecords>",
+            ))
+            .respond_with(ResponseTemplate::new(200).set_body_string(change_info("INSYNC")))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let provider = mock_provider(&server).await;
+        let records = provider.list_records("example.com").await.unwrap();
+        assert_eq!(records.len(), 2);
+        assert_eq!(records[1].id, "www.example.com.|A#2.2.2.2");
+
+        let result = provider
+            .update_record("example.com", "www.example.com.|A#1.1.1.1", "3.3.3.3")
+            .await
+            .unwrap();
+        assert_eq!(result.old_ip, "1.1.1.1");
+
+        // 删除其中一个值时提交剩余的值
+        provider
+            .delete_record("example.com", "www.example.com.|A#2.2.2.2")
+            .await
+            .unwrap();
+
+        // 创建时追加到已有的记录集, 而不是覆盖
+        let record = provider
+            .create_record("example.com", "www", DNSRecordType::A, "4.4.4.4")
+            .await
+            .unwrap();
+        assert_eq!(record.id, "www.example.com.|A#4.4.4.4");
+
+        // 不含值的 ID 不能确定要修改多值记录集中的哪一个值
+        assert!(matches!(
+            provider
+                .update_record("example.com", "www.example.com.|A", "3.3.3.3")
+                .await,
+            Err(AppError::Provider(ProviderError::RecordNotFound(_)))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_update_mx_record_with_priority() {
+        let server = MockServer::start().await;
+
+        Mock::given(method("GET"))
+            .and(path("/2013-04-01/hostedzone/Z123/rrset"))
+            .respond_with(ResponseTemplate::new(200).set_body_string(record_sets_page(
+                &record_set("example.com.", "MX", "10 mail.example.com."),
+                None,
+            )))
+            .mount(&server)
+            .await;
+        Mock::given(method("POST"))
+            .and(path("/2013-04-01/hostedzone/Z123/rrset/"))
+            .and(body_string_contains("<Value>20 mx.example.com.</Value>"))
+            .respond_with(ResponseTemplate::new(200).set_body_string(change_info("INSYNC")))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let provider = mock_provider(&server).await;
+        let records = provider.list_records("example.com").await.unwrap();
+        assert_eq!(records[0].content, "mail.example.com.");
+        assert_eq!(records[0].priority, Some(10));
+
+        let spec = RecordSpec {
+            priority: Some(20),
+            ..Default::default()
+        };
+        let result = provider
+            .update_record_with("example.com", "example.com.|MX", "mx.example.com.", &spec)
+            .await
+            .unwrap();
+        assert_eq!(result.old_ip, "10 mail.example.com.");
+    }
+
+    #[tokio::test]
+    async fn test_unknown_zone() {
+        let server = MockServer::start().await;
+        let provider = mock_provider(&server).await;
+
+        let result = provider.list_records("other.org").await;
+        assert!(result.is_err());
     }
 }
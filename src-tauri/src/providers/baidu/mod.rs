@@ -0,0 +1,815 @@
+use crate::error::{AppError, Result};
+use crate::providers::provider_trait::{
+    Credentials, DNSProvider, DNSRecord, DNSRecordType, ProviderCapabilities, ProviderError,
+    RecordContent, RecordSpec, UpdateResult,
+};
+use crate::providers::signing::{canonical_query_string, hex_encode, hmac_sha256, URI_ENCODE_SET};
+use async_trait::async_trait;
+use percent_encoding::utf8_percent_encode;
+use reqwest::{Client, Method};
+use serde::de::DeserializeOwned;
+use serde::Deserialize;
+use serde_json::json;
+
+/// 签名有效期 (秒)
+const EXPIRATION_SECONDS: u32 = 1800;
+
+/// 新建记录的默认 TTL
+const DEFAULT_TTL: u32 = 300;
+
+/// 记录列表每页数量
+const PAGE_SIZE: usize = 1000;
+
+/// 百度智能云 DNS 提供商
+pub struct BaiduProvider {
+    access_key_id: Option<String>,
+    secret_access_key: Option<String>,
+    endpoint: String,
+    client: Client,
+}
+
+/// 解析记录
+#[derive(Debug, Deserialize)]
+struct BaiduRecord {
+    id: String,
+    rr: String,
+    #[serde(rename = "type")]
+    record_type: String,
+    value: String,
+    #[serde(default)]
+    ttl: Option<u32>,
+    #[serde(default)]
+    priority: Option<u16>,
+    #[serde(default)]
+    description: Option<String>,
+}
+
+/// 记录列表 (按 marker 分页)
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct BaiduRecordList {
+    records: Vec<BaiduRecord>,
+    #[serde(default)]
+    is_truncated: bool,
+    #[serde(default)]
+    next_marker: Option<String>,
+}
+
+impl BaiduProvider {
+    pub fn new() -> Self {
+        Self::with_endpoint("https://dns.baidubce.com")
+    }
+
+    /// 使用自定义 API 地址创建提供商 (例如测试服务器)
+    pub fn with_endpoint(endpoint: impl Into<String>) -> Self {
+        Self {
+            access_key_id: None,
+            secret_access_key: None,
+            endpoint: endpoint.into().trim_end_matches('/').to_string(),
+            client: Client::builder()
+                .timeout(std::time::Duration::from_secs(30))
+                .build()
+                .unwrap(),
+        }
+    }
+
+    /// 发送签名后的 API 请求, 返回响应正文
+    async fn send_request(
+        &self,
+        method: Method,
+        path: &str,
+        query: &[(&str, String)],
+        body: Option<serde_json::Value>,
+    ) -> Result<String> {
+        let (access_key_id, secret_access_key) =
+            match (&self.access_key_id, &self.secret_access_key) {
+                (Some(id), Some(secret)) => (id, secret),
+                _ => {
+                    return Err(AppError::Provider(ProviderError::AuthenticationFailed(
+                        "未设置百度云 AccessKey".to_string(),
+                    )))
+                }
+            };
+
+        let url = reqwest::Url::parse(&format!("{}{}", self.endpoint, path))
+            .map_err(|e| AppError::Provider(ProviderError::InvalidConfig(e.to_string())))?;
+        let host = match (url.host_str(), url.port()) {
+            (Some(host), Some(port)) => format!("{}:{}", host, port),
+            (Some(host), None) => host.to_string(),
+            _ => {
+                return Err(AppError::Provider(ProviderError::InvalidConfig(format!(
+                    "无效的 API 地址: {}",
+                    self.endpoint
+                ))))
+            }
+        };
+
+        let canonical_query = canonical_query_string(query);
+        let timestamp = chrono::Utc::now().format("%Y-%m-%dT%H:%M:%SZ").to_string();
+
+        let authorization = sign_bce(&SigningParams {
+            access_key_id,
+            secret_access_key,
+            method: method.as_str(),
+            path,
+            canonical_query: &canonical_query,
+            headers: &[("host", &host), ("x-bce-date", &timestamp)],
+            timestamp: &timestamp,
+            expiration: EXPIRATION_SECONDS,
+        });
+
+        let full_url = if canonical_query.is_empty() {
+            format!("{}{}", self.endpoint, path)
+        } else {
+            format!("{}{}?{}", self.endpoint, path, canonical_query)
+        };
+
+        let mut request = self
+            .client
+            .request(method, &full_url)
+            .header("x-bce-date", &timestamp)
+            .header("Authorization", authorization);
+        if let Some(body) = body {
+            request = request
+                .header("Content-Type", "application/json")
+                .body(body.to_string());
+        }
+
+        let response = request.send().await.map_err(|e| {
+            AppError::Provider(ProviderError::NetworkError(format!("请求失败: {}", e)))
+        })?;
+
+        let status = response.status();
+        let text = response.text().await.map_err(|e| {
+            AppError::Provider(ProviderError::NetworkError(format!("读取响应失败: {}", e)))
+        })?;
+
+        if !status.is_success() {
+            return Err(parse_error(status.as_u16(), &text));
+        }
+
+        Ok(text)
+    }
+
+    /// 发送请求并解析 JSON 响应
+    async fn send<T: DeserializeOwned>(
+        &self,
+        method: Method,
+        path: &str,
+        query: &[(&str, String)],
+    ) -> Result<T> {
+        let text = self.send_request(method, path, query, None).await?;
+        serde_json::from_str(&text).map_err(|e| {
+            AppError::Provider(ProviderError::ParseError(format!("解析响应失败: {}", e)))
+        })
+    }
+
+    /// 分页查询记录, `rr` 为主机记录过滤条件
+    async fn list_zone_records(&self, domain: &str, rr: Option<&str>) -> Result<Vec<BaiduRecord>> {
+        let path = format!("/v1/dns/zone/{}/record", domain);
+        let mut records = Vec::new();
+        let mut marker: Option<String> = None;
+
+        loop {
+            let mut query = vec![("maxKeys", PAGE_SIZE.to_string())];
+            if let Some(rr) = rr {
+                query.push(("rr", rr.to_string()));
+            }
+            if let Some(marker) = &marker {
+                query.push(("marker", marker.clone()));
+            }
+
+            let page: BaiduRecordList = self.send(Method::GET, &path, &query).await?;
+            records.extend(page.records);
+
+            match page
+                .next_marker
+                .filter(|m| page.is_truncated && !m.is_empty())
+            {
+                Some(next) => marker = Some(next),
+                None => break,
+            }
+        }
+
+        Ok(records)
+    }
+
+    /// 按 ID 查找记录
+    async fn find_record(&self, domain: &str, record_id: &str) -> Result<BaiduRecord> {
+        self.list_zone_records(domain, None)
+            .await?
+            .into_iter()
+            .find(|r| r.id == record_id)
+            .ok_or_else(|| AppError::Provider(ProviderError::RecordNotFound(record_id.to_string())))
+    }
+}
+
+impl Default for BaiduProvider {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[async_trait]
+impl DNSProvider for BaiduProvider {
+    fn provider_id(&self) -> &'static str {
+        "baidu"
+    }
+
+    fn provider_name(&self) -> &'static str {
+        "Baidu Cloud (百度智能云)"
+    }
+
+    async fn initialize(&mut self, credentials: &Credentials) -> Result<()> {
+        self.access_key_id = credentials.access_key.clone().or_else(|| {
+            credentials
+                .extra
+                .get("access_key_id")
+                .and_then(|v| v.as_str())
+                .map(|v| v.to_string())
+        });
+        self.secret_access_key = credentials.api_secret.clone().or_else(|| {
+            credentials
+                .extra
+                .get("secret_access_key")
+                .and_then(|v| v.as_str())
+                .map(|v| v.to_string())
+        });
+
+        if self.access_key_id.is_none() || self.secret_access_key.is_none() {
+            return Err(AppError::Provider(ProviderError::InvalidConfig(
+                "百度云需要提供 access_key (Access Key ID) 和 api_secret (Secret Access Key)"
+                    .to_string(),
+            )));
+        }
+
+        // 测试连接
+        self.test_connection().await?;
+
+        Ok(())
+    }
+
+    async fn list_records(&self, domain: &str) -> Result<Vec<DNSRecord>> {
+        let records = self.list_zone_records(domain, None).await?;
+        Ok(records.into_iter().map(to_dns_record).collect())
+    }
+
+    async fn get_record(
+        &self,
+        domain: &str,
+        record_name: &str,
+        record_type: DNSRecordType,
+    ) -> Result<Option<DNSRecord>> {
+        let rr = if record_name.is_empty() {
+            "@"
+        } else {
+            record_name
+        };
+        let records = self.list_zone_records(domain, Some(rr)).await?;
+
+        Ok(records
+            .into_iter()
+            .filter(|r| r.rr == rr)
+            .map(to_dns_record)
+            .find(|r| r.record_type == record_type))
+    }
+
+    async fn update_record(
+        &self,
+        domain: &str,
+        record_id: &str,
+        new_content: &str,
+    ) -> Result<UpdateResult> {
+        self.update_record_with(domain, record_id, new_content, &RecordSpec::default())
+            .await
+    }
+
+    async fn update_record_with(
+        &self,
+        domain: &str,
+        record_id: &str,
+        new_content: &str,
+        spec: &RecordSpec,
+    ) -> Result<UpdateResult> {
+        spec.ensure_supported(&self.capabilities())?;
+        // 修改接口需要提交完整的记录, 先获取当前记录
+        let current = to_dns_record(self.find_record(domain, record_id).await?);
+        let mut content = RecordContent::from_input(&current.record_type, new_content, spec)?;
+        content.priority = content.priority.or(current.priority);
+
+        let mut body = json!({
+            "rr": current.name,
+            "type": current.record_type.to_string(),
+            "value": record_value(&current.record_type, &content),
+            "ttl": spec.ttl.unwrap_or(current.ttl),
+        });
+        if let Some(priority) = content
+            .priority
+            .filter(|_| current.record_type == DNSRecordType::MX)
+        {
+            body["priority"] = json!(priority);
+        }
+        if let Some(description) = spec.comment.as_ref().or(current.comment.as_ref()) {
+            body["description"] = json!(description);
+        }
+
+        self.send_request(
+            Method::PUT,
+            &format!("/v1/dns/zone/{}/record/{}", domain, record_id),
+            &[("clientToken", client_token())],
+            Some(body),
+        )
+        .await?;
+
+        Ok(UpdateResult {
+            success: true,
+            record_id: record_id.to_string(),
+            old_ip: current.rdata(),
+            new_ip: new_content.to_string(),
+            message: "更新成功".to_string(),
+        })
+    }
+
+    async fn create_record(
+        &self,
+        domain: &str,
+        record_name: &str,
+        record_type: DNSRecordType,
+        content: &str,
+    ) -> Result<DNSRecord> {
+        self.create_record_with(
+            domain,
+            record_name,
+            record_type,
+            content,
+            &RecordSpec::default(),
+        )
+        .await
+    }
+
+    async fn create_record_with(
+        &self,
+        domain: &str,
+        record_name: &str,
+        record_type: DNSRecordType,
+        content: &str,
+        spec: &RecordSpec,
+    ) -> Result<DNSRecord> {
+        spec.ensure_supported(&self.capabilities())?;
+        let content = RecordContent::from_input(&record_type, content, spec)?;
+        let rr = if record_name.is_empty() {
+            "@"
+        } else {
+            record_name
+        };
+        let mut body = json!({
+            "rr": rr,
+            "type": record_type.to_string(),
+            "value": record_value(&record_type, &content),
+            "ttl": spec.ttl.unwrap_or(DEFAULT_TTL),
+            "line": "default",
+        });
+        if let Some(priority) = content
+            .priority
+            .filter(|_| record_type == DNSRecordType::MX)
+        {
+            body["priority"] = json!(priority);
+        }
+        if let Some(description) = &spec.comment {
+            body["description"] = json!(description);
+        }
+        self.send_request(
+            Method::POST,
+            &format!("/v1/dns/zone/{}/record", domain),
+            &[("clientToken", client_token())],
+            Some(body),
+        )
+        .await?;
+
+        // 创建接口不返回记录 ID, 需要重新查询
+        self.get_record(domain, rr, record_type.clone())
+            .await?
+            .filter(|r| r.content == content.content)
+            .ok_or_else(|| {
+                AppError::Provider(ProviderError::ApiError(format!(
+                    "创建后未找到记录: {} {}",
+                    rr, record_type
+                )))
+            })
+    }
+
+    async fn delete_record(&self, domain: &str, record_id: &str) -> Result<()> {
+        self.send_request(
+            Method::DELETE,
+            &format!("/v1/dns/zone/{}/record/{}", domain, record_id),
+            &[("clientToken", client_token())],
+            None,
+        )
+        .await?;
+        Ok(())
+    }
+
+    async fn test_connection(&self) -> Result<bool> {
+        // 测试获取域名列表
+        self.send_request(
+            Method::GET,
+            "/v1/dns/zone",
+            &[("maxKeys", "1".to_string())],
+            None,
+        )
+        .await?;
+        Ok(true)
+    }
+
+    fn supported_record_types(&self) -> Vec<DNSRecordType> {
+        vec![
+            DNSRecordType::A,
+            DNSRecordType::AAAA,
+            DNSRecordType::CNAME,
+            DNSRecordType::MX,
+            DNSRecordType::TXT,
+            DNSRecordType::NS,
+            DNSRecordType::SRV,
+            DNSRecordType::CAA,
+        ]
+    }
+
+    fn capabilities(&self) -> ProviderCapabilities {
+        ProviderCapabilities {
+            proxy: false,
+            ttl: true,
+            line: true,
+            priority: true,
+            comment: true,
+        }
+    }
+}
+
+// ============ BCE auth-v1 签名 ============
+
+/// BCE auth-v1 签名参数
+struct SigningParams<'a> {
+    access_key_id: &'a str,
+    secret_access_key: &'a str,
+    method: &'a str,
+    path: &'a str,
+    canonical_query: &'a str,
+    /// 参与签名的请求头 (名称为小写)
+    headers: &'a [(&'a str, &'a str)],
+    /// UTC 时间, 格式 `2015-04-27T08:23:49Z`
+    timestamp: &'a str,
+    expiration: u32,
+}
+
+/// 构造规范请求
+fn canonical_request(params: &SigningParams) -> String {
+    let uri = params
+        .path
+        .split('/')
+        .map(|segment| utf8_percent_encode(segment, URI_ENCODE_SET).to_string())
+        .collect::<Vec<_>>()
+        .join("/");
+
+    let mut headers: Vec<String> = params
+        .headers
+        .iter()
+        .map(|(name, value)| {
+            format!(
+                "{}:{}",
+                utf8_percent_encode(&name.to_lowercase(), URI_ENCODE_SET),
+                utf8_percent_encode(value.trim(), URI_ENCODE_SET)
+            )
+        })
+        .collect();
+    headers.sort();
+
+    format!(
+        "{}\n{}\n{}\n{}",
+        params.method,
+        uri,
+        params.canonical_query,
+        headers.join("\n")
+    )
+}
+
+/// 计算 Authorization 头
+fn sign_bce(params: &SigningParams) -> String {
+    let auth_prefix = format!(
+        "bce-auth-v1/{}/{}/{}",
+        params.access_key_id, params.timestamp, params.expiration
+    );
+    // 派生密钥为十六进制字符串形式
+    let signing_key = hex_encode(&hmac_sha256(
+        params.secret_access_key.as_bytes(),
+        auth_prefix.as_bytes(),
+    ));
+    let signature = hex_encode(&hmac_sha256(
+        signing_key.as_bytes(),
+        canonical_request(params).as_bytes(),
+    ));
+
+    let mut signed_headers: Vec<String> = params
+        .headers
+        .iter()
+        .map(|(name, _)| name.to_lowercase())
+        .collect();
+    signed_headers.sort();
+
+    format!("{}/{}/{}", auth_prefix, signed_headers.join(";"), signature)
+}
+
+// ============ 辅助函数 ============
+
+/// 幂等令牌, 防止重试时重复执行写操作
+fn client_token() -> String {
+    uuid::Uuid::new_v4().to_string()
+}
+
+/// 将百度云错误响应 (`{"requestId", "code", "message"}`) 映射为 ProviderError
+fn parse_error(status: u16, text: &str) -> AppError {
+    let json: serde_json::Value = serde_json::from_str(text).unwrap_or_default();
+    let code = json["code"].as_str().unwrap_or_default();
+    let message = json["message"].as_str().unwrap_or(text);
+
+    let detail = format!("HTTP {} {}: {}", status, code, message);
+    let error = match status {
+        401 | 403 => ProviderError::AuthenticationFailed(detail),
+        429 => ProviderError::RateLimitExceeded,
+        _ if code.contains("Zone") && code.contains("NotExist") => {
+            ProviderError::DomainNotFound(detail)
+        }
+        404 => ProviderError::RecordNotFound(detail),
+        _ => ProviderError::ApiError(detail),
+    };
+
+    AppError::Provider(error)
+}
+
+/// 记录值: MX 的优先级单独传递, 其他类型使用区域文件格式
+fn record_value(record_type: &DNSRecordType, content: &RecordContent) -> String {
+    match record_type {
+        DNSRecordType::MX => content.content.clone(),
+        _ => content.to_rdata(record_type),
+    }
+}
+
+/// 转换为通用记录, 未内置的类型以 `Other` 保留
+fn to_dns_record(record: BaiduRecord) -> DNSRecord {
+    let record_type = record
+        .record_type
+        .parse()
+        .unwrap_or_else(|_| DNSRecordType::Other(record.record_type.clone()));
+    let content = RecordContent::parse(&record_type, &record.value);
+    let priority = match record_type {
+        DNSRecordType::MX => record.priority,
+        _ => content.priority,
+    };
+
+    DNSRecord {
+        id: record.id,
+        name: record.rr,
+        record_type,
+        content: content.content,
+        ttl: record.ttl.unwrap_or(DEFAULT_TTL),
+        proxied: None,
+        priority,
+        weight: content.weight,
+        port: content.port,
+        comment: record.description.filter(|d| !d.is_empty()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use wiremock::matchers::{
+        body_partial_json, method, path, query_param, query_param_is_missing,
+    };
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    fn fixed_params<'a>(query: &'a str, headers: &'a [(&'a str, &'a str)]) -> SigningParams<'a> {
+        SigningParams {
+            access_key_id: "aabbccddeeff11223344556677889900",
+            secret_access_key: "ccddeeff11223344556677889900aabb",
+            method: "GET",
+            path: "/v1/dns/zone/example.com/record",
+            canonical_query: query,
+            headers,
+            timestamp: "2024-01-01T00:00:00Z",
+            expiration: 1800,
+        }
+    }
+
+    fn mock_provider(server: &MockServer) -> BaiduProvider {
+        let mut provider = BaiduProvider::with_endpoint(server.uri());
+        provider.access_key_id = Some("ak".to_string());
+        provider.secret_access_key = Some("sk".to_string());
+        provider
+    }
+
+    fn record(id: &str, rr: &str, record_type: &str, value: &str) -> serde_json::Value {
+        json!({
+            "id": id,
+            "rr": rr,
+            "status": "running",
+            "type": record_type,
+            "value": value,
+            "ttl": 300,
+            "line": "default"
+        })
+    }
+
+    #[test]
+    fn test_canonical_request() {
+        let query =
+            canonical_query_string(&[("rr", "www".to_string()), ("maxKeys", "1000".to_string())]);
+        let headers = [
+            ("x-bce-date", "2024-01-01T00:00:00Z"),
+            ("Host", "dns.baidubce.com"),
+        ];
+        let params = fixed_params(&query, &headers);
+
+        assert_eq!(
+            canonical_request(&params),
+            "GET\n/v1/dns/zone/example.com/record\nmaxKeys=1000&rr=www\n\
+             host:dns.baidubce.com\n\
+             x-bce-date:2024-01-01T00%3A00%3A00Z"
+        );
+    }
+
+    #[test]
+    fn test_sign_bce() {
+        let query =
+            canonical_query_string(&[("rr", "www".to_string()), ("maxKeys", "1000".to_string())]);
+        let headers = [
+            ("host", "dns.baidubce.com"),
+            ("x-bce-date", "2024-01-01T00:00:00Z"),
+        ];
+
+        assert_eq!(
+            sign_bce(&fixed_params(&query, &headers)),
+            "bce-auth-v1/aabbccddeeff11223344556677889900/2024-01-01T00:00:00Z/1800/\
+             host;x-bce-date/fa23eb8cc7f94396608b11ef75c65aff906ee9bb18a3ae721d6a83a486106118"
+        );
+    }
+
+    #[test]
+    fn test_parse_error() {
+        let kind = |status: u16, body: &str| match parse_error(status, body) {
+            AppError::Provider(error) => error,
+            other => panic!("应该返回提供商错误: {:?}", other),
+        };
+
+        assert!(matches!(
+            kind(403, r#"{"code":"SignatureDoesNotMatch","message":"bad"}"#),
+            ProviderError::AuthenticationFailed(_)
+        ));
+        assert!(matches!(
+            kind(404, r#"{"code":"NoSuchRecord","message":"missing"}"#),
+            ProviderError::RecordNotFound(_)
+        ));
+        assert!(matches!(
+            kind(400, r#"{"code":"Dns.ZoneNotExist","message":"missing"}"#),
+            ProviderError::DomainNotFound(_)
+        ));
+        assert!(matches!(kind(500, "oops"), ProviderError::ApiError(_)));
+    }
+
+    #[tokio::test]
+    async fn test_list_records_follows_marker() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/v1/dns/zone/example.com/record"))
+            .and(query_param_is_missing("marker"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+                "records": [record("1", "www", "A", "1.1.1.1")],
+                "isTruncated": true,
+                "nextMarker": "1",
+                "maxKeys": 1000
+            })))
+            .expect(1)
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/v1/dns/zone/example.com/record"))
+            .and(query_param("marker", "1"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+                "records": [record("2", "www", "AAAA", "2001:db8::1")],
+                "isTruncated": false,
+                "maxKeys": 1000
+            })))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let provider = mock_provider(&server);
+        let records = provider.list_records("example.com").await.unwrap();
+
+        assert_eq!(records.len(), 2);
+        assert_eq!(records[1].record_type, DNSRecordType::AAAA);
+    }
+
+    #[tokio::test]
+    async fn test_update_record() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/v1/dns/zone/example.com/record"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+                "records": [record("7", "www", "A", "1.1.1.1")],
+                "isTruncated": false
+            })))
+            .mount(&server)
+            .await;
+        Mock::given(method("PUT"))
+            .and(path("/v1/dns/zone/example.com/record/7"))
+            .and(body_partial_json(json!({
+                "rr": "www",
+                "type": "A",
+                "value": "2.2.2.2",
+                "ttl": 300
+            })))
+            .respond_with(ResponseTemplate::new(200))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let provider = mock_provider(&server);
+        let result = provider
+            .update_record("example.com", "7", "2.2.2.2")
+            .await
+            .unwrap();
+
+        assert!(result.success);
+        assert_eq!(result.old_ip, "1.1.1.1");
+        assert_eq!(result.new_ip, "2.2.2.2");
+    }
+
+    #[tokio::test]
+    async fn test_update_mx_record_with_spec() {
+        let server = MockServer::start().await;
+        let mut current = record("8", "@", "MX", "mx.example.com");
+        current["priority"] = json!(10);
+        Mock::given(method("GET"))
+            .and(path("/v1/dns/zone/example.com/record"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+                "records": [current],
+                "isTruncated": false
+            })))
+            .mount(&server)
+            .await;
+        Mock::given(method("PUT"))
+            .and(path("/v1/dns/zone/example.com/record/8"))
+            .and(body_partial_json(json!({
+                "type": "MX",
+                "value": "mx2.example.com",
+                "ttl": 600,
+                "priority": 20,
+                "description": "backup"
+            })))
+            .respond_with(ResponseTemplate::new(200))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let provider = mock_provider(&server);
+        let spec = RecordSpec {
+            ttl: Some(600),
+            priority: Some(20),
+            comment: Some("backup".to_string()),
+            ..Default::default()
+        };
+        provider
+            .update_record_with("example.com", "8", "mx2.example.com", &spec)
+            .await
+            .unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_list_srv_and_unknown_records() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/v1/dns/zone/example.com/record"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+                "records": [
+                    record("1", "_sip._tcp", "SRV", "10 5 5060 sip.example.com"),
+                    record("2", "@", "PTR", "host.example.com")
+                ],
+                "isTruncated": false
+            })))
+            .mount(&server)
+            .await;
+
+        let provider = mock_provider(&server);
+        let records = provider.list_records("example.com").await.unwrap();
+        assert_eq!(records[0].content, "sip.example.com");
+        assert_eq!(
+            (records[0].priority, records[0].weight, records[0].port),
+            (Some(10), Some(5), Some(5060))
+        );
+        assert_eq!(records[0].rdata(), "10 5 5060 sip.example.com");
+        assert_eq!(
+            records[1].record_type,
+            DNSRecordType::Other("PTR".to_string())
+        );
+    }
+}
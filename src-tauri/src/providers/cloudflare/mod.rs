@@ -1,24 +1,48 @@
 use crate::error::{AppError, Result};
 use crate::providers::provider_trait::{
-    Credentials, DNSProvider, DNSRecord, DNSRecordType, ProviderError, UpdateResult,
+    full_record_name, CaaData, Credentials, DNSProvider, DNSRecord, DNSRecordType,
+    ProviderCapabilities, ProviderError, RecordContent, RecordSpec, UpdateResult,
 };
 use async_trait::async_trait;
+use reqwest::Method;
+use serde::de::DeserializeOwned;
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::sync::RwLock;
+
+/// 列出记录时每页的记录数
+const PAGE_SIZE: u32 = 100;
+
+/// TTL 为 1 表示由 Cloudflare 自动设置
+const AUTO_TTL: u32 = 1;
+
+/// 未指定优先级时 MX 记录使用的优先级
+const DEFAULT_MX_PRIORITY: u16 = 10;
 
 /// Cloudflare DNS 提供商
 pub struct CloudflareProvider {
     api_token: Option<String>,
     account_email: Option<String>,
     api_key: Option<String>,
+    endpoint: String,
+    /// Zone ID 缓存 (域名 -> Zone ID), 避免每次操作都查询一次 Zone
+    zone_ids: RwLock<HashMap<String, String>>,
     client: reqwest::Client,
 }
 
 impl CloudflareProvider {
     pub fn new() -> Self {
+        Self::with_endpoint("https://api.cloudflare.com/client/v4")
+    }
+
+    /// 使用自定义 API 地址创建提供商 (例如测试服务器)
+    pub fn with_endpoint(endpoint: impl Into<String>) -> Self {
         Self {
             api_token: None,
             account_email: None,
             api_key: None,
+            endpoint: endpoint.into().trim_end_matches('/').to_string(),
+            zone_ids: RwLock::new(HashMap::new()),
             client: reqwest::Client::builder()
                 .timeout(std::time::Duration::from_secs(30))
                 .build()
@@ -26,83 +50,80 @@ impl CloudflareProvider {
         }
     }
 
-    /// 获取 Zone ID
+    /// 获取 Zone ID (优先使用缓存)
     async fn get_zone_id(&self, domain: &str) -> Result<String> {
-        let url = format!("https://api.cloudflare.com/client/v4/zones?name={}", domain);
-
-        let response = if let Some(token) = &self.api_token {
-            self.client
-                .get(&url)
-                .header("Authorization", format!("Bearer {}", token))
-                .header("Content-Type", "application/json")
-                .send()
-                .await
-        } else if let (Some(email), Some(key)) = (&self.account_email, &self.api_key) {
-            self.client
-                .get(&url)
-                .header("X-Auth-Email", email)
-                .header("X-Auth-Key", key)
-                .header("Content-Type", "application/json")
-                .send()
-                .await
-        } else {
-            return Err(AppError::Provider(ProviderError::AuthenticationFailed(
-                "未设置 API 凭证".to_string(),
-            )));
-        };
-
-        let response = response.map_err(|e| {
-            AppError::Provider(ProviderError::NetworkError(format!("请求失败: {}", e)))
-        })?;
-
-        if !response.status().is_success() {
-            let status = response.status();
-            let error_text = response.text().await.unwrap_or_default();
-            return Err(AppError::Provider(ProviderError::ApiError(format!(
-                "HTTP {}: {}",
-                status, error_text
-            ))));
+        if let Some(zone_id) = self.zone_ids.read().unwrap().get(domain) {
+            return Ok(zone_id.clone());
         }
 
-        let zones_response: CloudflareZonesResponse = response.json().await.map_err(|e| {
-            AppError::Provider(ProviderError::ApiError(format!("解析响应失败: {}", e)))
-        })?;
+        let url = format!("{}/zones", self.endpoint);
+        let request = self.request(Method::GET, &url)?.query(&[("name", domain)]);
+        let zones_response: CloudflareZonesResponse = self.send(request, "请求失败").await?;
 
         if !zones_response.success {
             return Err(AppError::Provider(ProviderError::DomainNotFound(
-                zones_response
-                    .errors
-                    .first()
-                    .map(|e| e.message.clone())
-                    .unwrap_or_else(|| "未知错误".to_string()),
+                first_error(&zones_response.errors),
             )));
         }
 
-        zones_response
+        let zone_id = zones_response
             .result
             .first()
             .map(|zone| zone.id.clone())
             .ok_or_else(|| {
                 AppError::Provider(ProviderError::DomainNotFound("找不到域名".to_string()))
-            })
+            })?;
+
+        self.zone_ids
+            .write()
+            .unwrap()
+            .insert(domain.to_string(), zone_id.clone());
+
+        Ok(zone_id)
     }
 
-    /// 构建 API 请求
-    fn build_request(&self, url: &str) -> reqwest::RequestBuilder {
+    /// 构建带认证头的 API 请求
+    fn request(&self, method: Method, url: &str) -> Result<reqwest::RequestBuilder> {
+        let request = self
+            .client
+            .request(method, url)
+            .header("Content-Type", "application/json");
+
         if let Some(token) = &self.api_token {
-            self.client
-                .get(url)
-                .header("Authorization", format!("Bearer {}", token))
-                .header("Content-Type", "application/json")
+            Ok(request.header("Authorization", format!("Bearer {}", token)))
         } else if let (Some(email), Some(key)) = (&self.account_email, &self.api_key) {
-            self.client
-                .get(url)
+            Ok(request
                 .header("X-Auth-Email", email)
-                .header("X-Auth-Key", key)
-                .header("Content-Type", "application/json")
+                .header("X-Auth-Key", key))
         } else {
-            self.client.get(url)
+            Err(AppError::Provider(ProviderError::AuthenticationFailed(
+                "未设置 API 凭证".to_string(),
+            )))
+        }
+    }
+
+    /// 发送请求并解析 JSON 响应
+    async fn send<T: DeserializeOwned>(
+        &self,
+        request: reqwest::RequestBuilder,
+        action: &str,
+    ) -> Result<T> {
+        let response = request.send().await.map_err(|e| {
+            AppError::Provider(ProviderError::NetworkError(format!("{}: {}", action, e)))
+        })?;
+
+        if !response.status().is_success() {
+            let status = response.status();
+            let error_text = response.text().await.unwrap_or_default();
+            return Err(AppError::Provider(ProviderError::ApiError(format!(
+                "HTTP {}: {}",
+                status, error_text
+            ))));
         }
+
+        response.json().await.map_err(|e| {
+            AppError::Provider(ProviderError::ApiError(format!("解析响应失败: {}", e)))
+        })
     }
 }
 
@@ -137,6 +158,9 @@ impl DNSProvider for CloudflareProvider {
             )));
         }
 
+        // 凭证变化后缓存的 Zone 可能不再可见
+        self.zone_ids.write().unwrap().clear();
+
         // 测试连接
         self.test_connection().await?;
 
@@ -145,60 +169,74 @@ impl DNSProvider for CloudflareProvider {
 
     async fn list_records(&self, domain: &str) -> Result<Vec<DNSRecord>> {
         let zone_id = self.get_zone_id(domain).await?;
-        let url = format!(
-            "https://api.cloudflare.com/client/v4/zones/{}/dns_records",
-            zone_id
-        );
+        let url = format!("{}/zones/{}/dns_records", self.endpoint, zone_id);
+        let mut records = Vec::new();
+        let mut page = 1;
+
+        // 逐页读取, 直到最后一页
+        loop {
+            let request = self.request(Method::GET, &url)?.query(&[
+                ("page", page.to_string()),
+                ("per_page", PAGE_SIZE.to_string()),
+            ]);
+            let records_response: CloudflareRecordsResponse =
+                self.send(request, "请求失败").await?;
+
+            if !records_response.success {
+                return Err(AppError::Provider(ProviderError::ApiError(first_error(
+                    &records_response.errors,
+                ))));
+            }
+
+            let count = records_response.result.len();
+            records.extend(
+                records_response
+                    .result
+                    .into_iter()
+                    .map(CloudflareRecord::into_dns_record),
+            );
+
+            let total_pages = records_response
+                .result_info
+                .map(|info| info.total_pages)
+                .unwrap_or(page);
+            if page >= total_pages || count == 0 {
+                break;
+            }
+            page += 1;
+        }
 
-        let response = self.build_request(&url).send().await.map_err(|e| {
-            AppError::Provider(ProviderError::NetworkError(format!("请求失败: {}", e)))
-        })?;
+        Ok(records)
+    }
 
-        if !response.status().is_success() {
-            let status = response.status();
-            let error_text = response.text().await.unwrap_or_default();
-            return Err(AppError::Provider(ProviderError::ApiError(format!(
-                "HTTP {}: {}",
-                status, error_text
-            ))));
-        }
+    async fn get_record(
+        &self,
+        domain: &str,
+        record_name: &str,
+        record_type: DNSRecordType,
+    ) -> Result<Option<DNSRecord>> {
+        // 使用 name/type 过滤, 只查询目标记录而不是整个 Zone
+        let zone_id = self.get_zone_id(domain).await?;
+        let url = format!("{}/zones/{}/dns_records", self.endpoint, zone_id);
+        let name = full_record_name(domain, record_name);
+        let request = self.request(Method::GET, &url)?.query(&[
+            ("name", name.as_str()),
+            ("type", record_type.to_string().as_str()),
+        ]);
 
-        let records_response: CloudflareRecordsResponse = response.json().await.map_err(|e| {
-            AppError::Provider(ProviderError::ApiError(format!("解析响应失败: {}", e)))
-        })?;
+        let records_response: CloudflareRecordsResponse = self.send(request, "请求失败").await?;
 
         if !records_response.success {
-            return Err(AppError::Provider(ProviderError::ApiError(
-                records_response
-                    .errors
-                    .first()
-                    .map(|e| e.message.clone())
-                    .unwrap_or_else(|| "未知错误".to_string()),
-            )));
+            return Err(AppError::Provider(ProviderError::ApiError(first_error(
+                &records_response.errors,
+            ))));
         }
 
         Ok(records_response
             .result
             .into_iter()
-            .map(|r| DNSRecord {
-                id: r.id,
-                name: r.name,
-                record_type: match r.r#type.as_str() {
-                    "A" => DNSRecordType::A,
-                    "AAAA" => DNSRecordType::AAAA,
-                    "CNAME" => DNSRecordType::CNAME,
-                    "MX" => DNSRecordType::MX,
-                    "TXT" => DNSRecordType::TXT,
-                    "NS" => DNSRecordType::NS,
-                    "SOA" => DNSRecordType::SOA,
-                    _ => DNSRecordType::A, // 默认值
-                },
-                content: r.content,
-                ttl: r.ttl,
-                proxied: Some(r.proxied),
-                priority: r.priority,
-            })
-            .collect())
+            .next()
+            .map(CloudflareRecord::into_dns_record))
     }
 
     async fn update_record(
@@ -207,80 +245,57 @@ impl DNSProvider for CloudflareProvider {
         record_id: &str,
         new_content: &str,
     ) -> Result<UpdateResult> {
+        self.update_record_with(domain, record_id, new_content, &RecordSpec::default())
+            .await
+    }
+
+    async fn update_record_with(
+        &self,
+        domain: &str,
+        record_id: &str,
+        new_content: &str,
+        spec: &RecordSpec,
+    ) -> Result<UpdateResult> {
+        spec.ensure_supported(&self.capabilities())?;
+
         let zone_id = self.get_zone_id(domain).await?;
         let url = format!(
-            "https://api.cloudflare.com/client/v4/zones/{}/dns_records/{}",
-            zone_id, record_id
-        );
-
-        // 先获取当前记录
-        let current_url = format!(
-            "https://api.cloudflare.com/client/v4/zones/{}/dns_records/{}",
-            zone_id, record_id
+            "{}/zones/{}/dns_records/{}",
+            self.endpoint, zone_id, record_id
         );
 
-        let current_response = self.build_request(&current_url).send().await.map_err(|e| {
-            AppError::Provider(ProviderError::NetworkError(format!("获取记录失败: {}", e)))
-        })?;
-
-        let current_record: CloudflareRecordResponse =
-            current_response.json().await.map_err(|e| {
-                AppError::Provider(ProviderError::ApiError(format!("解析响应失败: {}", e)))
-            })?;
+        // 先获取当前记录, 未指定的选项沿用原值
+        let current_record: CloudflareRecordResponse = self
+            .send(self.request(Method::GET, &url)?, "获取记录失败")
+            .await?;
+        let current = current_record.result.into_dns_record();
 
-        let old_ip = current_record.result.content.clone();
+        let mut content = RecordContent::from_input(&current.record_type, new_content, spec)?;
+        content.priority = content.priority.or(current.priority);
 
         // 更新记录
-        let update_data = serde_json::json!({
-            "content": new_content,
-            "ttl": current_record.result.ttl,
-            "proxied": current_record.result.proxied,
+        let mut update_data = serde_json::json!({
+            "type": current.record_type.to_string(),
+            "name": current.name,
+            "ttl": spec.ttl.unwrap_or(current.ttl),
+            "proxied": spec.proxied.or(current.proxied).unwrap_or(false),
         });
-
-        let response = if let Some(token) = &self.api_token {
-            self.client
-                .put(&url)
-                .header("Authorization", format!("Bearer {}", token))
-                .header("Content-Type", "application/json")
-                .json(&update_data)
-                .send()
-                .await
-        } else if let (Some(email), Some(key)) = (&self.account_email, &self.api_key) {
-            self.client
-                .put(&url)
-                .header("X-Auth-Email", email)
-                .header("X-Auth-Key", key)
-                .header("Content-Type", "application/json")
-                .json(&update_data)
-                .send()
-                .await
-        } else {
-            return Err(AppError::Provider(ProviderError::AuthenticationFailed(
-                "未设置 API 凭证".to_string(),
-            )));
-        };
-
-        let response = response.map_err(|e| {
-            AppError::Provider(ProviderError::NetworkError(format!("更新失败: {}", e)))
-        })?;
-
-        if !response.status().is_success() {
-            let status = response.status();
-            let error_text = response.text().await.unwrap_or_default();
-            return Err(AppError::Provider(ProviderError::ApiError(format!(
-                "HTTP {}: {}",
-                status, error_text
-            ))));
+        set_content(&mut update_data, &current.record_type, &content)?;
+        if let Some(comment) = spec.comment.as_ref().or(current.comment.as_ref()) {
+            update_data["comment"] = serde_json::json!(comment);
         }
 
-        let update_response: CloudflareRecordResponse = response.json().await.map_err(|e| {
-            AppError::Provider(ProviderError::ApiError(format!("解析响应失败: {}", e)))
-        })?;
+        let update_response: CloudflareRecordResponse = self
+            .send(
+                self.request(Method::PUT, &url)?.json(&update_data),
+                "更新失败",
+            )
+            .await?;
 
         Ok(UpdateResult {
             success: update_response.success,
             record_id: update_response.result.id,
-            old_ip,
+            old_ip: current.rdata(),
             new_ip: new_content.to_string(),
             message: "更新成功".to_string(),
         })
@@ -293,131 +308,78 @@ impl DNSProvider for CloudflareProvider {
         record_type: DNSRecordType,
         content: &str,
     ) -> Result<DNSRecord> {
+        self.create_record_with(
+            domain,
+            record_name,
+            record_type,
+            content,
+            &RecordSpec::default(),
+        )
+        .await
+    }
+
+    async fn create_record_with(
+        &self,
+        domain: &str,
+        record_name: &str,
+        record_type: DNSRecordType,
+        content: &str,
+        spec: &RecordSpec,
+    ) -> Result<DNSRecord> {
+        spec.ensure_supported(&self.capabilities())?;
+        let mut content = RecordContent::from_input(&record_type, content, spec)?;
+        if record_type == DNSRecordType::MX {
+            content.priority = content.priority.or(Some(DEFAULT_MX_PRIORITY));
+        }
+
         let zone_id = self.get_zone_id(domain).await?;
-        let url = format!(
-            "https://api.cloudflare.com/client/v4/zones/{}/dns_records",
-            zone_id
-        );
+        let url = format!("{}/zones/{}/dns_records", self.endpoint, zone_id);
 
-        let create_data = serde_json::json!({
+        let mut create_data = serde_json::json!({
             "type": record_type.to_string(),
             "name": record_name,
-            "content": content,
-            "ttl": 1, // 自动 TTL
-            "proxied": false,
+            "ttl": spec.ttl.unwrap_or(AUTO_TTL),
+            "proxied": spec.proxied.unwrap_or(false),
         });
-
-        let response = if let Some(token) = &self.api_token {
-            self.client
-                .post(&url)
-                .header("Authorization", format!("Bearer {}", token))
-                .header("Content-Type", "application/json")
-                .json(&create_data)
-                .send()
-                .await
-        } else if let (Some(email), Some(key)) = (&self.account_email, &self.api_key) {
-            self.client
-                .post(&url)
-                .header("X-Auth-Email", email)
-                .header("X-Auth-Key", key)
-                .header("Content-Type", "application/json")
-                .json(&create_data)
-                .send()
-                .await
-        } else {
-            return Err(AppError::Provider(ProviderError::AuthenticationFailed(
-                "未设置 API 凭证".to_string(),
-            )));
-        };
-
-        let response = response.map_err(|e| {
-            AppError::Provider(ProviderError::NetworkError(format!("创建失败: {}", e)))
-        })?;
-
-        if !response.status().is_success() {
-            let status = response.status();
-            let error_text = response.text().await.unwrap_or_default();
-            return Err(AppError::Provider(ProviderError::ApiError(format!(
-                "HTTP {}: {}",
-                status, error_text
-            ))));
+        set_content(&mut create_data, &record_type, &content)?;
+        if let Some(comment) = &spec.comment {
+            create_data["comment"] = serde_json::json!(comment);
         }
 
-        let create_response: CloudflareRecordResponse = response.json().await.map_err(|e| {
-            AppError::Provider(ProviderError::ApiError(format!("解析响应失败: {}", e)))
-        })?;
+        let create_response: CloudflareRecordResponse = self
+            .send(
+                self.request(Method::POST, &url)?.json(&create_data),
+                "创建失败",
+            )
+            .await?;
 
-        Ok(DNSRecord {
-            id: create_response.result.id,
-            name: create_response.result.name,
-            record_type,
-            content: create_response.result.content,
-            ttl: create_response.result.ttl,
-            proxied: Some(create_response.result.proxied),
-            priority: create_response.result.priority,
-        })
+        Ok(create_response.result.into_dns_record())
     }
 
     async fn delete_record(&self, domain: &str, record_id: &str) -> Result<()> {
         let zone_id = self.get_zone_id(domain).await?;
         let url = format!(
-            "https://api.cloudflare.com/client/v4/zones/{}/dns_records/{}",
-            zone_id, record_id
+            "{}/zones/{}/dns_records/{}",
+            self.endpoint, zone_id, record_id
         );
 
-        let response = if let Some(token) = &self.api_token {
-            self.client
-                .delete(&url)
-                .header("Authorization", format!("Bearer {}", token))
-                .header("Content-Type", "application/json")
-                .send()
-                .await
-        } else if let (Some(email), Some(key)) = (&self.account_email, &self.api_key) {
-            self.client
-                .delete(&url)
-                .header("X-Auth-Email", email)
-                .header("X-Auth-Key", key)
-                .header("Content-Type", "application/json")
-                .send()
-                .await
-        } else {
-            return Err(AppError::Provider(ProviderError::AuthenticationFailed(
-                "未设置 API 凭证".to_string(),
-            )));
-        };
-
-        let response = response.map_err(|e| {
-            AppError::Provider(ProviderError::NetworkError(format!("删除失败: {}", e)))
-        })?;
-
-        if !response.status().is_success() {
-            let status = response.status();
-            let error_text = response.text().await.unwrap_or_default();
-            return Err(AppError::Provider(ProviderError::ApiError(format!(
-                "HTTP {}: {}",
-                status, error_text
-            ))));
-        }
+        let _: serde_json::Value = self
+            .send(self.request(Method::DELETE, &url)?, "删除失败")
+            .await?;
 
         Ok(())
     }
 
     async fn test_connection(&self) -> Result<bool> {
         // 测试凭据是否有效
-        let url = "https://api.cloudflare.com/client/v4/user/tokens/verify";
-
-        let response = if let Some(token) = &self.api_token {
-            self.client
-                .get(url)
-                .header("Authorization", format!("Bearer {}", token))
-                .send()
-                .await
-        } else {
+        let url = format!("{}/user/tokens/verify", self.endpoint);
+
+        if self.api_token.is_none() {
             // 如果使用 Global API Key,跳过验证
             return Ok(true);
-        };
+        }
 
-        match response {
+        match self.request(Method::GET, &url)?.send().await {
             Ok(resp) => Ok(resp.status().is_success()),
             Err(_) => Ok(false),
         }
@@ -432,8 +394,28 @@ impl DNSProvider for CloudflareProvider {
             DNSRecordType::TXT,
             DNSRecordType::NS,
             DNSRecordType::SOA,
+            DNSRecordType::SRV,
+            DNSRecordType::CAA,
         ]
     }
+
+    fn capabilities(&self) -> ProviderCapabilities {
+        ProviderCapabilities {
+            proxy: true,
+            ttl: true,
+            line: false,
+            priority: true,
+            comment: true,
+        }
+    }
+}
+
+/// 取出第一条错误信息
+fn first_error(errors: &[CloudflareError]) -> String {
+    errors
+        .first()
+        .map(|e| e.message.clone())
+        .unwrap_or_else(|| "未知错误".to_string())
 }
 
 // ============ Cloudflare API 数据结构 ============
@@ -458,6 +440,13 @@ struct CloudflareRecordsResponse {
     #[serde(default)]
     errors: Vec<CloudflareError>,
     result: Vec<CloudflareRecord>,
+    #[serde(default)]
+    result_info: Option<CloudflareResultInfo>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct CloudflareResultInfo {
+    total_pages: u32,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -479,6 +468,78 @@ struct CloudflareRecord {
     proxied: bool,
     #[serde(skip_serializing_if = "Option::is_none")]
     priority: Option<u16>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    comment: Option<String>,
+    /// SRV、CAA 等记录的结构化数据
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    data: Option<serde_json::Value>,
+}
+
+impl CloudflareRecord {
+    fn into_dns_record(self) -> DNSRecord {
+        let record_type = self
+            .r#type
+            .parse()
+            .unwrap_or_else(|_| DNSRecordType::Other(self.r#type.clone()));
+        let data = self.data.unwrap_or_default();
+        let field = |key: &str| {
+            data.get(key)
+                .and_then(serde_json::Value::as_u64)
+                .and_then(|v| u16::try_from(v).ok())
+        };
+
+        // SRV 记录的 content 为 "权重 端口 目标", 从 data 中取结构化字段
+        let (content, weight, port) = match (&record_type, data.get("target")) {
+            (DNSRecordType::SRV, Some(serde_json::Value::String(target))) => {
+                (target.clone(), field("weight"), field("port"))
+            }
+            _ => (self.content, None, None),
+        };
+
+        DNSRecord {
+            id: self.id,
+            name: self.name,
+            record_type,
+            content,
+            ttl: self.ttl,
+            proxied: Some(self.proxied),
+            priority: self.priority.or_else(|| field("priority")),
+            weight,
+            port,
+            comment: self.comment,
+        }
+    }
+}
+
+/// 按记录类型填充内容: SRV 与 CAA 记录使用结构化的 `data`
+fn set_content(
+    body: &mut serde_json::Value,
+    record_type: &DNSRecordType,
+    content: &RecordContent,
+) -> Result<()> {
+    match record_type {
+        DNSRecordType::SRV => {
+            body["data"] = serde_json::json!({
+                "priority": content.priority,
+                "weight": content.weight,
+                "port": content.port,
+                "target": content.content,
+            });
+        }
+        DNSRecordType::CAA => {
+            let caa = CaaData::parse(&content.content)?;
+            body["data"] = serde_json::json!({
+                "flags": caa.flags,
+                "tag": caa.tag,
+                "value": caa.value,
+            });
+        }
+        _ => body["content"] = serde_json::json!(content.content),
+    }
+    if let Some(priority) = content.priority {
+        body["priority"] = serde_json::json!(priority);
+    }
+    Ok(())
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -490,6 +551,41 @@ struct CloudflareError {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::providers::test_support::{paginate, PagedResponder};
+    use wiremock::matchers::{body_partial_json, header, method, path, query_param};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    fn mock_provider(server: &MockServer) -> CloudflareProvider {
+        let mut provider = CloudflareProvider::with_endpoint(server.uri());
+        provider.api_token = Some("token".to_string());
+        provider
+    }
+
+    async fn mount_zone(server: &MockServer, expected_calls: u64) {
+        Mock::given(method("GET"))
+            .and(path("/zones"))
+            .and(query_param("name", "example.com"))
+            .and(header("Authorization", "Bearer token"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "success": true,
+                "errors": [],
+                "result": [{ "id": "zone1", "name": "example.com" }]
+            })))
+            .expect(expected_calls)
+            .mount(server)
+            .await;
+    }
+
+    fn record_json(id: &str, name: &str, record_type: &str, content: &str) -> serde_json::Value {
+        serde_json::json!({
+            "id": id,
+            "name": name,
+            "type": record_type,
+            "content": content,
+            "ttl": 300,
+            "proxied": false
+        })
+    }
 
     #[test]
     fn test_provider_creation() {
@@ -504,4 +600,357 @@ mod tests {
         assert!(provider.api_token.is_none());
         assert!(provider.account_email.is_none());
     }
+
+    #[tokio::test]
+    async fn test_get_record_uses_name_and_type_filter() {
+        let server = MockServer::start().await;
+        mount_zone(&server, 1).await;
+        Mock::given(method("GET"))
+            .and(path("/zones/zone1/dns_records"))
+            .and(query_param("name", "www.example.com"))
+            .and(query_param("type", "AAAA"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "success": true,
+                "errors": [],
+                "result": [record_json("rec1", "www.example.com", "AAAA", "2001:db8::1")]
+            })))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let provider = mock_provider(&server);
+        let record = provider
+            .get_record("example.com", "www", DNSRecordType::AAAA)
+            .await
+            .unwrap()
+            .unwrap();
+
+        assert_eq!(record.id, "rec1");
+        assert_eq!(record.record_type, DNSRecordType::AAAA);
+        assert_eq!(record.content, "2001:db8::1");
+    }
+
+    #[tokio::test]
+    async fn test_list_records_reads_all_pages() {
+        let server = MockServer::start().await;
+        mount_zone(&server, 1).await;
+
+        let pages = paginate(250, PAGE_SIZE as usize, |i| {
+            record_json(&format!("rec{}", i), "www.example.com", "A", "1.1.1.1")
+        });
+        let total_pages = pages.len();
+        let pages = pages
+            .into_iter()
+            .enumerate()
+            .map(|(index, records)| {
+                serde_json::json!({
+                    "success": true,
+                    "errors": [],
+                    "result": records,
+                    "result_info": { "page": index + 1, "total_pages": total_pages }
+                })
+            })
+            .collect();
+        Mock::given(method("GET"))
+            .and(path("/zones/zone1/dns_records"))
+            .respond_with(PagedResponder::page_number("page", 1, pages))
+            .expect(3)
+            .mount(&server)
+            .await;
+
+        let provider = mock_provider(&server);
+        let records = provider.list_records("example.com").await.unwrap();
+
+        assert_eq!(records.len(), 250);
+        assert_eq!(records[249].id, "rec249");
+    }
+
+    #[tokio::test]
+    async fn test_zone_id_is_cached() {
+        let server = MockServer::start().await;
+        // 多次操作只查询一次 Zone
+        mount_zone(&server, 1).await;
+        Mock::given(method("GET"))
+            .and(path("/zones/zone1/dns_records"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "success": true,
+                "errors": [],
+                "result": []
+            })))
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/zones/zone1/dns_records/rec1"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "success": true,
+                "errors": [],
+                "result": record_json("rec1", "www.example.com", "A", "1.1.1.1")
+            })))
+            .mount(&server)
+            .await;
+        Mock::given(method("PUT"))
+            .and(path("/zones/zone1/dns_records/rec1"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "success": true,
+                "errors": [],
+                "result": record_json("rec1", "www.example.com", "A", "2.2.2.2")
+            })))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let provider = mock_provider(&server);
+        let missing = provider
+            .get_record("example.com", "api", DNSRecordType::A)
+            .await
+            .unwrap();
+        assert!(missing.is_none());
+        assert!(provider
+            .list_records("example.com")
+            .await
+            .unwrap()
+            .is_empty());
+
+        let result = provider
+            .update_record("example.com", "rec1", "2.2.2.2")
+            .await
+            .unwrap();
+        assert!(result.success);
+        assert_eq!(result.old_ip, "1.1.1.1");
+        assert_eq!(result.new_ip, "2.2.2.2");
+    }
+
+    #[tokio::test]
+    async fn test_create_record_with_spec() {
+        let server = MockServer::start().await;
+        mount_zone(&server, 1).await;
+        Mock::given(method("POST"))
+            .and(path("/zones/zone1/dns_records"))
+            .and(body_partial_json(serde_json::json!({
+                "type": "A",
+                "name": "www",
+                "content": "1.1.1.1",
+                "ttl": 120,
+                "proxied": true,
+                "comment": "home"
+            })))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "success": true,
+                "errors": [],
+                "result": {
+                    "id": "rec1",
+                    "name": "www.example.com",
+                    "type": "A",
+                    "content": "1.1.1.1",
+                    "ttl": 120,
+                    "proxied": true,
+                    "comment": "home"
+                }
+            })))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let provider = mock_provider(&server);
+        let spec = RecordSpec {
+            ttl: Some(120),
+            proxied: Some(true),
+            priority: None,
+            comment: Some("home".to_string()),
+        };
+        let record = provider
+            .create_record_with("example.com", "www", DNSRecordType::A, "1.1.1.1", &spec)
+            .await
+            .unwrap();
+
+        assert_eq!(record.ttl, 120);
+        assert_eq!(record.proxied, Some(true));
+        assert_eq!(record.comment.as_deref(), Some("home"));
+    }
+
+    #[tokio::test]
+    async fn test_create_record_defaults_to_auto_ttl() {
+        let server = MockServer::start().await;
+        mount_zone(&server, 1).await;
+        Mock::given(method("POST"))
+            .and(path("/zones/zone1/dns_records"))
+            .and(body_partial_json(
+                serde_json::json!({ "ttl": 1, "proxied": false }),
+            ))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "success": true,
+                "errors": [],
+                "result": record_json("rec1", "www.example.com", "A", "1.1.1.1")
+            })))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let provider = mock_provider(&server);
+        provider
+            .create_record("example.com", "www", DNSRecordType::A, "1.1.1.1")
+            .await
+            .unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_update_record_with_keeps_unspecified_fields() {
+        let server = MockServer::start().await;
+        mount_zone(&server, 1).await;
+        let mut current = record_json("rec1", "www.example.com", "A", "1.1.1.1");
+        current["proxied"] = serde_json::json!(true);
+        current["comment"] = serde_json::json!("home");
+        Mock::given(method("GET"))
+            .and(path("/zones/zone1/dns_records/rec1"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "success": true,
+                "errors": [],
+                "result": current
+            })))
+            .mount(&server)
+            .await;
+        Mock::given(method("PUT"))
+            .and(path("/zones/zone1/dns_records/rec1"))
+            .and(body_partial_json(serde_json::json!({
+                "content": "2.2.2.2",
+                "ttl": 60,
+                "proxied": true,
+                "comment": "home"
+            })))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "success": true,
+                "errors": [],
+                "result": record_json("rec1", "www.example.com", "A", "2.2.2.2")
+            })))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let provider = mock_provider(&server);
+        let spec = RecordSpec {
+            ttl: Some(60),
+            ..Default::default()
+        };
+        let result = provider
+            .update_record_with("example.com", "rec1", "2.2.2.2", &spec)
+            .await
+            .unwrap();
+        assert_eq!(result.old_ip, "1.1.1.1");
+    }
+
+    #[tokio::test]
+    async fn test_priority_rejected_for_a_record() {
+        let provider = CloudflareProvider::new();
+        let spec = RecordSpec {
+            priority: Some(10),
+            ..Default::default()
+        };
+        let result = provider
+            .create_record_with("example.com", "www", DNSRecordType::A, "1.1.1.1", &spec)
+            .await;
+        assert!(matches!(
+            result,
+            Err(AppError::Provider(ProviderError::InvalidConfig(_)))
+        ));
+    }
+
+    #[test]
+    fn test_into_dns_record_keeps_type_and_structure() {
+        let mut srv = record_json(
+            "rec1",
+            "_sip._tcp.example.com",
+            "SRV",
+            "5 5060 sip.example.com",
+        );
+        srv["priority"] = serde_json::json!(10);
+        srv["data"] = serde_json::json!({
+            "priority": 10, "weight": 5, "port": 5060, "target": "sip.example.com"
+        });
+        let record = serde_json::from_value::<CloudflareRecord>(srv)
+            .unwrap()
+            .into_dns_record();
+        assert_eq!(record.record_type, DNSRecordType::SRV);
+        assert_eq!(record.content, "sip.example.com");
+        assert_eq!(
+            (record.priority, record.weight, record.port),
+            (Some(10), Some(5), Some(5060))
+        );
+        assert_eq!(record.rdata(), "10 5 5060 sip.example.com");
+
+        // 未内置的类型不再被当作 A 记录
+        let https = record_json("rec2", "example.com", "HTTPS", "1 . alpn=\"h2\"");
+        let record = serde_json::from_value::<CloudflareRecord>(https)
+            .unwrap()
+            .into_dns_record();
+        assert_eq!(
+            record.record_type,
+            DNSRecordType::Other("HTTPS".to_string())
+        );
+    }
+
+    #[tokio::test]
+    async fn test_create_srv_and_caa_records_use_data() {
+        let server = MockServer::start().await;
+        mount_zone(&server, 1).await;
+        let mut srv = record_json(
+            "rec1",
+            "_sip._tcp.example.com",
+            "SRV",
+            "5 5060 sip.example.com",
+        );
+        srv["priority"] = serde_json::json!(10);
+        Mock::given(method("POST"))
+            .and(path("/zones/zone1/dns_records"))
+            .and(body_partial_json(serde_json::json!({
+                "type": "SRV",
+                "priority": 10,
+                "data": { "priority": 10, "weight": 5, "port": 5060, "target": "sip.example.com" }
+            })))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "success": true,
+                "errors": [],
+                "result": srv
+            })))
+            .expect(1)
+            .mount(&server)
+            .await;
+        Mock::given(method("POST"))
+            .and(path("/zones/zone1/dns_records"))
+            .and(body_partial_json(serde_json::json!({
+                "type": "CAA",
+                "data": { "flags": 0, "tag": "issue", "value": "letsencrypt.org" }
+            })))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "success": true,
+                "errors": [],
+                "result": record_json("rec2", "example.com", "CAA", "0 issue \"letsencrypt.org\"")
+            })))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let provider = mock_provider(&server);
+        let record = provider
+            .create_record(
+                "example.com",
+                "_sip._tcp",
+                DNSRecordType::SRV,
+                "10 5 5060 sip.example.com",
+            )
+            .await
+            .unwrap();
+        assert_eq!(record.record_type, DNSRecordType::SRV);
+        assert_eq!(record.priority, Some(10));
+
+        let record = provider
+            .create_record(
+                "example.com",
+                "@",
+                DNSRecordType::CAA,
+                "0 issue \"letsencrypt.org\"",
+            )
+            .await
+            .unwrap();
+        assert_eq!(record.content, "0 issue \"letsencrypt.org\"");
+    }
 }
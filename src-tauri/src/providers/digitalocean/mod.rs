@@ -0,0 +1,687 @@
+use crate::error::{AppError, Result};
+use crate::providers::provider_trait::{
+    full_record_name, CaaData, Credentials, DNSProvider, DNSRecord, DNSRecordType,
+    ProviderCapabilities, ProviderError, RecordContent, RecordSpec, UpdateResult,
+};
+use async_trait::async_trait;
+use reqwest::{Client, Method};
+use serde::de::DeserializeOwned;
+use serde::Deserialize;
+use serde_json::json;
+
+const DEFAULT_ENDPOINT: &str = "https://api.digitalocean.com/v2";
+
+/// 记录列表每页数量 (API 允许的最大值)
+const PAGE_SIZE: usize = 200;
+
+/// 新建记录的默认 TTL
+const DEFAULT_TTL: u32 = 1800;
+
+/// 未指定优先级时 MX 记录使用的优先级
+const DEFAULT_MX_PRIORITY: u16 = 10;
+
+/// DigitalOcean DNS 提供商
+pub struct DigitalOceanProvider {
+    api_token: Option<String>,
+    endpoint: String,
+    client: Client,
+}
+
+/// 域名记录, `name` 为相对名称 (根域名为 `@`)
+#[derive(Debug, Deserialize)]
+struct DigitalOceanRecord {
+    id: u64,
+    #[serde(rename = "type")]
+    record_type: String,
+    name: String,
+    data: String,
+    #[serde(default)]
+    priority: Option<u16>,
+    #[serde(default)]
+    weight: Option<u16>,
+    #[serde(default)]
+    port: Option<u16>,
+    /// CAA 记录的标志和标签, `data` 中只有值
+    #[serde(default)]
+    flags: Option<u8>,
+    #[serde(default)]
+    tag: Option<String>,
+    #[serde(default)]
+    ttl: Option<u32>,
+}
+
+#[derive(Debug, Deserialize)]
+struct DigitalOceanRecordList {
+    domain_records: Vec<DigitalOceanRecord>,
+    #[serde(default)]
+    meta: Option<DigitalOceanMeta>,
+}
+
+#[derive(Debug, Deserialize)]
+struct DigitalOceanMeta {
+    total: usize,
+}
+
+#[derive(Debug, Deserialize)]
+struct DigitalOceanRecordResponse {
+    domain_record: DigitalOceanRecord,
+}
+
+impl DigitalOceanProvider {
+    pub fn new() -> Self {
+        Self::with_endpoint(DEFAULT_ENDPOINT)
+    }
+
+    /// 使用自定义 API 地址创建提供商 (例如测试服务器)
+    pub fn with_endpoint(endpoint: impl Into<String>) -> Self {
+        Self {
+            api_token: None,
+            endpoint: endpoint.into().trim_end_matches('/').to_string(),
+            client: Client::builder()
+                .timeout(std::time::Duration::from_secs(30))
+                .build()
+                .unwrap(),
+        }
+    }
+
+    /// 发送 API 请求并解析 JSON 响应 (响应为空时按 `null` 解析)
+    async fn send<T: DeserializeOwned>(
+        &self,
+        method: Method,
+        path: &str,
+        query: &[(&str, String)],
+        body: Option<serde_json::Value>,
+    ) -> Result<T> {
+        let token = self.api_token.as_ref().ok_or_else(|| {
+            AppError::Provider(ProviderError::AuthenticationFailed(
+                "未设置 API Token".to_string(),
+            ))
+        })?;
+
+        let mut request = self
+            .client
+            .request(method, format!("{}{}", self.endpoint, path))
+            .bearer_auth(token)
+            .query(query);
+        if let Some(body) = body {
+            request = request.json(&body);
+        }
+
+        let response = request
+            .send()
+            .await
+            .map_err(|e| AppError::Provider(ProviderError::NetworkError(e.to_string())))?;
+        let status = response.status();
+        let text = response
+            .text()
+            .await
+            .map_err(|e| AppError::Provider(ProviderError::NetworkError(e.to_string())))?;
+
+        if !status.is_success() {
+            return Err(parse_error(status.as_u16(), &text));
+        }
+
+        let text = if text.trim().is_empty() {
+            "null"
+        } else {
+            &text
+        };
+        serde_json::from_str(text).map_err(|e| {
+            AppError::Provider(ProviderError::ParseError(format!("解析响应失败: {}", e)))
+        })
+    }
+
+    /// 逐页读取域名记录
+    async fn list_domain_records(
+        &self,
+        domain: &str,
+        filter: &[(&str, String)],
+    ) -> Result<Vec<DigitalOceanRecord>> {
+        let path = format!("/domains/{}/records", domain);
+        let mut records = Vec::new();
+        let mut page = 1;
+
+        loop {
+            let mut query = filter.to_vec();
+            query.push(("page", page.to_string()));
+            query.push(("per_page", PAGE_SIZE.to_string()));
+
+            let list: DigitalOceanRecordList = self.send(Method::GET, &path, &query, None).await?;
+            let count = list.domain_records.len();
+            records.extend(list.domain_records);
+
+            let total = list.meta.map(|m| m.total).unwrap_or(records.len());
+            if count == 0 || records.len() >= total {
+                break;
+            }
+            page += 1;
+        }
+
+        Ok(records)
+    }
+}
+
+impl Default for DigitalOceanProvider {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[async_trait]
+impl DNSProvider for DigitalOceanProvider {
+    fn provider_id(&self) -> &'static str {
+        "digitalocean"
+    }
+
+    fn provider_name(&self) -> &'static str {
+        "DigitalOcean"
+    }
+
+    async fn initialize(&mut self, credentials: &Credentials) -> Result<()> {
+        self.api_token = credentials.api_key.clone().or_else(|| {
+            credentials
+                .extra
+                .get("api_token")
+                .and_then(|v| v.as_str())
+                .map(|v| v.to_string())
+        });
+
+        if self.api_token.is_none() {
+            return Err(AppError::Provider(ProviderError::InvalidConfig(
+                "DigitalOcean 需要提供 api_token (Personal Access Token)".to_string(),
+            )));
+        }
+
+        // 测试连接
+        self.test_connection().await?;
+
+        Ok(())
+    }
+
+    async fn list_records(&self, domain: &str) -> Result<Vec<DNSRecord>> {
+        let records = self.list_domain_records(domain, &[]).await?;
+        Ok(records
+            .into_iter()
+            .map(|r| to_dns_record(domain, r))
+            .collect())
+    }
+
+    async fn get_record(
+        &self,
+        domain: &str,
+        record_name: &str,
+        record_type: DNSRecordType,
+    ) -> Result<Option<DNSRecord>> {
+        // 按完整域名和类型过滤
+        let records = self
+            .list_domain_records(
+                domain,
+                &[
+                    ("name", full_record_name(domain, record_name)),
+                    ("type", record_type.to_string()),
+                ],
+            )
+            .await?;
+
+        Ok(records
+            .into_iter()
+            .map(|r| to_dns_record(domain, r))
+            .find(|r| r.record_type == record_type))
+    }
+
+    async fn update_record(
+        &self,
+        domain: &str,
+        record_id: &str,
+        new_content: &str,
+    ) -> Result<UpdateResult> {
+        self.update_record_with(domain, record_id, new_content, &RecordSpec::default())
+            .await
+    }
+
+    async fn update_record_with(
+        &self,
+        domain: &str,
+        record_id: &str,
+        new_content: &str,
+        spec: &RecordSpec,
+    ) -> Result<UpdateResult> {
+        spec.ensure_supported(&self.capabilities())?;
+        let path = format!("/domains/{}/records/{}", domain, record_id);
+
+        // 先获取当前记录, 保留类型
+        let current: DigitalOceanRecordResponse = self.send(Method::GET, &path, &[], None).await?;
+        let current = to_dns_record(domain, current.domain_record);
+        let mut content = RecordContent::from_input(&current.record_type, new_content, spec)?;
+        content.priority = content.priority.or(current.priority);
+
+        let mut body = json!({ "type": current.record_type.to_string() });
+        set_content(&mut body, &current.record_type, &content)?;
+        if let Some(ttl) = spec.ttl {
+            body["ttl"] = json!(ttl);
+        }
+
+        let updated: DigitalOceanRecordResponse =
+            self.send(Method::PUT, &path, &[], Some(body)).await?;
+
+        Ok(UpdateResult {
+            success: true,
+            record_id: updated.domain_record.id.to_string(),
+            old_ip: current.rdata(),
+            new_ip: new_content.to_string(),
+            message: "更新成功".to_string(),
+        })
+    }
+
+    async fn create_record(
+        &self,
+        domain: &str,
+        record_name: &str,
+        record_type: DNSRecordType,
+        content: &str,
+    ) -> Result<DNSRecord> {
+        self.create_record_with(
+            domain,
+            record_name,
+            record_type,
+            content,
+            &RecordSpec::default(),
+        )
+        .await
+    }
+
+    async fn create_record_with(
+        &self,
+        domain: &str,
+        record_name: &str,
+        record_type: DNSRecordType,
+        content: &str,
+        spec: &RecordSpec,
+    ) -> Result<DNSRecord> {
+        spec.ensure_supported(&self.capabilities())?;
+        let mut content = RecordContent::from_input(&record_type, content, spec)?;
+        if record_type == DNSRecordType::MX {
+            content.priority = content.priority.or(Some(DEFAULT_MX_PRIORITY));
+        }
+        let name = match record_name {
+            "" => "@",
+            name => name,
+        };
+        let mut body = json!({
+            "type": record_type.to_string(),
+            "name": name,
+            "ttl": spec.ttl.unwrap_or(DEFAULT_TTL),
+        });
+        set_content(&mut body, &record_type, &content)?;
+
+        let created: DigitalOceanRecordResponse = self
+            .send(
+                Method::POST,
+                &format!("/domains/{}/records", domain),
+                &[],
+                Some(body),
+            )
+            .await?;
+
+        Ok(to_dns_record(domain, created.domain_record))
+    }
+
+    async fn delete_record(&self, domain: &str, record_id: &str) -> Result<()> {
+        let _: serde_json::Value = self
+            .send(
+                Method::DELETE,
+                &format!("/domains/{}/records/{}", domain, record_id),
+                &[],
+                None,
+            )
+            .await?;
+        Ok(())
+    }
+
+    async fn test_connection(&self) -> Result<bool> {
+        // 测试获取账户信息
+        let _: serde_json::Value = self.send(Method::GET, "/account", &[], None).await?;
+        Ok(true)
+    }
+
+    fn supported_record_types(&self) -> Vec<DNSRecordType> {
+        vec![
+            DNSRecordType::A,
+            DNSRecordType::AAAA,
+            DNSRecordType::CNAME,
+            DNSRecordType::MX,
+            DNSRecordType::TXT,
+            DNSRecordType::NS,
+            DNSRecordType::SRV,
+            DNSRecordType::CAA,
+        ]
+    }
+
+    fn capabilities(&self) -> ProviderCapabilities {
+        ProviderCapabilities {
+            proxy: false,
+            ttl: true,
+            line: false,
+            priority: true,
+            comment: false,
+        }
+    }
+}
+
+/// 将 DigitalOcean 错误响应 (`{"id", "message"}`) 映射为 ProviderError
+fn parse_error(status: u16, text: &str) -> AppError {
+    let json: serde_json::Value = serde_json::from_str(text).unwrap_or_default();
+    let id = json["id"].as_str().unwrap_or_default();
+    let message = json["message"].as_str().unwrap_or(text);
+
+    let detail = format!("HTTP {} {}: {}", status, id, message);
+    let error = match status {
+        401 | 403 => ProviderError::AuthenticationFailed(detail),
+        429 => ProviderError::RateLimitExceeded,
+        404 => ProviderError::RecordNotFound(detail),
+        _ => ProviderError::ApiError(detail),
+    };
+
+    AppError::Provider(error)
+}
+
+/// 按记录类型填充内容: MX/SRV 的优先级、权重和端口以及 CAA 的标志和标签为独立字段
+fn set_content(
+    body: &mut serde_json::Value,
+    record_type: &DNSRecordType,
+    content: &RecordContent,
+) -> Result<()> {
+    body["data"] = json!(content.content);
+    match record_type {
+        DNSRecordType::MX => body["priority"] = json!(content.priority),
+        DNSRecordType::SRV => {
+            body["priority"] = json!(content.priority);
+            body["weight"] = json!(content.weight);
+            body["port"] = json!(content.port);
+        }
+        DNSRecordType::CAA => {
+            let caa = CaaData::parse(&content.content)?;
+            body["data"] = json!(caa.value);
+            body["flags"] = json!(caa.flags);
+            body["tag"] = json!(caa.tag);
+        }
+        _ => {}
+    }
+    Ok(())
+}
+
+/// 转换为通用记录, 未内置的类型以 `Other` 保留
+fn to_dns_record(domain: &str, record: DigitalOceanRecord) -> DNSRecord {
+    let record_type = record
+        .record_type
+        .parse()
+        .unwrap_or_else(|_| DNSRecordType::Other(record.record_type.clone()));
+    let content = match (&record_type, record.tag) {
+        (DNSRecordType::CAA, Some(tag)) => CaaData {
+            flags: record.flags.unwrap_or_default(),
+            tag,
+            value: record.data,
+        }
+        .to_string(),
+        _ => record.data,
+    };
+
+    DNSRecord {
+        id: record.id.to_string(),
+        name: full_record_name(domain, &record.name),
+        record_type,
+        content,
+        ttl: record.ttl.unwrap_or(DEFAULT_TTL),
+        proxied: None,
+        priority: record.priority,
+        weight: record.weight,
+        port: record.port,
+        comment: None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::providers::test_support::{fixture, mock_provider, PagedResponder};
+    use wiremock::matchers::{body_json, header, method, path, query_param};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    mock_provider!(DigitalOceanProvider, "token");
+
+    #[test]
+    fn test_parse_error() {
+        let error = parse_error(401, &fixture!("error_unauthorized").to_string());
+        assert!(matches!(
+            error,
+            AppError::Provider(ProviderError::AuthenticationFailed(ref m)) if m.contains("Unable to authenticate")
+        ));
+        assert!(matches!(
+            parse_error(429, "{}"),
+            AppError::Provider(ProviderError::RateLimitExceeded)
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_list_records_reads_all_pages() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/domains/example.com/records"))
+            .and(header("authorization", "Bearer token"))
+            .respond_with(PagedResponder::page_number(
+                "page",
+                1,
+                vec![fixture!("records_page1"), fixture!("records_page2")],
+            ))
+            .expect(2)
+            .mount(&server)
+            .await;
+
+        let records = mock_provider(&server)
+            .list_records("example.com")
+            .await
+            .unwrap();
+
+        assert_eq!(records.len(), 5);
+        assert_eq!(records[0].name, "example.com");
+        assert_eq!(records[0].record_type, DNSRecordType::NS);
+        let mx = records
+            .iter()
+            .find(|r| r.record_type == DNSRecordType::MX)
+            .unwrap();
+        assert_eq!(mx.priority, Some(10));
+        assert_eq!(records[4].name, "www.example.com");
+        assert_eq!(records[4].content, "203.0.113.10");
+    }
+
+    #[tokio::test]
+    async fn test_get_record_uses_name_and_type_filter() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/domains/example.com/records"))
+            .and(query_param("name", "www.example.com"))
+            .and(query_param("type", "A"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+                "domain_records": [fixture!("record")["domain_record"]],
+                "links": {},
+                "meta": { "total": 1 }
+            })))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let record = mock_provider(&server)
+            .get_record("example.com", "www", DNSRecordType::A)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(record.id, "3352896");
+        assert_eq!(record.content, "203.0.113.10");
+    }
+
+    #[tokio::test]
+    async fn test_update_record() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/domains/example.com/records/3352896"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(fixture!("record")))
+            .mount(&server)
+            .await;
+        Mock::given(method("PUT"))
+            .and(path("/domains/example.com/records/3352896"))
+            .and(body_json(json!({ "type": "A", "data": "198.51.100.7" })))
+            .respond_with(ResponseTemplate::new(200).set_body_json(fixture!("record_updated")))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let result = mock_provider(&server)
+            .update_record("example.com", "3352896", "198.51.100.7")
+            .await
+            .unwrap();
+        assert_eq!(result.old_ip, "203.0.113.10");
+        assert_eq!(result.new_ip, "198.51.100.7");
+    }
+
+    #[tokio::test]
+    async fn test_update_record_with_ttl() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/domains/example.com/records/3352896"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(fixture!("record")))
+            .mount(&server)
+            .await;
+        Mock::given(method("PUT"))
+            .and(path("/domains/example.com/records/3352896"))
+            .and(body_json(
+                json!({ "type": "A", "data": "198.51.100.7", "ttl": 60 }),
+            ))
+            .respond_with(ResponseTemplate::new(200).set_body_json(fixture!("record_updated")))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let provider = mock_provider(&server);
+        let spec = RecordSpec {
+            ttl: Some(60),
+            ..Default::default()
+        };
+        provider
+            .update_record_with("example.com", "3352896", "198.51.100.7", &spec)
+            .await
+            .unwrap();
+
+        // A 记录不能设置优先级
+        let spec = RecordSpec {
+            priority: Some(10),
+            ..Default::default()
+        };
+        assert!(provider
+            .update_record_with("example.com", "3352896", "198.51.100.7", &spec)
+            .await
+            .is_err());
+    }
+
+    #[tokio::test]
+    async fn test_create_and_delete_record() {
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/domains/example.com/records"))
+            .and(body_json(json!({
+                "type": "A",
+                "name": "www",
+                "data": "198.51.100.7",
+                "ttl": DEFAULT_TTL
+            })))
+            .respond_with(ResponseTemplate::new(201).set_body_json(fixture!("record_updated")))
+            .expect(1)
+            .mount(&server)
+            .await;
+        Mock::given(method("DELETE"))
+            .and(path("/domains/example.com/records/3352896"))
+            .respond_with(ResponseTemplate::new(204))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let provider = mock_provider(&server);
+        let record = provider
+            .create_record("example.com", "www", DNSRecordType::A, "198.51.100.7")
+            .await
+            .unwrap();
+        assert_eq!(record.id, "3352896");
+        assert_eq!(record.name, "www.example.com");
+
+        provider
+            .delete_record("example.com", "3352896")
+            .await
+            .unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_create_srv_and_caa_records() {
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/domains/example.com/records"))
+            .and(body_json(json!({
+                "type": "SRV",
+                "name": "_sip._tcp",
+                "data": "sip.example.com.",
+                "priority": 10,
+                "weight": 5,
+                "port": 5060,
+                "ttl": DEFAULT_TTL
+            })))
+            .respond_with(ResponseTemplate::new(201).set_body_json(json!({
+                "domain_record": {
+                    "id": 1, "type": "SRV", "name": "_sip._tcp", "data": "sip.example.com",
+                    "priority": 10, "weight": 5, "port": 5060, "ttl": 1800
+                }
+            })))
+            .expect(1)
+            .mount(&server)
+            .await;
+        Mock::given(method("POST"))
+            .and(path("/domains/example.com/records"))
+            .and(body_json(json!({
+                "type": "CAA",
+                "name": "@",
+                "data": "letsencrypt.org",
+                "flags": 0,
+                "tag": "issue",
+                "ttl": DEFAULT_TTL
+            })))
+            .respond_with(ResponseTemplate::new(201).set_body_json(json!({
+                "domain_record": {
+                    "id": 2, "type": "CAA", "name": "@", "data": "letsencrypt.org",
+                    "flags": 0, "tag": "issue", "ttl": 1800
+                }
+            })))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let provider = mock_provider(&server);
+        let record = provider
+            .create_record(
+                "example.com",
+                "_sip._tcp",
+                DNSRecordType::SRV,
+                "10 5 5060 sip.example.com.",
+            )
+            .await
+            .unwrap();
+        assert_eq!(record.rdata(), "10 5 5060 sip.example.com");
+
+        let record = provider
+            .create_record(
+                "example.com",
+                "@",
+                DNSRecordType::CAA,
+                "0 issue \"letsencrypt.org\"",
+            )
+            .await
+            .unwrap();
+        assert_eq!(record.content, "0 issue \"letsencrypt.org\"");
+    }
+}
@@ -0,0 +1,399 @@
+use crate::error::{AppError, Result};
+use crate::providers::provider_trait::{
+    full_record_name, Credentials, DNSProvider, DNSRecord, DNSRecordType, ProviderError,
+    UpdateResult,
+};
+use async_trait::async_trait;
+use reqwest::Client;
+use std::collections::HashMap;
+use std::sync::RwLock;
+
+const DEFAULT_ENDPOINT: &str = "https://www.duckdns.org";
+
+/// DuckDNS 的根域名, 用户的主机均为其下一级子域名
+const DUCKDNS_ZONE: &str = "duckdns.org";
+
+/// DuckDNS 提供商
+///
+/// 接口只有一个带 token 的更新地址, 无法列出或删除记录
+pub struct DuckDnsProvider {
+    token: Option<String>,
+    endpoint: String,
+    /// 最近一次成功更新的记录, 键为 `subdomain|TYPE`
+    sent: RwLock<HashMap<String, DNSRecord>>,
+    client: Client,
+}
+
+impl DuckDnsProvider {
+    pub fn new() -> Self {
+        Self::with_endpoint(DEFAULT_ENDPOINT)
+    }
+
+    /// 使用自定义 API 地址创建提供商 (例如测试服务器)
+    pub fn with_endpoint(endpoint: impl Into<String>) -> Self {
+        Self {
+            token: None,
+            endpoint: endpoint.into().trim_end_matches('/').to_string(),
+            sent: RwLock::new(HashMap::new()),
+            client: Client::builder()
+                .timeout(std::time::Duration::from_secs(30))
+                .build()
+                .unwrap(),
+        }
+    }
+
+    /// 调用更新接口, DuckDNS 成功返回 `OK`, 失败返回 `KO`
+    async fn update(&self, host: &str, record_type: &DNSRecordType, content: &str) -> Result<()> {
+        let token = self.token.as_ref().ok_or_else(|| {
+            AppError::Provider(ProviderError::AuthenticationFailed(
+                "未设置 DuckDNS token".to_string(),
+            ))
+        })?;
+
+        let value_param = match record_type {
+            DNSRecordType::A => "ip",
+            DNSRecordType::AAAA => "ipv6",
+            DNSRecordType::TXT => "txt",
+            other => {
+                return Err(AppError::Provider(ProviderError::InvalidConfig(format!(
+                    "DuckDNS 不支持 {} 记录",
+                    other
+                ))))
+            }
+        };
+
+        let response = self
+            .client
+            .get(format!("{}/update", self.endpoint))
+            .query(&[
+                ("domains", host),
+                ("token", token.as_str()),
+                (value_param, content),
+            ])
+            .send()
+            .await
+            .map_err(|e| AppError::Provider(ProviderError::NetworkError(e.to_string())))?;
+
+        let text = response
+            .text()
+            .await
+            .map_err(|e| AppError::Provider(ProviderError::NetworkError(e.to_string())))?;
+
+        match text.lines().next().map(str::trim) {
+            Some("OK") => Ok(()),
+            Some("KO") => Err(AppError::Provider(ProviderError::AuthenticationFailed(
+                format!("token 无效或 {}.{} 不属于该账户", host, DUCKDNS_ZONE),
+            ))),
+            _ => Err(AppError::Provider(ProviderError::ApiError(format!(
+                "未知的响应: {}",
+                text.trim()
+            )))),
+        }
+    }
+
+    /// 更新记录并保存到内存
+    async fn push(
+        &self,
+        domain: &str,
+        record_name: &str,
+        record_type: DNSRecordType,
+        content: &str,
+    ) -> Result<DNSRecord> {
+        let fqdn = full_record_name(domain, record_name);
+        let host = duckdns_host(&fqdn)?;
+        self.update(&host, &record_type, content).await?;
+
+        let id = record_id(record_name, &record_type);
+        let record = DNSRecord {
+            id: id.clone(),
+            name: fqdn,
+            record_type,
+            content: content.to_string(),
+            ttl: 60,
+            proxied: None,
+            priority: None,
+            weight: None,
+            port: None,
+            comment: None,
+        };
+        self.sent.write().unwrap().insert(id, record.clone());
+        Ok(record)
+    }
+}
+
+impl Default for DuckDnsProvider {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[async_trait]
+impl DNSProvider for DuckDnsProvider {
+    fn provider_id(&self) -> &'static str {
+        "duckdns"
+    }
+
+    fn provider_name(&self) -> &'static str {
+        "DuckDNS"
+    }
+
+    async fn initialize(&mut self, credentials: &Credentials) -> Result<()> {
+        self.token = credentials.api_key.clone().or_else(|| {
+            credentials
+                .extra
+                .get("token")
+                .and_then(|v| v.as_str())
+                .map(|v| v.trim().to_string())
+                .filter(|v| !v.is_empty())
+        });
+
+        if self.token.is_none() {
+            return Err(AppError::Provider(ProviderError::InvalidConfig(
+                "DuckDNS 需要提供 token".to_string(),
+            )));
+        }
+        self.sent.write().unwrap().clear();
+
+        Ok(())
+    }
+
+    async fn list_records(&self, _domain: &str) -> Result<Vec<DNSRecord>> {
+        Err(AppError::Provider(ProviderError::Unknown(
+            "DuckDNS 不支持列出记录".to_string(),
+        )))
+    }
+
+    async fn get_record(
+        &self,
+        domain: &str,
+        record_name: &str,
+        record_type: DNSRecordType,
+    ) -> Result<Option<DNSRecord>> {
+        let id = record_id(record_name, &record_type);
+        if let Some(record) = self.sent.read().unwrap().get(&id) {
+            return Ok(Some(record.clone()));
+        }
+
+        // 接口没有查询功能, 通过 DNS 解析获取当前地址
+        let fqdn = full_record_name(domain, record_name);
+        let ip = resolve(&fqdn, &record_type).await;
+        Ok(ip.map(|ip| DNSRecord {
+            id,
+            name: fqdn,
+            record_type,
+            content: ip,
+            ttl: 60,
+            proxied: None,
+            priority: None,
+            weight: None,
+            port: None,
+            comment: None,
+        }))
+    }
+
+    async fn update_record(
+        &self,
+        domain: &str,
+        record_id: &str,
+        new_content: &str,
+    ) -> Result<UpdateResult> {
+        let (subdomain, record_type) = record_id.rsplit_once('|').ok_or_else(|| {
+            AppError::Provider(ProviderError::RecordNotFound(record_id.to_string()))
+        })?;
+        let old_ip = self
+            .sent
+            .read()
+            .unwrap()
+            .get(record_id)
+            .map(|r| r.content.clone())
+            .unwrap_or_default();
+
+        let record = self
+            .push(domain, subdomain, record_type.parse()?, new_content)
+            .await?;
+
+        Ok(UpdateResult {
+            success: true,
+            record_id: record.id,
+            old_ip,
+            new_ip: new_content.to_string(),
+            message: "更新成功".to_string(),
+        })
+    }
+
+    async fn create_record(
+        &self,
+        domain: &str,
+        record_name: &str,
+        record_type: DNSRecordType,
+        content: &str,
+    ) -> Result<DNSRecord> {
+        // DuckDNS 的主机在网页上注册, 这里只是为已有主机设置地址
+        self.push(domain, record_name, record_type, content).await
+    }
+
+    async fn delete_record(&self, _domain: &str, _record_id: &str) -> Result<()> {
+        Err(AppError::Provider(ProviderError::Unknown(
+            "DuckDNS 不支持删除记录".to_string(),
+        )))
+    }
+
+    async fn test_connection(&self) -> Result<bool> {
+        // 更新接口是唯一的接口, 这里只检查配置
+        if self.token.is_none() {
+            return Err(AppError::Provider(ProviderError::AuthenticationFailed(
+                "未设置 DuckDNS token".to_string(),
+            )));
+        }
+        Ok(true)
+    }
+
+    fn supported_record_types(&self) -> Vec<DNSRecordType> {
+        vec![DNSRecordType::A, DNSRecordType::AAAA, DNSRecordType::TXT]
+    }
+}
+
+fn record_id(record_name: &str, record_type: &DNSRecordType) -> String {
+    let subdomain = match record_name {
+        "" => "@",
+        name => name,
+    };
+    format!("{}|{}", subdomain, record_type)
+}
+
+/// 从完整域名中取出 DuckDNS 主机名, 例如 `www.myhost.duckdns.org` -> `myhost`
+fn duckdns_host(fqdn: &str) -> Result<String> {
+    let fqdn = fqdn.trim_end_matches('.').to_lowercase();
+    fqdn.strip_suffix(&format!(".{}", DUCKDNS_ZONE))
+        .and_then(|prefix| prefix.rsplit('.').next())
+        .filter(|host| !host.is_empty())
+        .map(|host| host.to_string())
+        .ok_or_else(|| {
+            AppError::Provider(ProviderError::InvalidConfig(format!(
+                "{} 不是 {} 的子域名",
+                fqdn, DUCKDNS_ZONE
+            )))
+        })
+}
+
+/// 通过 DNS 解析获取主机的当前地址 (仅支持 A / AAAA)
+async fn resolve(hostname: &str, record_type: &DNSRecordType) -> Option<String> {
+    let addrs: Vec<_> = tokio::net::lookup_host((hostname, 0))
+        .await
+        .ok()?
+        .map(|addr| addr.ip())
+        .collect();
+
+    addrs
+        .into_iter()
+        .find(|ip| match record_type {
+            DNSRecordType::A => ip.is_ipv4(),
+            DNSRecordType::AAAA => ip.is_ipv6(),
+            _ => false,
+        })
+        .map(|ip| ip.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use wiremock::matchers::{method, path, query_param};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    fn mock_provider(server: &MockServer) -> DuckDnsProvider {
+        let mut provider = DuckDnsProvider::with_endpoint(server.uri());
+        provider.token = Some("token".to_string());
+        provider
+    }
+
+    #[test]
+    fn test_duckdns_host() {
+        assert_eq!(duckdns_host("myhost.duckdns.org").unwrap(), "myhost");
+        assert_eq!(duckdns_host("www.myhost.duckdns.org.").unwrap(), "myhost");
+        assert!(duckdns_host("duckdns.org").is_err());
+        assert!(duckdns_host("myhost.example.com").is_err());
+    }
+
+    #[tokio::test]
+    async fn test_update_ipv4_ipv6_and_txt() {
+        let server = MockServer::start().await;
+        for (param, value) in [("ip", "1.2.3.4"), ("ipv6", "2001:db8::1"), ("txt", "hello")] {
+            Mock::given(method("GET"))
+                .and(path("/update"))
+                .and(query_param("domains", "myhost"))
+                .and(query_param("token", "token"))
+                .and(query_param(param, value))
+                .respond_with(ResponseTemplate::new(200).set_body_string("OK"))
+                .expect(1)
+                .mount(&server)
+                .await;
+        }
+
+        let provider = mock_provider(&server);
+        provider
+            .create_record("duckdns.org", "myhost", DNSRecordType::A, "1.2.3.4")
+            .await
+            .unwrap();
+        provider
+            .create_record(
+                "myhost.duckdns.org",
+                "@",
+                DNSRecordType::AAAA,
+                "2001:db8::1",
+            )
+            .await
+            .unwrap();
+        let txt = provider
+            .create_record("duckdns.org", "myhost", DNSRecordType::TXT, "hello")
+            .await
+            .unwrap();
+        assert_eq!(txt.id, "myhost|TXT");
+
+        let record = provider
+            .get_record("duckdns.org", "myhost", DNSRecordType::A)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(record.content, "1.2.3.4");
+    }
+
+    #[tokio::test]
+    async fn test_update_rejected() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/update"))
+            .respond_with(ResponseTemplate::new(200).set_body_string("KO"))
+            .mount(&server)
+            .await;
+
+        let provider = mock_provider(&server);
+        let result = provider
+            .update_record("duckdns.org", "myhost|A", "1.2.3.4")
+            .await;
+        assert!(matches!(
+            result,
+            Err(AppError::Provider(ProviderError::AuthenticationFailed(_)))
+        ));
+
+        // 不支持的操作
+        assert!(matches!(
+            provider.list_records("duckdns.org").await,
+            Err(AppError::Provider(ProviderError::Unknown(_)))
+        ));
+        assert!(matches!(
+            provider.delete_record("duckdns.org", "myhost|A").await,
+            Err(AppError::Provider(ProviderError::Unknown(_)))
+        ));
+        assert!(matches!(
+            provider
+                .create_record(
+                    "duckdns.org",
+                    "myhost",
+                    DNSRecordType::CNAME,
+                    "a.example.com"
+                )
+                .await,
+            Err(AppError::Provider(ProviderError::InvalidConfig(_)))
+        ));
+    }
+}
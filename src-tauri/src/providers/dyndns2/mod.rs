@@ -0,0 +1,597 @@
+use crate::error::{AppError, Result};
+use crate::providers::provider_trait::{
+    full_record_name, Credentials, DNSProvider, DNSRecord, DNSRecordType, ProviderError,
+    UpdateResult,
+};
+use async_trait::async_trait;
+use reqwest::Client;
+use std::collections::HashMap;
+use std::sync::{LazyLock, Mutex, RwLock};
+use std::time::{Duration, Instant};
+
+/// 未指定服务器时使用 Dyn 的更新地址
+const DEFAULT_SERVER: &str = "https://members.dyndns.org/nic/update";
+
+/// 服务器只给出主机时使用的协议路径
+const UPDATE_PATH: &str = "/nic/update";
+
+/// 协议要求客户端提供可识别的 User-Agent
+const USER_AGENT: &str = concat!("ddns/", env!("CARGO_PKG_VERSION"));
+
+/// 服务器返回 `911` / `dnserr` 后暂停更新的时长
+const SERVER_ERROR_BACKOFF: Duration = Duration::from_secs(30 * 60);
+
+/// 因 `abuse` / `911` 暂停更新的主机, 键为 `服务器|用户名|主机名`
+///
+/// 更新服务在出错后会丢弃提供商实例并重新创建, 因此暂停状态需要跨实例保存
+static BACKOFF: LazyLock<Mutex<HashMap<String, Backoff>>> =
+    LazyLock::new(|| Mutex::new(HashMap::new()));
+
+/// DynDNS2 协议 (`/nic/update`) 提供商
+///
+/// 适用于 Dyn、No-IP、OVH DynHost 以及众多兼容该协议的服务
+pub struct DynDns2Provider {
+    username: Option<String>,
+    password: Option<String>,
+    server: String,
+    dialect: Dialect,
+    /// 最近一次成功更新的记录, 键为 `subdomain|TYPE`
+    sent: RwLock<HashMap<String, DNSRecord>>,
+    client: Client,
+}
+
+/// 协议方言, 不同服务商对 IPv6 参数的处理不同
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) enum Dialect {
+    /// IPv4 和 IPv6 均通过 `myip` 提交
+    Standard,
+    /// Dynu: IPv6 通过 `myipv6` 提交, 省略的一方需设为 `no` 以免被改成请求来源地址
+    Dynu,
+}
+
+/// 暂停更新的原因
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Backoff {
+    /// 主机因滥用被封禁, 需要用户在服务商处解除后重启应用
+    Abuse,
+    /// 服务器故障, 在指定时间之前不再请求
+    ServerError(Instant),
+}
+
+/// 更新成功时的返回码
+#[derive(Debug, PartialEq)]
+enum UpdateStatus {
+    Good(String),
+    NoChange(String),
+}
+
+impl DynDns2Provider {
+    pub fn new() -> Self {
+        Self::with_server(DEFAULT_SERVER)
+    }
+
+    /// 使用自定义服务器创建提供商 (例如测试服务器)
+    pub fn with_server(server: impl Into<String>) -> Self {
+        Self::with_dialect(server, Dialect::Standard)
+    }
+
+    pub(crate) fn with_dialect(server: impl Into<String>, dialect: Dialect) -> Self {
+        Self {
+            username: None,
+            password: None,
+            server: server.into().trim_end_matches('/').to_string(),
+            dialect,
+            sent: RwLock::new(HashMap::new()),
+            client: Client::builder()
+                .timeout(std::time::Duration::from_secs(30))
+                .user_agent(USER_AGENT)
+                .build()
+                .unwrap(),
+        }
+    }
+
+    fn backoff_key(&self, hostname: &str) -> String {
+        format!(
+            "{}|{}|{}",
+            self.server,
+            self.username.as_deref().unwrap_or_default(),
+            hostname.to_lowercase()
+        )
+    }
+
+    /// 检查主机是否处于暂停更新状态
+    fn check_backoff(&self, hostname: &str) -> Result<()> {
+        let key = self.backoff_key(hostname);
+        let mut backoff = BACKOFF.lock().unwrap();
+
+        match backoff.get(&key).copied() {
+            Some(Backoff::Abuse) => Err(AppError::Provider(ProviderError::ApiError(format!(
+                "{} 已因滥用被服务器封禁 (abuse), 请在服务商处解除后重启应用",
+                hostname
+            )))),
+            Some(Backoff::ServerError(until)) if until > Instant::now() => {
+                Err(AppError::Provider(ProviderError::ApiError(format!(
+                    "服务器暂时不可用, 暂停更新 {} 还剩 {} 秒",
+                    hostname,
+                    (until - Instant::now()).as_secs()
+                ))))
+            }
+            Some(Backoff::ServerError(_)) => {
+                backoff.remove(&key);
+                Ok(())
+            }
+            None => Ok(()),
+        }
+    }
+
+    fn set_backoff(&self, hostname: &str, state: Backoff) {
+        BACKOFF
+            .lock()
+            .unwrap()
+            .insert(self.backoff_key(hostname), state);
+    }
+
+    /// 发送更新请求并解析返回码
+    async fn update(&self, hostname: &str, ip: &str) -> Result<UpdateStatus> {
+        let (username, password) = match (&self.username, &self.password) {
+            (Some(username), Some(password)) => (username, password),
+            _ => {
+                return Err(AppError::Provider(ProviderError::AuthenticationFailed(
+                    "未设置用户名或密码".to_string(),
+                )))
+            }
+        };
+
+        self.check_backoff(hostname)?;
+
+        let is_ipv6 = ip.contains(':');
+        let query = match (self.dialect, is_ipv6) {
+            (Dialect::Dynu, true) => vec![("hostname", hostname), ("myip", "no"), ("myipv6", ip)],
+            (Dialect::Dynu, false) => vec![("hostname", hostname), ("myip", ip), ("myipv6", "no")],
+            (Dialect::Standard, _) => vec![("hostname", hostname), ("myip", ip)],
+        };
+
+        let response = self
+            .client
+            .get(&self.server)
+            .basic_auth(username, Some(password))
+            .query(&query)
+            .send()
+            .await
+            .map_err(|e| AppError::Provider(ProviderError::NetworkError(e.to_string())))?;
+
+        let status = response.status();
+        let mut text = response
+            .text()
+            .await
+            .map_err(|e| AppError::Provider(ProviderError::NetworkError(e.to_string())))?;
+
+        // 部分服务器认证失败时只返回 401, 没有响应内容
+        if status.as_u16() == 401 && text.trim().is_empty() {
+            text = "badauth".to_string();
+        }
+
+        let result = parse_response(&text, hostname);
+        match text.split_whitespace().next() {
+            Some("abuse") => self.set_backoff(hostname, Backoff::Abuse),
+            Some("911") | Some("dnserr") => self.set_backoff(
+                hostname,
+                Backoff::ServerError(Instant::now() + SERVER_ERROR_BACKOFF),
+            ),
+            _ => {}
+        }
+        result
+    }
+
+    /// 更新主机并保存到内存
+    async fn push(
+        &self,
+        domain: &str,
+        record_name: &str,
+        record_type: &str,
+        content: &str,
+    ) -> Result<(DNSRecord, UpdateStatus)> {
+        let subdomain = match record_name {
+            "" => "@",
+            name => name,
+        };
+        let hostname = full_record_name(domain, record_name);
+        let status = self.update(&hostname, content).await?;
+
+        let id = record_id(subdomain, record_type);
+        let record = DNSRecord {
+            id: id.clone(),
+            name: hostname,
+            record_type: record_type.parse()?,
+            content: content.to_string(),
+            ttl: 0,
+            proxied: None,
+            priority: None,
+            weight: None,
+            port: None,
+            comment: None,
+        };
+        self.sent.write().unwrap().insert(id, record.clone());
+        Ok((record, status))
+    }
+}
+
+impl Default for DynDns2Provider {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[async_trait]
+impl DNSProvider for DynDns2Provider {
+    fn provider_id(&self) -> &'static str {
+        "dyndns2"
+    }
+
+    fn provider_name(&self) -> &'static str {
+        "DynDNS2 (通用 nic/update 协议)"
+    }
+
+    async fn initialize(&mut self, credentials: &Credentials) -> Result<()> {
+        let extra = |key: &str| {
+            credentials
+                .extra
+                .get(key)
+                .and_then(|v| v.as_str())
+                .map(|v| v.trim().to_string())
+                .filter(|v| !v.is_empty())
+        };
+
+        self.username = extra("username").or_else(|| credentials.api_key.clone());
+        self.password = extra("password").or_else(|| credentials.api_secret.clone());
+
+        if self.username.is_none() || self.password.is_none() {
+            return Err(AppError::Provider(ProviderError::InvalidConfig(
+                "DynDNS2 需要提供 username 和 password".to_string(),
+            )));
+        }
+
+        if let Some(server) = extra("server") {
+            self.server = server_url(&server)?;
+        }
+        self.sent.write().unwrap().clear();
+
+        Ok(())
+    }
+
+    async fn list_records(&self, _domain: &str) -> Result<Vec<DNSRecord>> {
+        Err(AppError::Provider(ProviderError::Unknown(
+            "DynDNS2 协议不支持列出记录".to_string(),
+        )))
+    }
+
+    async fn get_record(
+        &self,
+        domain: &str,
+        record_name: &str,
+        record_type: DNSRecordType,
+    ) -> Result<Option<DNSRecord>> {
+        let subdomain = match record_name {
+            "" => "@",
+            name => name,
+        };
+        let id = record_id(subdomain, &record_type.to_string());
+        if let Some(record) = self.sent.read().unwrap().get(&id) {
+            return Ok(Some(record.clone()));
+        }
+
+        // 协议没有查询接口, 通过 DNS 解析获取当前地址, 避免重复提交相同 IP (会被视为滥用)
+        let hostname = full_record_name(domain, record_name);
+        let addrs: Vec<_> = match tokio::net::lookup_host((hostname.as_str(), 0)).await {
+            Ok(addrs) => addrs.map(|addr| addr.ip()).collect(),
+            Err(_) => return Ok(None),
+        };
+
+        Ok(addrs
+            .into_iter()
+            .find(|ip| match record_type {
+                DNSRecordType::A => ip.is_ipv4(),
+                DNSRecordType::AAAA => ip.is_ipv6(),
+                _ => false,
+            })
+            .map(|ip| DNSRecord {
+                id,
+                name: hostname,
+                record_type,
+                content: ip.to_string(),
+                ttl: 0,
+                proxied: None,
+                priority: None,
+                weight: None,
+                port: None,
+                comment: None,
+            }))
+    }
+
+    async fn update_record(
+        &self,
+        domain: &str,
+        record_id: &str,
+        new_content: &str,
+    ) -> Result<UpdateResult> {
+        let (subdomain, record_type) = record_id.rsplit_once('|').ok_or_else(|| {
+            AppError::Provider(ProviderError::RecordNotFound(record_id.to_string()))
+        })?;
+        let old_ip = self
+            .sent
+            .read()
+            .unwrap()
+            .get(record_id)
+            .map(|r| r.content.clone())
+            .unwrap_or_default();
+
+        let (record, status) = self
+            .push(domain, subdomain, record_type, new_content)
+            .await?;
+
+        Ok(UpdateResult {
+            success: true,
+            record_id: record.id,
+            old_ip,
+            new_ip: new_content.to_string(),
+            message: match status {
+                UpdateStatus::Good(_) => "更新成功".to_string(),
+                UpdateStatus::NoChange(_) => "记录已是最新".to_string(),
+            },
+        })
+    }
+
+    async fn create_record(
+        &self,
+        domain: &str,
+        record_name: &str,
+        record_type: DNSRecordType,
+        content: &str,
+    ) -> Result<DNSRecord> {
+        let (record, _) = self
+            .push(domain, record_name, &record_type.to_string(), content)
+            .await?;
+        Ok(record)
+    }
+
+    async fn delete_record(&self, _domain: &str, _record_id: &str) -> Result<()> {
+        Err(AppError::Provider(ProviderError::Unknown(
+            "DynDNS2 协议不支持删除记录".to_string(),
+        )))
+    }
+
+    async fn test_connection(&self) -> Result<bool> {
+        // 协议没有只读接口, 任何请求都会更新主机, 这里只检查配置
+        if self.username.is_none() || self.password.is_none() {
+            return Err(AppError::Provider(ProviderError::AuthenticationFailed(
+                "未设置用户名或密码".to_string(),
+            )));
+        }
+        Ok(true)
+    }
+
+    fn supported_record_types(&self) -> Vec<DNSRecordType> {
+        vec![DNSRecordType::A, DNSRecordType::AAAA]
+    }
+}
+
+fn record_id(subdomain: &str, record_type: &str) -> String {
+    format!("{}|{}", subdomain, record_type)
+}
+
+/// 规范化服务器地址: 缺省协议时使用 https, 只有主机时补全 `/nic/update`
+fn server_url(server: &str) -> Result<String> {
+    let server = if server.contains("://") {
+        server.to_string()
+    } else {
+        format!("https://{}", server)
+    };
+
+    let mut url = reqwest::Url::parse(&server).map_err(|e| {
+        AppError::Provider(ProviderError::InvalidConfig(format!(
+            "无效的服务器地址 {}: {}",
+            server, e
+        )))
+    })?;
+    if url.path().is_empty() || url.path() == "/" {
+        url.set_path(UPDATE_PATH);
+    }
+
+    Ok(url.to_string())
+}
+
+/// 解析协议返回码
+fn parse_response(text: &str, hostname: &str) -> Result<UpdateStatus> {
+    let text = text.trim();
+    let mut parts = text.split_whitespace();
+    let code = parts.next().unwrap_or_default();
+    let ip = parts.next().unwrap_or_default().to_string();
+
+    let error = match code {
+        "good" => return Ok(UpdateStatus::Good(ip)),
+        "nochg" => return Ok(UpdateStatus::NoChange(ip)),
+        "badauth" => ProviderError::AuthenticationFailed("用户名或密码错误".to_string()),
+        "!yours" => ProviderError::AuthenticationFailed(format!("{} 不属于该账户", hostname)),
+        "nohost" => ProviderError::DomainNotFound(hostname.to_string()),
+        "notfqdn" => ProviderError::InvalidConfig(format!("{} 不是完整域名", hostname)),
+        "numhost" => ProviderError::InvalidConfig("一次请求的主机数量过多".to_string()),
+        "badagent" => ProviderError::InvalidConfig("客户端被服务器拒绝 (badagent)".to_string()),
+        "!donator" => ProviderError::InvalidConfig("该功能仅对付费账户开放".to_string()),
+        "abuse" => ProviderError::RateLimitExceeded,
+        "911" | "dnserr" => ProviderError::ApiError(format!(
+            "服务器故障 ({}), 暂停更新 {} 分钟",
+            code,
+            SERVER_ERROR_BACKOFF.as_secs() / 60
+        )),
+        _ => ProviderError::ApiError(format!("未知的响应: {}", text)),
+    };
+
+    Err(AppError::Provider(error))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use wiremock::matchers::{header, method, path, query_param};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    fn mock_provider(server: &MockServer) -> DynDns2Provider {
+        let mut provider = DynDns2Provider::with_server(format!("{}/nic/update", server.uri()));
+        provider.username = Some("user".to_string());
+        provider.password = Some("pass".to_string());
+        provider
+    }
+
+    #[test]
+    fn test_server_url() {
+        assert_eq!(
+            server_url("dynupdate.no-ip.com").unwrap(),
+            "https://dynupdate.no-ip.com/nic/update"
+        );
+        assert_eq!(
+            server_url("https://www.ovh.com/nic/update").unwrap(),
+            "https://www.ovh.com/nic/update"
+        );
+        assert_eq!(
+            server_url("http://router.lan:8080/").unwrap(),
+            "http://router.lan:8080/nic/update"
+        );
+    }
+
+    #[test]
+    fn test_parse_response() {
+        let host = "home.example.com";
+
+        assert_eq!(
+            parse_response("good 1.2.3.4\n", host).unwrap(),
+            UpdateStatus::Good("1.2.3.4".to_string())
+        );
+        assert_eq!(
+            parse_response("nochg 1.2.3.4", host).unwrap(),
+            UpdateStatus::NoChange("1.2.3.4".to_string())
+        );
+        assert!(matches!(
+            parse_response("badauth", host),
+            Err(AppError::Provider(ProviderError::AuthenticationFailed(_)))
+        ));
+        assert!(matches!(
+            parse_response("nohost", host),
+            Err(AppError::Provider(ProviderError::DomainNotFound(_)))
+        ));
+        assert!(matches!(
+            parse_response("notfqdn", host),
+            Err(AppError::Provider(ProviderError::InvalidConfig(_)))
+        ));
+        assert!(matches!(
+            parse_response("abuse", host),
+            Err(AppError::Provider(ProviderError::RateLimitExceeded))
+        ));
+        assert!(matches!(
+            parse_response("911", host),
+            Err(AppError::Provider(ProviderError::ApiError(_)))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_create_and_update_record() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/nic/update"))
+            .and(header("authorization", "Basic dXNlcjpwYXNz"))
+            .and(query_param("hostname", "home.example.com"))
+            .and(query_param("myip", "1.2.3.4"))
+            .respond_with(ResponseTemplate::new(200).set_body_string("good 1.2.3.4"))
+            .expect(1)
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/nic/update"))
+            .and(query_param("myip", "5.6.7.8"))
+            .respond_with(ResponseTemplate::new(200).set_body_string("nochg 5.6.7.8"))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let provider = mock_provider(&server);
+        let created = provider
+            .create_record("example.com", "home", DNSRecordType::A, "1.2.3.4")
+            .await
+            .unwrap();
+        assert_eq!(created.id, "home|A");
+
+        let record = provider
+            .get_record("example.com", "home", DNSRecordType::A)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(record.content, "1.2.3.4");
+
+        let result = provider
+            .update_record("example.com", &record.id, "5.6.7.8")
+            .await
+            .unwrap();
+        assert_eq!(result.old_ip, "1.2.3.4");
+        assert_eq!(result.message, "记录已是最新");
+    }
+
+    #[tokio::test]
+    async fn test_unauthorized_without_body() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/nic/update"))
+            .respond_with(ResponseTemplate::new(401))
+            .mount(&server)
+            .await;
+
+        let result = mock_provider(&server)
+            .create_record("example.com", "auth", DNSRecordType::A, "1.2.3.4")
+            .await;
+        assert!(matches!(
+            result,
+            Err(AppError::Provider(ProviderError::AuthenticationFailed(_)))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_backoff_after_abuse_and_911() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/nic/update"))
+            .and(query_param("hostname", "abuse.example.com"))
+            .respond_with(ResponseTemplate::new(200).set_body_string("abuse"))
+            .expect(1)
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/nic/update"))
+            .and(query_param("hostname", "down.example.com"))
+            .respond_with(ResponseTemplate::new(200).set_body_string("911"))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        for name in ["abuse", "down"] {
+            let first = mock_provider(&server)
+                .create_record("example.com", name, DNSRecordType::A, "1.2.3.4")
+                .await;
+            assert!(first.is_err());
+
+            // 新的提供商实例同样处于暂停状态, 不会再次请求服务器
+            let second = mock_provider(&server)
+                .create_record("example.com", name, DNSRecordType::A, "1.2.3.4")
+                .await;
+            assert!(matches!(
+                second,
+                Err(AppError::Provider(ProviderError::ApiError(_)))
+            ));
+        }
+
+        // 服务器故障的暂停到期后恢复更新
+        let provider = mock_provider(&server);
+        provider.set_backoff(
+            "down.example.com",
+            Backoff::ServerError(Instant::now() - Duration::from_secs(1)),
+        );
+        assert!(provider.check_backoff("down.example.com").is_ok());
+    }
+}
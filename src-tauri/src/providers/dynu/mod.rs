@@ -0,0 +1,158 @@
+use crate::error::Result;
+use crate::providers::dyndns2::{Dialect, DynDns2Provider};
+use crate::providers::provider_trait::{
+    Credentials, DNSProvider, DNSRecord, DNSRecordType, UpdateResult,
+};
+use async_trait::async_trait;
+
+/// Dynu 的 IP 更新地址
+const DEFAULT_SERVER: &str = "https://api.dynu.com/nic/update";
+
+/// Dynu 提供商
+///
+/// 使用 Dynu 兼容 DynDNS2 的 IP 更新接口, 用户名为 Dynu 账户名,
+/// 密码可以是账户密码或其 MD5 / SHA256 值
+pub struct DynuProvider {
+    inner: DynDns2Provider,
+}
+
+impl DynuProvider {
+    pub fn new() -> Self {
+        Self::with_server(DEFAULT_SERVER)
+    }
+
+    /// 使用自定义更新地址创建提供商 (例如测试服务器)
+    pub fn with_server(server: impl Into<String>) -> Self {
+        Self {
+            inner: DynDns2Provider::with_dialect(server, Dialect::Dynu),
+        }
+    }
+}
+
+impl Default for DynuProvider {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[async_trait]
+impl DNSProvider for DynuProvider {
+    fn provider_id(&self) -> &'static str {
+        "dynu"
+    }
+
+    fn provider_name(&self) -> &'static str {
+        "Dynu"
+    }
+
+    async fn initialize(&mut self, credentials: &Credentials) -> Result<()> {
+        self.inner.initialize(credentials).await
+    }
+
+    async fn list_records(&self, domain: &str) -> Result<Vec<DNSRecord>> {
+        self.inner.list_records(domain).await
+    }
+
+    async fn get_record(
+        &self,
+        domain: &str,
+        record_name: &str,
+        record_type: DNSRecordType,
+    ) -> Result<Option<DNSRecord>> {
+        self.inner
+            .get_record(domain, record_name, record_type)
+            .await
+    }
+
+    async fn update_record(
+        &self,
+        domain: &str,
+        record_id: &str,
+        new_content: &str,
+    ) -> Result<UpdateResult> {
+        self.inner
+            .update_record(domain, record_id, new_content)
+            .await
+    }
+
+    async fn create_record(
+        &self,
+        domain: &str,
+        record_name: &str,
+        record_type: DNSRecordType,
+        content: &str,
+    ) -> Result<DNSRecord> {
+        self.inner
+            .create_record(domain, record_name, record_type, content)
+            .await
+    }
+
+    async fn delete_record(&self, domain: &str, record_id: &str) -> Result<()> {
+        self.inner.delete_record(domain, record_id).await
+    }
+
+    async fn test_connection(&self) -> Result<bool> {
+        self.inner.test_connection().await
+    }
+
+    fn supported_record_types(&self) -> Vec<DNSRecordType> {
+        vec![DNSRecordType::A, DNSRecordType::AAAA]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::error::AppError;
+    use crate::providers::ProviderError;
+    use serde_json::json;
+    use wiremock::matchers::{method, path, query_param};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    #[tokio::test]
+    async fn test_update_sends_dynu_ipv6_parameters() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/nic/update"))
+            .and(query_param("hostname", "home.dynu.net"))
+            .and(query_param("myip", "1.2.3.4"))
+            .and(query_param("myipv6", "no"))
+            .respond_with(ResponseTemplate::new(200).set_body_string("good 1.2.3.4"))
+            .expect(1)
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/nic/update"))
+            .and(query_param("hostname", "home.dynu.net"))
+            .and(query_param("myip", "no"))
+            .and(query_param("myipv6", "2001:db8::1"))
+            .respond_with(ResponseTemplate::new(200).set_body_string("nochg 2001:db8::1"))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let mut provider = DynuProvider::with_server(format!("{}/nic/update", server.uri()));
+        provider
+            .initialize(&Credentials {
+                extra: json!({ "username": "user", "password": "pass" }),
+                ..Default::default()
+            })
+            .await
+            .unwrap();
+
+        provider
+            .create_record("dynu.net", "home", DNSRecordType::A, "1.2.3.4")
+            .await
+            .unwrap();
+        let result = provider
+            .update_record("dynu.net", "home|AAAA", "2001:db8::1")
+            .await
+            .unwrap();
+        assert_eq!(result.message, "记录已是最新");
+
+        assert!(matches!(
+            provider.list_records("dynu.net").await,
+            Err(AppError::Provider(ProviderError::Unknown(_)))
+        ));
+    }
+}
@@ -0,0 +1,394 @@
+use crate::error::{AppError, Result};
+use crate::providers::provider_trait::{
+    full_record_name, Credentials, DNSProvider, DNSRecord, DNSRecordType, ProviderError,
+    UpdateResult,
+};
+use async_trait::async_trait;
+use reqwest::Client;
+use std::collections::HashMap;
+use std::sync::RwLock;
+
+/// IPv4 同步地址
+const DEFAULT_ENDPOINT: &str = "https://sync.afraid.org";
+
+/// IPv6 同步地址 (AAAA 记录需要通过 IPv6 专用域名更新)
+const DEFAULT_ENDPOINT_V6: &str = "https://v6.sync.afraid.org";
+
+/// afraid.org FreeDNS 提供商
+///
+/// 使用动态更新 v2 接口 (`/u/{token}/`), 每条记录有独立的更新 token,
+/// 因此一个凭证只对应一个主机, 无法列出或删除记录
+pub struct FreeDnsProvider {
+    token: Option<String>,
+    /// AAAA 记录的更新 token, 未设置时使用 `token`
+    token_v6: Option<String>,
+    endpoint: String,
+    endpoint_v6: String,
+    /// 最近一次成功更新的记录, 键为 `subdomain|TYPE`
+    sent: RwLock<HashMap<String, DNSRecord>>,
+    client: Client,
+}
+
+/// 更新成功时的结果
+#[derive(Debug, PartialEq)]
+enum SyncStatus {
+    Updated,
+    NoChange,
+}
+
+impl FreeDnsProvider {
+    pub fn new() -> Self {
+        Self {
+            endpoint_v6: DEFAULT_ENDPOINT_V6.to_string(),
+            ..Self::with_endpoint(DEFAULT_ENDPOINT)
+        }
+    }
+
+    /// 使用自定义 API 地址创建提供商 (例如测试服务器), IPv4 和 IPv6 共用该地址
+    pub fn with_endpoint(endpoint: impl Into<String>) -> Self {
+        let endpoint = endpoint.into().trim_end_matches('/').to_string();
+        Self {
+            token: None,
+            token_v6: None,
+            endpoint_v6: endpoint.clone(),
+            endpoint,
+            sent: RwLock::new(HashMap::new()),
+            client: Client::builder()
+                .timeout(std::time::Duration::from_secs(30))
+                .build()
+                .unwrap(),
+        }
+    }
+
+    /// 调用同步接口
+    async fn sync(&self, record_type: &DNSRecordType, ip: &str) -> Result<SyncStatus> {
+        let (endpoint, token) = match record_type {
+            DNSRecordType::A => (&self.endpoint, self.token.as_ref()),
+            DNSRecordType::AAAA => (
+                &self.endpoint_v6,
+                self.token_v6.as_ref().or(self.token.as_ref()),
+            ),
+            other => {
+                return Err(AppError::Provider(ProviderError::InvalidConfig(format!(
+                    "FreeDNS 不支持 {} 记录",
+                    other
+                ))))
+            }
+        };
+        let token = token.ok_or_else(|| {
+            AppError::Provider(ProviderError::AuthenticationFailed(
+                "未设置 FreeDNS 更新 token".to_string(),
+            ))
+        })?;
+
+        let response = self
+            .client
+            .get(format!("{}/u/{}/", endpoint, urlencoding::encode(token)))
+            .query(&[("ip", ip)])
+            .send()
+            .await
+            .map_err(|e| AppError::Provider(ProviderError::NetworkError(e.to_string())))?;
+
+        let text = response
+            .text()
+            .await
+            .map_err(|e| AppError::Provider(ProviderError::NetworkError(e.to_string())))?;
+
+        parse_response(&text)
+    }
+
+    /// 更新记录并保存到内存
+    async fn push(
+        &self,
+        domain: &str,
+        record_name: &str,
+        record_type: DNSRecordType,
+        content: &str,
+    ) -> Result<(DNSRecord, SyncStatus)> {
+        let status = self.sync(&record_type, content).await?;
+
+        let id = record_id(record_name, &record_type);
+        let record = DNSRecord {
+            id: id.clone(),
+            name: full_record_name(domain, record_name),
+            record_type,
+            content: content.to_string(),
+            ttl: 0,
+            proxied: None,
+            priority: None,
+            weight: None,
+            port: None,
+            comment: None,
+        };
+        self.sent.write().unwrap().insert(id, record.clone());
+        Ok((record, status))
+    }
+}
+
+impl Default for FreeDnsProvider {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[async_trait]
+impl DNSProvider for FreeDnsProvider {
+    fn provider_id(&self) -> &'static str {
+        "freedns"
+    }
+
+    fn provider_name(&self) -> &'static str {
+        "FreeDNS (afraid.org)"
+    }
+
+    async fn initialize(&mut self, credentials: &Credentials) -> Result<()> {
+        let extra = |key: &str| {
+            credentials
+                .extra
+                .get(key)
+                .and_then(|v| v.as_str())
+                .map(|v| v.trim().to_string())
+                .filter(|v| !v.is_empty())
+        };
+
+        self.token = credentials.api_key.clone().or_else(|| extra("token"));
+        self.token_v6 = extra("token_v6");
+
+        if self.token.is_none() && self.token_v6.is_none() {
+            return Err(AppError::Provider(ProviderError::InvalidConfig(
+                "FreeDNS 需要提供记录的更新 token".to_string(),
+            )));
+        }
+        self.sent.write().unwrap().clear();
+
+        Ok(())
+    }
+
+    async fn list_records(&self, _domain: &str) -> Result<Vec<DNSRecord>> {
+        Err(AppError::Provider(ProviderError::Unknown(
+            "FreeDNS 不支持列出记录".to_string(),
+        )))
+    }
+
+    async fn get_record(
+        &self,
+        domain: &str,
+        record_name: &str,
+        record_type: DNSRecordType,
+    ) -> Result<Option<DNSRecord>> {
+        let id = record_id(record_name, &record_type);
+        if let Some(record) = self.sent.read().unwrap().get(&id) {
+            return Ok(Some(record.clone()));
+        }
+
+        // 接口没有查询功能, 通过 DNS 解析获取当前地址
+        let fqdn = full_record_name(domain, record_name);
+        let ip = resolve(&fqdn, &record_type).await;
+        Ok(ip.map(|ip| DNSRecord {
+            id,
+            name: fqdn,
+            record_type,
+            content: ip,
+            ttl: 0,
+            proxied: None,
+            priority: None,
+            weight: None,
+            port: None,
+            comment: None,
+        }))
+    }
+
+    async fn update_record(
+        &self,
+        domain: &str,
+        record_id: &str,
+        new_content: &str,
+    ) -> Result<UpdateResult> {
+        let (subdomain, record_type) = record_id.rsplit_once('|').ok_or_else(|| {
+            AppError::Provider(ProviderError::RecordNotFound(record_id.to_string()))
+        })?;
+        let old_ip = self
+            .sent
+            .read()
+            .unwrap()
+            .get(record_id)
+            .map(|r| r.content.clone())
+            .unwrap_or_default();
+
+        let (record, status) = self
+            .push(domain, subdomain, record_type.parse()?, new_content)
+            .await?;
+
+        Ok(UpdateResult {
+            success: true,
+            record_id: record.id,
+            old_ip,
+            new_ip: new_content.to_string(),
+            message: match status {
+                SyncStatus::Updated => "更新成功".to_string(),
+                SyncStatus::NoChange => "记录已是最新".to_string(),
+            },
+        })
+    }
+
+    async fn create_record(
+        &self,
+        domain: &str,
+        record_name: &str,
+        record_type: DNSRecordType,
+        content: &str,
+    ) -> Result<DNSRecord> {
+        // 记录需要先在 FreeDNS 网页上创建, 这里只是同步其地址
+        let (record, _) = self.push(domain, record_name, record_type, content).await?;
+        Ok(record)
+    }
+
+    async fn delete_record(&self, _domain: &str, _record_id: &str) -> Result<()> {
+        Err(AppError::Provider(ProviderError::Unknown(
+            "FreeDNS 不支持删除记录".to_string(),
+        )))
+    }
+
+    async fn test_connection(&self) -> Result<bool> {
+        // 同步接口是唯一的接口, 这里只检查配置
+        if self.token.is_none() && self.token_v6.is_none() {
+            return Err(AppError::Provider(ProviderError::AuthenticationFailed(
+                "未设置 FreeDNS 更新 token".to_string(),
+            )));
+        }
+        Ok(true)
+    }
+
+    fn supported_record_types(&self) -> Vec<DNSRecordType> {
+        vec![DNSRecordType::A, DNSRecordType::AAAA]
+    }
+}
+
+fn record_id(record_name: &str, record_type: &DNSRecordType) -> String {
+    let subdomain = match record_name {
+        "" => "@",
+        name => name,
+    };
+    format!("{}|{}", subdomain, record_type)
+}
+
+/// 解析同步接口的响应
+///
+/// 成功: `Updated 1 host(s) home.example.com to 1.2.3.4 in 0.1 seconds`
+/// 未变化: `No IP change detected for home.example.com with IP 1.2.3.4, skipping update`
+/// 失败: `ERROR: ...`
+fn parse_response(text: &str) -> Result<SyncStatus> {
+    let text = text.trim();
+
+    if text.starts_with("Updated") {
+        return Ok(SyncStatus::Updated);
+    }
+    if text.starts_with("No IP change") || text.contains("has not changed") {
+        return Ok(SyncStatus::NoChange);
+    }
+
+    let message = text.strip_prefix("ERROR:").unwrap_or(text).trim();
+    Err(AppError::Provider(
+        if message.contains("Unable to locate this record") {
+            ProviderError::AuthenticationFailed(format!("更新 token 无效: {}", message))
+        } else {
+            ProviderError::ApiError(message.to_string())
+        },
+    ))
+}
+
+/// 通过 DNS 解析获取主机的当前地址 (仅支持 A / AAAA)
+async fn resolve(hostname: &str, record_type: &DNSRecordType) -> Option<String> {
+    let addrs: Vec<_> = tokio::net::lookup_host((hostname, 0))
+        .await
+        .ok()?
+        .map(|addr| addr.ip())
+        .collect();
+
+    addrs
+        .into_iter()
+        .find(|ip| match record_type {
+            DNSRecordType::A => ip.is_ipv4(),
+            DNSRecordType::AAAA => ip.is_ipv6(),
+            _ => false,
+        })
+        .map(|ip| ip.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use wiremock::matchers::{method, path, query_param};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    #[test]
+    fn test_parse_response() {
+        assert_eq!(
+            parse_response("Updated 1 host(s) home.example.com to 1.2.3.4 in 0.12 seconds")
+                .unwrap(),
+            SyncStatus::Updated
+        );
+        assert_eq!(
+            parse_response(
+                "No IP change detected for home.example.com with IP 1.2.3.4, skipping update"
+            )
+            .unwrap(),
+            SyncStatus::NoChange
+        );
+        assert!(matches!(
+            parse_response("ERROR: Unable to locate this record (changed password recently?)"),
+            Err(AppError::Provider(ProviderError::AuthenticationFailed(_)))
+        ));
+        assert!(matches!(
+            parse_response("ERROR: Missing IP"),
+            Err(AppError::Provider(ProviderError::ApiError(_)))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_sync_uses_token_per_record_type() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/u/token4/"))
+            .and(query_param("ip", "1.2.3.4"))
+            .respond_with(
+                ResponseTemplate::new(200).set_body_string(
+                    "Updated 1 host(s) home.example.com to 1.2.3.4 in 0.1 seconds",
+                ),
+            )
+            .expect(1)
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/u/token6/"))
+            .and(query_param("ip", "2001:db8::1"))
+            .respond_with(ResponseTemplate::new(200).set_body_string(
+                "No IP change detected for home.example.com with IP 2001:db8::1, skipping update",
+            ))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let mut provider = FreeDnsProvider::with_endpoint(server.uri());
+        provider.token = Some("token4".to_string());
+        provider.token_v6 = Some("token6".to_string());
+
+        let record = provider
+            .create_record("example.com", "home", DNSRecordType::A, "1.2.3.4")
+            .await
+            .unwrap();
+        assert_eq!(record.id, "home|A");
+        assert_eq!(record.name, "home.example.com");
+
+        let result = provider
+            .update_record("example.com", "home|AAAA", "2001:db8::1")
+            .await
+            .unwrap();
+        assert_eq!(result.message, "记录已是最新");
+
+        assert!(matches!(
+            provider.delete_record("example.com", "home|A").await,
+            Err(AppError::Provider(ProviderError::Unknown(_)))
+        ));
+    }
+}
@@ -0,0 +1,715 @@
+use crate::error::{AppError, Result};
+use crate::providers::provider_trait::{
+    full_record_name, Credentials, DNSProvider, DNSRecord, DNSRecordType, ProviderCapabilities,
+    ProviderError, RecordContent, RecordSpec, UpdateResult,
+};
+use crate::providers::rrset::{remove_value, replace_value, split_value_id, value_id};
+use async_trait::async_trait;
+use reqwest::{Client, Method};
+use serde::de::DeserializeOwned;
+use serde::Deserialize;
+use serde_json::json;
+
+const DEFAULT_ENDPOINT: &str = "https://api.gandi.net/v5/livedns";
+
+/// 新建记录的默认 TTL (LiveDNS 允许的最小值)
+const DEFAULT_TTL: u32 = 300;
+
+/// 未指定优先级时 MX 记录使用的优先级
+const DEFAULT_MX_PRIORITY: u16 = 10;
+
+/// Gandi LiveDNS 提供商
+///
+/// 记录以记录集为单位管理, 记录集 ID 为 `名称/类型`, 例如 `www/A`,
+/// 记录集中的每个值对应一条记录, 记录 ID 为 `www/A#203.0.113.10`
+pub struct GandiProvider {
+    api_token: Option<String>,
+    endpoint: String,
+    client: Client,
+}
+
+/// 记录集, `rrset_name` 为相对名称 (根域名为 `@`)
+#[derive(Debug, Deserialize)]
+struct GandiRRSet {
+    rrset_name: String,
+    rrset_type: String,
+    #[serde(default)]
+    rrset_ttl: Option<u32>,
+    #[serde(default)]
+    rrset_values: Vec<String>,
+}
+
+impl GandiProvider {
+    pub fn new() -> Self {
+        Self::with_endpoint(DEFAULT_ENDPOINT)
+    }
+
+    /// 使用自定义 API 地址创建提供商 (例如测试服务器)
+    pub fn with_endpoint(endpoint: impl Into<String>) -> Self {
+        Self {
+            api_token: None,
+            endpoint: endpoint.into().trim_end_matches('/').to_string(),
+            client: Client::builder()
+                .timeout(std::time::Duration::from_secs(30))
+                .build()
+                .unwrap(),
+        }
+    }
+
+    /// 发送 API 请求并解析 JSON 响应 (响应为空时按 `null` 解析)
+    async fn send<T: DeserializeOwned>(
+        &self,
+        method: Method,
+        path: &str,
+        query: &[(&str, String)],
+        body: Option<serde_json::Value>,
+    ) -> Result<T> {
+        let token = self.api_token.as_ref().ok_or_else(|| {
+            AppError::Provider(ProviderError::AuthenticationFailed(
+                "未设置 Personal Access Token".to_string(),
+            ))
+        })?;
+
+        let mut request = self
+            .client
+            .request(method, format!("{}{}", self.endpoint, path))
+            .bearer_auth(token)
+            .query(query);
+        if let Some(body) = body {
+            request = request.json(&body);
+        }
+
+        let response = request
+            .send()
+            .await
+            .map_err(|e| AppError::Provider(ProviderError::NetworkError(e.to_string())))?;
+        let status = response.status();
+        let text = response
+            .text()
+            .await
+            .map_err(|e| AppError::Provider(ProviderError::NetworkError(e.to_string())))?;
+
+        if !status.is_success() {
+            return Err(parse_error(status.as_u16(), &text));
+        }
+
+        let text = if text.trim().is_empty() {
+            "null"
+        } else {
+            &text
+        };
+        serde_json::from_str(text).map_err(|e| {
+            AppError::Provider(ProviderError::ParseError(format!("解析响应失败: {}", e)))
+        })
+    }
+
+    /// 获取单个记录集, 不存在时返回 None
+    async fn get_rrset(&self, domain: &str, rrset_id: &str) -> Result<Option<GandiRRSet>> {
+        let result = self
+            .send(
+                Method::GET,
+                &format!("/domains/{}/records/{}", domain, rrset_id),
+                &[],
+                None,
+            )
+            .await;
+
+        match result {
+            Ok(rrset) => Ok(Some(rrset)),
+            Err(AppError::Provider(ProviderError::RecordNotFound(_))) => Ok(None),
+            Err(e) => Err(e),
+        }
+    }
+
+    /// 用新的值列表替换记录集中的全部值
+    async fn put_rrset(
+        &self,
+        domain: &str,
+        rrset_id: &str,
+        values: &[String],
+        ttl: u32,
+    ) -> Result<()> {
+        let _: serde_json::Value = self
+            .send(
+                Method::PUT,
+                &format!("/domains/{}/records/{}", domain, rrset_id),
+                &[],
+                Some(json!({
+                    "rrset_values": values,
+                    "rrset_ttl": ttl,
+                })),
+            )
+            .await?;
+        Ok(())
+    }
+}
+
+impl Default for GandiProvider {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[async_trait]
+impl DNSProvider for GandiProvider {
+    fn provider_id(&self) -> &'static str {
+        "gandi"
+    }
+
+    fn provider_name(&self) -> &'static str {
+        "Gandi LiveDNS"
+    }
+
+    async fn initialize(&mut self, credentials: &Credentials) -> Result<()> {
+        self.api_token = credentials.api_key.clone().or_else(|| {
+            credentials
+                .extra
+                .get("api_token")
+                .and_then(|v| v.as_str())
+                .map(|v| v.to_string())
+        });
+
+        if self.api_token.is_none() {
+            return Err(AppError::Provider(ProviderError::InvalidConfig(
+                "Gandi 需要提供 api_token (Personal Access Token)".to_string(),
+            )));
+        }
+
+        // 测试连接
+        self.test_connection().await?;
+
+        Ok(())
+    }
+
+    async fn list_records(&self, domain: &str) -> Result<Vec<DNSRecord>> {
+        let rrsets: Vec<GandiRRSet> = self
+            .send(
+                Method::GET,
+                &format!("/domains/{}/records", domain),
+                &[],
+                None,
+            )
+            .await?;
+
+        Ok(rrsets
+            .iter()
+            .flat_map(|r| to_dns_records(domain, r))
+            .collect())
+    }
+
+    async fn get_record(
+        &self,
+        domain: &str,
+        record_name: &str,
+        record_type: DNSRecordType,
+    ) -> Result<Option<DNSRecord>> {
+        let rrset_id = rrset_id(record_name, &record_type.to_string());
+        Ok(self
+            .get_rrset(domain, &rrset_id)
+            .await?
+            .and_then(|r| to_dns_records(domain, &r).into_iter().next()))
+    }
+
+    async fn update_record(
+        &self,
+        domain: &str,
+        record_id: &str,
+        new_content: &str,
+    ) -> Result<UpdateResult> {
+        self.update_record_with(domain, record_id, new_content, &RecordSpec::default())
+            .await
+    }
+
+    async fn update_record_with(
+        &self,
+        domain: &str,
+        record_id: &str,
+        new_content: &str,
+        spec: &RecordSpec,
+    ) -> Result<UpdateResult> {
+        spec.ensure_supported(&self.capabilities())?;
+        let not_found = || AppError::Provider(ProviderError::RecordNotFound(record_id.to_string()));
+        let (rrset_id, value) = split_value_id(record_id);
+        // 先获取当前记录集, 未指定 TTL 时保留原值
+        let rrset = self
+            .get_rrset(domain, rrset_id)
+            .await?
+            .ok_or_else(not_found)?;
+        let current = to_dns_records(domain, &rrset)
+            .into_iter()
+            .find(|r| value.is_none_or(|value| r.id == value_id(rrset_id, value)))
+            .ok_or_else(not_found)?;
+        let mut content = RecordContent::from_input(&current.record_type, new_content, spec)?;
+        content.priority = content.priority.or(current.priority);
+
+        // 只替换记录集中的这一个值, 其余值原样写回
+        let new_value = record_value(&current.record_type, &content);
+        let values =
+            replace_value(&rrset.rrset_values, value, new_value.clone()).ok_or_else(not_found)?;
+        self.put_rrset(domain, rrset_id, &values, spec.ttl.unwrap_or(current.ttl))
+            .await?;
+
+        Ok(UpdateResult {
+            success: true,
+            record_id: value_id(rrset_id, &new_value),
+            old_ip: current.rdata(),
+            new_ip: new_content.to_string(),
+            message: "更新成功".to_string(),
+        })
+    }
+
+    async fn create_record(
+        &self,
+        domain: &str,
+        record_name: &str,
+        record_type: DNSRecordType,
+        content: &str,
+    ) -> Result<DNSRecord> {
+        self.create_record_with(
+            domain,
+            record_name,
+            record_type,
+            content,
+            &RecordSpec::default(),
+        )
+        .await
+    }
+
+    async fn create_record_with(
+        &self,
+        domain: &str,
+        record_name: &str,
+        record_type: DNSRecordType,
+        content: &str,
+        spec: &RecordSpec,
+    ) -> Result<DNSRecord> {
+        spec.ensure_supported(&self.capabilities())?;
+        let mut content = RecordContent::from_input(&record_type, content, spec)?;
+        if record_type == DNSRecordType::MX {
+            content.priority = content.priority.or(Some(DEFAULT_MX_PRIORITY));
+        }
+        let rrset = GandiRRSet {
+            rrset_name: match record_name {
+                "" => "@".to_string(),
+                name => name.to_string(),
+            },
+            rrset_type: record_type.to_string(),
+            rrset_ttl: Some(spec.ttl.unwrap_or(DEFAULT_TTL)),
+            rrset_values: vec![record_value(&record_type, &content)],
+        };
+
+        // 创建接口只返回提示信息, 记录 ID 由名称和类型组成
+        let _: serde_json::Value = self
+            .send(
+                Method::POST,
+                &format!("/domains/{}/records", domain),
+                &[],
+                Some(json!({
+                    "rrset_name": rrset.rrset_name,
+                    "rrset_type": rrset.rrset_type,
+                    "rrset_ttl": rrset.rrset_ttl,
+                    "rrset_values": rrset.rrset_values,
+                })),
+            )
+            .await?;
+
+        to_dns_records(domain, &rrset)
+            .into_iter()
+            .next()
+            .ok_or_else(|| {
+                AppError::Provider(ProviderError::ParseError(
+                    "创建的记录集中没有记录值".to_string(),
+                ))
+            })
+    }
+
+    async fn delete_record(&self, domain: &str, record_id: &str) -> Result<()> {
+        let (rrset_id, value) = split_value_id(record_id);
+        let rrset = self.get_rrset(domain, rrset_id).await?.ok_or_else(|| {
+            AppError::Provider(ProviderError::RecordNotFound(record_id.to_string()))
+        })?;
+        let remaining = remove_value(&rrset.rrset_values, value).ok_or_else(|| {
+            AppError::Provider(ProviderError::RecordNotFound(record_id.to_string()))
+        })?;
+
+        // 记录集中还有其他值时只移除这一个值
+        if !remaining.is_empty() {
+            let ttl = rrset.rrset_ttl.unwrap_or(DEFAULT_TTL);
+            return self.put_rrset(domain, rrset_id, &remaining, ttl).await;
+        }
+
+        let _: serde_json::Value = self
+            .send(
+                Method::DELETE,
+                &format!("/domains/{}/records/{}", domain, rrset_id),
+                &[],
+                None,
+            )
+            .await?;
+        Ok(())
+    }
+
+    async fn test_connection(&self) -> Result<bool> {
+        // 测试获取域名列表
+        let _: serde_json::Value = self
+            .send(
+                Method::GET,
+                "/domains",
+                &[("per_page", "1".to_string())],
+                None,
+            )
+            .await?;
+        Ok(true)
+    }
+
+    fn supported_record_types(&self) -> Vec<DNSRecordType> {
+        vec![
+            DNSRecordType::A,
+            DNSRecordType::AAAA,
+            DNSRecordType::CNAME,
+            DNSRecordType::MX,
+            DNSRecordType::TXT,
+            DNSRecordType::NS,
+            DNSRecordType::SRV,
+            DNSRecordType::CAA,
+        ]
+    }
+
+    fn capabilities(&self) -> ProviderCapabilities {
+        ProviderCapabilities {
+            proxy: false,
+            ttl: true,
+            line: false,
+            priority: true,
+            comment: false,
+        }
+    }
+}
+
+/// 将 Gandi 错误响应 (`{"code", "message", "object", "cause"}`) 映射为 ProviderError
+fn parse_error(status: u16, text: &str) -> AppError {
+    let json: serde_json::Value = serde_json::from_str(text).unwrap_or_default();
+    let cause = json["cause"].as_str().unwrap_or_default();
+    let message = json["message"].as_str().unwrap_or(text);
+
+    let detail = format!("HTTP {} {}: {}", status, cause, message);
+    let error = match status {
+        401 | 403 => ProviderError::AuthenticationFailed(detail),
+        429 => ProviderError::RateLimitExceeded,
+        404 => ProviderError::RecordNotFound(detail),
+        _ => ProviderError::ApiError(detail),
+    };
+
+    AppError::Provider(error)
+}
+
+fn rrset_id(record_name: &str, record_type: &str) -> String {
+    let name = match record_name {
+        "" => "@",
+        name => name,
+    };
+    format!("{}/{}", name, record_type)
+}
+
+/// 记录值使用区域文件格式, TXT 记录值需要用双引号包裹
+fn record_value(record_type: &DNSRecordType, content: &RecordContent) -> String {
+    if *record_type == DNSRecordType::TXT && !content.content.starts_with('"') {
+        format!("\"{}\"", content.content)
+    } else {
+        content.to_rdata(record_type)
+    }
+}
+
+/// 转换为通用记录 (记录集中的每个值各一条), 未内置的类型以 `Other` 保留
+fn to_dns_records(domain: &str, rrset: &GandiRRSet) -> Vec<DNSRecord> {
+    let record_type: DNSRecordType = rrset
+        .rrset_type
+        .parse()
+        .unwrap_or_else(|_| DNSRecordType::Other(rrset.rrset_type.clone()));
+    let id = rrset_id(&rrset.rrset_name, &rrset.rrset_type);
+
+    rrset
+        .rrset_values
+        .iter()
+        .map(|value| {
+            let content = RecordContent::parse(&record_type, value);
+            DNSRecord {
+                id: value_id(&id, value),
+                name: full_record_name(domain, &rrset.rrset_name),
+                record_type: record_type.clone(),
+                content: content.content,
+                ttl: rrset.rrset_ttl.unwrap_or(DEFAULT_TTL),
+                proxied: None,
+                priority: content.priority,
+                weight: content.weight,
+                port: content.port,
+                comment: None,
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::providers::test_support::{fixture, mock_provider};
+    use wiremock::matchers::{body_json, header, method, path};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    mock_provider!(GandiProvider, "pat");
+
+    #[test]
+    fn test_parse_error() {
+        assert!(matches!(
+            parse_error(401, &fixture!("error_unauthorized").to_string()),
+            AppError::Provider(ProviderError::AuthenticationFailed(_))
+        ));
+        assert!(matches!(
+            parse_error(404, &fixture!("error_not_found").to_string()),
+            AppError::Provider(ProviderError::RecordNotFound(_))
+        ));
+        assert!(matches!(
+            parse_error(409, r#"{"code": 409, "message": "A DNS Record already exists with same value", "object": "HTTPConflict", "cause": "Conflict"}"#),
+            AppError::Provider(ProviderError::ApiError(ref m)) if m.contains("already exists")
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_list_and_get_records() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/domains/example.com/records"))
+            .and(header("authorization", "Bearer pat"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(fixture!("records")))
+            .expect(1)
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/domains/example.com/records/www/A"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(fixture!("rrset")))
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/domains/example.com/records/www/AAAA"))
+            .respond_with(ResponseTemplate::new(404).set_body_json(fixture!("error_not_found")))
+            .mount(&server)
+            .await;
+
+        let provider = mock_provider(&server);
+        let records = provider.list_records("example.com").await.unwrap();
+        // 记录集中的每个值各一条记录
+        assert_eq!(records.len(), 6);
+        assert_eq!(records[0].id, "@/MX#10 spool.mail.gandi.net.");
+        assert_eq!(records[0].content, "spool.mail.gandi.net.");
+        assert_eq!(records[0].priority, Some(10));
+        assert_eq!(records[1].id, "@/MX#50 fb.mail.gandi.net.");
+        assert_eq!(records[1].priority, Some(50));
+        assert_eq!(records[3].record_type, DNSRecordType::SRV);
+        assert_eq!(records[3].content, "mail.gandi.net.");
+        assert_eq!(
+            (records[3].priority, records[3].weight, records[3].port),
+            (Some(0), Some(1), Some(993))
+        );
+        assert_eq!(records[3].rdata(), "0 1 993 mail.gandi.net.");
+        assert_eq!(records[5].name, "www.example.com");
+
+        let record = provider
+            .get_record("example.com", "www", DNSRecordType::A)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(record.id, "www/A#203.0.113.10");
+        assert_eq!(record.content, "203.0.113.10");
+
+        assert!(provider
+            .get_record("example.com", "www", DNSRecordType::AAAA)
+            .await
+            .unwrap()
+            .is_none());
+    }
+
+    #[tokio::test]
+    async fn test_update_record_keeps_ttl() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/domains/example.com/records/www/A"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(fixture!("rrset")))
+            .mount(&server)
+            .await;
+        Mock::given(method("PUT"))
+            .and(path("/domains/example.com/records/www/A"))
+            .and(body_json(json!({
+                "rrset_values": ["198.51.100.7"],
+                "rrset_ttl": 1800
+            })))
+            .respond_with(ResponseTemplate::new(201).set_body_json(fixture!("created")))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let result = mock_provider(&server)
+            .update_record("example.com", "www/A#203.0.113.10", "198.51.100.7")
+            .await
+            .unwrap();
+        assert_eq!(result.old_ip, "203.0.113.10");
+        assert_eq!(result.record_id, "www/A#198.51.100.7");
+    }
+
+    /// 邮件记录集 (两个值)
+    fn mx_rrset() -> serde_json::Value {
+        fixture!("records")[0].clone()
+    }
+
+    #[tokio::test]
+    async fn test_update_one_value_keeps_rest_of_rrset() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/domains/example.com/records/@/MX"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(mx_rrset()))
+            .mount(&server)
+            .await;
+        Mock::given(method("PUT"))
+            .and(path("/domains/example.com/records/@/MX"))
+            .and(body_json(json!({
+                "rrset_values": ["10 spool.mail.gandi.net.", "20 mx2.example.org."],
+                "rrset_ttl": 10800
+            })))
+            .respond_with(ResponseTemplate::new(201).set_body_json(fixture!("created")))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let provider = mock_provider(&server);
+        let result = provider
+            .update_record(
+                "example.com",
+                "@/MX#50 fb.mail.gandi.net.",
+                "20 mx2.example.org.",
+            )
+            .await
+            .unwrap();
+        assert_eq!(result.old_ip, "50 fb.mail.gandi.net.");
+        assert_eq!(result.record_id, "@/MX#20 mx2.example.org.");
+
+        // 不含值的 ID 不能确定要修改多值记录集中的哪一个值
+        assert!(matches!(
+            provider
+                .update_record("example.com", "@/MX", "20 mx2.example.org.")
+                .await,
+            Err(AppError::Provider(ProviderError::RecordNotFound(_)))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_delete_one_value_keeps_rest_of_rrset() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/domains/example.com/records/@/MX"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(mx_rrset()))
+            .mount(&server)
+            .await;
+        Mock::given(method("PUT"))
+            .and(path("/domains/example.com/records/@/MX"))
+            .and(body_json(json!({
+                "rrset_values": ["50 fb.mail.gandi.net."],
+                "rrset_ttl": 10800
+            })))
+            .respond_with(ResponseTemplate::new(201).set_body_json(fixture!("created")))
+            .expect(1)
+            .mount(&server)
+            .await;
+        Mock::given(method("DELETE"))
+            .respond_with(ResponseTemplate::new(204))
+            .expect(0)
+            .mount(&server)
+            .await;
+
+        let provider = mock_provider(&server);
+        provider
+            .delete_record("example.com", "@/MX#10 spool.mail.gandi.net.")
+            .await
+            .unwrap();
+        assert!(matches!(
+            provider
+                .delete_record("example.com", "@/MX#10 mx9.example.org.")
+                .await,
+            Err(AppError::Provider(ProviderError::RecordNotFound(_)))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_update_record_with_ttl() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/domains/example.com/records/www/A"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(fixture!("rrset")))
+            .mount(&server)
+            .await;
+        Mock::given(method("PUT"))
+            .and(path("/domains/example.com/records/www/A"))
+            .and(body_json(json!({
+                "rrset_values": ["198.51.100.7"],
+                "rrset_ttl": 300
+            })))
+            .respond_with(ResponseTemplate::new(201).set_body_json(fixture!("created")))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let spec = RecordSpec {
+            ttl: Some(300),
+            ..Default::default()
+        };
+        mock_provider(&server)
+            .update_record_with("example.com", "www/A", "198.51.100.7", &spec)
+            .await
+            .unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_create_and_delete_record() {
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/domains/example.com/records"))
+            .and(body_json(json!({
+                "rrset_name": "_acme",
+                "rrset_type": "TXT",
+                "rrset_ttl": DEFAULT_TTL,
+                "rrset_values": ["\"token\""]
+            })))
+            .respond_with(ResponseTemplate::new(201).set_body_json(fixture!("created")))
+            .expect(1)
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/domains/example.com/records/_acme/TXT"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+                "rrset_name": "_acme",
+                "rrset_type": "TXT",
+                "rrset_ttl": DEFAULT_TTL,
+                "rrset_values": ["\"token\""]
+            })))
+            .mount(&server)
+            .await;
+        Mock::given(method("DELETE"))
+            .and(path("/domains/example.com/records/_acme/TXT"))
+            .respond_with(ResponseTemplate::new(204))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let provider = mock_provider(&server);
+        let record = provider
+            .create_record("example.com", "_acme", DNSRecordType::TXT, "token")
+            .await
+            .unwrap();
+        assert_eq!(record.id, "_acme/TXT#\"token\"");
+        assert_eq!(record.name, "_acme.example.com");
+
+        // 删除记录集中唯一的值时删除整个记录集
+        provider
+            .delete_record("example.com", &record.id)
+            .await
+            .unwrap();
+    }
+}
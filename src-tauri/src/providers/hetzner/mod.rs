@@ -0,0 +1,829 @@
+use crate::error::{AppError, Result};
+use crate::providers::provider_trait::{
+    full_record_name, Credentials, DNSProvider, DNSRecord, DNSRecordType, ProviderCapabilities,
+    ProviderError, RecordContent, RecordSpec, UpdateResult,
+};
+use crate::providers::rrset::{remove_value, replace_value, split_value_id, value_id};
+use async_trait::async_trait;
+use reqwest::{Client, Method};
+use serde::de::DeserializeOwned;
+use serde::Deserialize;
+use serde_json::json;
+
+/// Hetzner Cloud API (DNS 已从原 DNS Console 迁移到 Cloud API)
+const DEFAULT_ENDPOINT: &str = "https://api.hetzner.cloud/v1";
+
+/// 记录集列表每页数量 (API 允许的最大值)
+const PAGE_SIZE: usize = 100;
+
+/// 新建记录的默认 TTL
+const DEFAULT_TTL: u32 = 300;
+
+/// 未指定优先级时 MX 记录使用的优先级
+const DEFAULT_MX_PRIORITY: u16 = 10;
+
+/// Hetzner DNS 提供商
+///
+/// 记录以记录集 (RRSet) 为单位管理, 记录集 ID 为 `名称/类型`, 例如 `www/A`,
+/// 记录集中的每个值对应一条记录, 记录 ID 为 `www/A#203.0.113.10`
+pub struct HetznerProvider {
+    api_token: Option<String>,
+    endpoint: String,
+    client: Client,
+}
+
+/// 记录集, `name` 为相对名称 (根域名为 `@`)
+#[derive(Debug, Deserialize)]
+struct HetznerRRSet {
+    id: String,
+    name: String,
+    #[serde(rename = "type")]
+    record_type: String,
+    #[serde(default)]
+    ttl: Option<u32>,
+    #[serde(default)]
+    records: Vec<HetznerRecordValue>,
+}
+
+#[derive(Debug, Deserialize)]
+struct HetznerRecordValue {
+    value: String,
+    #[serde(default)]
+    comment: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct HetznerRRSetList {
+    rrsets: Vec<HetznerRRSet>,
+    meta: HetznerMeta,
+}
+
+#[derive(Debug, Deserialize)]
+struct HetznerMeta {
+    pagination: HetznerPagination,
+}
+
+#[derive(Debug, Deserialize)]
+struct HetznerPagination {
+    #[serde(default)]
+    next_page: Option<usize>,
+}
+
+#[derive(Debug, Deserialize)]
+struct HetznerRRSetResponse {
+    rrset: HetznerRRSet,
+}
+
+impl HetznerProvider {
+    pub fn new() -> Self {
+        Self::with_endpoint(DEFAULT_ENDPOINT)
+    }
+
+    /// 使用自定义 API 地址创建提供商 (例如测试服务器)
+    pub fn with_endpoint(endpoint: impl Into<String>) -> Self {
+        Self {
+            api_token: None,
+            endpoint: endpoint.into().trim_end_matches('/').to_string(),
+            client: Client::builder()
+                .timeout(std::time::Duration::from_secs(30))
+                .build()
+                .unwrap(),
+        }
+    }
+
+    /// 发送 API 请求并解析 JSON 响应
+    async fn send<T: DeserializeOwned>(
+        &self,
+        method: Method,
+        path: &str,
+        query: &[(&str, String)],
+        body: Option<serde_json::Value>,
+    ) -> Result<T> {
+        let token = self.api_token.as_ref().ok_or_else(|| {
+            AppError::Provider(ProviderError::AuthenticationFailed(
+                "未设置 API Token".to_string(),
+            ))
+        })?;
+
+        let mut request = self
+            .client
+            .request(method, format!("{}{}", self.endpoint, path))
+            .bearer_auth(token)
+            .query(query);
+        if let Some(body) = body {
+            request = request.json(&body);
+        }
+
+        let response = request
+            .send()
+            .await
+            .map_err(|e| AppError::Provider(ProviderError::NetworkError(e.to_string())))?;
+        let status = response.status();
+        let text = response
+            .text()
+            .await
+            .map_err(|e| AppError::Provider(ProviderError::NetworkError(e.to_string())))?;
+
+        if !status.is_success() {
+            return Err(parse_error(status.as_u16(), &text));
+        }
+
+        serde_json::from_str(&text).map_err(|e| {
+            AppError::Provider(ProviderError::ParseError(format!("解析响应失败: {}", e)))
+        })
+    }
+
+    /// 获取单个记录集, 不存在时返回 None
+    async fn get_rrset(&self, domain: &str, rrset_id: &str) -> Result<Option<HetznerRRSet>> {
+        let result: Result<HetznerRRSetResponse> = self
+            .send(
+                Method::GET,
+                &format!("/zones/{}/rrsets/{}", domain, rrset_id),
+                &[],
+                None,
+            )
+            .await;
+
+        match result {
+            Ok(response) => Ok(Some(response.rrset)),
+            Err(AppError::Provider(ProviderError::RecordNotFound(_))) => Ok(None),
+            Err(e) => Err(e),
+        }
+    }
+
+    /// 用新的值列表替换记录集中的全部值, 保留未改动的值的备注
+    async fn set_records(
+        &self,
+        domain: &str,
+        rrset: &HetznerRRSet,
+        values: &[String],
+    ) -> Result<()> {
+        let records: Vec<serde_json::Value> = values
+            .iter()
+            .map(|value| {
+                match rrset
+                    .records
+                    .iter()
+                    .find(|r| r.value == *value && !r.comment.is_empty())
+                {
+                    Some(r) => json!({ "value": value, "comment": r.comment }),
+                    None => json!({ "value": value }),
+                }
+            })
+            .collect();
+
+        let _: serde_json::Value = self
+            .send(
+                Method::POST,
+                &format!("/zones/{}/rrsets/{}/actions/set_records", domain, rrset.id),
+                &[],
+                Some(json!({ "records": records })),
+            )
+            .await?;
+        Ok(())
+    }
+}
+
+impl Default for HetznerProvider {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[async_trait]
+impl DNSProvider for HetznerProvider {
+    fn provider_id(&self) -> &'static str {
+        "hetzner"
+    }
+
+    fn provider_name(&self) -> &'static str {
+        "Hetzner"
+    }
+
+    async fn initialize(&mut self, credentials: &Credentials) -> Result<()> {
+        self.api_token = credentials.api_key.clone().or_else(|| {
+            credentials
+                .extra
+                .get("api_token")
+                .and_then(|v| v.as_str())
+                .map(|v| v.to_string())
+        });
+
+        if self.api_token.is_none() {
+            return Err(AppError::Provider(ProviderError::InvalidConfig(
+                "Hetzner 需要提供 api_token (项目 API Token)".to_string(),
+            )));
+        }
+
+        // 测试连接
+        self.test_connection().await?;
+
+        Ok(())
+    }
+
+    async fn list_records(&self, domain: &str) -> Result<Vec<DNSRecord>> {
+        let path = format!("/zones/{}/rrsets", domain);
+        let mut records = Vec::new();
+        let mut page = 1;
+
+        loop {
+            let list: HetznerRRSetList = self
+                .send(
+                    Method::GET,
+                    &path,
+                    &[
+                        ("page", page.to_string()),
+                        ("per_page", PAGE_SIZE.to_string()),
+                    ],
+                    None,
+                )
+                .await?;
+            records.extend(list.rrsets.iter().flat_map(|r| to_dns_records(domain, r)));
+
+            match list.meta.pagination.next_page {
+                Some(next) if next > page => page = next,
+                _ => break,
+            }
+        }
+
+        Ok(records)
+    }
+
+    async fn get_record(
+        &self,
+        domain: &str,
+        record_name: &str,
+        record_type: DNSRecordType,
+    ) -> Result<Option<DNSRecord>> {
+        let rrset_id = rrset_id(record_name, &record_type.to_string());
+        Ok(self
+            .get_rrset(domain, &rrset_id)
+            .await?
+            .and_then(|r| to_dns_records(domain, &r).into_iter().next()))
+    }
+
+    async fn update_record(
+        &self,
+        domain: &str,
+        record_id: &str,
+        new_content: &str,
+    ) -> Result<UpdateResult> {
+        self.update_record_with(domain, record_id, new_content, &RecordSpec::default())
+            .await
+    }
+
+    async fn update_record_with(
+        &self,
+        domain: &str,
+        record_id: &str,
+        new_content: &str,
+        spec: &RecordSpec,
+    ) -> Result<UpdateResult> {
+        spec.ensure_supported(&self.capabilities())?;
+        let not_found = || AppError::Provider(ProviderError::RecordNotFound(record_id.to_string()));
+        let (rrset_id, value) = split_value_id(record_id);
+        let rrset = self
+            .get_rrset(domain, rrset_id)
+            .await?
+            .ok_or_else(not_found)?;
+        let current = to_dns_records(domain, &rrset)
+            .into_iter()
+            .find(|r| value.is_none_or(|value| r.id == value_id(rrset_id, value)))
+            .ok_or_else(not_found)?;
+        let mut content = RecordContent::from_input(&current.record_type, new_content, spec)?;
+        content.priority = content.priority.or(current.priority);
+
+        // 只替换记录集中的这一个值, 其余值原样写回
+        let new_value = record_value(&current.record_type, &content);
+        let values: Vec<String> = rrset.records.iter().map(|r| r.value.clone()).collect();
+        let values = replace_value(&values, value, new_value.clone()).ok_or_else(not_found)?;
+        self.set_records(domain, &rrset, &values).await?;
+
+        // TTL 属于记录集, 需要单独的操作修改
+        if let Some(ttl) = spec.ttl.filter(|ttl| Some(*ttl) != rrset.ttl) {
+            let _: serde_json::Value = self
+                .send(
+                    Method::POST,
+                    &format!("/zones/{}/rrsets/{}/actions/change_ttl", domain, rrset_id),
+                    &[],
+                    Some(json!({ "ttl": ttl })),
+                )
+                .await?;
+        }
+
+        Ok(UpdateResult {
+            success: true,
+            old_ip: current.rdata(),
+            record_id: value_id(rrset_id, &new_value),
+            new_ip: new_content.to_string(),
+            message: "更新成功".to_string(),
+        })
+    }
+
+    async fn create_record(
+        &self,
+        domain: &str,
+        record_name: &str,
+        record_type: DNSRecordType,
+        content: &str,
+    ) -> Result<DNSRecord> {
+        self.create_record_with(
+            domain,
+            record_name,
+            record_type,
+            content,
+            &RecordSpec::default(),
+        )
+        .await
+    }
+
+    async fn create_record_with(
+        &self,
+        domain: &str,
+        record_name: &str,
+        record_type: DNSRecordType,
+        content: &str,
+        spec: &RecordSpec,
+    ) -> Result<DNSRecord> {
+        spec.ensure_supported(&self.capabilities())?;
+        let mut content = RecordContent::from_input(&record_type, content, spec)?;
+        if record_type == DNSRecordType::MX {
+            content.priority = content.priority.or(Some(DEFAULT_MX_PRIORITY));
+        }
+        let name = match record_name {
+            "" => "@",
+            name => name,
+        };
+
+        let created: HetznerRRSetResponse = self
+            .send(
+                Method::POST,
+                &format!("/zones/{}/rrsets", domain),
+                &[],
+                Some(json!({
+                    "name": name,
+                    "type": record_type.to_string(),
+                    "ttl": spec.ttl.unwrap_or(DEFAULT_TTL),
+                    "records": [{ "value": record_value(&record_type, &content) }]
+                })),
+            )
+            .await?;
+
+        to_dns_records(domain, &created.rrset)
+            .into_iter()
+            .next()
+            .ok_or_else(|| {
+                AppError::Provider(ProviderError::ParseError(
+                    "创建的记录集中没有记录值".to_string(),
+                ))
+            })
+    }
+
+    async fn delete_record(&self, domain: &str, record_id: &str) -> Result<()> {
+        let (rrset_id, value) = split_value_id(record_id);
+        let rrset = self.get_rrset(domain, rrset_id).await?.ok_or_else(|| {
+            AppError::Provider(ProviderError::RecordNotFound(record_id.to_string()))
+        })?;
+        let values: Vec<String> = rrset.records.iter().map(|r| r.value.clone()).collect();
+        let remaining = remove_value(&values, value).ok_or_else(|| {
+            AppError::Provider(ProviderError::RecordNotFound(record_id.to_string()))
+        })?;
+
+        // 记录集中还有其他值时只移除这一个值
+        if !remaining.is_empty() {
+            return self.set_records(domain, &rrset, &remaining).await;
+        }
+
+        let _: serde_json::Value = self
+            .send(
+                Method::DELETE,
+                &format!("/zones/{}/rrsets/{}", domain, rrset_id),
+                &[],
+                None,
+            )
+            .await?;
+        Ok(())
+    }
+
+    async fn test_connection(&self) -> Result<bool> {
+        // 测试获取 Zone 列表
+        let _: serde_json::Value = self
+            .send(
+                Method::GET,
+                "/zones",
+                &[("per_page", "1".to_string())],
+                None,
+            )
+            .await?;
+        Ok(true)
+    }
+
+    fn supported_record_types(&self) -> Vec<DNSRecordType> {
+        vec![
+            DNSRecordType::A,
+            DNSRecordType::AAAA,
+            DNSRecordType::CNAME,
+            DNSRecordType::MX,
+            DNSRecordType::TXT,
+            DNSRecordType::NS,
+            DNSRecordType::SRV,
+            DNSRecordType::CAA,
+        ]
+    }
+
+    fn capabilities(&self) -> ProviderCapabilities {
+        ProviderCapabilities {
+            proxy: false,
+            ttl: true,
+            line: false,
+            priority: true,
+            comment: false,
+        }
+    }
+}
+
+/// 将 Hetzner 错误响应 (`{"error": {"code", "message"}}`) 映射为 ProviderError
+fn parse_error(status: u16, text: &str) -> AppError {
+    let json: serde_json::Value = serde_json::from_str(text).unwrap_or_default();
+    let code = json["error"]["code"].as_str().unwrap_or_default();
+    let message = json["error"]["message"].as_str().unwrap_or(text);
+
+    let detail = format!("HTTP {} {}: {}", status, code, message);
+    let error = match (status, code) {
+        (401 | 403, _) | (_, "unauthorized" | "forbidden") => {
+            ProviderError::AuthenticationFailed(detail)
+        }
+        (429, _) | (_, "rate_limit_exceeded") => ProviderError::RateLimitExceeded,
+        (404, _) => ProviderError::RecordNotFound(detail),
+        _ => ProviderError::ApiError(detail),
+    };
+
+    AppError::Provider(error)
+}
+
+fn rrset_id(record_name: &str, record_type: &str) -> String {
+    let name = match record_name {
+        "" => "@",
+        name => name,
+    };
+    format!("{}/{}", name, record_type)
+}
+
+/// 记录值使用区域文件格式, TXT 记录值需要用双引号包裹
+fn record_value(record_type: &DNSRecordType, content: &RecordContent) -> String {
+    if *record_type == DNSRecordType::TXT && !content.content.starts_with('"') {
+        format!("\"{}\"", content.content)
+    } else {
+        content.to_rdata(record_type)
+    }
+}
+
+/// 转换为通用记录 (记录集中的每个值各一条), 未内置的类型以 `Other` 保留
+fn to_dns_records(domain: &str, rrset: &HetznerRRSet) -> Vec<DNSRecord> {
+    let record_type: DNSRecordType = rrset
+        .record_type
+        .parse()
+        .unwrap_or_else(|_| DNSRecordType::Other(rrset.record_type.clone()));
+
+    rrset
+        .records
+        .iter()
+        .map(|record| {
+            let content = RecordContent::parse(&record_type, &record.value);
+            DNSRecord {
+                id: value_id(&rrset.id, &record.value),
+                name: full_record_name(domain, &rrset.name),
+                record_type: record_type.clone(),
+                content: content.content,
+                ttl: rrset.ttl.unwrap_or(DEFAULT_TTL),
+                proxied: None,
+                priority: content.priority,
+                weight: content.weight,
+                port: content.port,
+                comment: None,
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::providers::test_support::{fixture, mock_provider, PagedResponder};
+    use wiremock::matchers::{body_json, header, method, path};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    mock_provider!(HetznerProvider, "token");
+
+    #[test]
+    fn test_parse_error() {
+        assert!(matches!(
+            parse_error(404, &fixture!("error_not_found").to_string()),
+            AppError::Provider(ProviderError::RecordNotFound(_))
+        ));
+        assert!(matches!(
+            parse_error(
+                401,
+                r#"{"error": {"code": "unauthorized", "message": "unable to authenticate"}}"#
+            ),
+            AppError::Provider(ProviderError::AuthenticationFailed(_))
+        ));
+        assert!(matches!(
+            parse_error(429, "{}"),
+            AppError::Provider(ProviderError::RateLimitExceeded)
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_list_records_reads_all_pages() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/zones/example.com/rrsets"))
+            .and(header("authorization", "Bearer token"))
+            .respond_with(PagedResponder::page_number(
+                "page",
+                1,
+                vec![fixture!("rrsets_page1"), fixture!("rrsets_page2")],
+            ))
+            .expect(2)
+            .mount(&server)
+            .await;
+
+        let records = mock_provider(&server)
+            .list_records("example.com")
+            .await
+            .unwrap();
+
+        // 记录集中的每个值各一条记录
+        assert_eq!(records.len(), 8);
+        assert_eq!(records[1].id, "@/MX#10 mail.example.com.");
+        assert_eq!(records[1].name, "example.com");
+        assert_eq!(records[1].priority, Some(10));
+        assert_eq!(records[1].content, "mail.example.com.");
+        assert_eq!(records[1].rdata(), "10 mail.example.com.");
+        assert_eq!(records[2].id, "@/MX#20 backup-mail.example.com.");
+        assert_eq!(records[2].priority, Some(20));
+        assert_eq!(records[6].id, "www/A#203.0.113.10");
+        assert_eq!(records[6].content, "203.0.113.10");
+        assert_eq!(records[7].content, "\"v=spf1 mx ~all\"");
+    }
+
+    #[tokio::test]
+    async fn test_get_record() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/zones/example.com/rrsets/www/A"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(fixture!("rrset")))
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/zones/example.com/rrsets/www/AAAA"))
+            .respond_with(ResponseTemplate::new(404).set_body_json(fixture!("error_not_found")))
+            .mount(&server)
+            .await;
+
+        let provider = mock_provider(&server);
+        let record = provider
+            .get_record("example.com", "www", DNSRecordType::A)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(record.id, "www/A#203.0.113.10");
+        assert_eq!(record.ttl, 300);
+
+        assert!(provider
+            .get_record("example.com", "www", DNSRecordType::AAAA)
+            .await
+            .unwrap()
+            .is_none());
+    }
+
+    #[tokio::test]
+    async fn test_update_record_sets_records() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/zones/example.com/rrsets/www/A"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(fixture!("rrset")))
+            .mount(&server)
+            .await;
+        Mock::given(method("POST"))
+            .and(path("/zones/example.com/rrsets/www/A/actions/set_records"))
+            .and(body_json(
+                json!({ "records": [{ "value": "198.51.100.7" }] }),
+            ))
+            .respond_with(ResponseTemplate::new(201).set_body_json(fixture!("action")))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let result = mock_provider(&server)
+            .update_record("example.com", "www/A#203.0.113.10", "198.51.100.7")
+            .await
+            .unwrap();
+        assert_eq!(result.record_id, "www/A#198.51.100.7");
+        assert_eq!(result.old_ip, "203.0.113.10");
+    }
+
+    /// 邮件记录集 (两个值) 的单条响应
+    fn mx_rrset() -> serde_json::Value {
+        json!({ "rrset": fixture!("rrsets_page1")["rrsets"][1] })
+    }
+
+    #[tokio::test]
+    async fn test_update_one_value_keeps_rest_of_rrset() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/zones/example.com/rrsets/@/MX"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(mx_rrset()))
+            .mount(&server)
+            .await;
+        Mock::given(method("POST"))
+            .and(path("/zones/example.com/rrsets/@/MX/actions/set_records"))
+            .and(body_json(json!({
+                "records": [
+                    { "value": "10 mail.example.com." },
+                    { "value": "30 mx3.example.org." }
+                ]
+            })))
+            .respond_with(ResponseTemplate::new(201).set_body_json(fixture!("action")))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let provider = mock_provider(&server);
+        let result = provider
+            .update_record(
+                "example.com",
+                "@/MX#20 backup-mail.example.com.",
+                "30 mx3.example.org.",
+            )
+            .await
+            .unwrap();
+        assert_eq!(result.record_id, "@/MX#30 mx3.example.org.");
+        assert_eq!(result.old_ip, "20 backup-mail.example.com.");
+
+        // 不含值的 ID 不能确定要修改多值记录集中的哪一个值
+        assert!(matches!(
+            provider
+                .update_record("example.com", "@/MX", "30 mx3.example.org.")
+                .await,
+            Err(AppError::Provider(ProviderError::RecordNotFound(_)))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_delete_one_value_keeps_rest_of_rrset() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/zones/example.com/rrsets/@/MX"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(mx_rrset()))
+            .mount(&server)
+            .await;
+        Mock::given(method("POST"))
+            .and(path("/zones/example.com/rrsets/@/MX/actions/set_records"))
+            .and(body_json(json!({
+                "records": [{ "value": "20 backup-mail.example.com." }]
+            })))
+            .respond_with(ResponseTemplate::new(201).set_body_json(fixture!("action")))
+            .expect(1)
+            .mount(&server)
+            .await;
+        Mock::given(method("DELETE"))
+            .respond_with(ResponseTemplate::new(201).set_body_json(fixture!("action")))
+            .expect(0)
+            .mount(&server)
+            .await;
+
+        let provider = mock_provider(&server);
+        provider
+            .delete_record("example.com", "@/MX#10 mail.example.com.")
+            .await
+            .unwrap();
+        assert!(matches!(
+            provider
+                .delete_record("example.com", "@/MX#10 mx9.example.org.")
+                .await,
+            Err(AppError::Provider(ProviderError::RecordNotFound(_)))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_update_record_with_ttl() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/zones/example.com/rrsets/www/A"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(fixture!("rrset")))
+            .mount(&server)
+            .await;
+        Mock::given(method("POST"))
+            .and(path("/zones/example.com/rrsets/www/A/actions/set_records"))
+            .respond_with(ResponseTemplate::new(201).set_body_json(fixture!("action")))
+            .expect(1)
+            .mount(&server)
+            .await;
+        Mock::given(method("POST"))
+            .and(path("/zones/example.com/rrsets/www/A/actions/change_ttl"))
+            .and(body_json(json!({ "ttl": 60 })))
+            .respond_with(ResponseTemplate::new(201).set_body_json(fixture!("action")))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let spec = RecordSpec {
+            ttl: Some(60),
+            ..Default::default()
+        };
+        mock_provider(&server)
+            .update_record_with("example.com", "www/A", "198.51.100.7", &spec)
+            .await
+            .unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_create_and_delete_record() {
+        let server = MockServer::start().await;
+        let mut created = fixture!("rrset");
+        created["rrset"]["id"] = json!("@/TXT");
+        created["rrset"]["name"] = json!("@");
+        created["rrset"]["type"] = json!("TXT");
+        created["rrset"]["records"] = json!([{ "value": "\"hello\"", "comment": "" }]);
+        created["action"] = fixture!("action")["action"].clone();
+
+        Mock::given(method("GET"))
+            .and(path("/zones/example.com/rrsets/@/TXT"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(created.clone()))
+            .mount(&server)
+            .await;
+        Mock::given(method("POST"))
+            .and(path("/zones/example.com/rrsets"))
+            .and(body_json(json!({
+                "name": "@",
+                "type": "TXT",
+                "ttl": DEFAULT_TTL,
+                "records": [{ "value": "\"hello\"" }]
+            })))
+            .respond_with(ResponseTemplate::new(201).set_body_json(created))
+            .expect(1)
+            .mount(&server)
+            .await;
+        Mock::given(method("DELETE"))
+            .and(path("/zones/example.com/rrsets/@/TXT"))
+            .respond_with(ResponseTemplate::new(201).set_body_json(fixture!("action")))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let provider = mock_provider(&server);
+        let record = provider
+            .create_record("example.com", "", DNSRecordType::TXT, "hello")
+            .await
+            .unwrap();
+        assert_eq!(record.id, "@/TXT#\"hello\"");
+        assert_eq!(record.name, "example.com");
+
+        // 删除记录集中唯一的值时删除整个记录集
+        provider
+            .delete_record("example.com", &record.id)
+            .await
+            .unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_create_caa_record() {
+        let server = MockServer::start().await;
+        let mut created = fixture!("rrset");
+        created["rrset"]["id"] = json!("@/CAA");
+        created["rrset"]["name"] = json!("@");
+        created["rrset"]["type"] = json!("CAA");
+        created["rrset"]["records"] =
+            json!([{ "value": "0 issue \"letsencrypt.org\"", "comment": "" }]);
+        created["action"] = fixture!("action")["action"].clone();
+
+        Mock::given(method("POST"))
+            .and(path("/zones/example.com/rrsets"))
+            .and(body_json(json!({
+                "name": "@",
+                "type": "CAA",
+                "ttl": DEFAULT_TTL,
+                "records": [{ "value": "0 issue \"letsencrypt.org\"" }]
+            })))
+            .respond_with(ResponseTemplate::new(201).set_body_json(created))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        // 值两侧的引号可以省略, 提交时统一为区域文件格式
+        let provider = mock_provider(&server);
+        let record = provider
+            .create_record(
+                "example.com",
+                "@",
+                DNSRecordType::CAA,
+                "0 issue letsencrypt.org",
+            )
+            .await
+            .unwrap();
+        assert_eq!(record.record_type, DNSRecordType::CAA);
+        assert_eq!(record.content, "0 issue \"letsencrypt.org\"");
+    }
+}
@@ -0,0 +1,1039 @@
+use crate::error::{AppError, Result};
+use crate::providers::provider_trait::{
+    full_record_name, Credentials, DNSProvider, DNSRecord, DNSRecordType, ProviderCapabilities,
+    ProviderError, RecordContent, RecordSpec, UpdateResult,
+};
+use crate::providers::rrset::{remove_value, replace_value, split_value_id, value_id};
+use crate::providers::signing::{
+    canonical_query_string, hex_encode, hmac_sha256, sha256_hex, URI_ENCODE_SET,
+};
+use async_trait::async_trait;
+use percent_encoding::utf8_percent_encode;
+use reqwest::{Client, Method};
+use serde::de::DeserializeOwned;
+use serde::Deserialize;
+use serde_json::json;
+use std::collections::HashMap;
+use std::sync::RwLock;
+
+/// 未指定区域时使用的全局终端节点
+const DEFAULT_ENDPOINT: &str = "https://dns.myhuaweicloud.com";
+
+/// 新建记录的默认 TTL
+const DEFAULT_TTL: u32 = 300;
+
+/// 未指定优先级时 MX 记录使用的优先级
+const DEFAULT_MX_PRIORITY: u16 = 10;
+
+/// 记录集列表每页数量 (API 允许的最大值)
+const PAGE_SIZE: usize = 500;
+
+/// 签名使用的请求体类型
+const CONTENT_TYPE: &str = "application/json";
+
+/// 区域不存在时的错误码 (记录集不存在为 DNS.0312)
+const ZONE_NOT_FOUND_CODE: &str = "DNS.0101";
+
+/// 华为云 DNS 提供商
+pub struct HuaweiProvider {
+    access_key: Option<String>,
+    secret_key: Option<String>,
+    endpoint: String,
+    /// 是否使用 `with_endpoint` 指定的地址 (此时不根据区域切换终端节点)
+    custom_endpoint: bool,
+    zone_ids: RwLock<HashMap<String, String>>,
+    client: Client,
+}
+
+/// 公网域名 (Zone)
+#[derive(Debug, Deserialize)]
+struct HuaweiZone {
+    id: String,
+    name: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct HuaweiZoneList {
+    zones: Vec<HuaweiZone>,
+}
+
+/// 记录集, 其中的每个值对应一条记录, 记录 ID 为 `<记录集 ID>#<值>`
+#[derive(Debug, Deserialize)]
+struct HuaweiRecordSet {
+    id: String,
+    name: String,
+    #[serde(rename = "type")]
+    record_type: String,
+    #[serde(default)]
+    ttl: Option<u32>,
+    #[serde(default)]
+    records: Vec<String>,
+    #[serde(default)]
+    description: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct HuaweiRecordSetList {
+    recordsets: Vec<HuaweiRecordSet>,
+    #[serde(default)]
+    metadata: Option<HuaweiMetadata>,
+}
+
+#[derive(Debug, Deserialize)]
+struct HuaweiMetadata {
+    total_count: usize,
+}
+
+impl HuaweiProvider {
+    pub fn new() -> Self {
+        Self {
+            custom_endpoint: false,
+            ..Self::with_endpoint(DEFAULT_ENDPOINT)
+        }
+    }
+
+    /// 使用自定义 API 地址创建提供商 (例如测试服务器)
+    pub fn with_endpoint(endpoint: impl Into<String>) -> Self {
+        Self {
+            access_key: None,
+            secret_key: None,
+            endpoint: endpoint.into().trim_end_matches('/').to_string(),
+            custom_endpoint: true,
+            zone_ids: RwLock::new(HashMap::new()),
+            client: Client::builder()
+                .timeout(std::time::Duration::from_secs(30))
+                .build()
+                .unwrap(),
+        }
+    }
+
+    /// 发送签名后的 API 请求
+    async fn send<T: DeserializeOwned>(
+        &self,
+        method: Method,
+        path: &str,
+        query: &[(&str, String)],
+        body: Option<serde_json::Value>,
+    ) -> Result<T> {
+        let (access_key, secret_key) = match (&self.access_key, &self.secret_key) {
+            (Some(ak), Some(sk)) => (ak, sk),
+            _ => {
+                return Err(AppError::Provider(ProviderError::AuthenticationFailed(
+                    "未设置 Access Key 或 Secret Key".to_string(),
+                )))
+            }
+        };
+
+        let url = reqwest::Url::parse(&format!("{}{}", self.endpoint, path))
+            .map_err(|e| AppError::Provider(ProviderError::InvalidConfig(e.to_string())))?;
+        let host = match (url.host_str(), url.port()) {
+            (Some(host), Some(port)) => format!("{}:{}", host, port),
+            (Some(host), None) => host.to_string(),
+            _ => {
+                return Err(AppError::Provider(ProviderError::InvalidConfig(format!(
+                    "无效的 API 地址: {}",
+                    self.endpoint
+                ))))
+            }
+        };
+
+        let canonical_query = canonical_query_string(query);
+        let payload = body.map(|b| b.to_string()).unwrap_or_default();
+        let sdk_date = chrono::Utc::now().format("%Y%m%dT%H%M%SZ").to_string();
+
+        let authorization = sign_sdk(&SigningParams {
+            access_key,
+            secret_key,
+            method: method.as_str(),
+            host: &host,
+            path,
+            canonical_query: &canonical_query,
+            payload: &payload,
+            sdk_date: &sdk_date,
+        });
+
+        let full_url = if canonical_query.is_empty() {
+            format!("{}{}", self.endpoint, path)
+        } else {
+            format!("{}{}?{}", self.endpoint, path, canonical_query)
+        };
+
+        let response = self
+            .client
+            .request(method, &full_url)
+            .header("Content-Type", CONTENT_TYPE)
+            .header("X-Sdk-Date", &sdk_date)
+            .header("Authorization", authorization)
+            .body(payload)
+            .send()
+            .await
+            .map_err(|e| {
+                AppError::Provider(ProviderError::NetworkError(format!("请求失败: {}", e)))
+            })?;
+
+        let status = response.status();
+        let text = response.text().await.map_err(|e| {
+            AppError::Provider(ProviderError::NetworkError(format!("读取响应失败: {}", e)))
+        })?;
+
+        if !status.is_success() {
+            return Err(parse_error(status.as_u16(), &text));
+        }
+
+        // DELETE 等请求的响应体可能为空
+        let text = if text.trim().is_empty() {
+            "null"
+        } else {
+            &text
+        };
+        serde_json::from_str(text).map_err(|e| {
+            AppError::Provider(ProviderError::ParseError(format!("解析响应失败: {}", e)))
+        })
+    }
+
+    /// 根据域名查找公网 Zone ID (结果会被缓存)
+    async fn get_zone_id(&self, domain: &str) -> Result<String> {
+        if let Some(zone_id) = self.zone_ids.read().unwrap().get(domain) {
+            return Ok(zone_id.clone());
+        }
+
+        let zone_name = format!("{}.", domain.trim_end_matches('.'));
+        let zones: HuaweiZoneList = self
+            .send(
+                Method::GET,
+                "/v2/zones",
+                &[("type", "public".to_string()), ("name", zone_name.clone())],
+                None,
+            )
+            .await?;
+
+        // name 参数为模糊匹配, 需要再精确比较
+        let zone_id = zones
+            .zones
+            .into_iter()
+            .find(|z| z.name.eq_ignore_ascii_case(&zone_name))
+            .map(|z| z.id)
+            .ok_or_else(|| {
+                AppError::Provider(ProviderError::DomainNotFound(format!(
+                    "找不到域名: {}",
+                    domain
+                )))
+            })?;
+
+        self.zone_ids
+            .write()
+            .unwrap()
+            .insert(domain.to_string(), zone_id.clone());
+        Ok(zone_id)
+    }
+
+    /// 分页查询记录集, `filter` 为额外的查询条件
+    async fn list_record_sets(
+        &self,
+        zone_id: &str,
+        filter: &[(&str, String)],
+    ) -> Result<Vec<HuaweiRecordSet>> {
+        let path = format!("/v2/zones/{}/recordsets", zone_id);
+        let mut record_sets = Vec::new();
+
+        loop {
+            let mut query = filter.to_vec();
+            query.push(("offset", record_sets.len().to_string()));
+            query.push(("limit", PAGE_SIZE.to_string()));
+
+            let page: HuaweiRecordSetList = self.send(Method::GET, &path, &query, None).await?;
+            let total_count = page.metadata.map(|m| m.total_count).unwrap_or(0);
+            let count = page.recordsets.len();
+            record_sets.extend(page.recordsets);
+
+            if count == 0 || record_sets.len() >= total_count {
+                break;
+            }
+        }
+
+        Ok(record_sets)
+    }
+
+    /// 用新的值列表替换记录集中的全部值, 未指定的 TTL 和描述保留原值
+    async fn put_record_set(
+        &self,
+        path: &str,
+        current: &HuaweiRecordSet,
+        values: &[String],
+        spec: &RecordSpec,
+    ) -> Result<HuaweiRecordSet> {
+        let mut body = json!({
+            "name": current.name,
+            "type": current.record_type,
+            "ttl": spec.ttl.or(current.ttl).unwrap_or(DEFAULT_TTL),
+            "records": values,
+        });
+        if let Some(description) = spec.comment.as_ref().or(current.description.as_ref()) {
+            body["description"] = json!(description);
+        }
+        self.send(Method::PUT, path, &[], Some(body)).await
+    }
+}
+
+impl Default for HuaweiProvider {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[async_trait]
+impl DNSProvider for HuaweiProvider {
+    fn provider_id(&self) -> &'static str {
+        "huawei"
+    }
+
+    fn provider_name(&self) -> &'static str {
+        "Huawei Cloud (华为云)"
+    }
+
+    async fn initialize(&mut self, credentials: &Credentials) -> Result<()> {
+        self.access_key = credentials.access_key.clone().or_else(|| {
+            credentials
+                .extra
+                .get("access_key")
+                .and_then(|v| v.as_str())
+                .map(|v| v.to_string())
+        });
+        self.secret_key = credentials.api_secret.clone().or_else(|| {
+            credentials
+                .extra
+                .get("secret_key")
+                .and_then(|v| v.as_str())
+                .map(|v| v.to_string())
+        });
+
+        if self.access_key.is_none() || self.secret_key.is_none() {
+            return Err(AppError::Provider(ProviderError::InvalidConfig(
+                "华为云需要提供 access_key (AK) 和 secret_key (SK)".to_string(),
+            )));
+        }
+
+        if !self.custom_endpoint {
+            self.endpoint = region_endpoint(credentials.region.as_deref());
+        }
+        self.zone_ids.write().unwrap().clear();
+
+        // 测试连接
+        self.test_connection().await?;
+
+        Ok(())
+    }
+
+    async fn list_records(&self, domain: &str) -> Result<Vec<DNSRecord>> {
+        let zone_id = self.get_zone_id(domain).await?;
+        let record_sets = self.list_record_sets(&zone_id, &[]).await?;
+
+        Ok(record_sets.iter().flat_map(to_dns_records).collect())
+    }
+
+    async fn get_record(
+        &self,
+        domain: &str,
+        record_name: &str,
+        record_type: DNSRecordType,
+    ) -> Result<Option<DNSRecord>> {
+        let zone_id = self.get_zone_id(domain).await?;
+        let name = format!("{}.", full_record_name(domain, record_name));
+        let record_sets = self
+            .list_record_sets(
+                &zone_id,
+                &[
+                    ("name", name.clone()),
+                    ("type", record_type.to_string()),
+                    ("search_mode", "equal".to_string()),
+                ],
+            )
+            .await?;
+
+        Ok(record_sets
+            .into_iter()
+            .filter(|r| r.name.eq_ignore_ascii_case(&name))
+            .flat_map(|r| to_dns_records(&r))
+            .find(|r| r.record_type == record_type))
+    }
+
+    async fn update_record(
+        &self,
+        domain: &str,
+        record_id: &str,
+        new_content: &str,
+    ) -> Result<UpdateResult> {
+        self.update_record_with(domain, record_id, new_content, &RecordSpec::default())
+            .await
+    }
+
+    async fn update_record_with(
+        &self,
+        domain: &str,
+        record_id: &str,
+        new_content: &str,
+        spec: &RecordSpec,
+    ) -> Result<UpdateResult> {
+        spec.ensure_supported(&self.capabilities())?;
+        let not_found = || AppError::Provider(ProviderError::RecordNotFound(record_id.to_string()));
+        let (rrset_id, value) = split_value_id(record_id);
+        let zone_id = self.get_zone_id(domain).await?;
+        let path = format!("/v2/zones/{}/recordsets/{}", zone_id, rrset_id);
+
+        // 先获取当前记录集, 只替换其中的这一个值, 其余值原样写回
+        let record_set: HuaweiRecordSet = self.send(Method::GET, &path, &[], None).await?;
+        let current = to_dns_records(&record_set)
+            .into_iter()
+            .find(|r| value.is_none_or(|value| r.id == value_id(rrset_id, value)))
+            .ok_or_else(not_found)?;
+        let mut content = RecordContent::from_input(&current.record_type, new_content, spec)?;
+        content.priority = content.priority.or(current.priority);
+
+        let new_value = record_value(&current.record_type, &content);
+        let values =
+            replace_value(&record_set.records, value, new_value.clone()).ok_or_else(not_found)?;
+        let updated = self
+            .put_record_set(&path, &record_set, &values, spec)
+            .await?;
+
+        Ok(UpdateResult {
+            success: true,
+            record_id: value_id(&updated.id, &new_value),
+            old_ip: current.rdata(),
+            new_ip: new_content.to_string(),
+            message: "更新成功".to_string(),
+        })
+    }
+
+    async fn create_record(
+        &self,
+        domain: &str,
+        record_name: &str,
+        record_type: DNSRecordType,
+        content: &str,
+    ) -> Result<DNSRecord> {
+        self.create_record_with(
+            domain,
+            record_name,
+            record_type,
+            content,
+            &RecordSpec::default(),
+        )
+        .await
+    }
+
+    async fn create_record_with(
+        &self,
+        domain: &str,
+        record_name: &str,
+        record_type: DNSRecordType,
+        content: &str,
+        spec: &RecordSpec,
+    ) -> Result<DNSRecord> {
+        spec.ensure_supported(&self.capabilities())?;
+        let mut content = RecordContent::from_input(&record_type, content, spec)?;
+        if record_type == DNSRecordType::MX {
+            content.priority = content.priority.or(Some(DEFAULT_MX_PRIORITY));
+        }
+        let zone_id = self.get_zone_id(domain).await?;
+
+        let mut body = json!({
+            "name": format!("{}.", full_record_name(domain, record_name)),
+            "type": record_type.to_string(),
+            "ttl": spec.ttl.unwrap_or(DEFAULT_TTL),
+            "records": [record_value(&record_type, &content)],
+        });
+        if let Some(description) = &spec.comment {
+            body["description"] = json!(description);
+        }
+        let created: HuaweiRecordSet = self
+            .send(
+                Method::POST,
+                &format!("/v2/zones/{}/recordsets", zone_id),
+                &[],
+                Some(body),
+            )
+            .await?;
+
+        to_dns_records(&created).into_iter().next().ok_or_else(|| {
+            AppError::Provider(ProviderError::ParseError(
+                "创建的记录集中没有记录值".to_string(),
+            ))
+        })
+    }
+
+    async fn delete_record(&self, domain: &str, record_id: &str) -> Result<()> {
+        let (rrset_id, value) = split_value_id(record_id);
+        let zone_id = self.get_zone_id(domain).await?;
+        let path = format!("/v2/zones/{}/recordsets/{}", zone_id, rrset_id);
+
+        // 记录集中还有其他值时只移除这一个值
+        if let Some(value) = value {
+            let record_set: HuaweiRecordSet = self.send(Method::GET, &path, &[], None).await?;
+            let remaining = remove_value(&record_set.records, Some(value)).ok_or_else(|| {
+                AppError::Provider(ProviderError::RecordNotFound(record_id.to_string()))
+            })?;
+            if !remaining.is_empty() {
+                self.put_record_set(&path, &record_set, &remaining, &RecordSpec::default())
+                    .await?;
+                return Ok(());
+            }
+        }
+
+        let _: serde_json::Value = self.send(Method::DELETE, &path, &[], None).await?;
+        Ok(())
+    }
+
+    async fn test_connection(&self) -> Result<bool> {
+        // 测试获取域名列表
+        let _: HuaweiZoneList = self
+            .send(
+                Method::GET,
+                "/v2/zones",
+                &[("type", "public".to_string()), ("limit", "1".to_string())],
+                None,
+            )
+            .await?;
+        Ok(true)
+    }
+
+    fn supported_record_types(&self) -> Vec<DNSRecordType> {
+        vec![
+            DNSRecordType::A,
+            DNSRecordType::AAAA,
+            DNSRecordType::CNAME,
+            DNSRecordType::MX,
+            DNSRecordType::TXT,
+            DNSRecordType::SRV,
+            DNSRecordType::CAA,
+        ]
+    }
+
+    fn capabilities(&self) -> ProviderCapabilities {
+        ProviderCapabilities {
+            proxy: false,
+            ttl: true,
+            line: false,
+            priority: true,
+            comment: true,
+        }
+    }
+}
+
+// ============ SDK-HMAC-SHA256 签名 ============
+
+/// SDK-HMAC-SHA256 签名参数
+struct SigningParams<'a> {
+    access_key: &'a str,
+    secret_key: &'a str,
+    method: &'a str,
+    host: &'a str,
+    path: &'a str,
+    canonical_query: &'a str,
+    payload: &'a str,
+    sdk_date: &'a str,
+}
+
+/// 构造规范请求 (签名头为 content-type、host 和 x-sdk-date, URI 以 `/` 结尾)
+fn canonical_request(params: &SigningParams) -> String {
+    let mut uri = params
+        .path
+        .split('/')
+        .map(|segment| utf8_percent_encode(segment, URI_ENCODE_SET).to_string())
+        .collect::<Vec<_>>()
+        .join("/");
+    if !uri.ends_with('/') {
+        uri.push('/');
+    }
+
+    format!(
+        "{}\n{}\n{}\ncontent-type:{}\nhost:{}\nx-sdk-date:{}\n\ncontent-type;host;x-sdk-date\n{}",
+        params.method,
+        uri,
+        params.canonical_query,
+        CONTENT_TYPE,
+        params.host,
+        params.sdk_date,
+        sha256_hex(params.payload.as_bytes())
+    )
+}
+
+/// 计算 SDK-HMAC-SHA256 Authorization 头
+fn sign_sdk(params: &SigningParams) -> String {
+    let string_to_sign = format!(
+        "SDK-HMAC-SHA256\n{}\n{}",
+        params.sdk_date,
+        sha256_hex(canonical_request(params).as_bytes())
+    );
+    let signature = hex_encode(&hmac_sha256(
+        params.secret_key.as_bytes(),
+        string_to_sign.as_bytes(),
+    ));
+
+    format!(
+        "SDK-HMAC-SHA256 Access={}, SignedHeaders=content-type;host;x-sdk-date, Signature={}",
+        params.access_key, signature
+    )
+}
+
+// ============ 辅助函数 ============
+
+/// 将华为云错误响应映射为 ProviderError
+///
+/// 网关错误为 `{"error_code", "error_msg"}`, DNS 服务错误为 `{"code", "message"}`
+fn parse_error(status: u16, text: &str) -> AppError {
+    let json: serde_json::Value = serde_json::from_str(text).unwrap_or_default();
+    let code = json["error_code"]
+        .as_str()
+        .or_else(|| json["code"].as_str())
+        .unwrap_or_default();
+    let message = json["error_msg"]
+        .as_str()
+        .or_else(|| json["message"].as_str())
+        .unwrap_or(text);
+
+    let detail = format!("HTTP {} {}: {}", status, code, message);
+    let error = match status {
+        401 | 403 => ProviderError::AuthenticationFailed(detail),
+        429 => ProviderError::RateLimitExceeded,
+        _ if code == ZONE_NOT_FOUND_CODE => ProviderError::DomainNotFound(detail),
+        404 => ProviderError::RecordNotFound(detail),
+        _ if code.starts_with("APIGW.03") => ProviderError::AuthenticationFailed(detail),
+        _ => ProviderError::ApiError(detail),
+    };
+
+    AppError::Provider(error)
+}
+
+/// 根据区域选择终端节点, 例如 cn-north-4 -> dns.cn-north-4.myhuaweicloud.com
+fn region_endpoint(region: Option<&str>) -> String {
+    match region.map(str::trim) {
+        Some(region) if !region.is_empty() => format!("https://dns.{}.myhuaweicloud.com", region),
+        _ => DEFAULT_ENDPOINT.to_string(),
+    }
+}
+
+/// 记录值使用区域文件格式, TXT 记录值需要用双引号包裹
+fn record_value(record_type: &DNSRecordType, content: &RecordContent) -> String {
+    if *record_type == DNSRecordType::TXT && !content.content.starts_with('"') {
+        format!("\"{}\"", content.content)
+    } else {
+        content.to_rdata(record_type)
+    }
+}
+
+/// 转换为通用记录 (记录集中的每个值各一条), 未内置的类型以 `Other` 保留
+fn to_dns_records(record_set: &HuaweiRecordSet) -> Vec<DNSRecord> {
+    let record_type: DNSRecordType = record_set
+        .record_type
+        .parse()
+        .unwrap_or_else(|_| DNSRecordType::Other(record_set.record_type.clone()));
+
+    record_set
+        .records
+        .iter()
+        .map(|value| {
+            let content = RecordContent::parse(&record_type, value);
+            DNSRecord {
+                id: value_id(&record_set.id, value),
+                name: record_set.name.trim_end_matches('.').to_string(),
+                record_type: record_type.clone(),
+                content: content.content,
+                ttl: record_set.ttl.unwrap_or(DEFAULT_TTL),
+                proxied: None,
+                priority: content.priority,
+                weight: content.weight,
+                port: content.port,
+                comment: record_set.description.clone().filter(|d| !d.is_empty()),
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::providers::test_support::{paginate, PagedResponder};
+    use wiremock::matchers::{body_partial_json, header_exists, method, path, query_param};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    fn mock_provider(server: &MockServer) -> HuaweiProvider {
+        let mut provider = HuaweiProvider::with_endpoint(server.uri());
+        provider.access_key = Some("ak".to_string());
+        provider.secret_key = Some("sk".to_string());
+        provider
+    }
+
+    async fn mount_zone(server: &MockServer) {
+        Mock::given(method("GET"))
+            .and(path("/v2/zones"))
+            .and(query_param("name", "example.com."))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+                "zones": [
+                    { "id": "zone-other", "name": "sub.example.com." },
+                    { "id": "zone-1", "name": "example.com." }
+                ],
+                "metadata": { "total_count": 2 }
+            })))
+            .expect(1)
+            .mount(server)
+            .await;
+    }
+
+    fn record_set(id: &str, name: &str, record_type: &str, value: &str) -> serde_json::Value {
+        json!({
+            "id": id,
+            "name": name,
+            "type": record_type,
+            "ttl": 300,
+            "records": [value]
+        })
+    }
+
+    #[test]
+    fn test_canonical_request() {
+        let params = SigningParams {
+            access_key: "AK",
+            secret_key: "SK",
+            method: "GET",
+            host: "dns.cn-north-4.myhuaweicloud.com",
+            path: "/v2/zones",
+            canonical_query: &canonical_query_string(&[
+                ("type", "public".to_string()),
+                ("name", "example.com.".to_string()),
+            ]),
+            payload: "",
+            sdk_date: "20240101T000000Z",
+        };
+
+        assert_eq!(
+            canonical_request(&params),
+            "GET\n/v2/zones/\nname=example.com.&type=public\n\
+             content-type:application/json\n\
+             host:dns.cn-north-4.myhuaweicloud.com\n\
+             x-sdk-date:20240101T000000Z\n\n\
+             content-type;host;x-sdk-date\n\
+             e3b0c44298fc1c149afbf4c8996fb92427ae41e4649b934ca495991b7852b855"
+        );
+        assert_eq!(
+            sign_sdk(&params),
+            "SDK-HMAC-SHA256 Access=AK, SignedHeaders=content-type;host;x-sdk-date, \
+             Signature=79c1ad34fc593be82024d9554d260f10d3022b51b2bd009ca2c382c9f25f554c"
+        );
+    }
+
+    #[test]
+    fn test_parse_error() {
+        let kind = |status: u16, body: &str| match parse_error(status, body) {
+            AppError::Provider(error) => error,
+            other => panic!("应该返回提供商错误: {:?}", other),
+        };
+
+        assert!(matches!(
+            kind(
+                401,
+                r#"{"error_code":"APIGW.0301","error_msg":"Incorrect IAM authentication information"}"#
+            ),
+            ProviderError::AuthenticationFailed(_)
+        ));
+        assert!(matches!(
+            kind(
+                404,
+                r#"{"code":"DNS.0312","message":"Record set does not exist."}"#
+            ),
+            ProviderError::RecordNotFound(_)
+        ));
+        assert!(matches!(
+            kind(
+                404,
+                r#"{"code":"DNS.0101","message":"The zone does not exist."}"#
+            ),
+            ProviderError::DomainNotFound(_)
+        ));
+        assert!(matches!(
+            kind(429, "too many requests"),
+            ProviderError::RateLimitExceeded
+        ));
+        assert!(matches!(
+            kind(
+                400,
+                r#"{"code":"DNS.0303","message":"Invalid record value."}"#
+            ),
+            ProviderError::ApiError(_)
+        ));
+    }
+
+    #[test]
+    fn test_region_endpoint() {
+        assert_eq!(
+            region_endpoint(Some("cn-north-4")),
+            "https://dns.cn-north-4.myhuaweicloud.com"
+        );
+        assert_eq!(region_endpoint(Some(" ")), DEFAULT_ENDPOINT);
+        assert_eq!(region_endpoint(None), DEFAULT_ENDPOINT);
+    }
+
+    #[tokio::test]
+    async fn test_list_records_reads_all_pages() {
+        let server = MockServer::start().await;
+        mount_zone(&server).await;
+
+        let pages = paginate(1200, PAGE_SIZE, |i| {
+            record_set(
+                &format!("rs-{}", i),
+                &format!("host{}.example.com.", i),
+                "A",
+                "1.1.1.1",
+            )
+        });
+        let pages = pages
+            .into_iter()
+            .map(|records| json!({ "recordsets": records, "metadata": { "total_count": 1200 } }))
+            .collect();
+        Mock::given(method("GET"))
+            .and(path("/v2/zones/zone-1/recordsets"))
+            .and(header_exists("X-Sdk-Date"))
+            .respond_with(PagedResponder::offset("offset", "limit", pages))
+            .expect(3)
+            .mount(&server)
+            .await;
+
+        let provider = mock_provider(&server);
+        let records = provider.list_records("example.com").await.unwrap();
+
+        assert_eq!(records.len(), 1200);
+        assert_eq!(records[0].name, "host0.example.com");
+        assert_eq!(records[1199].id, "rs-1199#1.1.1.1");
+    }
+
+    #[tokio::test]
+    async fn test_update_record_keeps_name_and_ttl() {
+        let server = MockServer::start().await;
+        mount_zone(&server).await;
+
+        Mock::given(method("GET"))
+            .and(path("/v2/zones/zone-1/recordsets/rs-1"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(record_set(
+                "rs-1",
+                "www.example.com.",
+                "A",
+                "1.1.1.1",
+            )))
+            .mount(&server)
+            .await;
+        Mock::given(method("PUT"))
+            .and(path("/v2/zones/zone-1/recordsets/rs-1"))
+            .and(body_partial_json(json!({
+                "name": "www.example.com.",
+                "type": "A",
+                "ttl": 300,
+                "records": ["2.2.2.2"]
+            })))
+            .respond_with(ResponseTemplate::new(202).set_body_json(record_set(
+                "rs-1",
+                "www.example.com.",
+                "A",
+                "2.2.2.2",
+            )))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let provider = mock_provider(&server);
+        let result = provider
+            .update_record("example.com", "rs-1#1.1.1.1", "2.2.2.2")
+            .await
+            .unwrap();
+
+        assert!(result.success);
+        assert_eq!(result.record_id, "rs-1#2.2.2.2");
+        assert_eq!(result.old_ip, "1.1.1.1");
+        assert_eq!(result.new_ip, "2.2.2.2");
+    }
+
+    #[tokio::test]
+    async fn test_create_and_delete_record() {
+        let server = MockServer::start().await;
+        mount_zone(&server).await;
+
+        Mock::given(method("POST"))
+            .and(path("/v2/zones/zone-1/recordsets"))
+            .and(body_partial_json(json!({
+                "name": "www.example.com.",
+                "type": "AAAA",
+                "records": ["2001:db8::1"]
+            })))
+            .respond_with(ResponseTemplate::new(202).set_body_json(record_set(
+                "rs-2",
+                "www.example.com.",
+                "AAAA",
+                "2001:db8::1",
+            )))
+            .expect(1)
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/v2/zones/zone-1/recordsets/rs-2"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(record_set(
+                "rs-2",
+                "www.example.com.",
+                "AAAA",
+                "2001:db8::1",
+            )))
+            .mount(&server)
+            .await;
+        Mock::given(method("DELETE"))
+            .and(path("/v2/zones/zone-1/recordsets/rs-2"))
+            .respond_with(ResponseTemplate::new(202).set_body_json(record_set(
+                "rs-2",
+                "www.example.com.",
+                "AAAA",
+                "2001:db8::1",
+            )))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let provider = mock_provider(&server);
+        let record = provider
+            .create_record("example.com", "www", DNSRecordType::AAAA, "2001:db8::1")
+            .await
+            .unwrap();
+        assert_eq!(record.id, "rs-2#2001:db8::1");
+        assert_eq!(record.record_type, DNSRecordType::AAAA);
+
+        provider
+            .delete_record("example.com", &record.id)
+            .await
+            .unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_multi_value_record_set_edits_one_value() {
+        let server = MockServer::start().await;
+        mount_zone(&server).await;
+
+        let mut mx = record_set("rs-3", "example.com.", "MX", "10 mx1.example.com.");
+        mx["records"] = json!(["10 mx1.example.com.", "20 mx2.example.com."]);
+        Mock::given(method("GET"))
+            .and(path("/v2/zones/zone-1/recordsets"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+                "recordsets": [mx.clone()],
+                "metadata": { "total_count": 1 }
+            })))
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/v2/zones/zone-1/recordsets/rs-3"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(mx.clone()))
+            .mount(&server)
+            .await;
+        Mock::given(method("PUT"))
+            .and(path("/v2/zones/zone-1/recordsets/rs-3"))
+            .and(body_partial_json(json!({
+                "records": ["5 mx0.example.com.", "20 mx2.example.com."]
+            })))
+            .respond_with(ResponseTemplate::new(202).set_body_json(mx.clone()))
+            .expect(1)
+            .mount(&server)
+            .await;
+        Mock::given(method("PUT"))
+            .and(path("/v2/zones/zone-1/recordsets/rs-3"))
+            .and(body_partial_json(
+                json!({ "records": ["10 mx1.example.com."] }),
+            ))
+            .respond_with(ResponseTemplate::new(202).set_body_json(mx))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let provider = mock_provider(&server);
+        let records = provider.list_records("example.com").await.unwrap();
+        assert_eq!(records.len(), 2);
+        assert_eq!(records[1].id, "rs-3#20 mx2.example.com.");
+        assert_eq!(records[1].priority, Some(20));
+
+        let result = provider
+            .update_record("example.com", &records[0].id, "5 mx0.example.com.")
+            .await
+            .unwrap();
+        assert_eq!(result.record_id, "rs-3#5 mx0.example.com.");
+
+        provider
+            .delete_record("example.com", &records[1].id)
+            .await
+            .unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_create_record_with_ttl_and_description() {
+        let server = MockServer::start().await;
+        mount_zone(&server).await;
+
+        let mut created = record_set("rs-3", "www.example.com.", "A", "1.1.1.1");
+        created["ttl"] = json!(60);
+        created["description"] = json!("home");
+        Mock::given(method("POST"))
+            .and(path("/v2/zones/zone-1/recordsets"))
+            .and(body_partial_json(json!({
+                "ttl": 60,
+                "description": "home",
+                "records": ["1.1.1.1"]
+            })))
+            .respond_with(ResponseTemplate::new(202).set_body_json(created))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let provider = mock_provider(&server);
+        let spec = RecordSpec {
+            ttl: Some(60),
+            comment: Some("home".to_string()),
+            ..Default::default()
+        };
+        let record = provider
+            .create_record_with("example.com", "www", DNSRecordType::A, "1.1.1.1", &spec)
+            .await
+            .unwrap();
+        assert_eq!(record.ttl, 60);
+        assert_eq!(record.comment.as_deref(), Some("home"));
+    }
+
+    #[tokio::test]
+    async fn test_create_srv_record() {
+        let server = MockServer::start().await;
+        mount_zone(&server).await;
+
+        Mock::given(method("POST"))
+            .and(path("/v2/zones/zone-1/recordsets"))
+            .and(body_partial_json(json!({
+                "type": "SRV",
+                "records": ["10 5 5060 sip.example.com."]
+            })))
+            .respond_with(ResponseTemplate::new(202).set_body_json(record_set(
+                "rs-4",
+                "_sip._tcp.example.com.",
+                "SRV",
+                "10 5 5060 sip.example.com.",
+            )))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let provider = mock_provider(&server);
+        let record = provider
+            .create_record(
+                "example.com",
+                "_sip._tcp",
+                DNSRecordType::SRV,
+                "10 5 5060 sip.example.com.",
+            )
+            .await
+            .unwrap();
+        assert_eq!(record.content, "sip.example.com.");
+        assert_eq!(
+            (record.priority, record.weight, record.port),
+            (Some(10), Some(5), Some(5060))
+        );
+    }
+}
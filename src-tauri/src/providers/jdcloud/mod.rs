@@ -0,0 +1,951 @@
+use crate::error::{AppError, Result};
+use crate::providers::provider_trait::{
+    Credentials, DNSProvider, DNSRecord, DNSRecordType, ProviderCapabilities, ProviderError,
+    RecordContent, RecordSpec, UpdateResult,
+};
+use crate::providers::signing::{
+    canonical_query_string, hex_encode, hmac_sha256, sha256_hex, URI_ENCODE_SET,
+};
+use async_trait::async_trait;
+use percent_encoding::utf8_percent_encode;
+use reqwest::{Client, Method};
+use serde::Deserialize;
+use serde_json::json;
+use std::collections::HashMap;
+use std::sync::RwLock;
+
+/// 签名服务名
+const SERVICE: &str = "domainservice";
+
+/// 参与签名的请求头
+const SIGNED_HEADERS: &str = "content-type;host;x-jdcloud-date;x-jdcloud-nonce";
+
+/// 未指定区域时使用的默认区域
+const DEFAULT_REGION: &str = "cn-north-1";
+
+/// 新建记录的默认 TTL
+const DEFAULT_TTL: u32 = 600;
+
+/// 默认解析线路
+const DEFAULT_VIEW: i64 = -1;
+
+/// 记录列表每页数量 (API 允许的最大值)
+const PAGE_SIZE: usize = 100;
+
+/// 京东云 DNS 提供商 (云解析 domainservice)
+pub struct JdcloudProvider {
+    access_key: Option<String>,
+    secret_key: Option<String>,
+    region: String,
+    endpoint: String,
+    domain_ids: RwLock<HashMap<String, u64>>,
+    client: Client,
+}
+
+/// 主域名
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct JdcloudDomain {
+    id: u64,
+    domain_name: String,
+}
+
+/// 解析记录
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct JdcloudRecord {
+    id: u64,
+    host_record: String,
+    host_value: String,
+    #[serde(rename = "type")]
+    record_type: String,
+    #[serde(default)]
+    ttl: Option<u32>,
+    #[serde(default)]
+    mx_priority: Option<u16>,
+    #[serde(default)]
+    view_value: Option<Vec<i64>>,
+}
+
+impl JdcloudProvider {
+    pub fn new() -> Self {
+        Self::with_endpoint("https://domainservice.jdcloud-api.com")
+    }
+
+    /// 使用自定义 API 地址创建提供商 (例如测试服务器)
+    pub fn with_endpoint(endpoint: impl Into<String>) -> Self {
+        Self {
+            access_key: None,
+            secret_key: None,
+            region: DEFAULT_REGION.to_string(),
+            endpoint: endpoint.into().trim_end_matches('/').to_string(),
+            domain_ids: RwLock::new(HashMap::new()),
+            client: Client::builder()
+                .timeout(std::time::Duration::from_secs(30))
+                .build()
+                .unwrap(),
+        }
+    }
+
+    /// 发送签名后的 API 请求, 返回 `result` 字段
+    async fn send_request(
+        &self,
+        method: Method,
+        path: &str,
+        query: &[(&str, String)],
+        body: Option<serde_json::Value>,
+    ) -> Result<serde_json::Value> {
+        let (access_key, secret_key) = match (&self.access_key, &self.secret_key) {
+            (Some(ak), Some(sk)) => (ak, sk),
+            _ => {
+                return Err(AppError::Provider(ProviderError::AuthenticationFailed(
+                    "未设置 Access Key 或 Secret Key".to_string(),
+                )))
+            }
+        };
+
+        let path = format!("/v2/regions/{}{}", self.region, path);
+        let url = reqwest::Url::parse(&format!("{}{}", self.endpoint, path))
+            .map_err(|e| AppError::Provider(ProviderError::InvalidConfig(e.to_string())))?;
+        let host = match (url.host_str(), url.port()) {
+            (Some(host), Some(port)) => format!("{}:{}", host, port),
+            (Some(host), None) => host.to_string(),
+            _ => {
+                return Err(AppError::Provider(ProviderError::InvalidConfig(format!(
+                    "无效的 API 地址: {}",
+                    self.endpoint
+                ))))
+            }
+        };
+
+        let canonical_query = canonical_query_string(query);
+        let payload = body.map(|b| b.to_string()).unwrap_or_default();
+        let date = chrono::Utc::now().format("%Y%m%dT%H%M%SZ").to_string();
+        let nonce = uuid::Uuid::new_v4().to_string();
+
+        let authorization = sign_jdcloud(&SigningParams {
+            access_key,
+            secret_key,
+            region: &self.region,
+            service: SERVICE,
+            method: method.as_str(),
+            host: &host,
+            path: &path,
+            canonical_query: &canonical_query,
+            payload: &payload,
+            date: &date,
+            nonce: &nonce,
+        });
+
+        let full_url = if canonical_query.is_empty() {
+            format!("{}{}", self.endpoint, path)
+        } else {
+            format!("{}{}?{}", self.endpoint, path, canonical_query)
+        };
+
+        let response = self
+            .client
+            .request(method, &full_url)
+            .header("Content-Type", "application/json")
+            .header("x-jdcloud-date", &date)
+            .header("x-jdcloud-nonce", &nonce)
+            .header("Authorization", authorization)
+            .body(payload)
+            .send()
+            .await
+            .map_err(|e| {
+                AppError::Provider(ProviderError::NetworkError(format!("请求失败: {}", e)))
+            })?;
+
+        let status = response.status();
+        let text = response.text().await.map_err(|e| {
+            AppError::Provider(ProviderError::NetworkError(format!("读取响应失败: {}", e)))
+        })?;
+        let json: serde_json::Value = serde_json::from_str(&text).unwrap_or_default();
+
+        if !status.is_success() || json["error"].is_object() {
+            return Err(parse_error(status.as_u16(), &json, &text));
+        }
+
+        Ok(json["result"].clone())
+    }
+
+    /// 根据域名查找主域名 ID (结果会被缓存)
+    async fn get_domain_id(&self, domain: &str) -> Result<u64> {
+        if let Some(domain_id) = self.domain_ids.read().unwrap().get(domain) {
+            return Ok(*domain_id);
+        }
+
+        let result = self
+            .send_request(
+                Method::GET,
+                "/domain",
+                &[
+                    ("domainName", domain.to_string()),
+                    ("pageNumber", "1".to_string()),
+                    ("pageSize", "10".to_string()),
+                ],
+                None,
+            )
+            .await?;
+        let domains: Vec<JdcloudDomain> = parse_value(&result["dataList"])?;
+
+        // domainName 参数为模糊匹配, 需要再精确比较
+        let domain_id = domains
+            .into_iter()
+            .find(|d| d.domain_name.eq_ignore_ascii_case(domain))
+            .map(|d| d.id)
+            .ok_or_else(|| {
+                AppError::Provider(ProviderError::DomainNotFound(format!(
+                    "找不到域名: {}",
+                    domain
+                )))
+            })?;
+
+        self.domain_ids
+            .write()
+            .unwrap()
+            .insert(domain.to_string(), domain_id);
+        Ok(domain_id)
+    }
+
+    /// 分页查询解析记录 (describeResourceRecord), `search` 为主机记录关键字
+    async fn describe_records(
+        &self,
+        domain_id: u64,
+        search: Option<&str>,
+    ) -> Result<Vec<JdcloudRecord>> {
+        let path = format!("/domain/{}/ResourceRecord", domain_id);
+        let mut records = Vec::new();
+        let mut page_number = 1;
+
+        loop {
+            let mut query = vec![
+                ("pageNumber", page_number.to_string()),
+                ("pageSize", PAGE_SIZE.to_string()),
+            ];
+            if let Some(search) = search {
+                query.push(("search", search.to_string()));
+            }
+
+            let result = self.send_request(Method::GET, &path, &query, None).await?;
+            let page: Vec<JdcloudRecord> = if result["dataList"].is_null() {
+                Vec::new()
+            } else {
+                parse_value(&result["dataList"])?
+            };
+            let total_count = result["totalCount"].as_u64().unwrap_or(0) as usize;
+
+            let count = page.len();
+            records.extend(page);
+
+            if count == 0 || records.len() >= total_count {
+                break;
+            }
+            page_number += 1;
+        }
+
+        Ok(records)
+    }
+}
+
+impl Default for JdcloudProvider {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[async_trait]
+impl DNSProvider for JdcloudProvider {
+    fn provider_id(&self) -> &'static str {
+        "jdcloud"
+    }
+
+    fn provider_name(&self) -> &'static str {
+        "JD Cloud (京东云)"
+    }
+
+    async fn initialize(&mut self, credentials: &Credentials) -> Result<()> {
+        self.access_key = credentials.access_key.clone().or_else(|| {
+            credentials
+                .extra
+                .get("access_key")
+                .and_then(|v| v.as_str())
+                .map(|v| v.to_string())
+        });
+        self.secret_key = credentials.api_secret.clone().or_else(|| {
+            credentials
+                .extra
+                .get("secret_key")
+                .and_then(|v| v.as_str())
+                .map(|v| v.to_string())
+        });
+        self.region = credentials
+            .region
+            .clone()
+            .filter(|r| !r.trim().is_empty())
+            .unwrap_or_else(|| DEFAULT_REGION.to_string());
+
+        if self.access_key.is_none() || self.secret_key.is_none() {
+            return Err(AppError::Provider(ProviderError::InvalidConfig(
+                "京东云需要提供 access_key 和 secret_key".to_string(),
+            )));
+        }
+        self.domain_ids.write().unwrap().clear();
+
+        // 测试连接
+        self.test_connection().await?;
+
+        Ok(())
+    }
+
+    async fn list_records(&self, domain: &str) -> Result<Vec<DNSRecord>> {
+        let domain_id = self.get_domain_id(domain).await?;
+        let records = self.describe_records(domain_id, None).await?;
+
+        Ok(records.into_iter().map(to_dns_record).collect())
+    }
+
+    async fn get_record(
+        &self,
+        domain: &str,
+        record_name: &str,
+        record_type: DNSRecordType,
+    ) -> Result<Option<DNSRecord>> {
+        let host = if record_name.is_empty() {
+            "@"
+        } else {
+            record_name
+        };
+        let domain_id = self.get_domain_id(domain).await?;
+        let records = self.describe_records(domain_id, Some(host)).await?;
+
+        Ok(records
+            .into_iter()
+            .filter(|r| r.host_record == host)
+            .map(to_dns_record)
+            .find(|r| r.record_type == record_type))
+    }
+
+    async fn update_record(
+        &self,
+        domain: &str,
+        record_id: &str,
+        new_content: &str,
+    ) -> Result<UpdateResult> {
+        self.update_record_with(domain, record_id, new_content, &RecordSpec::default())
+            .await
+    }
+
+    async fn update_record_with(
+        &self,
+        domain: &str,
+        record_id: &str,
+        new_content: &str,
+        spec: &RecordSpec,
+    ) -> Result<UpdateResult> {
+        spec.ensure_supported(&self.capabilities())?;
+        let domain_id = self.get_domain_id(domain).await?;
+
+        // 修改接口需要提交完整的记录, 先获取当前记录
+        let current = self
+            .describe_records(domain_id, None)
+            .await?
+            .into_iter()
+            .find(|r| r.id.to_string() == record_id)
+            .ok_or_else(|| {
+                AppError::Provider(ProviderError::RecordNotFound(record_id.to_string()))
+            })?;
+
+        let view = current
+            .view_value
+            .as_ref()
+            .and_then(|v| v.last().copied())
+            .unwrap_or(DEFAULT_VIEW);
+        let current = to_dns_record(current);
+        let mut content = RecordContent::from_input(&current.record_type, new_content, spec)?;
+        content.priority = content.priority.or(current.priority);
+
+        let mut req = json!({
+            "domainName": domain,
+            "hostRecord": current.name,
+            "hostValue": record_value(&current.record_type, &content),
+            "type": current.record_type.to_string(),
+            "ttl": spec.ttl.unwrap_or(current.ttl),
+            "viewValue": view,
+        });
+        if let Some(priority) = content
+            .priority
+            .filter(|_| current.record_type == DNSRecordType::MX)
+        {
+            req["mxPriority"] = json!(priority);
+        }
+
+        self.send_request(
+            Method::PUT,
+            &format!("/domain/{}/ResourceRecord/{}", domain_id, record_id),
+            &[],
+            Some(json!({ "req": req })),
+        )
+        .await?;
+
+        Ok(UpdateResult {
+            success: true,
+            record_id: record_id.to_string(),
+            old_ip: current.rdata(),
+            new_ip: new_content.to_string(),
+            message: "更新成功".to_string(),
+        })
+    }
+
+    async fn create_record(
+        &self,
+        domain: &str,
+        record_name: &str,
+        record_type: DNSRecordType,
+        content: &str,
+    ) -> Result<DNSRecord> {
+        self.create_record_with(
+            domain,
+            record_name,
+            record_type,
+            content,
+            &RecordSpec::default(),
+        )
+        .await
+    }
+
+    async fn create_record_with(
+        &self,
+        domain: &str,
+        record_name: &str,
+        record_type: DNSRecordType,
+        content: &str,
+        spec: &RecordSpec,
+    ) -> Result<DNSRecord> {
+        spec.ensure_supported(&self.capabilities())?;
+        let content = RecordContent::from_input(&record_type, content, spec)?;
+        let host = if record_name.is_empty() {
+            "@"
+        } else {
+            record_name
+        };
+        let domain_id = self.get_domain_id(domain).await?;
+
+        let mut req = json!({
+            "hostRecord": host,
+            "hostValue": record_value(&record_type, &content),
+            "type": record_type.to_string(),
+            "ttl": spec.ttl.unwrap_or(DEFAULT_TTL),
+            "viewValue": DEFAULT_VIEW,
+        });
+        if let Some(priority) = content
+            .priority
+            .filter(|_| record_type == DNSRecordType::MX)
+        {
+            req["mxPriority"] = json!(priority);
+        }
+        let result = self
+            .send_request(
+                Method::POST,
+                &format!("/domain/{}/ResourceRecord", domain_id),
+                &[],
+                Some(json!({ "req": req })),
+            )
+            .await?;
+
+        let record: JdcloudRecord = parse_value(&result["dataList"])?;
+        Ok(to_dns_record(record))
+    }
+
+    async fn delete_record(&self, domain: &str, record_id: &str) -> Result<()> {
+        let domain_id = self.get_domain_id(domain).await?;
+        self.send_request(
+            Method::DELETE,
+            &format!("/domain/{}/ResourceRecord/{}", domain_id, record_id),
+            &[],
+            None,
+        )
+        .await?;
+        Ok(())
+    }
+
+    async fn test_connection(&self) -> Result<bool> {
+        // 测试获取域名列表
+        self.send_request(
+            Method::GET,
+            "/domain",
+            &[
+                ("pageNumber", "1".to_string()),
+                ("pageSize", "10".to_string()),
+            ],
+            None,
+        )
+        .await?;
+        Ok(true)
+    }
+
+    fn supported_record_types(&self) -> Vec<DNSRecordType> {
+        vec![
+            DNSRecordType::A,
+            DNSRecordType::AAAA,
+            DNSRecordType::CNAME,
+            DNSRecordType::MX,
+            DNSRecordType::TXT,
+            DNSRecordType::NS,
+            DNSRecordType::SRV,
+            DNSRecordType::CAA,
+        ]
+    }
+
+    fn capabilities(&self) -> ProviderCapabilities {
+        ProviderCapabilities {
+            proxy: false,
+            ttl: true,
+            line: true,
+            priority: true,
+            comment: false,
+        }
+    }
+}
+
+// ============ JDCLOUD2-HMAC-SHA256 签名 ============
+
+/// JDCLOUD2-HMAC-SHA256 签名参数
+struct SigningParams<'a> {
+    access_key: &'a str,
+    secret_key: &'a str,
+    region: &'a str,
+    service: &'a str,
+    method: &'a str,
+    host: &'a str,
+    path: &'a str,
+    canonical_query: &'a str,
+    payload: &'a str,
+    /// `x-jdcloud-date`, 格式 `20190101T000000Z`
+    date: &'a str,
+    /// `x-jdcloud-nonce`
+    nonce: &'a str,
+}
+
+/// 构造规范请求 (签名头为 content-type、host、x-jdcloud-date 和 x-jdcloud-nonce)
+fn canonical_request(params: &SigningParams) -> String {
+    let uri = params
+        .path
+        .split('/')
+        .map(|segment| utf8_percent_encode(segment, URI_ENCODE_SET).to_string())
+        .collect::<Vec<_>>()
+        .join("/");
+
+    format!(
+        "{}\n{}\n{}\ncontent-type:application/json\nhost:{}\nx-jdcloud-date:{}\nx-jdcloud-nonce:{}\n\n{}\n{}",
+        params.method,
+        uri,
+        params.canonical_query,
+        params.host,
+        params.date,
+        params.nonce,
+        SIGNED_HEADERS,
+        sha256_hex(params.payload.as_bytes())
+    )
+}
+
+/// 计算 Authorization 头
+fn sign_jdcloud(params: &SigningParams) -> String {
+    let date = &params.date[..8];
+    let scope = format!(
+        "{}/{}/{}/jdcloud2_request",
+        date, params.region, params.service
+    );
+
+    let string_to_sign = format!(
+        "JDCLOUD2-HMAC-SHA256\n{}\n{}\n{}",
+        params.date,
+        scope,
+        sha256_hex(canonical_request(params).as_bytes())
+    );
+
+    let key = signing_key(params.secret_key, date, params.region, params.service);
+    let signature = hex_encode(&hmac_sha256(&key, string_to_sign.as_bytes()));
+
+    format!(
+        "JDCLOUD2-HMAC-SHA256 Credential={}/{}, SignedHeaders={}, Signature={}",
+        params.access_key, scope, SIGNED_HEADERS, signature
+    )
+}
+
+/// 派生签名密钥
+fn signing_key(secret: &str, date: &str, region: &str, service: &str) -> Vec<u8> {
+    let k_date = hmac_sha256(format!("JDCLOUD2{}", secret).as_bytes(), date.as_bytes());
+    let k_region = hmac_sha256(&k_date, region.as_bytes());
+    let k_service = hmac_sha256(&k_region, service.as_bytes());
+    hmac_sha256(&k_service, b"jdcloud2_request")
+}
+
+// ============ 辅助函数 ============
+
+fn parse_value<T: serde::de::DeserializeOwned>(value: &serde_json::Value) -> Result<T> {
+    serde_json::from_value(value.clone())
+        .map_err(|e| AppError::Provider(ProviderError::ParseError(e.to_string())))
+}
+
+/// 将京东云错误响应 (`{"error": {"code", "status", "message"}}`) 映射为 ProviderError
+fn parse_error(status: u16, json: &serde_json::Value, text: &str) -> AppError {
+    let error = &json["error"];
+    let code = error["status"].as_str().unwrap_or_default();
+    let message = error["message"].as_str().unwrap_or(text);
+    let status = error["code"].as_u64().map(|c| c as u16).unwrap_or(status);
+
+    let detail = format!("HTTP {} {}: {}", status, code, message);
+    let error = match (status, code) {
+        (401 | 403, _) | (_, "UNAUTHENTICATED" | "PERMISSION_DENIED") => {
+            ProviderError::AuthenticationFailed(detail)
+        }
+        (429, _) | (_, "RESOURCE_EXHAUSTED") => ProviderError::RateLimitExceeded,
+        (404, _) | (_, "NOT_FOUND") => ProviderError::RecordNotFound(detail),
+        _ => ProviderError::ApiError(detail),
+    };
+
+    AppError::Provider(error)
+}
+
+/// 记录值: MX 的优先级在 mxPriority 中, 其他类型使用区域文件格式
+fn record_value(record_type: &DNSRecordType, content: &RecordContent) -> String {
+    match record_type {
+        DNSRecordType::MX => content.content.clone(),
+        _ => content.to_rdata(record_type),
+    }
+}
+
+/// 转换为通用记录, 未内置的类型以 `Other` 保留
+fn to_dns_record(record: JdcloudRecord) -> DNSRecord {
+    let record_type = record
+        .record_type
+        .parse()
+        .unwrap_or_else(|_| DNSRecordType::Other(record.record_type.clone()));
+    let content = RecordContent::parse(&record_type, &record.host_value);
+    let priority = match record_type {
+        DNSRecordType::MX => record.mx_priority,
+        _ => content.priority,
+    };
+
+    DNSRecord {
+        id: record.id.to_string(),
+        name: record.host_record,
+        record_type,
+        content: content.content,
+        ttl: record.ttl.unwrap_or(DEFAULT_TTL),
+        proxied: None,
+        priority,
+        weight: content.weight,
+        port: content.port,
+        comment: None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::providers::test_support::{paginate, PagedResponder};
+    use wiremock::matchers::{body_partial_json, header_exists, method, path, query_param};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    fn mock_provider(server: &MockServer) -> JdcloudProvider {
+        let mut provider = JdcloudProvider::with_endpoint(server.uri());
+        provider.access_key = Some("ak".to_string());
+        provider.secret_key = Some("sk".to_string());
+        provider
+    }
+
+    async fn mount_domain(server: &MockServer) {
+        Mock::given(method("GET"))
+            .and(path("/v2/regions/cn-north-1/domain"))
+            .and(query_param("domainName", "example.com"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+                "requestId": "r1",
+                "result": {
+                    "dataList": [
+                        { "id": 11, "domainName": "myexample.com" },
+                        { "id": 12, "domainName": "example.com" }
+                    ],
+                    "totalCount": 2
+                }
+            })))
+            .expect(1)
+            .mount(server)
+            .await;
+    }
+
+    fn record(id: u64, host: &str, record_type: &str, value: &str) -> serde_json::Value {
+        json!({
+            "id": id,
+            "hostRecord": host,
+            "hostValue": value,
+            "type": record_type,
+            "ttl": 600,
+            "viewValue": [-1]
+        })
+    }
+
+    #[test]
+    fn test_sign_jdcloud() {
+        let params = SigningParams {
+            access_key: "AK",
+            secret_key: "SK",
+            region: "cn-north-1",
+            service: "domainservice",
+            method: "GET",
+            host: "domainservice.jdcloud-api.com",
+            path: "/v2/regions/cn-north-1/domain",
+            canonical_query: &canonical_query_string(&[
+                ("pageSize", "10".to_string()),
+                ("pageNumber", "1".to_string()),
+            ]),
+            payload: "",
+            date: "20240101T000000Z",
+            nonce: "ed558a3b-9808-4edb-8597-187bda63a4f2",
+        };
+
+        assert_eq!(
+            canonical_request(&params),
+            "GET\n/v2/regions/cn-north-1/domain\npageNumber=1&pageSize=10\n\
+             content-type:application/json\n\
+             host:domainservice.jdcloud-api.com\n\
+             x-jdcloud-date:20240101T000000Z\n\
+             x-jdcloud-nonce:ed558a3b-9808-4edb-8597-187bda63a4f2\n\n\
+             content-type;host;x-jdcloud-date;x-jdcloud-nonce\n\
+             e3b0c44298fc1c149afbf4c8996fb92427ae41e4649b934ca495991b7852b855"
+        );
+        assert_eq!(
+            sign_jdcloud(&params),
+            "JDCLOUD2-HMAC-SHA256 Credential=AK/20240101/cn-north-1/domainservice/jdcloud2_request, \
+             SignedHeaders=content-type;host;x-jdcloud-date;x-jdcloud-nonce, \
+             Signature=ec4d18f47508d2df1a4ee6e281858f1855d9f7c749b59ad61ecaf894cbc87dd2"
+        );
+    }
+
+    #[test]
+    fn test_parse_error() {
+        let kind = |status: u16, body: serde_json::Value| match parse_error(
+            status,
+            &body,
+            &body.to_string(),
+        ) {
+            AppError::Provider(error) => error,
+            other => panic!("应该返回提供商错误: {:?}", other),
+        };
+
+        assert!(matches!(
+            kind(
+                401,
+                json!({ "error": { "code": 401, "status": "UNAUTHENTICATED", "message": "sign error" } })
+            ),
+            ProviderError::AuthenticationFailed(_)
+        ));
+        assert!(matches!(
+            kind(
+                429,
+                json!({ "error": { "code": 429, "status": "RESOURCE_EXHAUSTED", "message": "limit" } })
+            ),
+            ProviderError::RateLimitExceeded
+        ));
+        assert!(matches!(
+            kind(
+                400,
+                json!({ "error": { "code": 400, "status": "INVALID_ARGUMENT", "message": "bad" } })
+            ),
+            ProviderError::ApiError(_)
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_list_records_reads_all_pages() {
+        let server = MockServer::start().await;
+        mount_domain(&server).await;
+
+        let pages = paginate(250, PAGE_SIZE, |i| {
+            record(i as u64, &format!("host{}", i), "A", "1.1.1.1")
+        });
+        let pages = pages
+            .into_iter()
+            .map(|records| json!({ "result": { "dataList": records, "totalCount": 250 } }))
+            .collect();
+        Mock::given(method("GET"))
+            .and(path("/v2/regions/cn-north-1/domain/12/ResourceRecord"))
+            .and(header_exists("x-jdcloud-nonce"))
+            .respond_with(PagedResponder::page_number("pageNumber", 1, pages))
+            .expect(3)
+            .mount(&server)
+            .await;
+
+        let provider = mock_provider(&server);
+        let records = provider.list_records("example.com").await.unwrap();
+
+        assert_eq!(records.len(), 250);
+        assert_eq!(records[249].id, "249");
+    }
+
+    #[tokio::test]
+    async fn test_update_record() {
+        let server = MockServer::start().await;
+        mount_domain(&server).await;
+
+        Mock::given(method("GET"))
+            .and(path("/v2/regions/cn-north-1/domain/12/ResourceRecord"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+                "result": { "dataList": [record(7, "www", "A", "1.1.1.1")], "totalCount": 1 }
+            })))
+            .mount(&server)
+            .await;
+        Mock::given(method("PUT"))
+            .and(path("/v2/regions/cn-north-1/domain/12/ResourceRecord/7"))
+            .and(body_partial_json(json!({
+                "req": {
+                    "domainName": "example.com",
+                    "hostRecord": "www",
+                    "hostValue": "2.2.2.2",
+                    "type": "A",
+                    "viewValue": -1
+                }
+            })))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({ "requestId": "r2" })))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let provider = mock_provider(&server);
+        let result = provider
+            .update_record("example.com", "7", "2.2.2.2")
+            .await
+            .unwrap();
+
+        assert!(result.success);
+        assert_eq!(result.old_ip, "1.1.1.1");
+        assert_eq!(result.new_ip, "2.2.2.2");
+    }
+
+    #[tokio::test]
+    async fn test_create_and_delete_record() {
+        let server = MockServer::start().await;
+        mount_domain(&server).await;
+
+        Mock::given(method("POST"))
+            .and(path("/v2/regions/cn-north-1/domain/12/ResourceRecord"))
+            .and(body_partial_json(json!({
+                "req": { "hostRecord": "www", "hostValue": "2001:db8::1", "type": "AAAA" }
+            })))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+                "result": { "dataList": record(8, "www", "AAAA", "2001:db8::1") }
+            })))
+            .expect(1)
+            .mount(&server)
+            .await;
+        Mock::given(method("DELETE"))
+            .and(path("/v2/regions/cn-north-1/domain/12/ResourceRecord/8"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({ "requestId": "r3" })))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let provider = mock_provider(&server);
+        let record = provider
+            .create_record("example.com", "www", DNSRecordType::AAAA, "2001:db8::1")
+            .await
+            .unwrap();
+        assert_eq!(record.id, "8");
+
+        provider.delete_record("example.com", "8").await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_create_mx_record_with_spec() {
+        let server = MockServer::start().await;
+        mount_domain(&server).await;
+
+        let mut created = record(9, "@", "MX", "mx.example.com");
+        created["ttl"] = json!(3600);
+        created["mxPriority"] = json!(5);
+        Mock::given(method("POST"))
+            .and(path("/v2/regions/cn-north-1/domain/12/ResourceRecord"))
+            .and(body_partial_json(json!({
+                "req": { "type": "MX", "ttl": 3600, "mxPriority": 5 }
+            })))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+                "result": { "dataList": created }
+            })))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let provider = mock_provider(&server);
+        let spec = RecordSpec {
+            ttl: Some(3600),
+            priority: Some(5),
+            ..Default::default()
+        };
+        let record = provider
+            .create_record_with(
+                "example.com",
+                "@",
+                DNSRecordType::MX,
+                "mx.example.com",
+                &spec,
+            )
+            .await
+            .unwrap();
+        assert_eq!(record.ttl, 3600);
+        assert_eq!(record.priority, Some(5));
+
+        // 京东云记录没有备注字段
+        let spec = RecordSpec {
+            comment: Some("mail".to_string()),
+            ..Default::default()
+        };
+        assert!(provider
+            .create_record_with(
+                "example.com",
+                "@",
+                DNSRecordType::MX,
+                "mx.example.com",
+                &spec
+            )
+            .await
+            .is_err());
+    }
+
+    #[tokio::test]
+    async fn test_create_srv_record() {
+        let server = MockServer::start().await;
+        mount_domain(&server).await;
+
+        Mock::given(method("POST"))
+            .and(path("/v2/regions/cn-north-1/domain/12/ResourceRecord"))
+            .and(body_partial_json(json!({
+                "req": { "type": "SRV", "hostValue": "10 5 5060 sip.example.com" }
+            })))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+                "result": {
+                    "dataList": record(10, "_sip._tcp", "SRV", "10 5 5060 sip.example.com")
+                }
+            })))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let provider = mock_provider(&server);
+        let record = provider
+            .create_record(
+                "example.com",
+                "_sip._tcp",
+                DNSRecordType::SRV,
+                "10 5 5060 sip.example.com",
+            )
+            .await
+            .unwrap();
+        assert_eq!(record.content, "sip.example.com");
+        assert_eq!(
+            (record.priority, record.weight, record.port),
+            (Some(10), Some(5), Some(5060))
+        );
+    }
+}
@@ -0,0 +1,754 @@
+use crate::error::{AppError, Result};
+use crate::providers::provider_trait::{
+    full_record_name, CaaData, Credentials, DNSProvider, DNSRecord, DNSRecordType,
+    ProviderCapabilities, ProviderError, RecordContent, RecordSpec, UpdateResult,
+};
+use async_trait::async_trait;
+use reqwest::{Client, Method};
+use serde::de::DeserializeOwned;
+use serde::Deserialize;
+use serde_json::json;
+use std::collections::HashMap;
+use std::sync::RwLock;
+
+const DEFAULT_ENDPOINT: &str = "https://api.linode.com/v4";
+
+/// 记录列表每页数量 (API 允许的最大值)
+const PAGE_SIZE: usize = 500;
+
+/// 新建记录的默认 TTL
+const DEFAULT_TTL: u32 = 300;
+
+/// 未指定优先级时 MX 记录使用的优先级
+const DEFAULT_MX_PRIORITY: u16 = 10;
+
+/// Linode (Akamai) DNS 提供商
+pub struct LinodeProvider {
+    api_token: Option<String>,
+    endpoint: String,
+    /// 域名 ID 缓存 (域名 -> 域名 ID)
+    domain_ids: RwLock<HashMap<String, u64>>,
+    client: Client,
+}
+
+/// 分页响应
+#[derive(Debug, Deserialize)]
+struct LinodePage<T> {
+    data: Vec<T>,
+    page: usize,
+    pages: usize,
+}
+
+#[derive(Debug, Deserialize)]
+struct LinodeDomain {
+    id: u64,
+    domain: String,
+}
+
+/// 域名记录, `name` 为相对名称 (根域名为空字符串)
+#[derive(Debug, Deserialize)]
+struct LinodeRecord {
+    id: u64,
+    #[serde(rename = "type")]
+    record_type: String,
+    name: String,
+    target: String,
+    #[serde(default)]
+    priority: Option<u16>,
+    #[serde(default)]
+    weight: Option<u16>,
+    #[serde(default)]
+    port: Option<u16>,
+    /// CAA 记录的标签, `target` 中只有值
+    #[serde(default)]
+    tag: Option<String>,
+    #[serde(default)]
+    ttl_sec: u32,
+}
+
+impl LinodeProvider {
+    pub fn new() -> Self {
+        Self::with_endpoint(DEFAULT_ENDPOINT)
+    }
+
+    /// 使用自定义 API 地址创建提供商 (例如测试服务器)
+    pub fn with_endpoint(endpoint: impl Into<String>) -> Self {
+        Self {
+            api_token: None,
+            endpoint: endpoint.into().trim_end_matches('/').to_string(),
+            domain_ids: RwLock::new(HashMap::new()),
+            client: Client::builder()
+                .timeout(std::time::Duration::from_secs(30))
+                .build()
+                .unwrap(),
+        }
+    }
+
+    /// 发送 API 请求并解析 JSON 响应
+    async fn send<T: DeserializeOwned>(
+        &self,
+        method: Method,
+        path: &str,
+        query: &[(&str, String)],
+        filter: Option<serde_json::Value>,
+        body: Option<serde_json::Value>,
+    ) -> Result<T> {
+        let token = self.api_token.as_ref().ok_or_else(|| {
+            AppError::Provider(ProviderError::AuthenticationFailed(
+                "未设置 API Token".to_string(),
+            ))
+        })?;
+
+        let mut request = self
+            .client
+            .request(method, format!("{}{}", self.endpoint, path))
+            .bearer_auth(token)
+            .query(query);
+        if let Some(filter) = filter {
+            request = request.header("X-Filter", filter.to_string());
+        }
+        if let Some(body) = body {
+            request = request.json(&body);
+        }
+
+        let response = request
+            .send()
+            .await
+            .map_err(|e| AppError::Provider(ProviderError::NetworkError(e.to_string())))?;
+        let status = response.status();
+        let text = response
+            .text()
+            .await
+            .map_err(|e| AppError::Provider(ProviderError::NetworkError(e.to_string())))?;
+
+        if !status.is_success() {
+            return Err(parse_error(status.as_u16(), &text));
+        }
+
+        serde_json::from_str(&text).map_err(|e| {
+            AppError::Provider(ProviderError::ParseError(format!("解析响应失败: {}", e)))
+        })
+    }
+
+    /// 逐页读取列表
+    async fn list_pages<T: DeserializeOwned>(
+        &self,
+        path: &str,
+        filter: Option<serde_json::Value>,
+    ) -> Result<Vec<T>> {
+        let mut items = Vec::new();
+        let mut page = 1;
+
+        loop {
+            let result: LinodePage<T> = self
+                .send(
+                    Method::GET,
+                    path,
+                    &[
+                        ("page", page.to_string()),
+                        ("page_size", PAGE_SIZE.to_string()),
+                    ],
+                    filter.clone(),
+                    None,
+                )
+                .await?;
+            items.extend(result.data);
+
+            if result.page >= result.pages {
+                break;
+            }
+            page += 1;
+        }
+
+        Ok(items)
+    }
+
+    /// 获取域名 ID (优先使用缓存)
+    async fn get_domain_id(&self, domain: &str) -> Result<u64> {
+        if let Some(id) = self.domain_ids.read().unwrap().get(domain) {
+            return Ok(*id);
+        }
+
+        let domains: Vec<LinodeDomain> = self
+            .list_pages("/domains", Some(json!({ "domain": domain })))
+            .await?;
+        let id = domains
+            .into_iter()
+            .find(|d| d.domain.eq_ignore_ascii_case(domain))
+            .map(|d| d.id)
+            .ok_or_else(|| AppError::Provider(ProviderError::DomainNotFound(domain.to_string())))?;
+
+        self.domain_ids
+            .write()
+            .unwrap()
+            .insert(domain.to_string(), id);
+        Ok(id)
+    }
+
+    async fn list_domain_records(&self, domain: &str) -> Result<Vec<LinodeRecord>> {
+        let domain_id = self.get_domain_id(domain).await?;
+        self.list_pages(&format!("/domains/{}/records", domain_id), None)
+            .await
+    }
+}
+
+impl Default for LinodeProvider {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[async_trait]
+impl DNSProvider for LinodeProvider {
+    fn provider_id(&self) -> &'static str {
+        "linode"
+    }
+
+    fn provider_name(&self) -> &'static str {
+        "Linode (Akamai)"
+    }
+
+    async fn initialize(&mut self, credentials: &Credentials) -> Result<()> {
+        self.api_token = credentials.api_key.clone().or_else(|| {
+            credentials
+                .extra
+                .get("api_token")
+                .and_then(|v| v.as_str())
+                .map(|v| v.to_string())
+        });
+
+        if self.api_token.is_none() {
+            return Err(AppError::Provider(ProviderError::InvalidConfig(
+                "Linode 需要提供 api_token (Personal Access Token)".to_string(),
+            )));
+        }
+        self.domain_ids.write().unwrap().clear();
+
+        // 测试连接
+        self.test_connection().await?;
+
+        Ok(())
+    }
+
+    async fn list_records(&self, domain: &str) -> Result<Vec<DNSRecord>> {
+        let records = self.list_domain_records(domain).await?;
+        Ok(records
+            .into_iter()
+            .map(|r| to_dns_record(domain, r))
+            .collect())
+    }
+
+    async fn get_record(
+        &self,
+        domain: &str,
+        record_name: &str,
+        record_type: DNSRecordType,
+    ) -> Result<Option<DNSRecord>> {
+        let name = full_record_name(domain, record_name);
+        let records = self.list_domain_records(domain).await?;
+
+        Ok(records
+            .into_iter()
+            .map(|r| to_dns_record(domain, r))
+            .find(|r| r.record_type == record_type && r.name.eq_ignore_ascii_case(&name)))
+    }
+
+    async fn update_record(
+        &self,
+        domain: &str,
+        record_id: &str,
+        new_content: &str,
+    ) -> Result<UpdateResult> {
+        self.update_record_with(domain, record_id, new_content, &RecordSpec::default())
+            .await
+    }
+
+    async fn update_record_with(
+        &self,
+        domain: &str,
+        record_id: &str,
+        new_content: &str,
+        spec: &RecordSpec,
+    ) -> Result<UpdateResult> {
+        spec.ensure_supported(&self.capabilities())?;
+        let domain_id = self.get_domain_id(domain).await?;
+        let path = format!("/domains/{}/records/{}", domain_id, record_id);
+
+        let current: LinodeRecord = self.send(Method::GET, &path, &[], None, None).await?;
+        let current = to_dns_record(domain, current);
+        let mut content = RecordContent::from_input(&current.record_type, new_content, spec)?;
+        content.priority = content.priority.or(current.priority);
+
+        let mut body = json!({});
+        set_content(&mut body, &current.record_type, &content)?;
+        if let Some(ttl) = spec.ttl {
+            body["ttl_sec"] = json!(ttl);
+        }
+
+        let updated: LinodeRecord = self.send(Method::PUT, &path, &[], None, Some(body)).await?;
+
+        Ok(UpdateResult {
+            success: true,
+            record_id: updated.id.to_string(),
+            old_ip: current.rdata(),
+            new_ip: new_content.to_string(),
+            message: "更新成功".to_string(),
+        })
+    }
+
+    async fn create_record(
+        &self,
+        domain: &str,
+        record_name: &str,
+        record_type: DNSRecordType,
+        content: &str,
+    ) -> Result<DNSRecord> {
+        self.create_record_with(
+            domain,
+            record_name,
+            record_type,
+            content,
+            &RecordSpec::default(),
+        )
+        .await
+    }
+
+    async fn create_record_with(
+        &self,
+        domain: &str,
+        record_name: &str,
+        record_type: DNSRecordType,
+        content: &str,
+        spec: &RecordSpec,
+    ) -> Result<DNSRecord> {
+        spec.ensure_supported(&self.capabilities())?;
+        let mut content = RecordContent::from_input(&record_type, content, spec)?;
+        if record_type == DNSRecordType::MX {
+            content.priority = content.priority.or(Some(DEFAULT_MX_PRIORITY));
+        }
+        let domain_id = self.get_domain_id(domain).await?;
+        let name = match record_name {
+            "@" => "",
+            name => name,
+        };
+        let mut body = json!({
+            "type": record_type.to_string(),
+            "name": name,
+            "ttl_sec": spec.ttl.unwrap_or(DEFAULT_TTL),
+        });
+        set_content(&mut body, &record_type, &content)?;
+        if record_type == DNSRecordType::SRV {
+            // SRV 记录的名称由 service 和 protocol 生成
+            let (service, protocol) = srv_service(name).ok_or_else(|| {
+                AppError::Provider(ProviderError::InvalidConfig(format!(
+                    "SRV 记录名称格式应为 _服务._协议: {}",
+                    record_name
+                )))
+            })?;
+            body["service"] = json!(service);
+            body["protocol"] = json!(protocol);
+        }
+
+        let created: LinodeRecord = self
+            .send(
+                Method::POST,
+                &format!("/domains/{}/records", domain_id),
+                &[],
+                None,
+                Some(body),
+            )
+            .await?;
+
+        Ok(to_dns_record(domain, created))
+    }
+
+    async fn delete_record(&self, domain: &str, record_id: &str) -> Result<()> {
+        let domain_id = self.get_domain_id(domain).await?;
+        let _: serde_json::Value = self
+            .send(
+                Method::DELETE,
+                &format!("/domains/{}/records/{}", domain_id, record_id),
+                &[],
+                None,
+                None,
+            )
+            .await?;
+        Ok(())
+    }
+
+    async fn test_connection(&self) -> Result<bool> {
+        // 测试获取用户信息
+        let _: serde_json::Value = self.send(Method::GET, "/profile", &[], None, None).await?;
+        Ok(true)
+    }
+
+    fn supported_record_types(&self) -> Vec<DNSRecordType> {
+        vec![
+            DNSRecordType::A,
+            DNSRecordType::AAAA,
+            DNSRecordType::CNAME,
+            DNSRecordType::MX,
+            DNSRecordType::TXT,
+            DNSRecordType::NS,
+            DNSRecordType::SRV,
+            DNSRecordType::CAA,
+        ]
+    }
+
+    fn capabilities(&self) -> ProviderCapabilities {
+        ProviderCapabilities {
+            proxy: false,
+            ttl: true,
+            line: false,
+            priority: true,
+            comment: false,
+        }
+    }
+}
+
+/// 将 Linode 错误响应 (`{"errors": [{"reason", "field"}]}`) 映射为 ProviderError
+fn parse_error(status: u16, text: &str) -> AppError {
+    let json: serde_json::Value = serde_json::from_str(text).unwrap_or_default();
+    let message = json["errors"]
+        .as_array()
+        .map(|errors| {
+            errors
+                .iter()
+                .filter_map(|e| e["reason"].as_str())
+                .collect::<Vec<_>>()
+                .join("; ")
+        })
+        .filter(|m| !m.is_empty())
+        .unwrap_or_else(|| text.to_string());
+
+    let detail = format!("HTTP {}: {}", status, message);
+    let error = match status {
+        401 | 403 => ProviderError::AuthenticationFailed(detail),
+        429 => ProviderError::RateLimitExceeded,
+        404 => ProviderError::RecordNotFound(detail),
+        _ => ProviderError::ApiError(detail),
+    };
+
+    AppError::Provider(error)
+}
+
+/// 按记录类型填充内容: MX/SRV 的优先级、权重和端口以及 CAA 的标签为独立字段
+fn set_content(
+    body: &mut serde_json::Value,
+    record_type: &DNSRecordType,
+    content: &RecordContent,
+) -> Result<()> {
+    body["target"] = json!(content.content);
+    match record_type {
+        DNSRecordType::MX => body["priority"] = json!(content.priority),
+        DNSRecordType::SRV => {
+            body["priority"] = json!(content.priority);
+            body["weight"] = json!(content.weight);
+            body["port"] = json!(content.port);
+        }
+        // Linode 的 CAA 记录不支持标志
+        DNSRecordType::CAA => {
+            let caa = CaaData::parse(&content.content)?;
+            body["target"] = json!(caa.value);
+            body["tag"] = json!(caa.tag);
+        }
+        _ => {}
+    }
+    Ok(())
+}
+
+/// 拆分 `_sip._tcp` 形式的 SRV 记录名称, Linode 会自动补上下划线
+fn srv_service(name: &str) -> Option<(&str, &str)> {
+    let (service, protocol) = name.strip_prefix('_')?.split_once('.')?;
+    let protocol = protocol.strip_prefix('_')?;
+    Some((service, protocol.split('.').next()?))
+}
+
+/// 转换为通用记录, 未内置的类型以 `Other` 保留
+fn to_dns_record(domain: &str, record: LinodeRecord) -> DNSRecord {
+    let record_type = record
+        .record_type
+        .parse()
+        .unwrap_or_else(|_| DNSRecordType::Other(record.record_type.clone()));
+    let content = match (&record_type, record.tag) {
+        (DNSRecordType::CAA, Some(tag)) => CaaData {
+            flags: 0,
+            tag,
+            value: record.target,
+        }
+        .to_string(),
+        _ => record.target,
+    };
+    let structured = |value: Option<u16>| value.filter(|_| record_type == DNSRecordType::SRV);
+
+    DNSRecord {
+        id: record.id.to_string(),
+        name: full_record_name(domain, &record.name),
+        content,
+        ttl: record.ttl_sec,
+        proxied: None,
+        priority: record.priority.filter(|_| record_type.has_priority()),
+        weight: structured(record.weight),
+        port: structured(record.port),
+        comment: None,
+        record_type,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::providers::test_support::{fixture, mock_provider, PagedResponder};
+    use wiremock::matchers::{body_json, header, method, path};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    mock_provider!(LinodeProvider, "token");
+
+    async fn mount_domain(server: &MockServer) {
+        Mock::given(method("GET"))
+            .and(path("/domains"))
+            .and(header("authorization", "Bearer token"))
+            .and(header("x-filter", r#"{"domain":"example.com"}"#))
+            .respond_with(ResponseTemplate::new(200).set_body_json(fixture!("domains")))
+            .expect(1)
+            .mount(server)
+            .await;
+    }
+
+    async fn mount_records(server: &MockServer) {
+        Mock::given(method("GET"))
+            .and(path("/domains/1234567/records"))
+            .respond_with(PagedResponder::page_number(
+                "page",
+                1,
+                vec![fixture!("records_page1"), fixture!("records_page2")],
+            ))
+            .mount(server)
+            .await;
+    }
+
+    #[test]
+    fn test_parse_error() {
+        let error = parse_error(401, &fixture!("error_unauthorized").to_string());
+        assert!(matches!(
+            error,
+            AppError::Provider(ProviderError::AuthenticationFailed(ref m)) if m.contains("Invalid Token")
+        ));
+        assert!(matches!(
+            parse_error(
+                400,
+                r#"{"errors": [{"reason": "Invalid target", "field": "target"}]}"#
+            ),
+            AppError::Provider(ProviderError::ApiError(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_list_records_reads_all_pages() {
+        let server = MockServer::start().await;
+        mount_domain(&server).await;
+        mount_records(&server).await;
+
+        let provider = mock_provider(&server);
+        let records = provider.list_records("example.com").await.unwrap();
+
+        assert_eq!(records.len(), 5);
+        assert_eq!(records[0].name, "example.com");
+        assert_eq!(records[0].priority, Some(10));
+        assert_eq!(records[1].priority, None);
+        assert_eq!(records[2].record_type, DNSRecordType::SRV);
+        assert_eq!(records[2].rdata(), "10 5 5060 sip.example.com");
+        assert_eq!(records[4].name, "www.example.com");
+        assert_eq!(records[4].record_type, DNSRecordType::AAAA);
+
+        // 域名 ID 已缓存
+        let record = provider
+            .get_record("example.com", "www", DNSRecordType::A)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(record.id, "28036651");
+        assert_eq!(record.content, "203.0.113.10");
+        assert!(provider
+            .get_record("example.com", "api", DNSRecordType::A)
+            .await
+            .unwrap()
+            .is_none());
+    }
+
+    #[tokio::test]
+    async fn test_update_record() {
+        let server = MockServer::start().await;
+        mount_domain(&server).await;
+        Mock::given(method("GET"))
+            .and(path("/domains/1234567/records/28036651"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(fixture!("record")))
+            .mount(&server)
+            .await;
+
+        let mut updated = fixture!("record");
+        updated["target"] = json!("198.51.100.7");
+        Mock::given(method("PUT"))
+            .and(path("/domains/1234567/records/28036651"))
+            .and(body_json(json!({ "target": "198.51.100.7" })))
+            .respond_with(ResponseTemplate::new(200).set_body_json(updated))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let result = mock_provider(&server)
+            .update_record("example.com", "28036651", "198.51.100.7")
+            .await
+            .unwrap();
+        assert_eq!(result.old_ip, "203.0.113.10");
+        assert_eq!(result.record_id, "28036651");
+    }
+
+    #[tokio::test]
+    async fn test_create_mx_record_with_spec_priority() {
+        let server = MockServer::start().await;
+        mount_domain(&server).await;
+
+        let mut created = fixture!("record");
+        created["type"] = json!("MX");
+        created["target"] = json!("mail.example.com");
+        created["priority"] = json!(5);
+        created["ttl_sec"] = json!(3600);
+        Mock::given(method("POST"))
+            .and(path("/domains/1234567/records"))
+            .and(body_json(json!({
+                "type": "MX",
+                "name": "",
+                "target": "mail.example.com",
+                "ttl_sec": 3600,
+                "priority": 5
+            })))
+            .respond_with(ResponseTemplate::new(200).set_body_json(created))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        // 显式指定的优先级覆盖记录值中的优先级
+        let spec = RecordSpec {
+            ttl: Some(3600),
+            priority: Some(5),
+            ..Default::default()
+        };
+        let record = mock_provider(&server)
+            .create_record_with(
+                "example.com",
+                "@",
+                DNSRecordType::MX,
+                "20 mail.example.com",
+                &spec,
+            )
+            .await
+            .unwrap();
+        assert_eq!(record.priority, Some(5));
+        assert_eq!(record.ttl, 3600);
+    }
+
+    #[tokio::test]
+    async fn test_create_and_delete_record() {
+        let server = MockServer::start().await;
+        mount_domain(&server).await;
+
+        let mut created = fixture!("record");
+        created["id"] = json!(28036699);
+        created["type"] = json!("MX");
+        created["name"] = json!("");
+        created["target"] = json!("mail.example.com");
+        created["priority"] = json!(20);
+        Mock::given(method("POST"))
+            .and(path("/domains/1234567/records"))
+            .and(body_json(json!({
+                "type": "MX",
+                "name": "",
+                "target": "mail.example.com",
+                "ttl_sec": DEFAULT_TTL,
+                "priority": 20
+            })))
+            .respond_with(ResponseTemplate::new(200).set_body_json(created))
+            .expect(1)
+            .mount(&server)
+            .await;
+        Mock::given(method("DELETE"))
+            .and(path("/domains/1234567/records/28036699"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({})))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let provider = mock_provider(&server);
+        let record = provider
+            .create_record("example.com", "@", DNSRecordType::MX, "20 mail.example.com")
+            .await
+            .unwrap();
+        assert_eq!(record.id, "28036699");
+        assert_eq!(record.name, "example.com");
+        assert_eq!(record.priority, Some(20));
+
+        provider
+            .delete_record("example.com", "28036699")
+            .await
+            .unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_create_srv_record_uses_service_and_protocol() {
+        let server = MockServer::start().await;
+        mount_domain(&server).await;
+
+        let mut created = fixture!("record");
+        created["id"] = json!(28036700);
+        created["type"] = json!("SRV");
+        created["name"] = json!("_sip._tcp");
+        created["target"] = json!("sip.example.com");
+        created["priority"] = json!(10);
+        created["weight"] = json!(5);
+        created["port"] = json!(5060);
+        Mock::given(method("POST"))
+            .and(path("/domains/1234567/records"))
+            .and(body_json(json!({
+                "type": "SRV",
+                "name": "_sip._tcp",
+                "service": "sip",
+                "protocol": "tcp",
+                "target": "sip.example.com",
+                "priority": 10,
+                "weight": 5,
+                "port": 5060,
+                "ttl_sec": DEFAULT_TTL
+            })))
+            .respond_with(ResponseTemplate::new(200).set_body_json(created))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let provider = mock_provider(&server);
+        let record = provider
+            .create_record(
+                "example.com",
+                "_sip._tcp",
+                DNSRecordType::SRV,
+                "10 5 5060 sip.example.com",
+            )
+            .await
+            .unwrap();
+        assert_eq!(
+            (record.priority, record.weight, record.port),
+            (Some(10), Some(5), Some(5060))
+        );
+
+        // 名称不是 _服务._协议 格式时不发送请求
+        assert!(provider
+            .create_record(
+                "example.com",
+                "sip",
+                DNSRecordType::SRV,
+                "10 5 5060 sip.example.com"
+            )
+            .await
+            .is_err());
+    }
+}
@@ -1,7 +1,31 @@
 pub mod aliyun;
 pub mod aws;
+pub mod azure;
+pub mod baidu;
 pub mod cloudflare;
+pub mod digitalocean;
+pub mod duckdns;
+pub mod dyndns2;
+pub mod dynu;
+pub mod freedns;
+pub mod gandi;
+pub mod gcp;
+pub mod hetzner;
+pub mod huawei;
+pub mod jdcloud;
+pub mod linode;
+pub mod namecheap;
+pub mod namecom;
+pub mod porkbun;
 pub mod provider_trait;
+pub mod registry;
+pub mod rfc2136;
+pub mod rrset;
+pub mod signing;
 pub mod tencent;
+pub mod webhook;
+
+#[cfg(test)]
+pub(crate) mod test_support;
 
 pub use provider_trait::*;
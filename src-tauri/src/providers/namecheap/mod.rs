@@ -0,0 +1,1029 @@
+use crate::error::{AppError, Result};
+use crate::providers::provider_trait::{
+    full_record_name, Credentials, DNSProvider, DNSRecord, DNSRecordType, ProviderCapabilities,
+    ProviderError, RecordContent, RecordSpec, UpdateResult,
+};
+use async_trait::async_trait;
+use reqwest::Client;
+
+const DEFAULT_ENDPOINT: &str = "https://api.namecheap.com/xml.response";
+
+/// 新建记录的默认 TTL (对应控制台中的 Automatic)
+const DEFAULT_TTL: u32 = 1799;
+
+/// 未指定优先级时 MX 记录使用的优先级
+const DEFAULT_MX_PRIORITY: u16 = 10;
+
+/// Namecheap DNS 提供商
+///
+/// XML API 没有单条记录的增删改接口, `setHosts` 会用提交的列表替换域名下的全部主机记录,
+/// 因此每次修改都先读取完整列表, 只改动目标记录后整体写回。
+/// 记录 ID 为 `名称|类型|值`, 例如 `www|A|203.0.113.10` (Namecheap 的 HostId 在每次写入后都会变化,
+/// 同名同类型可以有多条记录, 因此用值区分)
+pub struct NamecheapProvider {
+    api_user: Option<String>,
+    api_key: Option<String>,
+    username: Option<String>,
+    /// 已加入 API 白名单的 IPv4 地址
+    client_ip: Option<String>,
+    endpoint: String,
+    client: Client,
+}
+
+/// 主机记录, `name` 为相对名称 (根域名为 `@`)
+#[derive(Debug, Clone, PartialEq)]
+struct NamecheapHost {
+    name: String,
+    record_type: String,
+    address: String,
+    mx_pref: u16,
+    ttl: u32,
+}
+
+/// 域名下的全部主机记录
+#[derive(Debug, Clone, PartialEq)]
+struct HostList {
+    /// 邮件设置 (MX / MXE / FWD 等), 写回时需要原样提交, 否则邮件配置会被重置
+    email_type: String,
+    hosts: Vec<NamecheapHost>,
+}
+
+impl HostList {
+    /// 解析 `namecheap.domains.dns.getHosts` 响应
+    fn parse(text: &str) -> Result<Self> {
+        let doc = parse_xml(text)?;
+        let result = doc
+            .descendants()
+            .find(|n| n.has_tag_name("DomainDNSGetHostsResult"))
+            .ok_or_else(|| {
+                AppError::Provider(ProviderError::ParseError(
+                    "响应中缺少 DomainDNSGetHostsResult".to_string(),
+                ))
+            })?;
+
+        let hosts = result
+            .children()
+            .filter(|n| n.tag_name().name().eq_ignore_ascii_case("host"))
+            .map(|node| NamecheapHost {
+                name: node.attribute("Name").unwrap_or("@").to_string(),
+                record_type: node.attribute("Type").unwrap_or_default().to_string(),
+                address: node.attribute("Address").unwrap_or_default().to_string(),
+                mx_pref: node
+                    .attribute("MXPref")
+                    .and_then(|v| v.parse().ok())
+                    .unwrap_or(DEFAULT_MX_PRIORITY),
+                ttl: node
+                    .attribute("TTL")
+                    .and_then(|v| v.parse().ok())
+                    .unwrap_or(DEFAULT_TTL),
+            })
+            .collect();
+
+        Ok(Self {
+            email_type: result
+                .attribute("EmailType")
+                .unwrap_or_default()
+                .to_string(),
+            hosts,
+        })
+    }
+
+    fn find(&self, name: &str, record_type: &str) -> Option<&NamecheapHost> {
+        self.hosts.iter().find(|h| h.matches(name, record_type))
+    }
+
+    /// 按记录 ID 查找记录的位置
+    fn position(&self, key: &HostKey) -> Option<usize> {
+        self.hosts.iter().position(|h| h.is(key))
+    }
+
+    /// 替换指定位置记录的值, 未指定的 TTL 和优先级沿用原值, 同名同类型的其他记录不受影响
+    fn replace(&mut self, index: usize, content: &str, spec: &RecordSpec) {
+        let host = &mut self.hosts[index];
+        if is_mx(&host.record_type) {
+            let (priority, target) = split_priority(content);
+            host.address = target.to_string();
+            host.mx_pref = spec.priority.or(priority).unwrap_or(host.mx_pref);
+        } else {
+            host.address = content.to_string();
+        }
+        host.ttl = spec.ttl.unwrap_or(host.ttl);
+    }
+
+    /// 追加一条记录
+    fn add(
+        &mut self,
+        name: &str,
+        record_type: &str,
+        content: &str,
+        spec: &RecordSpec,
+    ) -> NamecheapHost {
+        let (mx_pref, address) = if is_mx(record_type) {
+            let (priority, target) = split_priority(content);
+            (
+                spec.priority.or(priority).unwrap_or(DEFAULT_MX_PRIORITY),
+                target,
+            )
+        } else {
+            (DEFAULT_MX_PRIORITY, content)
+        };
+
+        let host = NamecheapHost {
+            name: name.to_string(),
+            record_type: record_type.to_string(),
+            address: address.to_string(),
+            mx_pref,
+            ttl: spec.ttl.unwrap_or(DEFAULT_TTL),
+        };
+        self.hosts.push(host.clone());
+        host
+    }
+
+    /// 删除记录 ID 对应的一条记录, 没有匹配的记录时返回 false
+    fn remove(&mut self, key: &HostKey) -> bool {
+        match self.position(key) {
+            Some(index) => {
+                self.hosts.remove(index);
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// 生成 `namecheap.domains.dns.setHosts` 的请求参数 (序号从 1 开始)
+    fn to_params(&self) -> Vec<(String, String)> {
+        let mut params = Vec::new();
+
+        // 存在 MX 记录时邮件设置必须为 MX, 否则这些记录不会生效
+        let email_type = if self.hosts.iter().any(|h| h.record_type == "MX") {
+            "MX"
+        } else {
+            self.email_type.as_str()
+        };
+        if !email_type.is_empty() {
+            params.push(("EmailType".to_string(), email_type.to_string()));
+        }
+
+        for (i, host) in self.hosts.iter().enumerate() {
+            let n = i + 1;
+            params.push((format!("HostName{}", n), host.name.clone()));
+            params.push((format!("RecordType{}", n), host.record_type.clone()));
+            params.push((format!("Address{}", n), host.address.clone()));
+            if is_mx(&host.record_type) {
+                params.push((format!("MXPref{}", n), host.mx_pref.to_string()));
+            }
+            params.push((format!("TTL{}", n), host.ttl.to_string()));
+        }
+
+        params
+    }
+}
+
+impl NamecheapHost {
+    fn matches(&self, name: &str, record_type: &str) -> bool {
+        self.name.eq_ignore_ascii_case(name) && self.record_type.eq_ignore_ascii_case(record_type)
+    }
+
+    fn is(&self, key: &HostKey) -> bool {
+        self.matches(key.name, key.record_type) && self.address == key.address
+    }
+}
+
+/// 记录 ID 的组成部分 (MX 记录的值为目标主机, 不含优先级)
+struct HostKey<'a> {
+    name: &'a str,
+    record_type: &'a str,
+    address: &'a str,
+}
+
+impl NamecheapProvider {
+    pub fn new() -> Self {
+        Self::with_endpoint(DEFAULT_ENDPOINT)
+    }
+
+    /// 使用自定义 API 地址创建提供商 (例如测试服务器或 sandbox 环境)
+    pub fn with_endpoint(endpoint: impl Into<String>) -> Self {
+        Self {
+            api_user: None,
+            api_key: None,
+            username: None,
+            client_ip: None,
+            endpoint: endpoint.into().trim_end_matches('/').to_string(),
+            client: Client::builder()
+                .timeout(std::time::Duration::from_secs(30))
+                .build()
+                .unwrap(),
+        }
+    }
+
+    /// 调用 API 命令, 返回 XML 响应文本
+    ///
+    /// 使用 POST 表单提交, 避免主机记录较多时超出 GET 请求的长度限制
+    async fn call(&self, command: &str, params: Vec<(String, String)>) -> Result<String> {
+        let (api_user, api_key, client_ip) = match (&self.api_user, &self.api_key, &self.client_ip)
+        {
+            (Some(user), Some(key), Some(ip)) => (user, key, ip),
+            _ => {
+                return Err(AppError::Provider(ProviderError::AuthenticationFailed(
+                    "未设置 API 用户名、API Key 或白名单 IP".to_string(),
+                )))
+            }
+        };
+        let username = self.username.as_ref().unwrap_or(api_user);
+
+        let mut form = vec![
+            ("ApiUser".to_string(), api_user.clone()),
+            ("ApiKey".to_string(), api_key.clone()),
+            ("UserName".to_string(), username.clone()),
+            ("ClientIp".to_string(), client_ip.clone()),
+            ("Command".to_string(), command.to_string()),
+        ];
+        form.extend(params);
+
+        let response = self
+            .client
+            .post(&self.endpoint)
+            .form(&form)
+            .send()
+            .await
+            .map_err(|e| AppError::Provider(ProviderError::NetworkError(e.to_string())))?;
+        let status = response.status();
+        let text = response
+            .text()
+            .await
+            .map_err(|e| AppError::Provider(ProviderError::NetworkError(e.to_string())))?;
+
+        if !status.is_success() {
+            return Err(AppError::Provider(ProviderError::ApiError(format!(
+                "HTTP {}: {}",
+                status.as_u16(),
+                text
+            ))));
+        }
+
+        // 业务错误同样以 HTTP 200 返回, 需要检查 Status 属性
+        let doc = parse_xml(&text)?;
+        if doc.root_element().attribute("Status") != Some("OK") {
+            return Err(parse_error(&text));
+        }
+
+        Ok(text)
+    }
+
+    async fn get_hosts(&self, domain: &str) -> Result<HostList> {
+        let text = self
+            .call("namecheap.domains.dns.getHosts", domain_params(domain)?)
+            .await?;
+        HostList::parse(&text)
+    }
+
+    async fn set_hosts(&self, domain: &str, hosts: &HostList) -> Result<()> {
+        let mut params = domain_params(domain)?;
+        params.extend(hosts.to_params());
+
+        let text = self.call("namecheap.domains.dns.setHosts", params).await?;
+        let doc = parse_xml(&text)?;
+        let success = doc
+            .descendants()
+            .find(|n| n.has_tag_name("DomainDNSSetHostsResult"))
+            .and_then(|n| n.attribute("IsSuccess"))
+            .map(|v| v.eq_ignore_ascii_case("true"))
+            .unwrap_or(false);
+
+        if !success {
+            return Err(AppError::Provider(ProviderError::ApiError(format!(
+                "写入 {} 的主机记录失败",
+                domain
+            ))));
+        }
+        Ok(())
+    }
+}
+
+impl Default for NamecheapProvider {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[async_trait]
+impl DNSProvider for NamecheapProvider {
+    fn provider_id(&self) -> &'static str {
+        "namecheap"
+    }
+
+    fn provider_name(&self) -> &'static str {
+        "Namecheap"
+    }
+
+    async fn initialize(&mut self, credentials: &Credentials) -> Result<()> {
+        let extra = |key: &str| {
+            credentials
+                .extra
+                .get(key)
+                .and_then(|v| v.as_str())
+                .map(|v| v.trim().to_string())
+                .filter(|v| !v.is_empty())
+        };
+        self.api_user = extra("api_user");
+        self.api_key = credentials.api_key.clone().or_else(|| extra("api_key"));
+        self.username = extra("username");
+        self.client_ip = extra("client_ip");
+
+        if self.api_user.is_none() || self.api_key.is_none() {
+            return Err(AppError::Provider(ProviderError::InvalidConfig(
+                "Namecheap 需要提供 api_user 和 api_key".to_string(),
+            )));
+        }
+        if self.client_ip.is_none() {
+            return Err(AppError::Provider(ProviderError::InvalidConfig(
+                "Namecheap 需要提供已加入 API 白名单的 client_ip".to_string(),
+            )));
+        }
+
+        // 测试连接
+        self.test_connection().await?;
+
+        Ok(())
+    }
+
+    async fn list_records(&self, domain: &str) -> Result<Vec<DNSRecord>> {
+        let hosts = self.get_hosts(domain).await?;
+        Ok(hosts
+            .hosts
+            .iter()
+            .map(|h| to_dns_record(domain, h))
+            .collect())
+    }
+
+    async fn get_record(
+        &self,
+        domain: &str,
+        record_name: &str,
+        record_type: DNSRecordType,
+    ) -> Result<Option<DNSRecord>> {
+        let hosts = self.get_hosts(domain).await?;
+        Ok(hosts
+            .find(host_name(record_name), &record_type.to_string())
+            .map(|h| to_dns_record(domain, h)))
+    }
+
+    async fn update_record(
+        &self,
+        domain: &str,
+        record_id: &str,
+        new_content: &str,
+    ) -> Result<UpdateResult> {
+        self.update_record_with(domain, record_id, new_content, &RecordSpec::default())
+            .await
+    }
+
+    async fn update_record_with(
+        &self,
+        domain: &str,
+        record_id: &str,
+        new_content: &str,
+        spec: &RecordSpec,
+    ) -> Result<UpdateResult> {
+        spec.ensure_supported(&self.capabilities())?;
+        let key = parse_record_id(record_id)?;
+        let parsed_type = parse_record_type(key.record_type);
+        let content = RecordContent::from_input(&parsed_type, new_content, spec)?;
+
+        let mut hosts = self.get_hosts(domain).await?;
+        let index = hosts.position(&key).ok_or_else(|| {
+            AppError::Provider(ProviderError::RecordNotFound(record_id.to_string()))
+        })?;
+        let old_ip = to_dns_record(domain, &hosts.hosts[index]).rdata();
+        hosts.replace(index, &content.to_rdata(&parsed_type), spec);
+        self.set_hosts(domain, &hosts).await?;
+
+        Ok(UpdateResult {
+            success: true,
+            // 值变化后记录 ID 随之变化
+            record_id: self::record_id(&hosts.hosts[index]),
+            old_ip,
+            new_ip: new_content.to_string(),
+            message: "更新成功".to_string(),
+        })
+    }
+
+    async fn create_record(
+        &self,
+        domain: &str,
+        record_name: &str,
+        record_type: DNSRecordType,
+        content: &str,
+    ) -> Result<DNSRecord> {
+        self.create_record_with(
+            domain,
+            record_name,
+            record_type,
+            content,
+            &RecordSpec::default(),
+        )
+        .await
+    }
+
+    async fn create_record_with(
+        &self,
+        domain: &str,
+        record_name: &str,
+        record_type: DNSRecordType,
+        content: &str,
+        spec: &RecordSpec,
+    ) -> Result<DNSRecord> {
+        spec.ensure_supported(&self.capabilities())?;
+        let content = RecordContent::from_input(&record_type, content, spec)?;
+        let mut hosts = self.get_hosts(domain).await?;
+        let host = hosts.add(
+            host_name(record_name),
+            &record_type.to_string(),
+            &content.to_rdata(&record_type),
+            spec,
+        );
+        self.set_hosts(domain, &hosts).await?;
+
+        Ok(to_dns_record(domain, &host))
+    }
+
+    async fn delete_record(&self, domain: &str, record_id: &str) -> Result<()> {
+        let key = parse_record_id(record_id)?;
+
+        let mut hosts = self.get_hosts(domain).await?;
+        if !hosts.remove(&key) {
+            return Err(AppError::Provider(ProviderError::RecordNotFound(
+                record_id.to_string(),
+            )));
+        }
+        self.set_hosts(domain, &hosts).await
+    }
+
+    async fn test_connection(&self) -> Result<bool> {
+        // 测试获取域名列表
+        self.call(
+            "namecheap.domains.getList",
+            vec![("PageSize".to_string(), "10".to_string())],
+        )
+        .await?;
+        Ok(true)
+    }
+
+    fn supported_record_types(&self) -> Vec<DNSRecordType> {
+        vec![
+            DNSRecordType::A,
+            DNSRecordType::AAAA,
+            DNSRecordType::CNAME,
+            DNSRecordType::MX,
+            DNSRecordType::TXT,
+            DNSRecordType::NS,
+            DNSRecordType::CAA,
+        ]
+    }
+
+    fn capabilities(&self) -> ProviderCapabilities {
+        ProviderCapabilities {
+            proxy: false,
+            ttl: true,
+            line: false,
+            priority: true,
+            comment: false,
+        }
+    }
+}
+
+fn parse_xml(text: &str) -> Result<roxmltree::Document<'_>> {
+    roxmltree::Document::parse(text)
+        .map_err(|e| AppError::Provider(ProviderError::ParseError(format!("解析 XML 失败: {}", e))))
+}
+
+/// 将 Namecheap 错误响应 (`<Errors><Error Number="...">`) 映射为 ProviderError
+fn parse_error(text: &str) -> AppError {
+    let (number, message) = match roxmltree::Document::parse(text) {
+        Ok(doc) => doc
+            .descendants()
+            .find(|n| n.has_tag_name("Error"))
+            .map(|n| {
+                (
+                    n.attribute("Number").unwrap_or_default().to_string(),
+                    n.text().unwrap_or_default().trim().to_string(),
+                )
+            })
+            .unwrap_or_default(),
+        Err(_) => (String::new(), text.to_string()),
+    };
+
+    let detail = format!("{}: {}", number, message);
+    let error = match number.as_str() {
+        // API Key / 白名单 IP / 用户名无效或被禁用
+        "1011102" | "1011150" | "1017150" | "1017105" | "1017101" | "1016103" | "1017103"
+        | "1030408" => ProviderError::AuthenticationFailed(detail),
+        // 缺少或无效的参数
+        "1010101" | "1010102" | "1010105" | "1011105" | "2030288" => {
+            ProviderError::InvalidConfig(detail)
+        }
+        "2019166" | "2016166" => ProviderError::DomainNotFound(detail),
+        "500000" => ProviderError::RateLimitExceeded,
+        _ if message.to_lowercase().contains("too many requests") => {
+            ProviderError::RateLimitExceeded
+        }
+        _ => ProviderError::ApiError(detail),
+    };
+
+    AppError::Provider(error)
+}
+
+/// 拆分为 SLD 和 TLD 参数, 例如 `example.co.uk` -> `example` + `co.uk`
+fn domain_params(domain: &str) -> Result<Vec<(String, String)>> {
+    let (sld, tld) = domain
+        .trim_end_matches('.')
+        .split_once('.')
+        .ok_or_else(|| {
+            AppError::Provider(ProviderError::InvalidConfig(format!(
+                "无效的域名: {}",
+                domain
+            )))
+        })?;
+
+    Ok(vec![
+        ("SLD".to_string(), sld.to_string()),
+        ("TLD".to_string(), tld.to_string()),
+    ])
+}
+
+/// 根域名使用 `@`
+fn host_name(record_name: &str) -> &str {
+    match record_name {
+        "" => "@",
+        name => name,
+    }
+}
+
+fn record_id(host: &NamecheapHost) -> String {
+    format!("{}|{}|{}", host.name, host.record_type, host.address)
+}
+
+/// 值 (例如 URL 转发地址) 中可能包含 `|`, 只拆分前两段
+fn parse_record_id(record_id: &str) -> Result<HostKey<'_>> {
+    let mut parts = record_id.splitn(3, '|');
+    match (parts.next(), parts.next(), parts.next()) {
+        (Some(name), Some(record_type), Some(address)) => Ok(HostKey {
+            name,
+            record_type,
+            address,
+        }),
+        _ => Err(AppError::Provider(ProviderError::InvalidConfig(format!(
+            "无效的 Namecheap 记录 ID: {}",
+            record_id
+        )))),
+    }
+}
+
+fn is_mx(record_type: &str) -> bool {
+    matches!(record_type, "MX" | "MXE")
+}
+
+/// 拆分 `10 mail.example.com` 形式的 MX 内容
+fn split_priority(content: &str) -> (Option<u16>, &str) {
+    match content.trim().split_once(char::is_whitespace) {
+        Some((priority, target)) => match priority.parse() {
+            Ok(priority) => (Some(priority), target.trim()),
+            Err(_) => (None, content.trim()),
+        },
+        None => (None, content.trim()),
+    }
+}
+
+/// URL 转发、MXE 等未内置的类型以 `Other` 保留
+fn parse_record_type(record_type: &str) -> DNSRecordType {
+    record_type
+        .parse()
+        .unwrap_or_else(|_| DNSRecordType::Other(record_type.to_string()))
+}
+
+/// 转换为通用记录
+fn to_dns_record(domain: &str, host: &NamecheapHost) -> DNSRecord {
+    let record_type = parse_record_type(&host.record_type);
+    let priority = match record_type {
+        DNSRecordType::MX => Some(host.mx_pref),
+        _ => None,
+    };
+
+    DNSRecord {
+        id: record_id(host),
+        name: full_record_name(domain, &host.name),
+        record_type,
+        content: host.address.clone(),
+        ttl: host.ttl,
+        proxied: None,
+        priority,
+        weight: None,
+        port: None,
+        comment: None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+    use wiremock::matchers::{body_string_contains, method, path};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    const GET_HOSTS: &str = include_str!("fixtures/get_hosts.xml");
+    const GET_HOSTS_TXT: &str = include_str!("fixtures/get_hosts_txt.xml");
+    const SET_HOSTS: &str = include_str!("fixtures/set_hosts.xml");
+    const ERROR_API_KEY: &str = include_str!("fixtures/error_api_key.xml");
+    const ERROR_DOMAIN_NOT_FOUND: &str = include_str!("fixtures/error_domain_not_found.xml");
+
+    fn mock_provider(server: &MockServer) -> NamecheapProvider {
+        let mut provider =
+            NamecheapProvider::with_endpoint(format!("{}/xml.response", server.uri()));
+        provider.api_user = Some("apiuser".to_string());
+        provider.api_key = Some("apikey".to_string());
+        provider.client_ip = Some("192.0.2.1".to_string());
+        provider
+    }
+
+    async fn mount_command(server: &MockServer, command: &str, body: &str) {
+        Mock::given(method("POST"))
+            .and(path("/xml.response"))
+            .and(body_string_contains(format!("Command={}", command)))
+            .respond_with(ResponseTemplate::new(200).set_body_string(body))
+            .mount(server)
+            .await;
+    }
+
+    /// 解析表单请求体
+    fn form(body: &[u8]) -> HashMap<String, String> {
+        let decode = |s: &str| {
+            urlencoding::decode(&s.replace('+', " "))
+                .unwrap()
+                .into_owned()
+        };
+        String::from_utf8_lossy(body)
+            .split('&')
+            .filter_map(|pair| pair.split_once('='))
+            .map(|(k, v)| (decode(k), decode(v)))
+            .collect()
+    }
+
+    /// 从 setHosts 参数中还原主机列表
+    fn hosts_from_form(form: &HashMap<String, String>) -> Vec<NamecheapHost> {
+        (1..)
+            .map_while(|n| {
+                let name = form.get(&format!("HostName{}", n))?;
+                Some(NamecheapHost {
+                    name: name.clone(),
+                    record_type: form[&format!("RecordType{}", n)].clone(),
+                    address: form[&format!("Address{}", n)].clone(),
+                    mx_pref: form
+                        .get(&format!("MXPref{}", n))
+                        .map(|v| v.parse().unwrap())
+                        .unwrap_or(DEFAULT_MX_PRIORITY),
+                    ttl: form[&format!("TTL{}", n)].parse().unwrap(),
+                })
+            })
+            .collect()
+    }
+
+    /// 服务器收到的 setHosts 请求参数
+    async fn set_hosts_requests(server: &MockServer) -> Vec<HashMap<String, String>> {
+        server
+            .received_requests()
+            .await
+            .unwrap()
+            .iter()
+            .map(|r| form(&r.body))
+            .filter(|f| {
+                f.get("Command").map(String::as_str) == Some("namecheap.domains.dns.setHosts")
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_parse_hosts() {
+        let list = HostList::parse(GET_HOSTS).unwrap();
+        assert_eq!(list.email_type, "MX");
+        assert_eq!(list.hosts.len(), 8);
+        assert_eq!(list.hosts[1].name, "www");
+        assert_eq!(list.hosts[1].ttl, 300);
+        assert_eq!(list.hosts[3].mx_pref, 20);
+        // 属性中的实体被还原
+        assert_eq!(
+            list.hosts[6].address,
+            "https://store.example.org/?ref=a&b=c"
+        );
+        assert_eq!(list.hosts[7].address, "0 issue \"letsencrypt.org\"");
+    }
+
+    fn key<'a>(name: &'a str, record_type: &'a str, address: &'a str) -> HostKey<'a> {
+        HostKey {
+            name,
+            record_type,
+            address,
+        }
+    }
+
+    #[test]
+    fn test_replace_keeps_unrelated_hosts() {
+        let original = HostList::parse(GET_HOSTS).unwrap();
+
+        // 同名不同类型的记录 (@ MX / TXT / CAA) 不受影响
+        let mut list = original.clone();
+        let index = list.position(&key("@", "A", "203.0.113.1")).unwrap();
+        list.replace(index, "198.51.100.7", &RecordSpec::default());
+        assert_eq!(list.hosts.len(), original.hosts.len());
+        assert_eq!(list.hosts[0].address, "198.51.100.7");
+        assert_eq!(list.hosts[0].ttl, 1799);
+        assert_eq!(list.hosts[1..], original.hosts[1..]);
+
+        // 名称和类型不区分大小写
+        assert_eq!(original.position(&key("WWW", "a", "203.0.113.10")), Some(1));
+
+        // 只替换记录集中的一个值, 其余值保持不变
+        let mut list = original.clone();
+        let index = list
+            .position(&key("@", "MX", "mx1.mail.example.net."))
+            .unwrap();
+        list.replace(index, "5 mx.example.org.", &RecordSpec::default());
+        assert_eq!(list.hosts.len(), original.hosts.len());
+        assert_eq!(list.hosts[2].address, "mx.example.org.");
+        assert_eq!(list.hosts[2].mx_pref, 5);
+        assert_eq!(list.hosts[3].address, "mx2.mail.example.net.");
+        assert_eq!(list.hosts[3..], original.hosts[3..]);
+
+        assert!(list.position(&key("ftp", "A", "198.51.100.9")).is_none());
+        assert!(list
+            .position(&key("@", "MX", "mx1.mail.example.net."))
+            .is_none());
+    }
+
+    #[test]
+    fn test_replace_and_add_apply_spec() {
+        let spec = RecordSpec {
+            ttl: Some(60),
+            priority: Some(30),
+            ..Default::default()
+        };
+
+        let mut list = HostList::parse(GET_HOSTS).unwrap();
+        list.replace(2, "mx.example.org.", &spec);
+        assert_eq!(list.hosts[2].mx_pref, 30);
+        assert_eq!(list.hosts[2].ttl, 60);
+
+        let host = list.add("@", "MX", "5 mx2.example.org.", &spec);
+        assert_eq!(host.mx_pref, 30);
+        assert_eq!(host.ttl, 60);
+    }
+
+    #[test]
+    fn test_add_and_remove_keep_unrelated_hosts() {
+        let original = HostList::parse(GET_HOSTS).unwrap();
+
+        let mut list = original.clone();
+        list.add("home", "AAAA", "2001:db8::1", &RecordSpec::default());
+        assert_eq!(list.hosts[..8], original.hosts[..]);
+        assert_eq!(list.hosts[8].name, "home");
+        assert_eq!(list.hosts[8].ttl, DEFAULT_TTL);
+
+        // 只删除记录集中的一个值
+        let mut list = original.clone();
+        assert!(list.remove(&key("@", "MX", "mx2.mail.example.net.")));
+        assert_eq!(list.hosts.len(), 7);
+        assert_eq!(list.hosts[2], original.hosts[2]);
+        assert_eq!(list.hosts[3..], original.hosts[4..]);
+        assert!(!list.remove(&key("@", "MX", "mx2.mail.example.net.")));
+    }
+
+    #[test]
+    fn test_parse_record_id() {
+        let key = parse_record_id("shop|URL301|https://store.example.org/?a=1|2").unwrap();
+        assert_eq!(key.name, "shop");
+        assert_eq!(key.record_type, "URL301");
+        assert_eq!(key.address, "https://store.example.org/?a=1|2");
+        assert!(parse_record_id("www|A").is_err());
+    }
+
+    #[test]
+    fn test_to_params_round_trip() {
+        let list = HostList::parse(GET_HOSTS).unwrap();
+        let params: HashMap<String, String> = list.to_params().into_iter().collect();
+
+        assert_eq!(params["EmailType"], "MX");
+        assert_eq!(params["RecordType7"], "URL301");
+        assert_eq!(params["MXPref4"], "20");
+        assert!(!params.contains_key("MXPref1"));
+        assert_eq!(hosts_from_form(&params), list.hosts);
+
+        // 新增 MX 记录时邮件设置切换为 MX
+        let mut list = HostList {
+            email_type: "FWD".to_string(),
+            hosts: Vec::new(),
+        };
+        list.add("@", "MX", "mail.example.com.", &RecordSpec::default());
+        let params: HashMap<String, String> = list.to_params().into_iter().collect();
+        assert_eq!(params["EmailType"], "MX");
+        assert_eq!(params["MXPref1"], "10");
+    }
+
+    #[test]
+    fn test_parse_error() {
+        assert!(matches!(
+            parse_error(ERROR_API_KEY),
+            AppError::Provider(ProviderError::AuthenticationFailed(ref m)) if m.contains("1011102")
+        ));
+        assert!(matches!(
+            parse_error(ERROR_DOMAIN_NOT_FOUND),
+            AppError::Provider(ProviderError::DomainNotFound(_))
+        ));
+    }
+
+    #[test]
+    fn test_domain_params() {
+        assert_eq!(
+            domain_params("example.co.uk").unwrap(),
+            vec![
+                ("SLD".to_string(), "example".to_string()),
+                ("TLD".to_string(), "co.uk".to_string())
+            ]
+        );
+        assert!(domain_params("localhost").is_err());
+    }
+
+    #[tokio::test]
+    async fn test_list_and_get_records() {
+        let server = MockServer::start().await;
+        mount_command(&server, "namecheap.domains.dns.getHosts", GET_HOSTS).await;
+
+        let provider = mock_provider(&server);
+        let records = provider.list_records("example.com").await.unwrap();
+        assert_eq!(records.len(), 8);
+        assert_eq!(records[2].id, "@|MX|mx1.mail.example.net.");
+        assert_eq!(records[3].id, "@|MX|mx2.mail.example.net.");
+        assert_eq!(records[2].priority, Some(10));
+        assert_eq!(records[2].rdata(), "10 mx1.mail.example.net.");
+        assert_eq!(
+            records[6].record_type,
+            DNSRecordType::Other("URL301".to_string())
+        );
+        assert_eq!(records[7].record_type, DNSRecordType::CAA);
+        assert_eq!(records[7].content, "0 issue \"letsencrypt.org\"");
+
+        let record = provider
+            .get_record("example.com", "www", DNSRecordType::A)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(record.id, "www|A|203.0.113.10");
+        assert_eq!(record.name, "www.example.com");
+        assert_eq!(record.content, "203.0.113.10");
+
+        let requests = server.received_requests().await.unwrap();
+        let params = form(&requests[0].body);
+        assert_eq!(params["ApiUser"], "apiuser");
+        assert_eq!(params["UserName"], "apiuser");
+        assert_eq!(params["ClientIp"], "192.0.2.1");
+        assert_eq!(params["SLD"], "example");
+        assert_eq!(params["TLD"], "com");
+    }
+
+    #[tokio::test]
+    async fn test_update_record_writes_back_all_hosts() {
+        let server = MockServer::start().await;
+        mount_command(&server, "namecheap.domains.dns.getHosts", GET_HOSTS).await;
+        mount_command(&server, "namecheap.domains.dns.setHosts", SET_HOSTS).await;
+
+        let result = mock_provider(&server)
+            .update_record("example.com", "www|A|203.0.113.10", "198.51.100.7")
+            .await
+            .unwrap();
+        assert_eq!(result.old_ip, "203.0.113.10");
+        assert_eq!(result.record_id, "www|A|198.51.100.7");
+
+        let requests = set_hosts_requests(&server).await;
+        assert_eq!(requests.len(), 1);
+        let sent = &requests[0];
+        assert_eq!(sent["EmailType"], "MX");
+
+        let mut expected = HostList::parse(GET_HOSTS).unwrap().hosts;
+        expected[1].address = "198.51.100.7".to_string();
+        assert_eq!(hosts_from_form(sent), expected);
+    }
+
+    #[tokio::test]
+    async fn test_create_and_delete_record() {
+        let server = MockServer::start().await;
+        mount_command(&server, "namecheap.domains.dns.getHosts", GET_HOSTS).await;
+        mount_command(&server, "namecheap.domains.dns.setHosts", SET_HOSTS).await;
+
+        let provider = mock_provider(&server);
+        let record = provider
+            .create_record("example.com", "home", DNSRecordType::AAAA, "2001:db8::1")
+            .await
+            .unwrap();
+        assert_eq!(record.id, "home|AAAA|2001:db8::1");
+        assert_eq!(record.name, "home.example.com");
+
+        provider
+            .delete_record("example.com", "blog|CNAME|hosting.example.org.")
+            .await
+            .unwrap();
+        assert!(matches!(
+            provider
+                .delete_record("example.com", "ftp|A|198.51.100.9")
+                .await,
+            Err(AppError::Provider(ProviderError::RecordNotFound(_)))
+        ));
+
+        let original = HostList::parse(GET_HOSTS).unwrap().hosts;
+        let requests = set_hosts_requests(&server).await;
+        assert_eq!(requests.len(), 2);
+
+        let created = hosts_from_form(&requests[0]);
+        assert_eq!(created[..8], original[..]);
+        assert_eq!(created[8].address, "2001:db8::1");
+
+        let deleted = hosts_from_form(&requests[1]);
+        let mut expected = original.clone();
+        expected.remove(5);
+        assert_eq!(deleted, expected);
+    }
+
+    #[tokio::test]
+    async fn test_edit_one_of_several_txt_hosts() {
+        let server = MockServer::start().await;
+        mount_command(&server, "namecheap.domains.dns.getHosts", GET_HOSTS_TXT).await;
+        mount_command(&server, "namecheap.domains.dns.setHosts", SET_HOSTS).await;
+
+        let provider = mock_provider(&server);
+        let records = provider.list_records("example.com").await.unwrap();
+        assert_ne!(records[0].id, records[1].id);
+
+        let result = provider
+            .update_record(
+                "example.com",
+                "@|TXT|google-site-verification=abc123",
+                "google-site-verification=xyz789",
+            )
+            .await
+            .unwrap();
+        assert_eq!(result.old_ip, "google-site-verification=abc123");
+        provider
+            .delete_record("example.com", "@|TXT|v=spf1 include:spf.example.net ~all")
+            .await
+            .unwrap();
+
+        let requests = set_hosts_requests(&server).await;
+        assert_eq!(requests.len(), 2);
+        let original = HostList::parse(GET_HOSTS_TXT).unwrap().hosts;
+
+        // 另一条 @ TXT 记录保留
+        let updated = hosts_from_form(&requests[0]);
+        assert_eq!(updated.len(), 3);
+        assert_eq!(updated[0], original[0]);
+        assert_eq!(updated[1].address, "google-site-verification=xyz789");
+        assert_eq!(updated[2], original[2]);
+
+        let deleted = hosts_from_form(&requests[1]);
+        assert_eq!(deleted, original[1..]);
+    }
+
+    #[tokio::test]
+    async fn test_update_caa_record_normalizes_value() {
+        let server = MockServer::start().await;
+        mount_command(&server, "namecheap.domains.dns.getHosts", GET_HOSTS).await;
+        mount_command(&server, "namecheap.domains.dns.setHosts", SET_HOSTS).await;
+
+        let result = mock_provider(&server)
+            .update_record(
+                "example.com",
+                "@|CAA|0 issue \"letsencrypt.org\"",
+                "0 issue sectigo.com",
+            )
+            .await
+            .unwrap();
+        assert_eq!(result.old_ip, "0 issue \"letsencrypt.org\"");
+
+        let requests = set_hosts_requests(&server).await;
+        let sent = hosts_from_form(&requests[0]);
+        assert_eq!(sent[7].address, "0 issue \"sectigo.com\"");
+    }
+
+    #[tokio::test]
+    async fn test_error_response() {
+        let server = MockServer::start().await;
+        mount_command(&server, "namecheap.domains.dns.getHosts", ERROR_API_KEY).await;
+        mount_command(&server, "namecheap.domains.dns.setHosts", SET_HOSTS).await;
+
+        let result = mock_provider(&server)
+            .update_record("example.com", "www|A|203.0.113.10", "198.51.100.7")
+            .await;
+        assert!(matches!(
+            result,
+            Err(AppError::Provider(ProviderError::AuthenticationFailed(_)))
+        ));
+        // 读取失败时不会写回
+        assert!(set_hosts_requests(&server).await.is_empty());
+    }
+}
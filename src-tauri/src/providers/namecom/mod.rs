@@ -0,0 +1,682 @@
+use crate::error::{AppError, Result};
+use crate::providers::provider_trait::{
+    full_record_name, Credentials, DNSProvider, DNSRecord, DNSRecordType, ProviderCapabilities,
+    ProviderError, RecordContent, RecordSpec, UpdateResult,
+};
+use async_trait::async_trait;
+use reqwest::{Client, Method};
+use serde::de::DeserializeOwned;
+use serde::Deserialize;
+use serde_json::json;
+
+/// Name.com Core API
+const DEFAULT_ENDPOINT: &str = "https://api.name.com/core/v1";
+
+/// 记录列表每页数量 (API 允许的最大值)
+const PAGE_SIZE: usize = 1000;
+
+/// 新建记录的默认 TTL (Name.com 允许的最小值)
+const DEFAULT_TTL: u32 = 300;
+
+/// 未指定优先级时 MX 记录使用的优先级
+const DEFAULT_MX_PRIORITY: u16 = 10;
+
+/// Name.com DNS 提供商
+///
+/// 使用用户名 + API Token 进行 HTTP Basic 认证
+pub struct NameComProvider {
+    username: Option<String>,
+    api_token: Option<String>,
+    endpoint: String,
+    client: Client,
+}
+
+/// 记录, `host` 为相对名称 (根域名为空)
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct NameComRecord {
+    id: u64,
+    #[serde(default)]
+    host: String,
+    #[serde(rename = "type")]
+    record_type: String,
+    answer: String,
+    #[serde(default)]
+    ttl: Option<u32>,
+    #[serde(default)]
+    priority: Option<u16>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct NameComRecordList {
+    #[serde(default)]
+    records: Vec<NameComRecord>,
+    #[serde(default)]
+    next_page: Option<usize>,
+}
+
+impl NameComProvider {
+    pub fn new() -> Self {
+        Self::with_endpoint(DEFAULT_ENDPOINT)
+    }
+
+    /// 使用自定义 API 地址创建提供商 (例如测试服务器)
+    pub fn with_endpoint(endpoint: impl Into<String>) -> Self {
+        Self {
+            username: None,
+            api_token: None,
+            endpoint: endpoint.into().trim_end_matches('/').to_string(),
+            client: Client::builder()
+                .timeout(std::time::Duration::from_secs(30))
+                .build()
+                .unwrap(),
+        }
+    }
+
+    /// 发送 API 请求并解析 JSON 响应
+    async fn send<T: DeserializeOwned>(
+        &self,
+        method: Method,
+        path: &str,
+        query: &[(&str, String)],
+        body: Option<serde_json::Value>,
+    ) -> Result<T> {
+        let (username, token) = match (&self.username, &self.api_token) {
+            (Some(username), Some(token)) => (username, token),
+            _ => {
+                return Err(AppError::Provider(ProviderError::AuthenticationFailed(
+                    "未设置用户名或 API Token".to_string(),
+                )))
+            }
+        };
+
+        let mut request = self
+            .client
+            .request(method, format!("{}{}", self.endpoint, path))
+            .basic_auth(username, Some(token))
+            .query(query);
+        if let Some(body) = body {
+            request = request.json(&body);
+        }
+
+        let response = request
+            .send()
+            .await
+            .map_err(|e| AppError::Provider(ProviderError::NetworkError(e.to_string())))?;
+        let status = response.status();
+        let text = response
+            .text()
+            .await
+            .map_err(|e| AppError::Provider(ProviderError::NetworkError(e.to_string())))?;
+
+        if !status.is_success() {
+            return Err(parse_error(status.as_u16(), &text));
+        }
+
+        let text = if text.trim().is_empty() {
+            "null"
+        } else {
+            &text
+        };
+        serde_json::from_str(text).map_err(|e| {
+            AppError::Provider(ProviderError::ParseError(format!("解析响应失败: {}", e)))
+        })
+    }
+
+    /// 逐页读取域名下的全部记录
+    async fn list_all(&self, domain: &str) -> Result<Vec<NameComRecord>> {
+        let path = format!("/domains/{}/records", domain);
+        let mut records = Vec::new();
+        let mut page = 1;
+
+        loop {
+            let list: NameComRecordList = self
+                .send(
+                    Method::GET,
+                    &path,
+                    &[
+                        ("page", page.to_string()),
+                        ("perPage", PAGE_SIZE.to_string()),
+                    ],
+                    None,
+                )
+                .await?;
+            records.extend(list.records);
+
+            match list.next_page {
+                Some(next) if next > page => page = next,
+                _ => break,
+            }
+        }
+
+        Ok(records)
+    }
+}
+
+impl Default for NameComProvider {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[async_trait]
+impl DNSProvider for NameComProvider {
+    fn provider_id(&self) -> &'static str {
+        "namecom"
+    }
+
+    fn provider_name(&self) -> &'static str {
+        "Name.com"
+    }
+
+    async fn initialize(&mut self, credentials: &Credentials) -> Result<()> {
+        let extra = |key: &str| {
+            credentials
+                .extra
+                .get(key)
+                .and_then(|v| v.as_str())
+                .map(|v| v.to_string())
+        };
+        self.username = extra("username").or_else(|| credentials.access_key.clone());
+        self.api_token = credentials.api_key.clone().or_else(|| extra("api_token"));
+
+        if self.username.is_none() || self.api_token.is_none() {
+            return Err(AppError::Provider(ProviderError::InvalidConfig(
+                "Name.com 需要提供 username 和 api_token".to_string(),
+            )));
+        }
+
+        // 测试连接
+        self.test_connection().await?;
+
+        Ok(())
+    }
+
+    async fn list_records(&self, domain: &str) -> Result<Vec<DNSRecord>> {
+        let records = self.list_all(domain).await?;
+        Ok(records
+            .into_iter()
+            .map(|r| to_dns_record(domain, r))
+            .collect())
+    }
+
+    async fn get_record(
+        &self,
+        domain: &str,
+        record_name: &str,
+        record_type: DNSRecordType,
+    ) -> Result<Option<DNSRecord>> {
+        // 接口不支持按名称过滤, 在本地查找
+        let name = full_record_name(domain, record_name);
+        let records = self.list_records(domain).await?;
+
+        Ok(records
+            .into_iter()
+            .find(|r| r.record_type == record_type && r.name.eq_ignore_ascii_case(&name)))
+    }
+
+    async fn update_record(
+        &self,
+        domain: &str,
+        record_id: &str,
+        new_content: &str,
+    ) -> Result<UpdateResult> {
+        self.update_record_with(domain, record_id, new_content, &RecordSpec::default())
+            .await
+    }
+
+    async fn update_record_with(
+        &self,
+        domain: &str,
+        record_id: &str,
+        new_content: &str,
+        spec: &RecordSpec,
+    ) -> Result<UpdateResult> {
+        spec.ensure_supported(&self.capabilities())?;
+        let path = format!("/domains/{}/records/{}", domain, record_id);
+
+        // 更新接口会覆盖整条记录, 先获取当前记录
+        let current: NameComRecord = self.send(Method::GET, &path, &[], None).await?;
+        let host = current.host.clone();
+        let current = to_dns_record(domain, current);
+        let mut content = RecordContent::from_input(&current.record_type, new_content, spec)?;
+        content.priority = content.priority.or(current.priority);
+
+        let mut body = json!({
+            "host": host,
+            "type": current.record_type.to_string(),
+            "answer": record_value(&current.record_type, &content),
+            "ttl": spec.ttl.unwrap_or(current.ttl),
+        });
+        if current.record_type.has_priority() {
+            body["priority"] = json!(content.priority.unwrap_or(DEFAULT_MX_PRIORITY));
+        }
+
+        let updated: NameComRecord = self.send(Method::PUT, &path, &[], Some(body)).await?;
+
+        Ok(UpdateResult {
+            success: true,
+            record_id: updated.id.to_string(),
+            old_ip: current.rdata(),
+            new_ip: new_content.to_string(),
+            message: "更新成功".to_string(),
+        })
+    }
+
+    async fn create_record(
+        &self,
+        domain: &str,
+        record_name: &str,
+        record_type: DNSRecordType,
+        content: &str,
+    ) -> Result<DNSRecord> {
+        self.create_record_with(
+            domain,
+            record_name,
+            record_type,
+            content,
+            &RecordSpec::default(),
+        )
+        .await
+    }
+
+    async fn create_record_with(
+        &self,
+        domain: &str,
+        record_name: &str,
+        record_type: DNSRecordType,
+        content: &str,
+        spec: &RecordSpec,
+    ) -> Result<DNSRecord> {
+        spec.ensure_supported(&self.capabilities())?;
+        let content = RecordContent::from_input(&record_type, content, spec)?;
+        let host = match record_name {
+            "@" => "",
+            name => name,
+        };
+        let mut body = json!({
+            "host": host,
+            "type": record_type.to_string(),
+            "answer": record_value(&record_type, &content),
+            "ttl": spec.ttl.unwrap_or(DEFAULT_TTL),
+        });
+        if record_type.has_priority() {
+            body["priority"] = json!(content.priority.unwrap_or(DEFAULT_MX_PRIORITY));
+        }
+
+        let created: NameComRecord = self
+            .send(
+                Method::POST,
+                &format!("/domains/{}/records", domain),
+                &[],
+                Some(body),
+            )
+            .await?;
+
+        Ok(to_dns_record(domain, created))
+    }
+
+    async fn delete_record(&self, domain: &str, record_id: &str) -> Result<()> {
+        let _: serde_json::Value = self
+            .send(
+                Method::DELETE,
+                &format!("/domains/{}/records/{}", domain, record_id),
+                &[],
+                None,
+            )
+            .await?;
+        Ok(())
+    }
+
+    async fn test_connection(&self) -> Result<bool> {
+        // hello 接口返回当前登录的用户名
+        let _: serde_json::Value = self.send(Method::GET, "/hello", &[], None).await?;
+        Ok(true)
+    }
+
+    fn supported_record_types(&self) -> Vec<DNSRecordType> {
+        vec![
+            DNSRecordType::A,
+            DNSRecordType::AAAA,
+            DNSRecordType::CNAME,
+            DNSRecordType::MX,
+            DNSRecordType::TXT,
+            DNSRecordType::NS,
+            DNSRecordType::SRV,
+        ]
+    }
+
+    fn capabilities(&self) -> ProviderCapabilities {
+        ProviderCapabilities {
+            proxy: false,
+            ttl: true,
+            line: false,
+            priority: true,
+            comment: false,
+        }
+    }
+}
+
+/// 将 Name.com 错误响应 (`{"message", "details"}`) 映射为 ProviderError
+fn parse_error(status: u16, text: &str) -> AppError {
+    let json: serde_json::Value = serde_json::from_str(text).unwrap_or_default();
+    let message = json["message"].as_str().unwrap_or(text);
+    let detail = match json["details"].as_str() {
+        Some(details) if !details.is_empty() => {
+            format!("HTTP {}: {} ({})", status, message, details)
+        }
+        _ => format!("HTTP {}: {}", status, message),
+    };
+
+    let error = match status {
+        401 | 403 => ProviderError::AuthenticationFailed(detail),
+        429 => ProviderError::RateLimitExceeded,
+        404 => ProviderError::RecordNotFound(detail),
+        _ => ProviderError::ApiError(detail),
+    };
+
+    AppError::Provider(error)
+}
+
+/// 优先级通过 `priority` 单独传递, 内容中不含优先级
+///
+/// SRV 内容为 `权重 端口 目标`
+fn record_value(record_type: &DNSRecordType, content: &RecordContent) -> String {
+    match (record_type, content.weight, content.port) {
+        (DNSRecordType::SRV, Some(weight), Some(port)) => {
+            format!("{} {} {}", weight, port, content.content)
+        }
+        (DNSRecordType::MX, _, _) => content.content.clone(),
+        _ => content.to_rdata(record_type),
+    }
+}
+
+/// 转换为通用记录, ANAME 等未内置的类型以 `Other` 保留
+fn to_dns_record(domain: &str, record: NameComRecord) -> DNSRecord {
+    let record_type = record
+        .record_type
+        .parse()
+        .unwrap_or_else(|_| DNSRecordType::Other(record.record_type.clone()));
+    let content = match (record_type.has_priority(), record.priority) {
+        (true, Some(priority)) => {
+            RecordContent::parse(&record_type, &format!("{} {}", priority, record.answer))
+        }
+        _ => RecordContent::parse(&record_type, &record.answer),
+    };
+
+    DNSRecord {
+        id: record.id.to_string(),
+        name: full_record_name(domain, &record.host),
+        record_type,
+        content: content.content,
+        ttl: record.ttl.unwrap_or(DEFAULT_TTL),
+        proxied: None,
+        priority: content.priority,
+        weight: content.weight,
+        port: content.port,
+        comment: None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::providers::test_support::PagedResponder;
+    use wiremock::matchers::{body_json, header, method, path};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    fn mock_provider(server: &MockServer) -> NameComProvider {
+        let mut provider = NameComProvider::with_endpoint(server.uri());
+        provider.username = Some("user".to_string());
+        provider.api_token = Some("token".to_string());
+        provider
+    }
+
+    fn record(id: u64, host: &str, record_type: &str, answer: &str) -> serde_json::Value {
+        json!({
+            "id": id,
+            "domainName": "example.com",
+            "host": host,
+            "fqdn": if host.is_empty() {
+                "example.com.".to_string()
+            } else {
+                format!("{}.example.com.", host)
+            },
+            "type": record_type,
+            "answer": answer,
+            "ttl": 300
+        })
+    }
+
+    #[test]
+    fn test_parse_error() {
+        assert!(matches!(
+            parse_error(401, r#"{"message": "Unauthenticated"}"#),
+            AppError::Provider(ProviderError::AuthenticationFailed(_))
+        ));
+        assert!(matches!(
+            parse_error(404, r#"{"message": "Not Found", "details": "record not found"}"#),
+            AppError::Provider(ProviderError::RecordNotFound(ref m)) if m.contains("record not found")
+        ));
+        assert!(matches!(
+            parse_error(400, r#"{"message": "Invalid Argument"}"#),
+            AppError::Provider(ProviderError::ApiError(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_list_records_reads_all_pages() {
+        let server = MockServer::start().await;
+        let mut srv = record(2, "_sip._tcp", "SRV", "10 5060 sip.example.com");
+        srv["priority"] = json!(1);
+        // 用户名 "user" + token "token" 的 Basic 认证
+        Mock::given(method("GET"))
+            .and(path("/domains/example.com/records"))
+            .and(header("authorization", "Basic dXNlcjp0b2tlbg=="))
+            .respond_with(PagedResponder::page_number(
+                "page",
+                1,
+                vec![
+                    json!({
+                        "records": [
+                            record(1, "", "A", "203.0.113.1"),
+                            srv
+                        ],
+                        "nextPage": 2,
+                        "lastPage": 2
+                    }),
+                    json!({
+                        "records": [record(3, "www", "A", "203.0.113.10")],
+                        "lastPage": 2
+                    }),
+                ],
+            ))
+            .expect(4)
+            .mount(&server)
+            .await;
+
+        let provider = mock_provider(&server);
+        let records = provider.list_records("example.com").await.unwrap();
+        assert_eq!(records.len(), 3);
+        assert_eq!(records[0].name, "example.com");
+        assert_eq!(records[1].name, "_sip._tcp.example.com");
+        assert_eq!(records[1].content, "sip.example.com");
+        assert_eq!(records[1].weight, Some(10));
+        assert_eq!(records[1].rdata(), "1 10 5060 sip.example.com");
+
+        let record = provider
+            .get_record("example.com", "www", DNSRecordType::A)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(record.id, "3");
+        assert_eq!(record.content, "203.0.113.10");
+    }
+
+    #[tokio::test]
+    async fn test_update_record() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/domains/example.com/records/3"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(record(
+                3,
+                "www",
+                "A",
+                "203.0.113.10",
+            )))
+            .mount(&server)
+            .await;
+        Mock::given(method("PUT"))
+            .and(path("/domains/example.com/records/3"))
+            .and(body_json(json!({
+                "host": "www",
+                "type": "A",
+                "answer": "198.51.100.7",
+                "ttl": 300
+            })))
+            .respond_with(ResponseTemplate::new(200).set_body_json(record(
+                3,
+                "www",
+                "A",
+                "198.51.100.7",
+            )))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let result = mock_provider(&server)
+            .update_record("example.com", "3", "198.51.100.7")
+            .await
+            .unwrap();
+        assert_eq!(result.record_id, "3");
+        assert_eq!(result.old_ip, "203.0.113.10");
+    }
+
+    #[tokio::test]
+    async fn test_create_mx_record_with_spec() {
+        let server = MockServer::start().await;
+        let mut created = record(5, "", "MX", "mail.example.com");
+        created["priority"] = json!(5);
+        created["ttl"] = json!(600);
+        Mock::given(method("POST"))
+            .and(path("/domains/example.com/records"))
+            .and(body_json(json!({
+                "host": "",
+                "type": "MX",
+                "answer": "mail.example.com",
+                "ttl": 600,
+                "priority": 5
+            })))
+            .respond_with(ResponseTemplate::new(200).set_body_json(created))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let spec = RecordSpec {
+            ttl: Some(600),
+            priority: Some(5),
+            ..Default::default()
+        };
+        let provider = mock_provider(&server);
+        let record = provider
+            .create_record_with(
+                "example.com",
+                "",
+                DNSRecordType::MX,
+                "mail.example.com",
+                &spec,
+            )
+            .await
+            .unwrap();
+        assert_eq!(record.priority, Some(5));
+        assert_eq!(record.ttl, 600);
+
+        // 优先级只适用于 MX 记录
+        let result = provider
+            .create_record_with(
+                "example.com",
+                "www",
+                DNSRecordType::A,
+                "203.0.113.10",
+                &spec,
+            )
+            .await;
+        assert!(matches!(
+            result,
+            Err(AppError::Provider(ProviderError::InvalidConfig(_)))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_create_and_delete_record() {
+        let server = MockServer::start().await;
+        let mut created = record(4, "", "MX", "mail.example.com");
+        created["priority"] = json!(20);
+        Mock::given(method("POST"))
+            .and(path("/domains/example.com/records"))
+            .and(body_json(json!({
+                "host": "",
+                "type": "MX",
+                "answer": "mail.example.com",
+                "ttl": DEFAULT_TTL,
+                "priority": 20
+            })))
+            .respond_with(ResponseTemplate::new(200).set_body_json(created))
+            .expect(1)
+            .mount(&server)
+            .await;
+        Mock::given(method("DELETE"))
+            .and(path("/domains/example.com/records/4"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({})))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let provider = mock_provider(&server);
+        let record = provider
+            .create_record("example.com", "@", DNSRecordType::MX, "20 mail.example.com")
+            .await
+            .unwrap();
+        assert_eq!(record.id, "4");
+        assert_eq!(record.name, "example.com");
+        assert_eq!(record.priority, Some(20));
+
+        provider
+            .delete_record("example.com", &record.id)
+            .await
+            .unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_create_srv_record() {
+        let server = MockServer::start().await;
+        let mut created = record(5, "_sip._tcp", "SRV", "5 5060 sip.example.com");
+        created["priority"] = json!(10);
+        Mock::given(method("POST"))
+            .and(path("/domains/example.com/records"))
+            .and(body_json(json!({
+                "host": "_sip._tcp",
+                "type": "SRV",
+                "answer": "5 5060 sip.example.com",
+                "ttl": DEFAULT_TTL,
+                "priority": 10
+            })))
+            .respond_with(ResponseTemplate::new(200).set_body_json(created))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let record = mock_provider(&server)
+            .create_record(
+                "example.com",
+                "_sip._tcp",
+                DNSRecordType::SRV,
+                "10 5 5060 sip.example.com",
+            )
+            .await
+            .unwrap();
+        assert_eq!(record.rdata(), "10 5 5060 sip.example.com");
+        assert_eq!(record.port, Some(5060));
+    }
+}
@@ -0,0 +1,709 @@
+use crate::error::{AppError, Result};
+use crate::providers::provider_trait::{
+    full_record_name, Credentials, DNSProvider, DNSRecord, DNSRecordType, ProviderCapabilities,
+    ProviderError, RecordContent, RecordSpec, UpdateResult,
+};
+use async_trait::async_trait;
+use reqwest::Client;
+use serde::de::DeserializeOwned;
+use serde::Deserialize;
+use serde_json::json;
+
+const DEFAULT_ENDPOINT: &str = "https://api.porkbun.com/api/json/v3";
+
+/// 新建记录的默认 TTL (Porkbun 允许的最小值)
+const DEFAULT_TTL: u32 = 600;
+
+/// 未指定优先级时 MX 记录使用的优先级
+const DEFAULT_MX_PRIORITY: u16 = 10;
+
+/// Porkbun DNS 提供商
+///
+/// 所有接口均为 POST, 密钥放在 JSON 请求体中
+pub struct PorkbunProvider {
+    api_key: Option<String>,
+    secret_api_key: Option<String>,
+    endpoint: String,
+    client: Client,
+}
+
+/// 记录, `name` 为完整域名, 数值字段以字符串返回
+#[derive(Debug, Deserialize)]
+struct PorkbunRecord {
+    id: String,
+    name: String,
+    #[serde(rename = "type")]
+    record_type: String,
+    content: String,
+    #[serde(default)]
+    ttl: Option<String>,
+    #[serde(default)]
+    prio: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct PorkbunRecordList {
+    #[serde(default)]
+    records: Vec<PorkbunRecord>,
+}
+
+/// 创建记录的响应, `id` 为数字
+#[derive(Debug, Deserialize)]
+struct PorkbunCreated {
+    id: serde_json::Value,
+}
+
+impl PorkbunProvider {
+    pub fn new() -> Self {
+        Self::with_endpoint(DEFAULT_ENDPOINT)
+    }
+
+    /// 使用自定义 API 地址创建提供商 (例如测试服务器)
+    pub fn with_endpoint(endpoint: impl Into<String>) -> Self {
+        Self {
+            api_key: None,
+            secret_api_key: None,
+            endpoint: endpoint.into().trim_end_matches('/').to_string(),
+            client: Client::builder()
+                .timeout(std::time::Duration::from_secs(30))
+                .build()
+                .unwrap(),
+        }
+    }
+
+    /// 发送 API 请求 (自动加入密钥) 并解析 JSON 响应
+    async fn send<T: DeserializeOwned>(&self, path: &str, body: serde_json::Value) -> Result<T> {
+        let (api_key, secret_api_key) = match (&self.api_key, &self.secret_api_key) {
+            (Some(key), Some(secret)) => (key, secret),
+            _ => {
+                return Err(AppError::Provider(ProviderError::AuthenticationFailed(
+                    "未设置 API Key 或 Secret API Key".to_string(),
+                )))
+            }
+        };
+
+        let mut body = body;
+        body["apikey"] = json!(api_key);
+        body["secretapikey"] = json!(secret_api_key);
+
+        let response = self
+            .client
+            .post(format!("{}{}", self.endpoint, path))
+            .json(&body)
+            .send()
+            .await
+            .map_err(|e| AppError::Provider(ProviderError::NetworkError(e.to_string())))?;
+        let status = response.status();
+        let text = response
+            .text()
+            .await
+            .map_err(|e| AppError::Provider(ProviderError::NetworkError(e.to_string())))?;
+
+        // 失败时响应为 {"status": "ERROR", "message": "..."}
+        let json: serde_json::Value = serde_json::from_str(&text).unwrap_or_default();
+        if !status.is_success() || json["status"] != "SUCCESS" {
+            return Err(parse_error(status.as_u16(), &text));
+        }
+
+        serde_json::from_value(json).map_err(|e| {
+            AppError::Provider(ProviderError::ParseError(format!("解析响应失败: {}", e)))
+        })
+    }
+
+    async fn get_by_id(&self, domain: &str, record_id: &str) -> Result<Option<PorkbunRecord>> {
+        let list: PorkbunRecordList = self
+            .send(
+                &format!("/dns/retrieve/{}/{}", domain, record_id),
+                json!({}),
+            )
+            .await?;
+        Ok(list.records.into_iter().next())
+    }
+}
+
+impl Default for PorkbunProvider {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[async_trait]
+impl DNSProvider for PorkbunProvider {
+    fn provider_id(&self) -> &'static str {
+        "porkbun"
+    }
+
+    fn provider_name(&self) -> &'static str {
+        "Porkbun"
+    }
+
+    async fn initialize(&mut self, credentials: &Credentials) -> Result<()> {
+        let extra = |key: &str| {
+            credentials
+                .extra
+                .get(key)
+                .and_then(|v| v.as_str())
+                .map(|v| v.to_string())
+        };
+        self.api_key = credentials.api_key.clone().or_else(|| extra("api_key"));
+        self.secret_api_key = credentials
+            .api_secret
+            .clone()
+            .or_else(|| extra("secret_api_key"));
+
+        if self.api_key.is_none() || self.secret_api_key.is_none() {
+            return Err(AppError::Provider(ProviderError::InvalidConfig(
+                "Porkbun 需要提供 api_key 和 secret_api_key".to_string(),
+            )));
+        }
+
+        // 测试连接
+        self.test_connection().await?;
+
+        Ok(())
+    }
+
+    async fn list_records(&self, domain: &str) -> Result<Vec<DNSRecord>> {
+        let list: PorkbunRecordList = self
+            .send(&format!("/dns/retrieve/{}", domain), json!({}))
+            .await?;
+
+        Ok(list.records.into_iter().map(to_dns_record).collect())
+    }
+
+    async fn get_record(
+        &self,
+        domain: &str,
+        record_name: &str,
+        record_type: DNSRecordType,
+    ) -> Result<Option<DNSRecord>> {
+        // 根域名不带子域名部分
+        let path = match relative_name(domain, &full_record_name(domain, record_name)) {
+            "" => format!("/dns/retrieveByNameType/{}/{}", domain, record_type),
+            name => format!(
+                "/dns/retrieveByNameType/{}/{}/{}",
+                domain, record_type, name
+            ),
+        };
+        let list: PorkbunRecordList = self.send(&path, json!({})).await?;
+
+        Ok(list.records.into_iter().next().map(to_dns_record))
+    }
+
+    async fn update_record(
+        &self,
+        domain: &str,
+        record_id: &str,
+        new_content: &str,
+    ) -> Result<UpdateResult> {
+        self.update_record_with(domain, record_id, new_content, &RecordSpec::default())
+            .await
+    }
+
+    async fn update_record_with(
+        &self,
+        domain: &str,
+        record_id: &str,
+        new_content: &str,
+        spec: &RecordSpec,
+    ) -> Result<UpdateResult> {
+        spec.ensure_supported(&self.capabilities())?;
+        // 编辑接口需要提交完整记录, 先获取当前记录
+        let current = self
+            .get_by_id(domain, record_id)
+            .await?
+            .map(to_dns_record)
+            .ok_or_else(|| {
+                AppError::Provider(ProviderError::RecordNotFound(record_id.to_string()))
+            })?;
+        let mut content = RecordContent::from_input(&current.record_type, new_content, spec)?;
+        content.priority = content.priority.or(current.priority);
+
+        let ttl = spec.ttl.unwrap_or(current.ttl);
+        let mut body = json!({
+            "name": relative_name(domain, &current.name),
+            "type": current.record_type.to_string(),
+            "content": record_value(&current.record_type, &content),
+            "ttl": ttl.to_string(),
+        });
+        if current.record_type.has_priority() {
+            body["prio"] = json!(content.priority.unwrap_or(DEFAULT_MX_PRIORITY).to_string());
+        }
+
+        let _: serde_json::Value = self
+            .send(&format!("/dns/edit/{}/{}", domain, record_id), body)
+            .await?;
+
+        Ok(UpdateResult {
+            success: true,
+            record_id: record_id.to_string(),
+            old_ip: current.rdata(),
+            new_ip: new_content.to_string(),
+            message: "更新成功".to_string(),
+        })
+    }
+
+    async fn create_record(
+        &self,
+        domain: &str,
+        record_name: &str,
+        record_type: DNSRecordType,
+        content: &str,
+    ) -> Result<DNSRecord> {
+        self.create_record_with(
+            domain,
+            record_name,
+            record_type,
+            content,
+            &RecordSpec::default(),
+        )
+        .await
+    }
+
+    async fn create_record_with(
+        &self,
+        domain: &str,
+        record_name: &str,
+        record_type: DNSRecordType,
+        content: &str,
+        spec: &RecordSpec,
+    ) -> Result<DNSRecord> {
+        spec.ensure_supported(&self.capabilities())?;
+        let mut content = RecordContent::from_input(&record_type, content, spec)?;
+        let ttl = spec.ttl.unwrap_or(DEFAULT_TTL);
+        let fqdn = full_record_name(domain, record_name);
+        let mut body = json!({
+            "name": relative_name(domain, &fqdn),
+            "type": record_type.to_string(),
+            "content": record_value(&record_type, &content),
+            "ttl": ttl.to_string(),
+        });
+        if record_type.has_priority() {
+            let priority = content.priority.unwrap_or(DEFAULT_MX_PRIORITY);
+            body["prio"] = json!(priority.to_string());
+            content.priority = Some(priority);
+        }
+
+        let created: PorkbunCreated = self.send(&format!("/dns/create/{}", domain), body).await?;
+        let id = match created.id {
+            serde_json::Value::String(id) => id,
+            id => id.to_string(),
+        };
+
+        Ok(DNSRecord {
+            id,
+            name: fqdn,
+            record_type,
+            content: content.content,
+            ttl,
+            proxied: None,
+            priority: content.priority,
+            weight: content.weight,
+            port: content.port,
+            comment: None,
+        })
+    }
+
+    async fn delete_record(&self, domain: &str, record_id: &str) -> Result<()> {
+        let _: serde_json::Value = self
+            .send(&format!("/dns/delete/{}/{}", domain, record_id), json!({}))
+            .await?;
+        Ok(())
+    }
+
+    async fn test_connection(&self) -> Result<bool> {
+        // ping 接口只验证密钥
+        let _: serde_json::Value = self.send("/ping", json!({})).await?;
+        Ok(true)
+    }
+
+    fn supported_record_types(&self) -> Vec<DNSRecordType> {
+        vec![
+            DNSRecordType::A,
+            DNSRecordType::AAAA,
+            DNSRecordType::CNAME,
+            DNSRecordType::MX,
+            DNSRecordType::TXT,
+            DNSRecordType::NS,
+            DNSRecordType::SRV,
+            DNSRecordType::CAA,
+        ]
+    }
+
+    fn capabilities(&self) -> ProviderCapabilities {
+        ProviderCapabilities {
+            proxy: false,
+            ttl: true,
+            line: false,
+            priority: true,
+            comment: false,
+        }
+    }
+}
+
+/// 将 Porkbun 错误响应 (`{"status": "ERROR", "message"}`) 映射为 ProviderError
+fn parse_error(status: u16, text: &str) -> AppError {
+    let json: serde_json::Value = serde_json::from_str(text).unwrap_or_default();
+    let message = json["message"].as_str().unwrap_or(text);
+
+    let detail = format!("HTTP {}: {}", status, message);
+    let lower = message.to_lowercase();
+    let error = if status == 429 || lower.contains("rate limit") {
+        ProviderError::RateLimitExceeded
+    } else if status == 401
+        || status == 403
+        || lower.contains("api key")
+        || lower.contains("not opted in to api access")
+    {
+        ProviderError::AuthenticationFailed(detail)
+    } else if lower.contains("invalid domain") {
+        ProviderError::DomainNotFound(detail)
+    } else {
+        ProviderError::ApiError(detail)
+    };
+
+    AppError::Provider(error)
+}
+
+/// 将完整域名转换为相对名称, 根域名返回空字符串
+fn relative_name<'a>(domain: &str, fqdn: &'a str) -> &'a str {
+    let fqdn = fqdn.trim_end_matches('.');
+    if fqdn.eq_ignore_ascii_case(domain) {
+        return "";
+    }
+    match fqdn.len().checked_sub(domain.len() + 1) {
+        Some(end)
+            if fqdn[end..].starts_with('.') && fqdn[end + 1..].eq_ignore_ascii_case(domain) =>
+        {
+            &fqdn[..end]
+        }
+        _ => fqdn,
+    }
+}
+
+/// 优先级通过 `prio` 单独传递, 内容中不含优先级
+///
+/// SRV 内容为 `权重 端口 目标`
+fn record_value(record_type: &DNSRecordType, content: &RecordContent) -> String {
+    match (record_type, content.weight, content.port) {
+        (DNSRecordType::SRV, Some(weight), Some(port)) => {
+            format!("{} {} {}", weight, port, content.content)
+        }
+        (DNSRecordType::MX, _, _) => content.content.clone(),
+        _ => content.to_rdata(record_type),
+    }
+}
+
+/// 转换为通用记录, 未内置的类型以 `Other` 保留
+fn to_dns_record(record: PorkbunRecord) -> DNSRecord {
+    let record_type = record
+        .record_type
+        .parse()
+        .unwrap_or_else(|_| DNSRecordType::Other(record.record_type.clone()));
+    let priority = record.prio.as_deref().and_then(|p| p.parse::<u16>().ok());
+    let content = match (record_type.has_priority(), priority) {
+        (true, Some(priority)) => {
+            RecordContent::parse(&record_type, &format!("{} {}", priority, record.content))
+        }
+        _ => RecordContent::parse(&record_type, &record.content),
+    };
+
+    DNSRecord {
+        id: record.id,
+        name: record.name,
+        record_type,
+        content: content.content,
+        ttl: record
+            .ttl
+            .as_deref()
+            .and_then(|t| t.parse().ok())
+            .unwrap_or(DEFAULT_TTL),
+        proxied: None,
+        priority: content.priority,
+        weight: content.weight,
+        port: content.port,
+        comment: None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use wiremock::matchers::{body_partial_json, method, path};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    fn mock_provider(server: &MockServer) -> PorkbunProvider {
+        let mut provider = PorkbunProvider::with_endpoint(server.uri());
+        provider.api_key = Some("pk1_key".to_string());
+        provider.secret_api_key = Some("sk1_secret".to_string());
+        provider
+    }
+
+    fn record(id: &str, name: &str, record_type: &str, content: &str) -> serde_json::Value {
+        json!({
+            "id": id,
+            "name": name,
+            "type": record_type,
+            "content": content,
+            "ttl": "600",
+            "prio": if record_type == "MX" { json!("10") } else { json!(null) },
+            "notes": ""
+        })
+    }
+
+    #[test]
+    fn test_relative_name() {
+        assert_eq!(relative_name("example.com", "example.com"), "");
+        assert_eq!(relative_name("example.com", "www.example.com"), "www");
+        assert_eq!(relative_name("example.com", "a.b.Example.com."), "a.b");
+        assert_eq!(
+            relative_name("example.com", "www.other.com"),
+            "www.other.com"
+        );
+        assert_eq!(
+            relative_name("example.com", "wwwexample.com"),
+            "wwwexample.com"
+        );
+    }
+
+    #[test]
+    fn test_parse_error() {
+        assert!(matches!(
+            parse_error(
+                400,
+                r#"{"status": "ERROR", "message": "Invalid API key. (002)"}"#
+            ),
+            AppError::Provider(ProviderError::AuthenticationFailed(_))
+        ));
+        assert!(matches!(
+            parse_error(
+                400,
+                r#"{"status": "ERROR", "message": "Domain is not opted in to API access."}"#
+            ),
+            AppError::Provider(ProviderError::AuthenticationFailed(_))
+        ));
+        assert!(matches!(
+            parse_error(400, r#"{"status": "ERROR", "message": "Invalid domain."}"#),
+            AppError::Provider(ProviderError::DomainNotFound(_))
+        ));
+        assert!(matches!(
+            parse_error(503, "Service Unavailable"),
+            AppError::Provider(ProviderError::ApiError(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_list_and_get_records() {
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/dns/retrieve/example.com"))
+            .and(body_partial_json(
+                json!({"apikey": "pk1_key", "secretapikey": "sk1_secret"}),
+            ))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+                "status": "SUCCESS",
+                "cloudflare": "enabled",
+                "records": [
+                    record("1", "example.com", "MX", "mail.example.com"),
+                    record("2", "example.com", "SOA", "curitiba.ns.porkbun.com"),
+                    record("3", "www.example.com", "A", "203.0.113.10")
+                ]
+            })))
+            .mount(&server)
+            .await;
+        Mock::given(method("POST"))
+            .and(path("/dns/retrieveByNameType/example.com/A/www"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+                "status": "SUCCESS",
+                "records": [record("3", "www.example.com", "A", "203.0.113.10")]
+            })))
+            .mount(&server)
+            .await;
+        Mock::given(method("POST"))
+            .and(path("/dns/retrieveByNameType/example.com/AAAA"))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .set_body_json(json!({"status": "SUCCESS", "records": []})),
+            )
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let provider = mock_provider(&server);
+        let records = provider.list_records("example.com").await.unwrap();
+        assert_eq!(records.len(), 3);
+        assert_eq!(records[0].priority, Some(10));
+        assert_eq!(records[0].ttl, 600);
+
+        let record = provider
+            .get_record("example.com", "www", DNSRecordType::A)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(record.id, "3");
+        assert_eq!(record.content, "203.0.113.10");
+
+        assert!(provider
+            .get_record("example.com", "@", DNSRecordType::AAAA)
+            .await
+            .unwrap()
+            .is_none());
+    }
+
+    #[tokio::test]
+    async fn test_update_record() {
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/dns/retrieve/example.com/3"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+                "status": "SUCCESS",
+                "records": [record("3", "www.example.com", "A", "203.0.113.10")]
+            })))
+            .mount(&server)
+            .await;
+        Mock::given(method("POST"))
+            .and(path("/dns/edit/example.com/3"))
+            .and(body_partial_json(json!({
+                "name": "www",
+                "type": "A",
+                "content": "198.51.100.7",
+                "ttl": "600"
+            })))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({"status": "SUCCESS"})))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let result = mock_provider(&server)
+            .update_record("example.com", "3", "198.51.100.7")
+            .await
+            .unwrap();
+        assert_eq!(result.old_ip, "203.0.113.10");
+        assert_eq!(result.new_ip, "198.51.100.7");
+    }
+
+    #[tokio::test]
+    async fn test_update_mx_record_with_spec() {
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/dns/retrieve/example.com/4"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+                "status": "SUCCESS",
+                "records": [record("4", "example.com", "MX", "mail.example.com")]
+            })))
+            .mount(&server)
+            .await;
+        Mock::given(method("POST"))
+            .and(path("/dns/edit/example.com/4"))
+            .and(body_partial_json(json!({
+                "content": "mx2.example.com",
+                "ttl": "300",
+                "prio": "5"
+            })))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({"status": "SUCCESS"})))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let spec = RecordSpec {
+            ttl: Some(300),
+            priority: Some(5),
+            ..Default::default()
+        };
+        mock_provider(&server)
+            .update_record_with("example.com", "4", "mx2.example.com", &spec)
+            .await
+            .unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_create_and_delete_record() {
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/dns/create/example.com"))
+            .and(body_partial_json(json!({
+                "name": "",
+                "type": "MX",
+                "content": "mail.example.com",
+                "prio": "20"
+            })))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .set_body_json(json!({"status": "SUCCESS", "id": 106926659})),
+            )
+            .expect(1)
+            .mount(&server)
+            .await;
+        Mock::given(method("POST"))
+            .and(path("/dns/delete/example.com/106926659"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({"status": "SUCCESS"})))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let provider = mock_provider(&server);
+        let record = provider
+            .create_record("example.com", "@", DNSRecordType::MX, "20 mail.example.com")
+            .await
+            .unwrap();
+        assert_eq!(record.id, "106926659");
+        assert_eq!(record.name, "example.com");
+        assert_eq!(record.priority, Some(20));
+
+        provider
+            .delete_record("example.com", &record.id)
+            .await
+            .unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_create_srv_record() {
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/dns/create/example.com"))
+            .and(body_partial_json(json!({
+                "name": "_sip._tcp",
+                "type": "SRV",
+                "content": "5 5060 sip.example.com",
+                "prio": "10"
+            })))
+            .respond_with(
+                ResponseTemplate::new(200).set_body_json(json!({"status": "SUCCESS", "id": 7})),
+            )
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let record = mock_provider(&server)
+            .create_record(
+                "example.com",
+                "_sip._tcp",
+                DNSRecordType::SRV,
+                "10 5 5060 sip.example.com",
+            )
+            .await
+            .unwrap();
+        assert_eq!(record.id, "7");
+        assert_eq!(record.rdata(), "10 5 5060 sip.example.com");
+        assert_eq!(record.port, Some(5060));
+    }
+
+    #[tokio::test]
+    async fn test_error_status_in_body() {
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/ping"))
+            .respond_with(ResponseTemplate::new(400).set_body_json(json!({
+                "status": "ERROR",
+                "message": "Invalid API key. (002)"
+            })))
+            .mount(&server)
+            .await;
+
+        let result = mock_provider(&server).test_connection().await;
+        assert!(matches!(
+            result,
+            Err(AppError::Provider(ProviderError::AuthenticationFailed(_)))
+        ));
+    }
+}
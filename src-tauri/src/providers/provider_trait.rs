@@ -1,10 +1,13 @@
+use super::registry::{self, ProviderDescriptor};
 use crate::error::{AppError, Result};
 use async_trait::async_trait;
 use serde::{Deserialize, Serialize};
 
 /// DNS 记录类型
-#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
-#[serde(rename_all = "UPPERCASE")]
+///
+/// 未内置的类型保存在 `Other` 中 (统一为大写), 读取记录时不会丢失类型信息。
+/// 序列化为类型名称字符串, 如 `"A"`、`"SRV"`、`"PTR"`
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub enum DNSRecordType {
     A,
     AAAA,
@@ -13,6 +16,16 @@ pub enum DNSRecordType {
     TXT,
     NS,
     SOA,
+    SRV,
+    CAA,
+    Other(String),
+}
+
+impl DNSRecordType {
+    /// 记录数据是否带优先级
+    pub fn has_priority(&self) -> bool {
+        matches!(self, DNSRecordType::MX | DNSRecordType::SRV)
+    }
 }
 
 impl std::fmt::Display for DNSRecordType {
@@ -25,10 +38,55 @@ impl std::fmt::Display for DNSRecordType {
             DNSRecordType::TXT => write!(f, "TXT"),
             DNSRecordType::NS => write!(f, "NS"),
             DNSRecordType::SOA => write!(f, "SOA"),
+            DNSRecordType::SRV => write!(f, "SRV"),
+            DNSRecordType::CAA => write!(f, "CAA"),
+            DNSRecordType::Other(name) => write!(f, "{}", name),
+        }
+    }
+}
+
+impl std::str::FromStr for DNSRecordType {
+    type Err = AppError;
+
+    /// 解析类型名称 (不区分大小写), 未内置的类型返回 `Other`
+    fn from_str(s: &str) -> Result<Self> {
+        let name = s.trim().to_uppercase();
+        match name.as_str() {
+            "A" => Ok(DNSRecordType::A),
+            "AAAA" => Ok(DNSRecordType::AAAA),
+            "CNAME" => Ok(DNSRecordType::CNAME),
+            "MX" => Ok(DNSRecordType::MX),
+            "TXT" => Ok(DNSRecordType::TXT),
+            "NS" => Ok(DNSRecordType::NS),
+            "SOA" => Ok(DNSRecordType::SOA),
+            "SRV" => Ok(DNSRecordType::SRV),
+            "CAA" => Ok(DNSRecordType::CAA),
+            _ if !name.is_empty() && name.chars().all(|c| c.is_ascii_alphanumeric()) => {
+                Ok(DNSRecordType::Other(name))
+            }
+            _ => Err(AppError::Validation(format!("无效的记录类型: {}", s))),
         }
     }
 }
 
+impl Serialize for DNSRecordType {
+    fn serialize<S: serde::Serializer>(
+        &self,
+        serializer: S,
+    ) -> std::result::Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+impl<'de> Deserialize<'de> for DNSRecordType {
+    fn deserialize<D: serde::Deserializer<'de>>(
+        deserializer: D,
+    ) -> std::result::Result<Self, D::Error> {
+        let name = String::deserialize(deserializer)?;
+        name.parse().map_err(serde::de::Error::custom)
+    }
+}
+
 /// DNS 记录
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct DNSRecord {
@@ -39,13 +97,169 @@ pub struct DNSRecord {
     /// 记录类型
     pub record_type: DNSRecordType,
     /// 记录内容 (IP 地址或域名)
+    ///
+    /// MX 与 SRV 记录只包含目标主机, 优先级、权重和端口见对应字段
     pub content: String,
     /// TTL (秒)
     pub ttl: u32,
     /// 是否启用 (仅 Cloudflare)
     pub proxied: Option<bool>,
-    /// 优先级 (MX 记录)
+    /// 优先级 (MX/SRV 记录)
     pub priority: Option<u16>,
+    /// 权重 (SRV 记录)
+    #[serde(default)]
+    pub weight: Option<u16>,
+    /// 端口 (SRV 记录)
+    #[serde(default)]
+    pub port: Option<u16>,
+    /// 备注 (提供商支持时)
+    #[serde(default)]
+    pub comment: Option<String>,
+}
+
+impl DNSRecord {
+    /// 区域文件格式的记录数据, 如 MX `10 mail.example.com`、SRV `10 5 5060 sip.example.com`
+    pub fn rdata(&self) -> String {
+        RecordContent {
+            content: self.content.clone(),
+            priority: self.priority,
+            weight: self.weight,
+            port: self.port,
+        }
+        .to_rdata(&self.record_type)
+    }
+}
+
+/// 拆分后的记录内容
+///
+/// MX 与 SRV 记录的优先级、权重和端口与目标主机分开存放, 其他类型只有 `content`
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct RecordContent {
+    pub content: String,
+    pub priority: Option<u16>,
+    pub weight: Option<u16>,
+    pub port: Option<u16>,
+}
+
+impl RecordContent {
+    fn plain(content: &str) -> Self {
+        Self {
+            content: content.to_string(),
+            ..Default::default()
+        }
+    }
+
+    /// 解析区域文件格式的记录数据
+    ///
+    /// MX 为 `优先级 目标`, SRV 为 `优先级 权重 端口 目标`; 格式不符时原样保留为内容
+    pub fn parse(record_type: &DNSRecordType, rdata: &str) -> Self {
+        let fields: Vec<&str> = rdata.split_whitespace().collect();
+        match (record_type, fields.as_slice()) {
+            (DNSRecordType::MX, [priority, target]) => match priority.parse() {
+                Ok(priority) => Self {
+                    priority: Some(priority),
+                    ..Self::plain(target)
+                },
+                Err(_) => Self::plain(rdata),
+            },
+            (DNSRecordType::SRV, [priority, weight, port, target]) => {
+                match (priority.parse(), weight.parse(), port.parse()) {
+                    (Ok(priority), Ok(weight), Ok(port)) => Self {
+                        content: target.to_string(),
+                        priority: Some(priority),
+                        weight: Some(weight),
+                        port: Some(port),
+                    },
+                    _ => Self::plain(rdata),
+                }
+            }
+            _ => Self::plain(rdata),
+        }
+    }
+
+    /// 解析创建/更新记录时传入的内容
+    ///
+    /// MX 可以省略优先级; SRV 必须包含全部字段; CAA 统一为 `标志 标签 "值"`。
+    /// `spec.priority` 优先于内容中的优先级
+    pub fn from_input(
+        record_type: &DNSRecordType,
+        content: &str,
+        spec: &RecordSpec,
+    ) -> Result<Self> {
+        let priority = spec.priority_for(record_type)?;
+        let mut parsed = Self::parse(record_type, content.trim());
+        match record_type {
+            DNSRecordType::SRV if parsed.port.is_none() => {
+                return Err(AppError::Provider(ProviderError::InvalidConfig(format!(
+                    "SRV 记录内容格式应为 \"优先级 权重 端口 目标\": {}",
+                    content
+                ))));
+            }
+            DNSRecordType::CAA => {
+                parsed.content = CaaData::parse(&parsed.content)?.to_string();
+            }
+            _ => {}
+        }
+        parsed.priority = priority.or(parsed.priority);
+        Ok(parsed)
+    }
+
+    /// 按区域文件格式输出, 缺少的结构化字段被省略
+    pub fn to_rdata(&self, record_type: &DNSRecordType) -> String {
+        match (record_type, self.priority, self.weight, self.port) {
+            (DNSRecordType::MX, Some(priority), _, _) => format!("{} {}", priority, self.content),
+            (DNSRecordType::SRV, Some(priority), Some(weight), Some(port)) => {
+                format!("{} {} {} {}", priority, weight, port, self.content)
+            }
+            _ => self.content.clone(),
+        }
+    }
+}
+
+/// CAA 记录数据, 区域文件格式为 `标志 标签 "值"`, 如 `0 issue "letsencrypt.org"`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CaaData {
+    pub flags: u8,
+    pub tag: String,
+    pub value: String,
+}
+
+impl CaaData {
+    /// 解析区域文件格式的 CAA 记录, 值两侧的引号可以省略
+    pub fn parse(content: &str) -> Result<Self> {
+        let invalid = || {
+            AppError::Provider(ProviderError::InvalidConfig(format!(
+                "CAA 记录内容格式应为 \"标志 标签 值\": {}",
+                content
+            )))
+        };
+        let mut fields = content.trim().splitn(3, char::is_whitespace);
+        let flags = fields
+            .next()
+            .and_then(|f| f.parse().ok())
+            .ok_or_else(invalid)?;
+        let tag = fields
+            .next()
+            .filter(|t| !t.is_empty())
+            .ok_or_else(invalid)?;
+        let value = fields.next().map(str::trim).ok_or_else(invalid)?;
+        let value = value
+            .strip_prefix('"')
+            .and_then(|v| v.strip_suffix('"'))
+            .unwrap_or(value);
+
+        Ok(Self {
+            flags,
+            tag: tag.to_string(),
+            value: value.to_string(),
+        })
+    }
+}
+
+impl std::fmt::Display for CaaData {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} {} \"{}\"", self.flags, self.tag, self.value)
+    }
 }
 
 /// DNS 更新结果
@@ -63,6 +277,80 @@ pub struct UpdateResult {
     pub message: String,
 }
 
+/// 提供商能力
+///
+/// 声明提供商在创建/更新记录时能处理哪些 [`RecordSpec`] 选项
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, Eq)]
+pub struct ProviderCapabilities {
+    /// 代理 (如 Cloudflare 橙色云)
+    pub proxy: bool,
+    /// 按记录设置 TTL
+    pub ttl: bool,
+    /// 解析线路 (如电信/联通/海外)
+    pub line: bool,
+    /// MX/SRV 优先级
+    pub priority: bool,
+    /// 记录备注
+    pub comment: bool,
+}
+
+/// 创建/更新记录时的附加选项
+///
+/// `None` 表示不指定: 创建时使用提供商默认值, 更新时保留记录原值
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+pub struct RecordSpec {
+    /// TTL (秒)
+    pub ttl: Option<u32>,
+    /// 是否开启代理
+    pub proxied: Option<bool>,
+    /// 优先级 (MX/SRV 记录)
+    pub priority: Option<u16>,
+    /// 备注
+    pub comment: Option<String>,
+}
+
+impl RecordSpec {
+    /// 检查选项是否都被提供商支持
+    ///
+    /// `proxied: Some(false)` 与不开启代理等价, 因此总是允许
+    pub fn ensure_supported(&self, capabilities: &ProviderCapabilities) -> Result<()> {
+        let mut unsupported = Vec::new();
+        if self.ttl.is_some() && !capabilities.ttl {
+            unsupported.push("ttl");
+        }
+        if self.proxied == Some(true) && !capabilities.proxy {
+            unsupported.push("proxied");
+        }
+        if self.priority.is_some() && !capabilities.priority {
+            unsupported.push("priority");
+        }
+        if self.comment.is_some() && !capabilities.comment {
+            unsupported.push("comment");
+        }
+
+        if unsupported.is_empty() {
+            Ok(())
+        } else {
+            Err(AppError::Provider(ProviderError::InvalidConfig(format!(
+                "提供商不支持以下记录选项: {}",
+                unsupported.join(", ")
+            ))))
+        }
+    }
+
+    /// 取出优先级, 只有 MX 和 SRV 记录可以设置
+    pub fn priority_for(&self, record_type: &DNSRecordType) -> Result<Option<u16>> {
+        match (self.priority, record_type) {
+            (None, _) => Ok(None),
+            (Some(priority), record_type) if record_type.has_priority() => Ok(Some(priority)),
+            (Some(_), other) => Err(AppError::Provider(ProviderError::InvalidConfig(format!(
+                "{} 记录不能设置优先级",
+                other
+            )))),
+        }
+    }
+}
+
 /// 提供商错误
 #[derive(Debug, thiserror::Error)]
 pub enum ProviderError {
@@ -144,16 +432,19 @@ pub trait DNSProvider: Send + Sync {
     async fn list_records(&self, domain: &str) -> Result<Vec<DNSRecord>>;
 
     /// 获取特定的 DNS 记录
+    ///
+    /// `record_name` 为相对名称 (例如 `www`, `@`), 同时兼容返回完整域名的提供商
     async fn get_record(
         &self,
         domain: &str,
         record_name: &str,
         record_type: DNSRecordType,
     ) -> Result<Option<DNSRecord>> {
+        let fqdn = full_record_name(domain, record_name);
         let records = self.list_records(domain).await?;
         Ok(records
             .into_iter()
-            .find(|r| r.name == record_name && r.record_type == record_type))
+            .find(|r| (r.name == record_name || r.name == fqdn) && r.record_type == record_type))
     }
 
     /// 更新 DNS 记录
@@ -173,6 +464,36 @@ pub trait DNSProvider: Send + Sync {
         content: &str,
     ) -> Result<DNSRecord>;
 
+    /// 按附加选项更新 DNS 记录
+    ///
+    /// 默认实现不支持任何选项, 选项为空时等同于 `update_record`
+    async fn update_record_with(
+        &self,
+        domain: &str,
+        record_id: &str,
+        new_content: &str,
+        spec: &RecordSpec,
+    ) -> Result<UpdateResult> {
+        spec.ensure_supported(&self.capabilities())?;
+        self.update_record(domain, record_id, new_content).await
+    }
+
+    /// 按附加选项创建 DNS 记录
+    ///
+    /// 默认实现不支持任何选项, 选项为空时等同于 `create_record`
+    async fn create_record_with(
+        &self,
+        domain: &str,
+        record_name: &str,
+        record_type: DNSRecordType,
+        content: &str,
+        spec: &RecordSpec,
+    ) -> Result<DNSRecord> {
+        spec.ensure_supported(&self.capabilities())?;
+        self.create_record(domain, record_name, record_type, content)
+            .await
+    }
+
     /// 删除 DNS 记录
     async fn delete_record(&self, domain: &str, record_id: &str) -> Result<()>;
 
@@ -183,6 +504,20 @@ pub trait DNSProvider: Send + Sync {
     fn supported_record_types(&self) -> Vec<DNSRecordType> {
         vec![DNSRecordType::A, DNSRecordType::AAAA, DNSRecordType::CNAME]
     }
+
+    /// 获取提供商能力, 默认不支持任何附加选项
+    fn capabilities(&self) -> ProviderCapabilities {
+        ProviderCapabilities::default()
+    }
+}
+
+/// 拼接完整记录名称 (`@` 或空子域名表示根域名)
+pub fn full_record_name(domain: &str, record_name: &str) -> String {
+    if record_name.is_empty() || record_name == "@" {
+        domain.to_string()
+    } else {
+        format!("{}.{}", record_name, domain)
+    }
 }
 
 /// 提供商工厂
@@ -191,20 +526,19 @@ pub struct ProviderFactory;
 impl ProviderFactory {
     /// 根据提供商 ID 创建提供商实例
     pub fn create(provider_id: &str) -> Result<Box<dyn DNSProvider>> {
-        match provider_id {
-            "cloudflare" => Ok(Box::new(
-                crate::providers::cloudflare::CloudflareProvider::new(),
-            )),
-            "aliyun" => Ok(Box::new(crate::providers::aliyun::AliyunProvider::new())),
-            "tencent" => Ok(Box::new(crate::providers::tencent::TencentProvider::new())),
-            "aws" => Ok(Box::new(crate::providers::aws::AwsProvider::new())),
-            _ => Err(AppError::Custom(format!("未知的提供商: {}", provider_id))),
-        }
+        registry::find(provider_id)
+            .map(|descriptor| descriptor.create())
+            .ok_or_else(|| AppError::Custom(format!("未知的提供商: {}", provider_id)))
     }
 
     /// 列出所有支持的提供商
     pub fn list_providers() -> Vec<&'static str> {
-        vec!["cloudflare", "aliyun", "tencent", "aws"]
+        Self::descriptors().iter().map(|d| d.id).collect()
+    }
+
+    /// 列出所有提供商的描述 (名称、凭证字段、记录类型与能力)
+    pub fn descriptors() -> &'static [ProviderDescriptor] {
+        registry::descriptors()
     }
 }
 
@@ -219,10 +553,180 @@ mod tests {
         assert_eq!(DNSRecordType::CNAME.to_string(), "CNAME");
     }
 
+    #[test]
+    fn test_record_type_from_str() {
+        assert_eq!("A".parse::<DNSRecordType>().unwrap(), DNSRecordType::A);
+        assert_eq!(
+            "aaaa".parse::<DNSRecordType>().unwrap(),
+            DNSRecordType::AAAA
+        );
+        assert_eq!("srv".parse::<DNSRecordType>().unwrap(), DNSRecordType::SRV);
+        assert_eq!(
+            "ptr".parse::<DNSRecordType>().unwrap(),
+            DNSRecordType::Other("PTR".to_string())
+        );
+        assert!("".parse::<DNSRecordType>().is_err());
+        assert!("A B".parse::<DNSRecordType>().is_err());
+    }
+
+    #[test]
+    fn test_record_type_serde() {
+        let types: Vec<DNSRecordType> = serde_json::from_str(r#"["A", "caa", "HTTPS"]"#).unwrap();
+        assert_eq!(
+            types,
+            vec![
+                DNSRecordType::A,
+                DNSRecordType::CAA,
+                DNSRecordType::Other("HTTPS".to_string())
+            ]
+        );
+        assert_eq!(
+            serde_json::to_string(&types).unwrap(),
+            r#"["A","CAA","HTTPS"]"#
+        );
+    }
+
+    #[test]
+    fn test_record_content_parse() {
+        let mx = RecordContent::parse(&DNSRecordType::MX, "10 mail.example.com.");
+        assert_eq!(mx.content, "mail.example.com.");
+        assert_eq!(mx.priority, Some(10));
+        assert_eq!(mx.to_rdata(&DNSRecordType::MX), "10 mail.example.com.");
+
+        let srv = RecordContent::parse(&DNSRecordType::SRV, "10 5 5060 sip.example.com");
+        assert_eq!(srv.content, "sip.example.com");
+        assert_eq!(
+            (srv.priority, srv.weight, srv.port),
+            (Some(10), Some(5), Some(5060))
+        );
+        assert_eq!(
+            srv.to_rdata(&DNSRecordType::SRV),
+            "10 5 5060 sip.example.com"
+        );
+
+        // 格式不符时保留原始内容
+        let partial = RecordContent::parse(&DNSRecordType::SRV, "5 5060 sip.example.com");
+        assert_eq!(partial.content, "5 5060 sip.example.com");
+        assert_eq!(partial.priority, None);
+        let txt = RecordContent::parse(&DNSRecordType::TXT, "10 mail.example.com");
+        assert_eq!(txt.content, "10 mail.example.com");
+    }
+
+    #[test]
+    fn test_record_content_from_input() {
+        let spec = RecordSpec {
+            priority: Some(20),
+            ..Default::default()
+        };
+        let mx =
+            RecordContent::from_input(&DNSRecordType::MX, "10 mail.example.com", &spec).unwrap();
+        assert_eq!(mx.priority, Some(20));
+        assert_eq!(mx.content, "mail.example.com");
+
+        let mx = RecordContent::from_input(
+            &DNSRecordType::MX,
+            "mail.example.com",
+            &RecordSpec::default(),
+        )
+        .unwrap();
+        assert_eq!(mx.priority, None);
+
+        assert!(RecordContent::from_input(
+            &DNSRecordType::SRV,
+            "5060 sip.example.com",
+            &RecordSpec::default()
+        )
+        .is_err());
+        assert!(
+            RecordContent::from_input(&DNSRecordType::CAA, "issue", &RecordSpec::default())
+                .is_err()
+        );
+        let caa = RecordContent::from_input(
+            &DNSRecordType::CAA,
+            "0 issue letsencrypt.org",
+            &RecordSpec::default(),
+        )
+        .unwrap();
+        assert_eq!(caa.content, "0 issue \"letsencrypt.org\"");
+        assert!(RecordContent::from_input(&DNSRecordType::A, "203.0.113.10", &spec).is_err());
+    }
+
+    #[test]
+    fn test_caa_data() {
+        let caa = CaaData::parse("0 issue \"letsencrypt.org\"").unwrap();
+        assert_eq!(caa.flags, 0);
+        assert_eq!(caa.tag, "issue");
+        assert_eq!(caa.value, "letsencrypt.org");
+        assert_eq!(caa.to_string(), "0 issue \"letsencrypt.org\"");
+
+        let caa = CaaData::parse("128 iodef mailto:security@example.com").unwrap();
+        assert_eq!(caa.flags, 128);
+        assert_eq!(caa.value, "mailto:security@example.com");
+
+        assert!(CaaData::parse("issue letsencrypt.org").is_err());
+    }
+
+    #[test]
+    fn test_full_record_name() {
+        assert_eq!(full_record_name("example.com", "www"), "www.example.com");
+        assert_eq!(full_record_name("example.com", "@"), "example.com");
+        assert_eq!(full_record_name("example.com", ""), "example.com");
+    }
+
     #[test]
     fn test_credentials_default() {
         let creds = Credentials::default();
         assert!(creds.api_key.is_none());
         assert!(creds.api_secret.is_none());
     }
+
+    #[test]
+    fn test_record_spec_ensure_supported() {
+        let ttl_only = ProviderCapabilities {
+            ttl: true,
+            ..Default::default()
+        };
+        assert!(RecordSpec::default()
+            .ensure_supported(&ProviderCapabilities::default())
+            .is_ok());
+
+        let spec = RecordSpec {
+            ttl: Some(600),
+            proxied: Some(false),
+            ..Default::default()
+        };
+        assert!(spec.ensure_supported(&ttl_only).is_ok());
+
+        let spec = RecordSpec {
+            ttl: Some(600),
+            proxied: Some(true),
+            comment: Some("home".to_string()),
+            ..Default::default()
+        };
+        match spec.ensure_supported(&ttl_only) {
+            Err(AppError::Provider(ProviderError::InvalidConfig(message))) => {
+                assert!(message.contains("proxied"));
+                assert!(message.contains("comment"));
+                assert!(!message.contains("ttl"));
+            }
+            other => panic!("unexpected result: {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_record_spec_priority_for() {
+        let spec = RecordSpec {
+            priority: Some(10),
+            ..Default::default()
+        };
+        assert_eq!(spec.priority_for(&DNSRecordType::MX).unwrap(), Some(10));
+        assert_eq!(spec.priority_for(&DNSRecordType::SRV).unwrap(), Some(10));
+        assert!(spec.priority_for(&DNSRecordType::A).is_err());
+        assert_eq!(
+            RecordSpec::default()
+                .priority_for(&DNSRecordType::A)
+                .unwrap(),
+            None
+        );
+    }
 }
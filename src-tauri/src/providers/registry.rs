@@ -0,0 +1,315 @@
+//! DNS 提供商注册表
+//!
+//! 每个提供商在这里登记一次: 构造函数与凭证字段。
+//! 显示名称、支持的记录类型与能力直接取自提供商实现, 避免两处维护。
+//! `ProviderFactory` 和前端的域名表单都从注册表读取。
+
+use super::provider_trait::{DNSProvider, DNSRecordType, ProviderCapabilities};
+use serde::Serialize;
+use std::sync::LazyLock;
+
+/// 凭证字段的输入类型
+#[derive(Debug, Clone, Copy, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum CredentialFieldType {
+    /// 普通文本
+    Text,
+    /// 敏感信息, 输入时隐藏
+    Password,
+    /// 多行文本 (JSON 等)
+    Textarea,
+}
+
+/// 提供商所需的一个凭证字段
+///
+/// `key` 即前端提交的凭证键名: `api_key`/`api_secret`/`access_key`/`region`
+/// 落入同名字段, 其余键进入 `extra`。
+#[derive(Debug, Clone, Serialize)]
+pub struct CredentialField {
+    pub key: &'static str,
+    pub label: &'static str,
+    #[serde(rename = "type")]
+    pub field_type: CredentialFieldType,
+    pub required: bool,
+    pub help: Option<&'static str>,
+}
+
+impl CredentialField {
+    /// 必填字段
+    fn required(key: &'static str, label: &'static str) -> Self {
+        Self {
+            key,
+            label,
+            field_type: CredentialFieldType::Text,
+            required: true,
+            help: None,
+        }
+    }
+
+    /// 可选字段
+    fn optional(key: &'static str, label: &'static str) -> Self {
+        Self {
+            required: false,
+            ..Self::required(key, label)
+        }
+    }
+
+    fn secret(mut self) -> Self {
+        self.field_type = CredentialFieldType::Password;
+        self
+    }
+
+    fn textarea(mut self) -> Self {
+        self.field_type = CredentialFieldType::Textarea;
+        self
+    }
+
+    fn help(mut self, help: &'static str) -> Self {
+        self.help = Some(help);
+        self
+    }
+}
+
+/// 提供商描述
+#[derive(Debug, Clone, Serialize)]
+pub struct ProviderDescriptor {
+    pub id: &'static str,
+    pub name: &'static str,
+    pub credential_fields: Vec<CredentialField>,
+    pub record_types: Vec<DNSRecordType>,
+    pub capabilities: ProviderCapabilities,
+    #[serde(skip)]
+    constructor: fn() -> Box<dyn DNSProvider>,
+}
+
+impl ProviderDescriptor {
+    fn of<P: DNSProvider + Default + 'static>(credential_fields: Vec<CredentialField>) -> Self {
+        let provider = P::default();
+        Self {
+            id: provider.provider_id(),
+            name: provider.provider_name(),
+            credential_fields,
+            record_types: provider.supported_record_types(),
+            capabilities: provider.capabilities(),
+            constructor: || Box::new(P::default()),
+        }
+    }
+
+    /// 创建一个未初始化的提供商实例
+    pub fn create(&self) -> Box<dyn DNSProvider> {
+        (self.constructor)()
+    }
+
+    /// 必填的凭证字段
+    pub fn required_fields(&self) -> impl Iterator<Item = &CredentialField> {
+        self.credential_fields.iter().filter(|field| field.required)
+    }
+}
+
+static REGISTRY: LazyLock<Vec<ProviderDescriptor>> = LazyLock::new(build_registry);
+
+/// 所有已注册的提供商, 按界面展示顺序排列
+pub fn descriptors() -> &'static [ProviderDescriptor] {
+    &REGISTRY
+}
+
+/// 按 ID 查找提供商
+pub fn find(provider_id: &str) -> Option<&'static ProviderDescriptor> {
+    REGISTRY.iter().find(|d| d.id == provider_id)
+}
+
+fn build_registry() -> Vec<ProviderDescriptor> {
+    use super::*;
+    use CredentialField as F;
+
+    vec![
+        ProviderDescriptor::of::<cloudflare::CloudflareProvider>(vec![
+            F::optional("api_key", "API Token")
+                .secret()
+                .help("推荐。需要 Zone.DNS 编辑权限; 未填写时使用下方 Email + Global API Key"),
+            F::optional("email", "账户 Email").help("仅在使用 Global API Key 时填写"),
+            F::optional("api_secret", "Global API Key").secret(),
+        ]),
+        ProviderDescriptor::of::<aliyun::AliyunProvider>(vec![
+            F::required("access_key_id", "AccessKey ID"),
+            F::required("access_key_secret", "AccessKey Secret").secret(),
+            F::optional("region", "地域").help("默认 cn-hangzhou"),
+        ]),
+        ProviderDescriptor::of::<tencent::TencentProvider>(vec![
+            F::required("secret_id", "SecretId"),
+            F::required("secret_key", "SecretKey").secret(),
+        ]),
+        ProviderDescriptor::of::<aws::AwsProvider>(vec![
+            F::required("access_key_id", "Access Key ID"),
+            F::required("secret_access_key", "Secret Access Key").secret(),
+            F::optional("change_timeout", "同步等待超时 (秒)")
+                .help("等待 Route53 变更同步 (INSYNC) 的最长时间, 默认 120"),
+        ]),
+        ProviderDescriptor::of::<huawei::HuaweiProvider>(vec![
+            F::required("access_key", "Access Key (AK)"),
+            F::required("secret_key", "Secret Key (SK)").secret(),
+            F::optional("region", "Region").help("如 cn-north-4, 默认使用全局终端节点"),
+        ]),
+        ProviderDescriptor::of::<baidu::BaiduProvider>(vec![
+            F::required("access_key_id", "Access Key ID"),
+            F::required("secret_access_key", "Secret Access Key").secret(),
+        ]),
+        ProviderDescriptor::of::<jdcloud::JdcloudProvider>(vec![
+            F::required("access_key", "Access Key"),
+            F::required("secret_key", "Secret Key").secret(),
+        ]),
+        ProviderDescriptor::of::<rfc2136::Rfc2136Provider>(vec![
+            F::required("server", "主服务器").help("如 ns1.example.com:53, 端口默认 53"),
+            F::required("key_name", "TSIG 密钥名称"),
+            F::required("key_secret", "TSIG 密钥")
+                .secret()
+                .help("Base64 编码"),
+            F::optional("algorithm", "TSIG 算法").help("hmac-sha256 (默认) 或 hmac-sha512"),
+            F::optional("protocol", "传输协议").help("udp (默认, 响应被截断时自动改用 TCP) 或 tcp"),
+            F::optional("zone", "区域名称").help("默认与域名相同"),
+        ]),
+        ProviderDescriptor::of::<webhook::WebhookProvider>(vec![
+            F::required("url", "URL 模板")
+                .help("支持 {ip} {domain} {subdomain} {fqdn} {record_type} 占位符"),
+            F::optional("method", "请求方法").help("默认 GET"),
+            F::optional("headers", "请求头")
+                .textarea()
+                .help("JSON 对象"),
+            F::optional("body", "请求体模板").textarea(),
+            F::optional("success_status", "成功状态码").help("如 200 或 2xx, 默认 2xx"),
+            F::optional("success_body_regex", "响应正则"),
+            F::optional("success_json_path", "JSON 路径").help("如 $.result.ok"),
+            F::optional("success_json_value", "JSON 期望值"),
+        ]),
+        ProviderDescriptor::of::<dyndns2::DynDns2Provider>(vec![
+            F::optional("server", "更新服务器")
+                .help("默认 members.dyndns.org, No-IP 填写 dynupdate.no-ip.com"),
+            F::required("username", "用户名"),
+            F::required("password", "密码").secret(),
+        ]),
+        ProviderDescriptor::of::<duckdns::DuckDnsProvider>(vec![
+            F::required("token", "Token").secret()
+        ]),
+        ProviderDescriptor::of::<dynu::DynuProvider>(vec![
+            F::required("username", "用户名"),
+            F::required("password", "密码")
+                .secret()
+                .help("也可填写密码的 MD5 或 SHA256"),
+        ]),
+        ProviderDescriptor::of::<freedns::FreeDnsProvider>(vec![
+            F::optional("token", "更新 Token")
+                .secret()
+                .help("与 AAAA Token 至少填写一个"),
+            F::optional("token_v6", "AAAA 记录更新 Token")
+                .secret()
+                .help("未填写时 AAAA 记录也使用上方 Token"),
+        ]),
+        ProviderDescriptor::of::<gandi::GandiProvider>(vec![F::required(
+            "api_token",
+            "Personal Access Token",
+        )
+        .secret()]),
+        ProviderDescriptor::of::<digitalocean::DigitalOceanProvider>(vec![F::required(
+            "api_token",
+            "API Token",
+        )
+        .secret()]),
+        ProviderDescriptor::of::<linode::LinodeProvider>(vec![F::required(
+            "api_token",
+            "Personal Access Token",
+        )
+        .secret()]),
+        ProviderDescriptor::of::<hetzner::HetznerProvider>(vec![F::required(
+            "api_token",
+            "API Token",
+        )
+        .secret()
+        .help("在 Hetzner Cloud Console 中创建")]),
+        ProviderDescriptor::of::<porkbun::PorkbunProvider>(vec![
+            F::required("api_key", "API Key"),
+            F::required("secret_api_key", "Secret API Key").secret(),
+        ]),
+        ProviderDescriptor::of::<namecheap::NamecheapProvider>(vec![
+            F::required("api_user", "API User"),
+            F::required("api_key", "API Key").secret(),
+            F::required("client_ip", "白名单 IP").help("已加入 API 白名单的 IPv4 地址"),
+            F::optional("username", "账户用户名").help("默认与 API User 相同"),
+        ]),
+        ProviderDescriptor::of::<namecom::NameComProvider>(vec![
+            F::required("username", "用户名"),
+            F::required("api_token", "API Token").secret(),
+        ]),
+        ProviderDescriptor::of::<azure::AzureProvider>(vec![
+            F::required("tenant_id", "租户 ID (Tenant ID)"),
+            F::required("client_id", "应用程序 ID (Client ID)"),
+            F::required("client_secret", "客户端密码 (Client Secret)").secret(),
+            F::required("subscription_id", "订阅 ID (Subscription ID)"),
+            F::required("resource_group", "资源组").help("DNS 区域所在的资源组"),
+        ]),
+        ProviderDescriptor::of::<gcp::GcpProvider>(vec![
+            F::required("service_account_json", "服务账号密钥")
+                .textarea()
+                .help("粘贴 JSON 密钥文件的完整内容"),
+            F::optional("project_id", "项目 ID").help("默认取密钥中的 project_id"),
+            F::optional("managed_zone", "托管区域名称").help("默认按域名查找"),
+        ]),
+    ]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashSet;
+
+    #[test]
+    fn test_registry_ids_are_unique() {
+        let ids: HashSet<_> = descriptors().iter().map(|d| d.id).collect();
+        assert_eq!(ids.len(), descriptors().len());
+    }
+
+    #[test]
+    fn test_descriptor_creates_matching_provider() {
+        for descriptor in descriptors() {
+            assert_eq!(descriptor.create().provider_id(), descriptor.id);
+            assert!(
+                !descriptor.credential_fields.is_empty(),
+                "{}",
+                descriptor.id
+            );
+            assert!(!descriptor.record_types.is_empty(), "{}", descriptor.id);
+        }
+    }
+
+    #[test]
+    fn test_credential_keys_are_unique_per_provider() {
+        for descriptor in descriptors() {
+            let keys: HashSet<_> = descriptor.credential_fields.iter().map(|f| f.key).collect();
+            assert_eq!(
+                keys.len(),
+                descriptor.credential_fields.len(),
+                "{}",
+                descriptor.id
+            );
+        }
+    }
+
+    #[test]
+    fn test_cloudflare_descriptor() {
+        let cloudflare = find("cloudflare").unwrap();
+        assert!(cloudflare.capabilities.proxy);
+        assert_eq!(cloudflare.required_fields().count(), 0);
+        let keys: Vec<_> = cloudflare.credential_fields.iter().map(|f| f.key).collect();
+        assert_eq!(keys, vec!["api_key", "email", "api_secret"]);
+    }
+
+    #[test]
+    fn test_descriptor_serialization() {
+        let json = serde_json::to_value(find("aliyun").unwrap()).unwrap();
+        assert_eq!(json["id"], "aliyun");
+        assert_eq!(json["capabilities"]["line"], true);
+        assert_eq!(json["credential_fields"][0]["key"], "access_key_id");
+        assert_eq!(json["credential_fields"][1]["type"], "password");
+        assert_eq!(json["credential_fields"][2]["required"], false);
+        assert!(json.get("constructor").is_none());
+    }
+}
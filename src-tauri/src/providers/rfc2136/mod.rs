@@ -0,0 +1,1365 @@
+// RFC 2136 动态更新 (用于自建的 BIND / Knot / PowerDNS 等权威服务器)
+use crate::error::{AppError, Result};
+use crate::providers::provider_trait::{
+    full_record_name, CaaData, Credentials, DNSProvider, DNSRecord, DNSRecordType,
+    ProviderCapabilities, ProviderError, RecordContent, RecordSpec, UpdateResult,
+};
+use async_trait::async_trait;
+use base64::Engine;
+use std::net::SocketAddr;
+use std::str::FromStr;
+use std::time::Duration;
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::{TcpStream, UdpSocket};
+use trust_dns_proto::op::{Message, MessageType, OpCode, Query, ResponseCode};
+use trust_dns_proto::rr::dnssec::rdata::tsig::{
+    make_tsig_record, message_tbs, signed_bitmessage_to_buf, TsigAlgorithm, TSIG,
+};
+use trust_dns_proto::rr::dnssec::rdata::DNSSECRData;
+use trust_dns_proto::rr::rdata::{A, AAAA, CAA, CNAME, MX, NS, SRV, TXT};
+use trust_dns_proto::rr::{DNSClass, Name, RData, Record, RecordType};
+use trust_dns_proto::serialize::binary::{BinDecoder, BinEncodable, BinEncoder, Restrict};
+
+/// 默认 DNS 端口
+const DEFAULT_PORT: u16 = 53;
+
+/// 新建记录的默认 TTL
+const DEFAULT_TTL: u32 = 300;
+
+/// 未指定优先级时 MX 记录使用的优先级
+const DEFAULT_MX_PRIORITY: u16 = 10;
+
+/// TSIG 允许的时间误差 (秒)
+const TSIG_FUDGE: u16 = 300;
+
+/// UDP 响应缓冲区大小
+const UDP_BUFFER_SIZE: usize = 4096;
+
+/// 传输协议
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Protocol {
+    Udp,
+    Tcp,
+}
+
+/// TSIG 密钥
+#[derive(Clone)]
+struct TsigKey {
+    name: Name,
+    algorithm: TsigAlgorithm,
+    secret: Vec<u8>,
+}
+
+/// RFC 2136 动态更新提供商
+///
+/// 凭证通过 `extra` 配置:
+/// - `server`: 主服务器地址, 例如 `ns1.example.com` 或 `192.0.2.1:5353`
+/// - `key_name` / `key_secret`: TSIG 密钥名称和 Base64 编码的密钥
+/// - `algorithm`: `hmac-sha256` (默认) 或 `hmac-sha512`
+/// - `protocol`: `udp` (默认, 响应被截断时自动改用 TCP) 或 `tcp`
+/// - `zone`: 区域名称, 默认与域名相同
+pub struct Rfc2136Provider {
+    server: Option<String>,
+    key: Option<TsigKey>,
+    protocol: Protocol,
+    zone: Option<String>,
+    timeout: Duration,
+}
+
+impl Rfc2136Provider {
+    pub fn new() -> Self {
+        Self {
+            server: None,
+            key: None,
+            protocol: Protocol::Udp,
+            zone: None,
+            timeout: Duration::from_secs(10),
+        }
+    }
+
+    /// 解析服务器地址
+    async fn server_addr(&self) -> Result<SocketAddr> {
+        let server = self.server.as_deref().ok_or_else(|| {
+            AppError::Provider(ProviderError::InvalidConfig(
+                "未设置 DNS 服务器".to_string(),
+            ))
+        })?;
+
+        if let Ok(addr) = server.parse::<SocketAddr>() {
+            return Ok(addr);
+        }
+        if let Ok(ip) = server.parse::<std::net::IpAddr>() {
+            return Ok(SocketAddr::new(ip, DEFAULT_PORT));
+        }
+
+        let target = if server.contains(':') {
+            server.to_string()
+        } else {
+            format!("{}:{}", server, DEFAULT_PORT)
+        };
+        let mut addrs = tokio::net::lookup_host(&target).await.map_err(|e| {
+            AppError::Provider(ProviderError::NetworkError(format!(
+                "解析服务器地址 {} 失败: {}",
+                server, e
+            )))
+        })?;
+        addrs.next().ok_or_else(|| {
+            AppError::Provider(ProviderError::NetworkError(format!(
+                "无法解析服务器地址: {}",
+                server
+            )))
+        })
+    }
+
+    /// 获取域名所在的区域
+    fn zone_name(&self, domain: &str) -> Result<Name> {
+        parse_name(self.zone.as_deref().unwrap_or(domain))
+    }
+
+    /// 签名并发送消息, 返回服务器响应
+    async fn exchange(&self, mut message: Message) -> Result<Message> {
+        let key = self.key.as_ref().ok_or_else(|| {
+            AppError::Provider(ProviderError::AuthenticationFailed(
+                "未设置 TSIG 密钥".to_string(),
+            ))
+        })?;
+        let request_mac = sign_message(&mut message, key, unix_time())?;
+        let request = message.to_vec().map_err(proto_error)?;
+        let addr = self.server_addr().await?;
+
+        let mut bytes = match self.protocol {
+            Protocol::Udp => self.send_udp(addr, &request).await?,
+            Protocol::Tcp => self.send_tcp(addr, &request).await?,
+        };
+        let mut response = Message::from_vec(&bytes).map_err(proto_error)?;
+
+        // 响应被截断时改用 TCP 重新发送
+        if self.protocol == Protocol::Udp && response.truncated() {
+            bytes = self.send_tcp(addr, &request).await?;
+            response = Message::from_vec(&bytes).map_err(proto_error)?;
+        }
+
+        if response.id() != message.id() {
+            return Err(AppError::Provider(ProviderError::NetworkError(
+                "响应 ID 与请求不匹配".to_string(),
+            )));
+        }
+        authenticate_response(key, &request_mac, &bytes, &response, true)?;
+        check_response(&response)?;
+
+        Ok(response)
+    }
+
+    async fn send_udp(&self, addr: SocketAddr, request: &[u8]) -> Result<Vec<u8>> {
+        let bind: SocketAddr = if addr.is_ipv6() {
+            "[::]:0".parse().unwrap()
+        } else {
+            "0.0.0.0:0".parse().unwrap()
+        };
+        let socket = UdpSocket::bind(bind).await?;
+        socket.connect(addr).await?;
+        socket.send(request).await?;
+
+        let mut buffer = vec![0u8; UDP_BUFFER_SIZE];
+        let len = tokio::time::timeout(self.timeout, socket.recv(&mut buffer))
+            .await
+            .map_err(|_| timeout_error(addr))??;
+        buffer.truncate(len);
+        Ok(buffer)
+    }
+
+    async fn send_tcp(&self, addr: SocketAddr, request: &[u8]) -> Result<Vec<u8>> {
+        let mut stream = self.connect_tcp(addr).await?;
+        write_tcp_message(&mut stream, request).await?;
+        tokio::time::timeout(self.timeout, read_tcp_message(&mut stream))
+            .await
+            .map_err(|_| timeout_error(addr))?
+    }
+
+    async fn connect_tcp(&self, addr: SocketAddr) -> Result<TcpStream> {
+        tokio::time::timeout(self.timeout, TcpStream::connect(addr))
+            .await
+            .map_err(|_| timeout_error(addr))?
+            .map_err(|e| {
+                AppError::Provider(ProviderError::NetworkError(format!(
+                    "连接 {} 失败: {}",
+                    addr, e
+                )))
+            })
+    }
+
+    /// 通过 AXFR 获取区域内的全部记录 (始终使用 TCP, 每条响应消息都需要有效的 TSIG 签名)
+    async fn zone_transfer(&self, zone: &Name) -> Result<Vec<Record>> {
+        let key = self.key.as_ref().ok_or_else(|| {
+            AppError::Provider(ProviderError::AuthenticationFailed(
+                "未设置 TSIG 密钥".to_string(),
+            ))
+        })?;
+
+        let mut message = new_message(OpCode::Query);
+        message.add_query(Query::query(zone.clone(), RecordType::AXFR));
+        let request_mac = sign_message(&mut message, key, unix_time())?;
+        let request = message.to_vec().map_err(proto_error)?;
+
+        let addr = self.server_addr().await?;
+        let mut stream = self.connect_tcp(addr).await?;
+        write_tcp_message(&mut stream, &request).await?;
+
+        // 区域传送以 SOA 记录开始, 并以同一条 SOA 记录结束, 可能分为多条消息
+        // 后续消息的签名以上一条消息的 MAC 为前缀, 形成签名链
+        let mut records = Vec::new();
+        let mut soa_count = 0;
+        let mut previous_mac = request_mac;
+        let mut first_message = true;
+        while soa_count < 2 {
+            let bytes = tokio::time::timeout(self.timeout, read_tcp_message(&mut stream))
+                .await
+                .map_err(|_| timeout_error(addr))??;
+            let response = Message::from_vec(&bytes).map_err(proto_error)?;
+            if response.id() != message.id() {
+                return Err(AppError::Provider(ProviderError::NetworkError(
+                    "响应 ID 与请求不匹配".to_string(),
+                )));
+            }
+            previous_mac =
+                authenticate_response(key, &previous_mac, &bytes, &response, first_message)?;
+            first_message = false;
+            check_response(&response)?;
+            if response.answers().is_empty() {
+                break;
+            }
+
+            for record in response.answers() {
+                if record.record_type() == RecordType::SOA {
+                    soa_count += 1;
+                } else {
+                    records.push(record.clone());
+                }
+            }
+        }
+
+        Ok(records)
+    }
+
+    /// 查询指定名称和类型的记录
+    async fn query(&self, name: &Name, record_type: &DNSRecordType) -> Result<Vec<Record>> {
+        let mut message = new_message(OpCode::Query);
+        message.add_query(Query::query(name.clone(), to_record_type(record_type)?));
+
+        let response = match self.exchange(message).await {
+            Ok(response) => response,
+            Err(AppError::Provider(ProviderError::RecordNotFound(_))) => return Ok(Vec::new()),
+            Err(e) => return Err(e),
+        };
+
+        Ok(response
+            .answers()
+            .iter()
+            .filter(|r| r.name() == name)
+            .cloned()
+            .collect())
+    }
+
+    /// 发送 UPDATE 消息, `updates` 为更新区中的记录
+    async fn send_update(&self, zone: &Name, updates: Vec<Record>) -> Result<()> {
+        let mut message = new_message(OpCode::Update);
+        let mut zone_query = Query::query(zone.clone(), RecordType::SOA);
+        zone_query.set_query_class(DNSClass::IN);
+        message.add_query(zone_query);
+        message.add_name_servers(updates);
+
+        self.exchange(message).await?;
+        Ok(())
+    }
+}
+
+impl Default for Rfc2136Provider {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[async_trait]
+impl DNSProvider for Rfc2136Provider {
+    fn provider_id(&self) -> &'static str {
+        "rfc2136"
+    }
+
+    fn provider_name(&self) -> &'static str {
+        "RFC 2136 (自建 DNS 服务器)"
+    }
+
+    async fn initialize(&mut self, credentials: &Credentials) -> Result<()> {
+        let extra = |key: &str| {
+            credentials
+                .extra
+                .get(key)
+                .and_then(|v| v.as_str())
+                .map(|v| v.trim().to_string())
+                .filter(|v| !v.is_empty())
+        };
+
+        self.server = extra("server");
+        self.zone = extra("zone");
+        self.protocol = match extra("protocol").as_deref() {
+            None | Some("udp") => Protocol::Udp,
+            Some("tcp") => Protocol::Tcp,
+            Some(other) => {
+                return Err(AppError::Provider(ProviderError::InvalidConfig(format!(
+                    "不支持的传输协议: {} (可选 udp 或 tcp)",
+                    other
+                ))))
+            }
+        };
+
+        let algorithm = match extra("algorithm").as_deref() {
+            None | Some("hmac-sha256") => TsigAlgorithm::HmacSha256,
+            Some("hmac-sha512") => TsigAlgorithm::HmacSha512,
+            Some(other) => {
+                return Err(AppError::Provider(ProviderError::InvalidConfig(format!(
+                    "不支持的 TSIG 算法: {} (可选 hmac-sha256 或 hmac-sha512)",
+                    other
+                ))))
+            }
+        };
+
+        let (key_name, key_secret) = match (
+            extra("key_name").or_else(|| credentials.api_key.clone()),
+            extra("key_secret").or_else(|| credentials.api_secret.clone()),
+        ) {
+            (Some(name), Some(secret)) => (name, secret),
+            _ => {
+                return Err(AppError::Provider(ProviderError::InvalidConfig(
+                    "RFC 2136 需要提供 key_name 和 key_secret (TSIG 密钥)".to_string(),
+                )))
+            }
+        };
+        let secret = base64::engine::general_purpose::STANDARD
+            .decode(key_secret.trim())
+            .map_err(|e| {
+                AppError::Provider(ProviderError::InvalidConfig(format!(
+                    "TSIG 密钥不是有效的 Base64: {}",
+                    e
+                )))
+            })?;
+
+        if self.server.is_none() {
+            return Err(AppError::Provider(ProviderError::InvalidConfig(
+                "RFC 2136 需要提供 server (主服务器地址)".to_string(),
+            )));
+        }
+
+        self.key = Some(TsigKey {
+            name: parse_name(&key_name)?,
+            algorithm,
+            secret,
+        });
+
+        // 测试连接
+        self.test_connection().await?;
+
+        Ok(())
+    }
+
+    async fn list_records(&self, domain: &str) -> Result<Vec<DNSRecord>> {
+        let zone = self.zone_name(domain)?;
+        let records = self.zone_transfer(&zone).await?;
+
+        Ok(records.iter().filter_map(to_dns_record).collect())
+    }
+
+    async fn get_record(
+        &self,
+        domain: &str,
+        record_name: &str,
+        record_type: DNSRecordType,
+    ) -> Result<Option<DNSRecord>> {
+        let name = parse_name(&full_record_name(domain, record_name))?;
+        let records = self.query(&name, &record_type).await?;
+
+        Ok(records
+            .iter()
+            .filter_map(to_dns_record)
+            .find(|r| r.record_type == record_type))
+    }
+
+    async fn update_record(
+        &self,
+        domain: &str,
+        record_id: &str,
+        new_content: &str,
+    ) -> Result<UpdateResult> {
+        self.update_record_with(domain, record_id, new_content, &RecordSpec::default())
+            .await
+    }
+
+    async fn update_record_with(
+        &self,
+        domain: &str,
+        record_id: &str,
+        new_content: &str,
+        spec: &RecordSpec,
+    ) -> Result<UpdateResult> {
+        spec.ensure_supported(&self.capabilities())?;
+        let (name, record_type, old_rdata) = parse_record_id(record_id)?;
+        let zone = self.zone_name(domain)?;
+
+        // 查询当前记录, 未指定 TTL 和优先级时保留原值
+        let (current, old) = self
+            .query(&name, &record_type)
+            .await?
+            .into_iter()
+            .filter_map(|r| to_dns_record(&r).map(|d| (r, d)))
+            .find(|(_, d)| d.rdata() == old_rdata)
+            .ok_or_else(|| {
+                AppError::Provider(ProviderError::RecordNotFound(record_id.to_string()))
+            })?;
+
+        let mut content = RecordContent::from_input(&record_type, new_content, spec)?;
+        content.priority = content.priority.or(old.priority);
+        let new_rdata = content.to_rdata(&record_type);
+
+        let mut delete = current.clone();
+        delete.set_dns_class(DNSClass::NONE).set_ttl(0);
+        let add = Record::from_rdata(
+            name.clone(),
+            spec.ttl.unwrap_or(current.ttl()),
+            to_rdata(&record_type, &new_rdata)?,
+        );
+        self.send_update(&zone, vec![delete, add]).await?;
+
+        Ok(UpdateResult {
+            success: true,
+            record_id: record_id_for(&name, &record_type, &new_rdata),
+            old_ip: old_rdata,
+            new_ip: new_content.to_string(),
+            message: "更新成功".to_string(),
+        })
+    }
+
+    async fn create_record(
+        &self,
+        domain: &str,
+        record_name: &str,
+        record_type: DNSRecordType,
+        content: &str,
+    ) -> Result<DNSRecord> {
+        self.create_record_with(
+            domain,
+            record_name,
+            record_type,
+            content,
+            &RecordSpec::default(),
+        )
+        .await
+    }
+
+    async fn create_record_with(
+        &self,
+        domain: &str,
+        record_name: &str,
+        record_type: DNSRecordType,
+        content: &str,
+        spec: &RecordSpec,
+    ) -> Result<DNSRecord> {
+        spec.ensure_supported(&self.capabilities())?;
+        let mut content = RecordContent::from_input(&record_type, content, spec)?;
+        if record_type == DNSRecordType::MX {
+            content.priority = content.priority.or(Some(DEFAULT_MX_PRIORITY));
+        }
+
+        let zone = self.zone_name(domain)?;
+        let name = parse_name(&full_record_name(domain, record_name))?;
+        let record = Record::from_rdata(
+            name,
+            spec.ttl.unwrap_or(DEFAULT_TTL),
+            to_rdata(&record_type, &content.to_rdata(&record_type))?,
+        );
+
+        self.send_update(&zone, vec![record.clone()]).await?;
+
+        to_dns_record(&record).ok_or_else(|| {
+            AppError::Provider(ProviderError::ParseError(format!(
+                "不支持的记录类型: {}",
+                record_type
+            )))
+        })
+    }
+
+    async fn delete_record(&self, domain: &str, record_id: &str) -> Result<()> {
+        let (name, record_type, content) = parse_record_id(record_id)?;
+        let zone = self.zone_name(domain)?;
+
+        // CLASS NONE 表示从记录集中删除这一条记录
+        let mut delete = Record::from_rdata(name, 0, to_rdata(&record_type, &content)?);
+        delete.set_dns_class(DNSClass::NONE);
+
+        self.send_update(&zone, vec![delete]).await
+    }
+
+    async fn test_connection(&self) -> Result<bool> {
+        match &self.zone {
+            // 查询区域的 SOA 记录, 同时验证 TSIG 密钥
+            Some(zone) => {
+                let zone = parse_name(zone)?;
+                let records = self.query(&zone, &DNSRecordType::SOA).await?;
+                Ok(!records.is_empty())
+            }
+            None => {
+                self.server_addr().await?;
+                Ok(true)
+            }
+        }
+    }
+
+    fn supported_record_types(&self) -> Vec<DNSRecordType> {
+        vec![
+            DNSRecordType::A,
+            DNSRecordType::AAAA,
+            DNSRecordType::CNAME,
+            DNSRecordType::MX,
+            DNSRecordType::TXT,
+            DNSRecordType::NS,
+            DNSRecordType::SRV,
+            DNSRecordType::CAA,
+        ]
+    }
+
+    fn capabilities(&self) -> ProviderCapabilities {
+        ProviderCapabilities {
+            proxy: false,
+            ttl: true,
+            line: false,
+            priority: true,
+            comment: false,
+        }
+    }
+}
+
+// ============ TSIG ============
+
+/// 为消息添加 TSIG 签名, 返回请求的 MAC (用于验证响应)
+fn sign_message(message: &mut Message, key: &TsigKey, time: u64) -> Result<Vec<u8>> {
+    let pre_tsig = TSIG::new(
+        key.algorithm.clone(),
+        time,
+        TSIG_FUDGE,
+        Vec::new(),
+        message.id(),
+        0,
+        Vec::new(),
+    );
+    let tbs = message_tbs(None, message, &pre_tsig, &key.name).map_err(proto_error)?;
+    let mac = tsig_mac(key, &tbs)?;
+
+    message.add_tsig(make_tsig_record(
+        key.name.clone(),
+        pre_tsig.set_mac(mac.clone()),
+    ));
+    Ok(mac)
+}
+
+/// 校验响应的 TSIG 签名, 返回响应的 MAC (区域传送的下一条消息以它为前缀)
+///
+/// 签名请求的响应必须带有有效签名。服务器不认识密钥 (BADKEY) 或请求签名错误 (BADSIG) 时,
+/// RFC 8945 允许返回不带 MAC 的 NOTAUTH 响应, 这类响应只会被当作认证失败
+fn authenticate_response(
+    key: &TsigKey,
+    previous_mac: &[u8],
+    bytes: &[u8],
+    response: &Message,
+    first_message: bool,
+) -> Result<Vec<u8>> {
+    let signed = response.signature().iter().any(|record| {
+        matches!(record.data(), Some(RData::DNSSEC(DNSSECRData::TSIG(tsig))) if !tsig.mac().is_empty())
+    });
+    if signed {
+        return verify_response(key, previous_mac, bytes, first_message);
+    }
+
+    let detail = if response.response_code() == ResponseCode::NotAuth {
+        format!("DNS 服务器拒绝了 TSIG 密钥 {}", key.name)
+    } else {
+        format!("DNS 服务器返回了未签名的 {} 响应", response.response_code())
+    };
+    Err(AppError::Provider(ProviderError::AuthenticationFailed(
+        detail,
+    )))
+}
+
+/// 验证响应中的 TSIG 签名, 返回响应的 MAC
+fn verify_response(
+    key: &TsigKey,
+    previous_mac: &[u8],
+    response: &[u8],
+    first_message: bool,
+) -> Result<Vec<u8>> {
+    let (tbv, record) = signed_bitmessage_to_buf(Some(previous_mac), response, first_message)
+        .map_err(proto_error)?;
+    let tsig = match record.data() {
+        Some(RData::DNSSEC(DNSSECRData::TSIG(tsig))) => tsig,
+        _ => {
+            return Err(AppError::Provider(ProviderError::AuthenticationFailed(
+                "响应中的 TSIG 记录无效".to_string(),
+            )))
+        }
+    };
+
+    if record.name() != &key.name || tsig.mac() != tsig_mac(key, &tbv)?.as_slice() {
+        return Err(AppError::Provider(ProviderError::AuthenticationFailed(
+            "响应的 TSIG 签名校验失败".to_string(),
+        )));
+    }
+
+    Ok(tsig.mac().to_vec())
+}
+
+/// 计算 HMAC
+fn tsig_mac(key: &TsigKey, data: &[u8]) -> Result<Vec<u8>> {
+    use hmac::{Hmac, Mac};
+    use sha2::{Sha256, Sha512};
+
+    match key.algorithm {
+        TsigAlgorithm::HmacSha256 => {
+            let mut mac =
+                Hmac::<Sha256>::new_from_slice(&key.secret).expect("HMAC can take key of any size");
+            mac.update(data);
+            Ok(mac.finalize().into_bytes().to_vec())
+        }
+        TsigAlgorithm::HmacSha512 => {
+            let mut mac =
+                Hmac::<Sha512>::new_from_slice(&key.secret).expect("HMAC can take key of any size");
+            mac.update(data);
+            Ok(mac.finalize().into_bytes().to_vec())
+        }
+        ref other => Err(AppError::Provider(ProviderError::InvalidConfig(format!(
+            "不支持的 TSIG 算法: {:?}",
+            other
+        )))),
+    }
+}
+
+// ============ 辅助函数 ============
+
+fn new_message(op_code: OpCode) -> Message {
+    let mut message = Message::new();
+    message
+        .set_id(uuid::Uuid::new_v4().as_u128() as u16)
+        .set_message_type(MessageType::Query)
+        .set_op_code(op_code)
+        .set_recursion_desired(false);
+    message
+}
+
+/// 将响应码映射为 ProviderError
+fn check_response(response: &Message) -> Result<()> {
+    let code = response.response_code();
+    let detail = format!("DNS 服务器返回 {}", code);
+
+    let error = match code {
+        ResponseCode::NoError => return Ok(()),
+        ResponseCode::NotAuth
+        | ResponseCode::Refused
+        | ResponseCode::BADSIG
+        | ResponseCode::BADKEY
+        | ResponseCode::BADTIME => ProviderError::AuthenticationFailed(detail),
+        ResponseCode::NXDomain | ResponseCode::NXRRSet => ProviderError::RecordNotFound(detail),
+        ResponseCode::NotZone => ProviderError::DomainNotFound(detail),
+        _ => ProviderError::ApiError(detail),
+    };
+
+    Err(AppError::Provider(error))
+}
+
+async fn write_tcp_message(stream: &mut TcpStream, message: &[u8]) -> Result<()> {
+    stream
+        .write_all(&(message.len() as u16).to_be_bytes())
+        .await?;
+    stream.write_all(message).await?;
+    Ok(())
+}
+
+async fn read_tcp_message(stream: &mut TcpStream) -> Result<Vec<u8>> {
+    let mut len = [0u8; 2];
+    stream.read_exact(&mut len).await?;
+    let mut buffer = vec![0u8; u16::from_be_bytes(len) as usize];
+    stream.read_exact(&mut buffer).await?;
+    Ok(buffer)
+}
+
+fn unix_time() -> u64 {
+    chrono::Utc::now().timestamp() as u64
+}
+
+fn proto_error(e: trust_dns_proto::error::ProtoError) -> AppError {
+    AppError::Provider(ProviderError::ParseError(format!("DNS 消息错误: {}", e)))
+}
+
+fn timeout_error(addr: SocketAddr) -> AppError {
+    AppError::Provider(ProviderError::NetworkError(format!(
+        "DNS 服务器 {} 响应超时",
+        addr
+    )))
+}
+
+/// 解析为完整域名 (FQDN)
+fn parse_name(name: &str) -> Result<Name> {
+    let name = format!("{}.", name.trim_end_matches('.'));
+    Name::from_ascii(&name).map_err(|e| {
+        AppError::Provider(ProviderError::InvalidConfig(format!(
+            "无效的域名 {}: {}",
+            name, e
+        )))
+    })
+}
+
+fn to_record_type(record_type: &DNSRecordType) -> Result<RecordType> {
+    RecordType::from_str(&record_type.to_string()).map_err(proto_error)
+}
+
+/// 将区域文件格式的记录数据转换为 RDATA
+///
+/// MX 为 `10 mail.example.com`, SRV 为 `10 5 5060 sip.example.com`, CAA 为 `0 issue "ca.example"`
+fn to_rdata(record_type: &DNSRecordType, rdata: &str) -> Result<RData> {
+    let invalid = || {
+        AppError::Provider(ProviderError::InvalidConfig(format!(
+            "无效的 {} 记录内容: {}",
+            record_type, rdata
+        )))
+    };
+    let parsed = RecordContent::parse(record_type, rdata);
+
+    Ok(match record_type {
+        DNSRecordType::A => RData::A(A(rdata.parse().map_err(|_| invalid())?)),
+        DNSRecordType::AAAA => RData::AAAA(AAAA(rdata.parse().map_err(|_| invalid())?)),
+        DNSRecordType::CNAME => RData::CNAME(CNAME(parse_name(rdata)?)),
+        DNSRecordType::NS => RData::NS(NS(parse_name(rdata)?)),
+        DNSRecordType::TXT => RData::TXT(TXT::new(vec![rdata.to_string()])),
+        DNSRecordType::MX => RData::MX(MX::new(
+            parsed.priority.ok_or_else(invalid)?,
+            parse_name(&parsed.content)?,
+        )),
+        DNSRecordType::SRV => match (parsed.priority, parsed.weight, parsed.port) {
+            (Some(priority), Some(weight), Some(port)) => RData::SRV(SRV::new(
+                priority,
+                weight,
+                port,
+                parse_name(&parsed.content)?,
+            )),
+            _ => return Err(invalid()),
+        },
+        DNSRecordType::CAA => caa_rdata(&CaaData::parse(rdata)?).ok_or_else(invalid)?,
+        DNSRecordType::SOA | DNSRecordType::Other(_) => return Err(invalid()),
+    })
+}
+
+/// CAA 值的格式由标签决定, 先按线路格式编码再交给 trust-dns 解析
+fn caa_rdata(caa: &CaaData) -> Option<RData> {
+    let mut bytes = vec![caa.flags, u8::try_from(caa.tag.len()).ok()?];
+    bytes.extend_from_slice(caa.tag.as_bytes());
+    bytes.extend_from_slice(caa.value.as_bytes());
+
+    let length = Restrict::new(u16::try_from(bytes.len()).ok()?);
+    RData::read(&mut BinDecoder::new(&bytes), RecordType::CAA, length).ok()
+}
+
+/// 从线路格式中读取 CAA 的标志、标签和值
+fn caa_data(caa: &CAA) -> Option<CaaData> {
+    let mut bytes = Vec::new();
+    caa.emit(&mut BinEncoder::new(&mut bytes)).ok()?;
+    let (&flags, rest) = bytes.split_first()?;
+    let (&tag_len, rest) = rest.split_first()?;
+    if rest.len() < tag_len as usize {
+        return None;
+    }
+    let (tag, value) = rest.split_at(tag_len as usize);
+
+    Some(CaaData {
+        flags,
+        tag: String::from_utf8_lossy(tag).into_owned(),
+        value: String::from_utf8_lossy(value).into_owned(),
+    })
+}
+
+/// 转换为通用记录, 未内置的类型以 `Other` 保留
+fn to_dns_record(record: &Record) -> Option<DNSRecord> {
+    let name = record.name().to_ascii();
+    let name = name.trim_end_matches('.');
+    let plain = |content: String| RecordContent {
+        content,
+        ..Default::default()
+    };
+
+    let (record_type, content) = match record.data()? {
+        RData::A(a) => (DNSRecordType::A, plain(a.0.to_string())),
+        RData::AAAA(aaaa) => (DNSRecordType::AAAA, plain(aaaa.0.to_string())),
+        RData::CNAME(cname) => (DNSRecordType::CNAME, plain(display_name(&cname.0))),
+        RData::NS(ns) => (DNSRecordType::NS, plain(display_name(&ns.0))),
+        RData::MX(mx) => (
+            DNSRecordType::MX,
+            RecordContent {
+                priority: Some(mx.preference()),
+                ..plain(display_name(mx.exchange()))
+            },
+        ),
+        RData::SRV(srv) => (
+            DNSRecordType::SRV,
+            RecordContent {
+                content: display_name(srv.target()),
+                priority: Some(srv.priority()),
+                weight: Some(srv.weight()),
+                port: Some(srv.port()),
+            },
+        ),
+        RData::CAA(caa) => (DNSRecordType::CAA, plain(caa_data(caa)?.to_string())),
+        RData::TXT(txt) => (
+            DNSRecordType::TXT,
+            plain(
+                txt.txt_data()
+                    .iter()
+                    .map(|s| String::from_utf8_lossy(s).into_owned())
+                    .collect::<String>(),
+            ),
+        ),
+        other => {
+            let record_type = record.record_type().to_string();
+            (
+                record_type
+                    .parse()
+                    .unwrap_or(DNSRecordType::Other(record_type)),
+                plain(other.to_string()),
+            )
+        }
+    };
+
+    Some(DNSRecord {
+        id: format!(
+            "{}|{}|{}",
+            name,
+            record_type,
+            content.to_rdata(&record_type)
+        ),
+        name: name.to_string(),
+        record_type,
+        content: content.content,
+        ttl: record.ttl(),
+        proxied: None,
+        priority: content.priority,
+        weight: content.weight,
+        port: content.port,
+        comment: None,
+    })
+}
+
+fn display_name(name: &Name) -> String {
+    name.to_ascii().trim_end_matches('.').to_string()
+}
+
+/// 记录 ID 格式: `<完整名称>|<类型>|<区域文件格式的记录数据>`
+fn record_id_for(name: &Name, record_type: &DNSRecordType, content: &str) -> String {
+    format!("{}|{}|{}", display_name(name), record_type, content)
+}
+
+fn parse_record_id(record_id: &str) -> Result<(Name, DNSRecordType, String)> {
+    let mut parts = record_id.splitn(3, '|');
+    match (parts.next(), parts.next(), parts.next()) {
+        (Some(name), Some(record_type), Some(content)) => {
+            Ok((parse_name(name)?, record_type.parse()?, content.to_string()))
+        }
+        _ => Err(AppError::Provider(ProviderError::InvalidConfig(format!(
+            "无效的 RFC 2136 记录 ID: {}",
+            record_id
+        )))),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicBool, Ordering};
+    use std::sync::{Arc, Mutex};
+    use tokio::net::TcpListener;
+    use trust_dns_proto::serialize::binary::BinEncoder;
+
+    const SECRET: &[u8] = b"0123456789abcdef0123456789abcdef";
+
+    fn test_key(algorithm: TsigAlgorithm) -> TsigKey {
+        TsigKey {
+            name: parse_name("ddns-key").unwrap(),
+            algorithm,
+            secret: SECRET.to_vec(),
+        }
+    }
+
+    /// 进程内的权威服务器, 校验 TSIG 并在内存中应用更新
+    struct TestServer {
+        addr: SocketAddr,
+        records: Arc<Mutex<Vec<Record>>>,
+        /// 为 false 时返回不带 TSIG 的响应, 模拟伪造的应答
+        sign: Arc<AtomicBool>,
+    }
+
+    impl TestServer {
+        async fn start(key: TsigKey) -> Self {
+            let zone = parse_name("example.com").unwrap();
+            let soa = Record::from_rdata(
+                zone.clone(),
+                3600,
+                RData::SOA(trust_dns_proto::rr::rdata::SOA::new(
+                    parse_name("ns1.example.com").unwrap(),
+                    parse_name("admin.example.com").unwrap(),
+                    1,
+                    3600,
+                    600,
+                    86400,
+                    300,
+                )),
+            );
+            let records = Arc::new(Mutex::new(vec![
+                soa,
+                Record::from_rdata(
+                    parse_name("www.example.com").unwrap(),
+                    600,
+                    RData::A(A("192.0.2.1".parse().unwrap())),
+                ),
+            ]));
+
+            let tcp = TcpListener::bind("127.0.0.1:0").await.unwrap();
+            let addr = tcp.local_addr().unwrap();
+            let udp = UdpSocket::bind(addr).await.unwrap();
+            let sign = Arc::new(AtomicBool::new(true));
+
+            let state = (key.clone(), zone.clone(), records.clone(), sign.clone());
+            tokio::spawn(async move {
+                let mut buffer = vec![0u8; UDP_BUFFER_SIZE];
+                while let Ok((len, peer)) = udp.recv_from(&mut buffer).await {
+                    let (key, zone, records, sign) = &state;
+                    let sign = sign.load(Ordering::SeqCst);
+                    for response in handle(key, zone, records, sign, &buffer[..len]) {
+                        udp.send_to(&response, peer).await.unwrap();
+                    }
+                }
+            });
+
+            let state = (key, zone, records.clone(), sign.clone());
+            tokio::spawn(async move {
+                while let Ok((mut stream, _)) = tcp.accept().await {
+                    let (key, zone, records, sign) = state.clone();
+                    tokio::spawn(async move {
+                        while let Ok(request) = read_tcp_message(&mut stream).await {
+                            let sign = sign.load(Ordering::SeqCst);
+                            for response in handle(&key, &zone, &records, sign, &request) {
+                                write_tcp_message(&mut stream, &response).await.unwrap();
+                            }
+                        }
+                    });
+                }
+            });
+
+            Self {
+                addr,
+                records,
+                sign,
+            }
+        }
+
+        /// 之后的响应都不带 TSIG 签名
+        fn reply_unsigned(&self) {
+            self.sign.store(false, Ordering::SeqCst);
+        }
+
+        async fn provider(&self, protocol: &str, algorithm: &str) -> Rfc2136Provider {
+            let mut provider = Rfc2136Provider::new();
+            provider
+                .initialize(&Credentials {
+                    extra: serde_json::json!({
+                        "server": self.addr.to_string(),
+                        "key_name": "ddns-key",
+                        "key_secret": base64::engine::general_purpose::STANDARD.encode(SECRET),
+                        "protocol": protocol,
+                        "algorithm": algorithm,
+                        "zone": "example.com",
+                    }),
+                    ..Default::default()
+                })
+                .await
+                .unwrap();
+            provider
+        }
+
+        fn contents(&self, name: &str) -> Vec<String> {
+            let name = parse_name(name).unwrap();
+            self.records
+                .lock()
+                .unwrap()
+                .iter()
+                .filter(|r| r.name() == &name)
+                .filter_map(to_dns_record)
+                .map(|r| r.content)
+                .collect()
+        }
+    }
+
+    /// 处理一条请求, 返回响应消息 (AXFR 可能返回多条)
+    fn handle(
+        key: &TsigKey,
+        zone: &Name,
+        records: &Mutex<Vec<Record>>,
+        sign: bool,
+        request: &[u8],
+    ) -> Vec<Vec<u8>> {
+        let message = Message::from_vec(request).unwrap();
+        let mut response = Message::new();
+        response
+            .set_id(message.id())
+            .set_message_type(MessageType::Response)
+            .set_op_code(message.op_code())
+            .add_queries(message.queries().to_vec());
+
+        // 请求必须带有正确的 TSIG 签名
+        let request_mac = match signed_bitmessage_to_buf(None, request, true) {
+            Ok((tbv, record)) => match record.data() {
+                Some(RData::DNSSEC(DNSSECRData::TSIG(tsig)))
+                    if record.name() == &key.name
+                        && tsig.mac() == tsig_mac(key, &tbv).unwrap().as_slice() =>
+                {
+                    tsig.mac().to_vec()
+                }
+                _ => {
+                    response.set_response_code(ResponseCode::NotAuth);
+                    return vec![response.to_vec().unwrap()];
+                }
+            },
+            Err(_) => {
+                response.set_response_code(ResponseCode::Refused);
+                return vec![response.to_vec().unwrap()];
+            }
+        };
+
+        let query = message.queries()[0].clone();
+        let mut records = records.lock().unwrap();
+
+        let responses = if query.query_type() == RecordType::AXFR {
+            // 拆分为两条消息, 验证客户端读取到结尾的 SOA
+            let soa = records[0].clone();
+            let mut first = response.clone();
+            first.add_answer(soa.clone());
+            first.add_answers(records[1..].iter().cloned());
+            let mut last = response;
+            last.add_answer(soa);
+            vec![first, last]
+        } else {
+            if message.op_code() == OpCode::Update {
+                if query.name() != zone {
+                    response.set_response_code(ResponseCode::NotZone);
+                } else {
+                    for update in message.name_servers() {
+                        match update.dns_class() {
+                            DNSClass::NONE => records.retain(|r| {
+                                !(r.name() == update.name() && r.data() == update.data())
+                            }),
+                            _ => records.push(update.clone()),
+                        }
+                    }
+                }
+            } else {
+                let answers: Vec<Record> = records
+                    .iter()
+                    .filter(|r| r.name() == query.name() && r.record_type() == query.query_type())
+                    .cloned()
+                    .collect();
+                if answers.is_empty() && !records.iter().any(|r| r.name() == query.name()) {
+                    response.set_response_code(ResponseCode::NXDomain);
+                }
+                response.add_answers(answers);
+            }
+            vec![response]
+        };
+
+        if !sign {
+            return responses.iter().map(|r| r.to_vec().unwrap()).collect();
+        }
+
+        // 第一条响应以请求的 MAC 签名, 之后的每条以上一条响应的 MAC 签名
+        let mut previous_mac = request_mac;
+        responses
+            .into_iter()
+            .enumerate()
+            .map(|(index, response)| {
+                let (bytes, mac) = sign_response(key, response, &previous_mac, index == 0);
+                previous_mac = mac;
+                bytes
+            })
+            .collect()
+    }
+
+    /// 签名一条响应, 返回消息和 MAC
+    fn sign_response(
+        key: &TsigKey,
+        mut response: Message,
+        previous_mac: &[u8],
+        first_message: bool,
+    ) -> (Vec<u8>, Vec<u8>) {
+        let pre_tsig = TSIG::new(
+            key.algorithm.clone(),
+            unix_time(),
+            TSIG_FUDGE,
+            Vec::new(),
+            response.id(),
+            0,
+            Vec::new(),
+        );
+        // message_tbs 在前缀之后编码消息会使名称压缩指针偏移, 因此手动拼接
+        let mut tbs = (previous_mac.len() as u16).to_be_bytes().to_vec();
+        tbs.extend_from_slice(previous_mac);
+        tbs.extend(response.to_vec().unwrap());
+        let mut variables = Vec::new();
+        let mut encoder = BinEncoder::new(&mut variables);
+        if first_message {
+            pre_tsig.emit_tsig_for_mac(&mut encoder, &key.name).unwrap();
+        } else {
+            // 后续消息只覆盖时间和允许误差
+            encoder.emit_u16((pre_tsig.time() >> 32) as u16).unwrap();
+            encoder.emit_u32(pre_tsig.time() as u32).unwrap();
+            encoder.emit_u16(pre_tsig.fudge()).unwrap();
+        }
+        tbs.extend(variables);
+        let mac = tsig_mac(key, &tbs).unwrap();
+        response.add_tsig(make_tsig_record(
+            key.name.clone(),
+            pre_tsig.set_mac(mac.clone()),
+        ));
+
+        (response.to_vec().unwrap(), mac)
+    }
+
+    #[test]
+    fn test_sign_message() {
+        for algorithm in [TsigAlgorithm::HmacSha256, TsigAlgorithm::HmacSha512] {
+            let key = test_key(algorithm);
+            let mut message = new_message(OpCode::Update);
+            message.add_query(Query::query(
+                parse_name("example.com").unwrap(),
+                RecordType::SOA,
+            ));
+            let mac = sign_message(&mut message, &key, 1_700_000_000).unwrap();
+
+            // 按接收方的方式重新计算 MAC
+            let bytes = message.to_vec().unwrap();
+            let (tbv, record) = signed_bitmessage_to_buf(None, &bytes, true).unwrap();
+            assert_eq!(record.record_type(), RecordType::TSIG);
+            assert_eq!(record.name(), &key.name);
+            assert_eq!(tsig_mac(&key, &tbv).unwrap(), mac);
+        }
+    }
+
+    #[test]
+    fn test_check_response() {
+        let kind = |code: ResponseCode| {
+            let mut message = Message::new();
+            message.set_response_code(code);
+            match check_response(&message) {
+                Ok(()) => None,
+                Err(AppError::Provider(error)) => Some(error),
+                Err(other) => panic!("应该返回提供商错误: {:?}", other),
+            }
+        };
+
+        assert!(kind(ResponseCode::NoError).is_none());
+        assert!(matches!(
+            kind(ResponseCode::NotAuth),
+            Some(ProviderError::AuthenticationFailed(_))
+        ));
+        assert!(matches!(
+            kind(ResponseCode::Refused),
+            Some(ProviderError::AuthenticationFailed(_))
+        ));
+        assert!(matches!(
+            kind(ResponseCode::NXRRSet),
+            Some(ProviderError::RecordNotFound(_))
+        ));
+        assert!(matches!(
+            kind(ResponseCode::NotZone),
+            Some(ProviderError::DomainNotFound(_))
+        ));
+        assert!(matches!(
+            kind(ResponseCode::ServFail),
+            Some(ProviderError::ApiError(_))
+        ));
+    }
+
+    #[test]
+    fn test_rdata_round_trip() {
+        let name = parse_name("www.example.com").unwrap();
+        for (record_type, rdata, content) in [
+            (DNSRecordType::A, "192.0.2.1", "192.0.2.1"),
+            (DNSRecordType::AAAA, "2001:db8::1", "2001:db8::1"),
+            (
+                DNSRecordType::CNAME,
+                "target.example.com",
+                "target.example.com",
+            ),
+            (DNSRecordType::MX, "10 mail.example.com", "mail.example.com"),
+            (DNSRecordType::TXT, "hello world", "hello world"),
+            (
+                DNSRecordType::SRV,
+                "10 5 5060 sip.example.com",
+                "sip.example.com",
+            ),
+            (
+                DNSRecordType::CAA,
+                "0 issue \"letsencrypt.org\"",
+                "0 issue \"letsencrypt.org\"",
+            ),
+        ] {
+            let record =
+                Record::from_rdata(name.clone(), 300, to_rdata(&record_type, rdata).unwrap());
+            let dns_record = to_dns_record(&record).unwrap();
+            assert_eq!(dns_record.record_type, record_type);
+            assert_eq!(dns_record.content, content);
+            assert_eq!(dns_record.rdata(), rdata);
+            assert_eq!(
+                parse_record_id(&dns_record.id).unwrap(),
+                (name.clone(), record_type, rdata.to_string())
+            );
+        }
+
+        // 未内置的类型以 Other 保留, 不会被丢弃
+        let ptr = Record::from_rdata(
+            name.clone(),
+            300,
+            RData::PTR(trust_dns_proto::rr::rdata::PTR(
+                parse_name("host.example.com").unwrap(),
+            )),
+        );
+        let dns_record = to_dns_record(&ptr).unwrap();
+        assert_eq!(
+            dns_record.record_type,
+            DNSRecordType::Other("PTR".to_string())
+        );
+        assert!(to_rdata(&dns_record.record_type, &dns_record.content).is_err());
+    }
+
+    #[tokio::test]
+    async fn test_update_over_udp() {
+        let server = TestServer::start(test_key(TsigAlgorithm::HmacSha256)).await;
+        let provider = server.provider("udp", "hmac-sha256").await;
+
+        let record = provider
+            .get_record("example.com", "www", DNSRecordType::A)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(record.content, "192.0.2.1");
+        assert_eq!(record.ttl, 600);
+
+        let spec = RecordSpec {
+            ttl: Some(120),
+            ..Default::default()
+        };
+        let result = provider
+            .update_record_with("example.com", &record.id, "192.0.2.2", &spec)
+            .await
+            .unwrap();
+        assert_eq!(result.old_ip, "192.0.2.1");
+        assert_eq!(server.contents("www.example.com"), vec!["192.0.2.2"]);
+        let updated = provider
+            .get_record("example.com", "www", DNSRecordType::A)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(updated.ttl, 120);
+
+        // 不存在的记录
+        assert!(provider
+            .get_record("example.com", "missing", DNSRecordType::A)
+            .await
+            .unwrap()
+            .is_none());
+    }
+
+    #[tokio::test]
+    async fn test_create_list_delete_over_tcp() {
+        let server = TestServer::start(test_key(TsigAlgorithm::HmacSha512)).await;
+        let provider = server.provider("tcp", "hmac-sha512").await;
+
+        let created = provider
+            .create_record("example.com", "v6", DNSRecordType::AAAA, "2001:db8::1")
+            .await
+            .unwrap();
+        assert_eq!(created.name, "v6.example.com");
+
+        let records = provider.list_records("example.com").await.unwrap();
+        assert_eq!(records.len(), 2);
+        assert!(records.iter().any(|r| r.content == "2001:db8::1"));
+
+        provider
+            .delete_record("example.com", &created.id)
+            .await
+            .unwrap();
+        assert!(server.contents("v6.example.com").is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_wrong_key_is_rejected() {
+        let server = TestServer::start(test_key(TsigAlgorithm::HmacSha256)).await;
+        let mut provider = server.provider("udp", "hmac-sha256").await;
+        provider.key = Some(TsigKey {
+            secret: b"wrong".to_vec(),
+            ..test_key(TsigAlgorithm::HmacSha256)
+        });
+
+        let result = provider
+            .create_record("example.com", "www", DNSRecordType::A, "192.0.2.9")
+            .await;
+        assert!(matches!(
+            result,
+            Err(AppError::Provider(ProviderError::AuthenticationFailed(_)))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_update_outside_zone() {
+        let server = TestServer::start(test_key(TsigAlgorithm::HmacSha256)).await;
+        let mut provider = server.provider("udp", "hmac-sha256").await;
+        provider.zone = Some("other.org".to_string());
+
+        let result = provider
+            .create_record("other.org", "www", DNSRecordType::A, "192.0.2.9")
+            .await;
+        assert!(matches!(
+            result,
+            Err(AppError::Provider(ProviderError::DomainNotFound(_)))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_unsigned_responses_are_rejected() {
+        let server = TestServer::start(test_key(TsigAlgorithm::HmacSha256)).await;
+        let provider = server.provider("udp", "hmac-sha256").await;
+        let tcp = server.provider("tcp", "hmac-sha256").await;
+        server.reply_unsigned();
+        let is_auth_error = |e: &AppError| {
+            matches!(
+                e,
+                AppError::Provider(ProviderError::AuthenticationFailed(_))
+            )
+        };
+
+        // 未签名的 NOERROR 不能被当作更新成功
+        let result = provider
+            .create_record("example.com", "spoof", DNSRecordType::A, "192.0.2.9")
+            .await;
+        assert!(is_auth_error(&result.unwrap_err()));
+
+        // 未签名的 NXDOMAIN 不能被当作记录不存在
+        let result = provider
+            .get_record("example.com", "missing", DNSRecordType::A)
+            .await;
+        assert!(is_auth_error(&result.unwrap_err()));
+
+        // 区域传送的响应同样需要签名
+        let result = tcp.list_records("example.com").await;
+        assert!(is_auth_error(&result.unwrap_err()));
+    }
+}
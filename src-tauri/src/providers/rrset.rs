@@ -0,0 +1,108 @@
+// 以记录集 (RRSet) 为单位管理记录的提供商的公共工具
+//
+// 记录集可以包含多个值, 每个值对应一条通用记录, 记录 ID 为 `<记录集 ID>#<值>`。
+// 修改或删除某个值时先读取整个记录集, 只改动该值后整体写回, 其余值保持不变。
+
+/// 记录集 ID 与值之间的分隔符 (记录集 ID 中不会出现, 值中可能出现, 因此只按第一个拆分)
+const VALUE_SEPARATOR: char = '#';
+
+/// 记录集中单个值的记录 ID
+pub fn value_id(rrset_id: &str, value: &str) -> String {
+    format!("{}{}{}", rrset_id, VALUE_SEPARATOR, value)
+}
+
+/// 拆分为记录集 ID 和值, 不含值的 ID 表示整个记录集
+pub fn split_value_id(record_id: &str) -> (&str, Option<&str>) {
+    match record_id.split_once(VALUE_SEPARATOR) {
+        Some((rrset_id, value)) => (rrset_id, Some(value)),
+        None => (record_id, None),
+    }
+}
+
+/// 将值列表中的 `old` 替换为 `new`, 其余值保持原有顺序
+///
+/// `old` 为 None 时只允许替换单值记录集; 找不到 `old` 时返回 None
+pub fn replace_value(values: &[String], old: Option<&str>, new: String) -> Option<Vec<String>> {
+    let index = match old {
+        Some(old) => values.iter().position(|v| v == old)?,
+        None if values.len() <= 1 => 0,
+        None => return None,
+    };
+
+    let mut result: Vec<String> = values
+        .iter()
+        .enumerate()
+        .filter(|(i, v)| *i != index && **v != new)
+        .map(|(_, v)| v.clone())
+        .collect();
+    result.insert(index.min(result.len()), new);
+    Some(result)
+}
+
+/// 从值列表中移除 `old`, 找不到时返回 None; `old` 为 None 时移除全部值
+pub fn remove_value(values: &[String], old: Option<&str>) -> Option<Vec<String>> {
+    match old {
+        Some(old) => {
+            values.iter().position(|v| v == old)?;
+            Some(values.iter().filter(|v| *v != old).cloned().collect())
+        }
+        None => Some(Vec::new()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn values(items: &[&str]) -> Vec<String> {
+        items.iter().map(|v| v.to_string()).collect()
+    }
+
+    #[test]
+    fn test_value_id_round_trip() {
+        let id = value_id("www/TXT", "\"a#b\"");
+        assert_eq!(id, "www/TXT#\"a#b\"");
+        assert_eq!(split_value_id(&id), ("www/TXT", Some("\"a#b\"")));
+        assert_eq!(split_value_id("www/A"), ("www/A", None));
+    }
+
+    #[test]
+    fn test_replace_value_keeps_other_values() {
+        let current = values(&["10 mx1.example.net.", "20 mx2.example.net."]);
+        assert_eq!(
+            replace_value(
+                &current,
+                Some("10 mx1.example.net."),
+                "5 mx.example.org.".into()
+            ),
+            Some(values(&["5 mx.example.org.", "20 mx2.example.net."]))
+        );
+        // 新值已存在时不重复写入
+        assert_eq!(
+            replace_value(
+                &current,
+                Some("10 mx1.example.net."),
+                "20 mx2.example.net.".into()
+            ),
+            Some(values(&["20 mx2.example.net."]))
+        );
+        assert_eq!(replace_value(&current, Some("missing"), "x".into()), None);
+        // 不含值的 ID 不能替换多值记录集
+        assert_eq!(replace_value(&current, None, "x".into()), None);
+        assert_eq!(
+            replace_value(&values(&["1.1.1.1"]), None, "2.2.2.2".into()),
+            Some(values(&["2.2.2.2"]))
+        );
+    }
+
+    #[test]
+    fn test_remove_value() {
+        let current = values(&["1.1.1.1", "2.2.2.2"]);
+        assert_eq!(
+            remove_value(&current, Some("1.1.1.1")),
+            Some(values(&["2.2.2.2"]))
+        );
+        assert_eq!(remove_value(&current, Some("3.3.3.3")), None);
+        assert_eq!(remove_value(&current, None), Some(Vec::new()));
+    }
+}
@@ -0,0 +1,74 @@
+// API 签名的公共工具 (AWS SigV4 及其衍生的 TC3、SDK-HMAC-SHA256、BCE、JDCLOUD2 签名)
+use percent_encoding::{utf8_percent_encode, AsciiSet, NON_ALPHANUMERIC};
+
+/// URI 编码: 仅保留 A-Z a-z 0-9 - _ . ~
+pub const URI_ENCODE_SET: &AsciiSet = &NON_ALPHANUMERIC
+    .remove(b'-')
+    .remove(b'_')
+    .remove(b'.')
+    .remove(b'~');
+
+/// 规范查询字符串: 参数名和值分别 URI 编码后按参数名排序, 以 `&` 连接
+pub fn canonical_query_string(query: &[(&str, String)]) -> String {
+    let mut pairs: Vec<(String, String)> = query
+        .iter()
+        .map(|(k, v)| {
+            (
+                utf8_percent_encode(k, URI_ENCODE_SET).to_string(),
+                utf8_percent_encode(v, URI_ENCODE_SET).to_string(),
+            )
+        })
+        .collect();
+    pairs.sort();
+
+    pairs
+        .into_iter()
+        .map(|(k, v)| format!("{}={}", k, v))
+        .collect::<Vec<_>>()
+        .join("&")
+}
+
+pub fn hmac_sha256(key: &[u8], data: &[u8]) -> Vec<u8> {
+    use hmac::{Hmac, Mac};
+    use sha2::Sha256;
+    type HmacSha256 = Hmac<Sha256>;
+
+    let mut mac = HmacSha256::new_from_slice(key).expect("HMAC can take key of any size");
+    mac.update(data);
+    mac.finalize().into_bytes().to_vec()
+}
+
+pub fn sha256_hex(data: &[u8]) -> String {
+    use sha2::{Digest, Sha256};
+    hex_encode(&Sha256::digest(data))
+}
+
+pub fn hex_encode(data: &[u8]) -> String {
+    data.iter().map(|b| format!("{:02x}", b)).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_canonical_query_string() {
+        let query = [
+            ("name", "www.example.com.".to_string()),
+            ("Action", "List Records".to_string()),
+            ("a~b", "1/2".to_string()),
+        ];
+        assert_eq!(
+            canonical_query_string(&query),
+            "Action=List%20Records&a~b=1%2F2&name=www.example.com."
+        );
+    }
+
+    #[test]
+    fn test_sha256_hex() {
+        assert_eq!(
+            sha256_hex(b""),
+            "e3b0c44298fc1c149afbf4c8996fb92427ae41e4649b934ca495991b7852b855"
+        );
+    }
+}
@@ -1,216 +1,216 @@
 use crate::error::{AppError, Result};
 use crate::providers::provider_trait::{
-    Credentials, DNSProvider, DNSRecord, DNSRecordType, ProviderError, UpdateResult,
+    Credentials, DNSProvider, DNSRecord, DNSRecordType, ProviderCapabilities, ProviderError,
+    RecordContent, RecordSpec, UpdateResult,
 };
+use crate::providers::signing::{hex_encode, hmac_sha256, sha256_hex};
 use async_trait::async_trait;
 use reqwest::Client;
-use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use serde::Deserialize;
+use serde_json::json;
 
-/// 腾讯云 DNS 提供商 (DNSPod)
+/// DNSPod API 3.0 服务名和版本
+const SERVICE: &str = "dnspod";
+const API_VERSION: &str = "2021-03-23";
+
+/// 签名使用的请求体类型
+const CONTENT_TYPE: &str = "application/json; charset=utf-8";
+
+/// 默认解析线路
+const DEFAULT_LINE: &str = "默认";
+
+/// DescribeRecordList 每页记录数 (DNSPod 允许的最大值)
+const PAGE_SIZE: usize = 3000;
+
+/// 新建记录的默认 TTL
+const DEFAULT_TTL: u32 = 600;
+
+/// 腾讯云 DNS 提供商 (DNSPod API 3.0)
 pub struct TencentProvider {
-    initialized: bool,
     secret_id: Option<String>,
     secret_key: Option<String>,
+    region: Option<String>,
+    endpoint: String,
     client: Client,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
-struct TencentDNSRecord {
-    id: String,
+/// DescribeRecordList 返回的记录
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+struct TencentRecord {
+    record_id: u64,
     name: String,
-    #[serde(rename = "type")]
+    #[serde(rename = "Type")]
     record_type: String,
     value: String,
-    ttl: u64,
+    #[serde(rename = "TTL")]
+    ttl: u32,
+    #[serde(rename = "MX", default)]
+    mx: Option<u16>,
+    #[serde(default)]
+    remark: Option<String>,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
-struct DescribeRecordListResponse {
-    total_count: i64,
-    record_list: Vec<TencentDNSRecord>,
-}
-
-#[derive(Debug, Serialize)]
-struct TencentRequest {
-    secret_id: String,
-    secret_key: String,
-    action: String,
-    region: String,
-    timestamp: u64,
-    nonce: u64,
-    #[serde(skip_serializing_if = "Option::is_none")]
-    domain: Option<String>,
-    #[serde(skip_serializing_if = "Option::is_none")]
-    record_id: Option<String>,
-    #[serde(skip_serializing_if = "Option::is_none")]
-    sub_domain: Option<String>,
-    #[serde(skip_serializing_if = "Option::is_none")]
-    record_type: Option<String>,
-    #[serde(skip_serializing_if = "Option::is_none")]
-    record_line: Option<String>,
-    #[serde(skip_serializing_if = "Option::is_none")]
-    value: Option<String>,
-}
-
-#[derive(Debug, Serialize, Deserialize)]
-struct ModifyRecordResponse {
-    record_id: String,
+/// DescribeRecord 返回的记录详情
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+struct TencentRecordInfo {
+    id: u64,
+    sub_domain: String,
+    record_type: String,
+    record_line: String,
+    value: String,
+    #[serde(rename = "TTL")]
+    ttl: u32,
+    #[serde(rename = "MX", default)]
+    mx: Option<u16>,
+    #[serde(default)]
+    remark: Option<String>,
 }
 
 impl TencentProvider {
     pub fn new() -> Self {
+        Self::with_endpoint("https://dnspod.tencentcloudapi.com")
+    }
+
+    /// 使用自定义 API 地址创建提供商 (例如测试服务器)
+    pub fn with_endpoint(endpoint: impl Into<String>) -> Self {
         Self {
-            initialized: false,
             secret_id: None,
             secret_key: None,
-            client: Client::new(),
+            region: None,
+            endpoint: endpoint.into().trim_end_matches('/').to_string(),
+            client: Client::builder()
+                .timeout(std::time::Duration::from_secs(30))
+                .build()
+                .unwrap(),
         }
     }
 
-    /// 构造腾讯云 API 签名
-    fn build_signature(&self, req: &TencentRequest) -> String {
-        let nonce = req.nonce.to_string();
-        let timestamp = req.timestamp.to_string();
-
-        let params = vec![
-            ("Action", req.action.as_str()),
-            ("Nonce", nonce.as_str()),
-            ("Region", req.region.as_str()),
-            ("SecretId", req.secret_id.as_str()),
-            ("Timestamp", timestamp.as_str()),
-            ("domain", req.domain.as_deref().unwrap_or("")),
-            ("recordId", req.record_id.as_deref().unwrap_or("")),
-            ("subDomain", req.sub_domain.as_deref().unwrap_or("")),
-            ("recordType", req.record_type.as_deref().unwrap_or("")),
-            ("recordLine", req.record_line.as_deref().unwrap_or("")),
-            ("value", req.value.as_deref().unwrap_or("")),
-        ];
-
-        // 按字典序排序参数
-        let mut sorted_params: Vec<_> = params.iter().filter(|(_, v)| !v.is_empty()).collect();
-        sorted_params.sort_by(|a, b| a.0.cmp(b.0));
-
-        // 构造查询字符串
-        let query_str = sorted_params
-            .iter()
-            .map(|(k, v)| format!("{}={}", k, Self::percent_encode(v)))
-            .collect::<Vec<String>>()
-            .join("&");
-
-        // 构造待签名字符串
-        let method = "GET";
-        let endpoint = "cns.api.qcloud.com";
-        let string_to_sign = format!("{}{}?{}", method, endpoint, query_str);
-
-        // 计算签名
-        let signature = hmac_sha1(
-            format!("{}&", req.secret_key).as_bytes(),
-            string_to_sign.as_bytes(),
-        );
-
-        base64_encode(&signature)
-    }
-
-    /// URL 编码
-    fn percent_encode(s: &str) -> String {
-        use percent_encoding::{utf8_percent_encode, NON_ALPHANUMERIC};
-        utf8_percent_encode(s, NON_ALPHANUMERIC).to_string()
-    }
-
-    /// 发送 API 请求
+    /// 发送 API 请求, 返回 `Response` 字段
     async fn send_request(
         &self,
         action: &str,
-        params: HashMap<String, String>,
+        payload: serde_json::Value,
     ) -> Result<serde_json::Value> {
-        let req = TencentRequest {
-            secret_id: self.secret_id.clone().unwrap(),
-            secret_key: self.secret_key.clone().unwrap(),
-            action: action.to_string(),
-            region: "ap-guangzhou".to_string(),
-            timestamp: chrono::Utc::now().timestamp() as u64,
-            nonce: uuid::Uuid::new_v4().as_u128() as u64,
-            domain: params.get("domain").cloned(),
-            record_id: params.get("recordId").cloned(),
-            sub_domain: params.get("subDomain").cloned(),
-            record_type: params.get("recordType").cloned(),
-            record_line: params
-                .get("recordLine")
-                .cloned()
-                .or_else(|| Some("默认".to_string())),
-            value: params.get("value").cloned(),
+        let (secret_id, secret_key) = match (&self.secret_id, &self.secret_key) {
+            (Some(id), Some(key)) => (id, key),
+            _ => {
+                return Err(AppError::Provider(ProviderError::AuthenticationFailed(
+                    "未设置 SecretId 或 SecretKey".to_string(),
+                )))
+            }
         };
 
-        let signature = self.build_signature(&req);
-
-        let nonce_str = req.nonce.to_string();
-        let timestamp_str = req.timestamp.to_string();
-
-        let mut query_params = vec![
-            ("Action", action),
-            ("Nonce", nonce_str.as_str()),
-            ("Region", &req.region),
-            ("SecretId", &req.secret_id),
-            ("Signature", &signature),
-            ("Timestamp", timestamp_str.as_str()),
-        ];
+        let url = reqwest::Url::parse(&format!("{}/", self.endpoint)).map_err(|e| {
+            AppError::Provider(ProviderError::InvalidConfig(format!(
+                "无效的 API 地址 {}: {}",
+                self.endpoint, e
+            )))
+        })?;
+        let host = match url.port() {
+            Some(port) => format!("{}:{}", url.host_str().unwrap_or_default(), port),
+            None => url.host_str().unwrap_or_default().to_string(),
+        };
 
-        if let Some(domain) = &req.domain {
-            query_params.push(("domain", domain));
-        }
-        if let Some(record_id) = &req.record_id {
-            query_params.push(("recordId", record_id));
-        }
-        if let Some(sub_domain) = &req.sub_domain {
-            query_params.push(("subDomain", sub_domain));
-        }
-        if let Some(record_type) = &req.record_type {
-            query_params.push(("recordType", record_type));
+        let body = payload.to_string();
+        let timestamp = chrono::Utc::now().timestamp();
+        let authorization = sign_tc3(&SigningParams {
+            secret_id,
+            secret_key,
+            service: SERVICE,
+            host: &host,
+            payload: &body,
+            timestamp,
+        });
+
+        let mut request = self
+            .client
+            .post(url)
+            .header("Authorization", authorization)
+            .header("Content-Type", CONTENT_TYPE)
+            .header("X-TC-Action", action)
+            .header("X-TC-Timestamp", timestamp.to_string())
+            .header("X-TC-Version", API_VERSION);
+        if let Some(region) = &self.region {
+            request = request.header("X-TC-Region", region);
         }
-        if let Some(record_line) = &req.record_line {
-            query_params.push(("recordLine", record_line));
+
+        let response = request.body(body).send().await.map_err(|e| {
+            AppError::Provider(ProviderError::NetworkError(format!("请求失败: {}", e)))
+        })?;
+
+        let status = response.status();
+        let text = response.text().await.map_err(|e| {
+            AppError::Provider(ProviderError::NetworkError(format!("读取响应失败: {}", e)))
+        })?;
+        let json: serde_json::Value = serde_json::from_str(&text).map_err(|_| {
+            AppError::Provider(ProviderError::ApiError(format!(
+                "HTTP {}: {}",
+                status, text
+            )))
+        })?;
+
+        let error = &json["Response"]["Error"];
+        if let Some(code) = error["Code"].as_str() {
+            return Err(parse_error(
+                code,
+                error["Message"].as_str().unwrap_or_default(),
+            ));
         }
-        if let Some(value) = &req.value {
-            query_params.push(("value", value));
+        if !status.is_success() {
+            return Err(AppError::Provider(ProviderError::ApiError(format!(
+                "HTTP {}: {}",
+                status, text
+            ))));
         }
 
-        let query_string = query_params
-            .iter()
-            .map(|(k, v)| format!("{}={}", k, urlencoding::encode(v)))
-            .collect::<Vec<String>>()
-            .join("&");
-
-        let url = format!("https://cns.api.qcloud.com/v2/index.php?{}", query_string);
+        Ok(json["Response"].clone())
+    }
 
-        let response = self
-            .client
-            .get(&url)
-            .send()
-            .await
-            .map_err(|e| AppError::Provider(ProviderError::Network(e.to_string())))?;
-
-        if response.status().is_success() {
-            let json: serde_json::Value = response
-                .json()
-                .await
-                .map_err(|e| AppError::Provider(ProviderError::ParseError(e.to_string())))?;
-
-            // 检查响应码
-            if let Some(code) = json["response"]["Error"]["code"].as_str() {
-                return Err(AppError::Provider(ProviderError::Unknown(format!(
-                    "API 错误: {}",
-                    code
-                ))));
+    /// 分页查询记录列表, `filter` 中可以指定 Subdomain / RecordType 过滤条件
+    async fn describe_records(
+        &self,
+        domain: &str,
+        filter: serde_json::Value,
+    ) -> Result<Vec<DNSRecord>> {
+        let mut records = Vec::new();
+        let mut offset = 0;
+
+        loop {
+            let mut payload = json!({
+                "Domain": domain,
+                "Offset": offset,
+                "Limit": PAGE_SIZE,
+            });
+            if let (Some(payload), Some(filter)) = (payload.as_object_mut(), filter.as_object()) {
+                payload.extend(filter.clone());
             }
 
-            Ok(json["response"].clone())
-        } else {
-            Err(AppError::Provider(ProviderError::Unknown(format!(
-                "API 请求失败: {}",
-                response.status()
-            ))))
+            let response = match self.send_request("DescribeRecordList", payload).await {
+                Ok(response) => response,
+                // 没有任何记录时 DNSPod 返回错误而不是空列表
+                Err(AppError::Provider(ProviderError::RecordNotFound(_))) => break,
+                Err(e) => return Err(e),
+            };
+
+            let page: Vec<TencentRecord> =
+                serde_json::from_value(response["RecordList"].clone())
+                    .map_err(|e| AppError::Provider(ProviderError::ParseError(e.to_string())))?;
+            let total_count = response["RecordCountInfo"]["TotalCount"]
+                .as_u64()
+                .unwrap_or(0) as usize;
+
+            let count = page.len();
+            offset += count;
+            records.extend(page.into_iter().map(to_dns_record));
+
+            if count == 0 || offset >= total_count {
+                break;
+            }
         }
+
+        Ok(records)
     }
 }
 
@@ -237,9 +237,10 @@ impl DNSProvider for TencentProvider {
         if let Some(secret_key) = credentials.extra.get("secret_key") {
             self.secret_key = Some(secret_key.as_str().unwrap_or_default().to_string());
         }
+        self.region = credentials.region.clone();
 
         if self.secret_id.is_none() || self.secret_key.is_none() {
-            return Err(AppError::Provider(ProviderError::Authentication(
+            return Err(AppError::Provider(ProviderError::InvalidConfig(
                 "缺少 SecretId 或 SecretKey".to_string(),
             )));
         }
@@ -247,70 +248,120 @@ impl DNSProvider for TencentProvider {
         // 测试连接
         self.test_connection().await?;
 
-        self.initialized = true;
         Ok(())
     }
 
     async fn list_records(&self, domain: &str) -> Result<Vec<DNSRecord>> {
-        let mut params = HashMap::new();
-        params.insert("domain".to_string(), domain.to_string());
-
-        let response = self.send_request("DescribeRecordList", params).await?;
-
-        if let Some(records) = response["recordList"].as_array() {
-            Ok(records
-                .iter()
-                .filter_map(|r| serde_json::from_value(r.clone()).ok())
-                .map(|r: TencentDNSRecord| DNSRecord {
-                    id: r.id,
-                    name: r.name,
-                    record_type: match r.record_type.as_str() {
-                        "A" => DNSRecordType::A,
-                        "AAAA" => DNSRecordType::AAAA,
-                        "CNAME" => DNSRecordType::CNAME,
-                        _ => DNSRecordType::A,
-                    },
-                    content: r.value,
-                    ttl: r.ttl as u32,
-                    proxied: None,
-                    priority: None,
-                })
-                .collect())
+        self.describe_records(domain, json!({})).await
+    }
+
+    async fn get_record(
+        &self,
+        domain: &str,
+        record_name: &str,
+        record_type: DNSRecordType,
+    ) -> Result<Option<DNSRecord>> {
+        let subdomain = if record_name.is_empty() {
+            "@"
         } else {
-            Ok(vec![])
-        }
+            record_name
+        };
+        let records = self
+            .describe_records(
+                domain,
+                json!({ "Subdomain": subdomain, "RecordType": record_type.to_string() }),
+            )
+            .await?;
+
+        Ok(records
+            .into_iter()
+            .find(|r| r.name == subdomain && r.record_type == record_type))
     }
 
     async fn update_record(
         &self,
-        _domain: &str,
+        domain: &str,
         record_id: &str,
         new_content: &str,
     ) -> Result<UpdateResult> {
-        // 先获取记录详情
-        let mut params = HashMap::new();
-        params.insert("recordId".to_string(), record_id.to_string());
-
-        let response = self.send_request("DescribeRecord", params).await?;
-        let record: TencentDNSRecord = serde_json::from_value(response["record"].clone())
-            .map_err(|e| AppError::Provider(ProviderError::ParseError(e.to_string())))?;
+        self.update_record_with(domain, record_id, new_content, &RecordSpec::default())
+            .await
+    }
 
-        // 更新记录
-        let mut params = HashMap::new();
-        params.insert("domain".to_string(), _domain.to_string());
-        params.insert("recordId".to_string(), record_id.to_string());
-        params.insert("subDomain".to_string(), record.name);
-        params.insert("recordType".to_string(), record.record_type);
-        params.insert("value".to_string(), new_content.to_string());
+    async fn update_record_with(
+        &self,
+        domain: &str,
+        record_id: &str,
+        new_content: &str,
+        spec: &RecordSpec,
+    ) -> Result<UpdateResult> {
+        spec.ensure_supported(&self.capabilities())?;
+        let id = parse_record_id(record_id)?;
 
-        let response = self.send_request("ModifyRecord", params).await?;
-        let update_result: ModifyRecordResponse = serde_json::from_value(response)
+        // 先获取记录详情
+        let response = self
+            .send_request(
+                "DescribeRecord",
+                json!({ "Domain": domain, "RecordId": id }),
+            )
+            .await?;
+        let record: TencentRecordInfo = serde_json::from_value(response["RecordInfo"].clone())
             .map_err(|e| AppError::Provider(ProviderError::ParseError(e.to_string())))?;
+        let record_type = parse_record_type(&record.record_type);
+        let mut current = RecordContent::parse(&record_type, &record.value);
+        if record_type == DNSRecordType::MX {
+            current.priority = record.mx.filter(|mx| *mx > 0);
+        }
+        let mut content = RecordContent::from_input(&record_type, new_content, spec)?;
+        content.priority = content.priority.or(current.priority);
+        let value = record_value(&record_type, &content);
+        let ttl = spec.ttl.unwrap_or(record.ttl);
+        let remark = spec.comment.as_ref().or(record.remark.as_ref());
+        let settings_unchanged = ttl == record.ttl && remark == record.remark.as_ref();
+
+        // 仅修改 A/AAAA 记录值时使用动态解析接口, 其他情况使用 ModifyRecord 并保留原有设置
+        let response = if matches!(record.record_type.as_str(), "A" | "AAAA") && settings_unchanged
+        {
+            self.send_request(
+                "ModifyDynamicDNS",
+                json!({
+                    "Domain": domain,
+                    "SubDomain": record.sub_domain,
+                    "RecordId": id,
+                    "RecordLine": record.record_line,
+                    "Value": value,
+                }),
+            )
+            .await?
+        } else {
+            let mut payload = json!({
+                "Domain": domain,
+                "SubDomain": record.sub_domain,
+                "RecordType": record.record_type,
+                "RecordLine": record.record_line,
+                "Value": value,
+                "TTL": ttl,
+                "RecordId": id,
+            });
+            if let Some(mx) = content
+                .priority
+                .filter(|_| record_type == DNSRecordType::MX)
+            {
+                payload["MX"] = json!(mx);
+            }
+            if let Some(remark) = remark {
+                payload["Remark"] = json!(remark);
+            }
+            self.send_request("ModifyRecord", payload).await?
+        };
 
         Ok(UpdateResult {
             success: true,
-            record_id: update_result.record_id,
-            old_ip: record.value.clone(),
+            record_id: response["RecordId"]
+                .as_u64()
+                .unwrap_or(record.id)
+                .to_string(),
+            old_ip: current.to_rdata(&record_type),
             new_ip: new_content.to_string(),
             message: "更新成功".to_string(),
         })
@@ -323,57 +374,577 @@ impl DNSProvider for TencentProvider {
         record_type: DNSRecordType,
         content: &str,
     ) -> Result<DNSRecord> {
-        let mut params = HashMap::new();
-        params.insert("domain".to_string(), domain.to_string());
-        params.insert("subDomain".to_string(), record_name.to_string());
-        params.insert("recordType".to_string(), format!("{:?}", record_type));
-        params.insert("recordLine".to_string(), "默认".to_string());
-        params.insert("value".to_string(), content.to_string());
-
-        let response = self.send_request("CreateRecord", params).await?;
-        let record: TencentDNSRecord = serde_json::from_value(response["record"].clone())
-            .map_err(|e| AppError::Provider(ProviderError::ParseError(e.to_string())))?;
+        self.create_record_with(
+            domain,
+            record_name,
+            record_type,
+            content,
+            &RecordSpec::default(),
+        )
+        .await
+    }
+
+    async fn create_record_with(
+        &self,
+        domain: &str,
+        record_name: &str,
+        record_type: DNSRecordType,
+        content: &str,
+        spec: &RecordSpec,
+    ) -> Result<DNSRecord> {
+        spec.ensure_supported(&self.capabilities())?;
+        let content = RecordContent::from_input(&record_type, content, spec)?;
+
+        let subdomain = if record_name.is_empty() {
+            "@"
+        } else {
+            record_name
+        };
+        let ttl = spec.ttl.unwrap_or(DEFAULT_TTL);
+        let mut payload = json!({
+            "Domain": domain,
+            "SubDomain": subdomain,
+            "RecordType": record_type.to_string(),
+            "RecordLine": DEFAULT_LINE,
+            "Value": record_value(&record_type, &content),
+            "TTL": ttl,
+        });
+        if let Some(mx) = content
+            .priority
+            .filter(|_| record_type == DNSRecordType::MX)
+        {
+            payload["MX"] = json!(mx);
+        }
+        if let Some(remark) = &spec.comment {
+            payload["Remark"] = json!(remark);
+        }
+        let response = self.send_request("CreateRecord", payload).await?;
+
+        let record_id = response["RecordId"].as_u64().ok_or_else(|| {
+            AppError::Provider(ProviderError::ParseError("响应中缺少 RecordId".to_string()))
+        })?;
 
         Ok(DNSRecord {
-            id: record.id,
-            name: record.name,
+            id: record_id.to_string(),
+            name: subdomain.to_string(),
             record_type,
-            content: record.value,
-            ttl: record.ttl as u32,
+            content: content.content,
+            ttl,
             proxied: None,
-            priority: None,
+            priority: content.priority,
+            weight: content.weight,
+            port: content.port,
+            comment: spec.comment.clone(),
         })
     }
 
-    async fn delete_record(&self, _domain: &str, record_id: &str) -> Result<()> {
-        let mut params = HashMap::new();
-        params.insert("recordId".to_string(), record_id.to_string());
-
-        self.send_request("DeleteRecord", params).await?;
+    async fn delete_record(&self, domain: &str, record_id: &str) -> Result<()> {
+        let id = parse_record_id(record_id)?;
+        self.send_request("DeleteRecord", json!({ "Domain": domain, "RecordId": id }))
+            .await?;
         Ok(())
     }
 
     async fn test_connection(&self) -> Result<bool> {
         // 测试获取域名列表
-        let params = HashMap::new();
-        let response = self.send_request("DescribeDomainList", params).await?;
-        Ok(response["domainCount"].as_i64().unwrap_or(0) >= 0)
+        let response = self
+            .send_request("DescribeDomainList", json!({ "Limit": 1 }))
+            .await?;
+        Ok(response["DomainCountInfo"].is_object())
+    }
+
+    fn supported_record_types(&self) -> Vec<DNSRecordType> {
+        vec![
+            DNSRecordType::A,
+            DNSRecordType::AAAA,
+            DNSRecordType::CNAME,
+            DNSRecordType::MX,
+            DNSRecordType::TXT,
+            DNSRecordType::NS,
+            DNSRecordType::SRV,
+            DNSRecordType::CAA,
+        ]
+    }
+
+    fn capabilities(&self) -> ProviderCapabilities {
+        ProviderCapabilities {
+            proxy: false,
+            ttl: true,
+            line: true,
+            priority: true,
+            comment: true,
+        }
     }
 }
 
-// HMAC-SHA1
-fn hmac_sha1(key: &[u8], data: &[u8]) -> Vec<u8> {
-    use hmac::{Hmac, Mac};
-    use sha1::Sha1;
-    type HmacSha1 = Hmac<Sha1>;
+/// TC3-HMAC-SHA256 签名参数
+struct SigningParams<'a> {
+    secret_id: &'a str,
+    secret_key: &'a str,
+    service: &'a str,
+    host: &'a str,
+    payload: &'a str,
+    timestamp: i64,
+}
+
+/// 构造规范请求 (POST /, 签名头为 content-type 和 host)
+fn canonical_request(params: &SigningParams) -> String {
+    format!(
+        "POST\n/\n\ncontent-type:{}\nhost:{}\n\ncontent-type;host\n{}",
+        CONTENT_TYPE,
+        params.host,
+        sha256_hex(params.payload.as_bytes())
+    )
+}
 
-    let mut mac = HmacSha1::new_from_slice(key).expect("HMAC can take key of any size");
-    mac.update(data);
-    mac.finalize().into_bytes().to_vec()
+/// 计算 TC3-HMAC-SHA256 签名, 返回 Authorization 头
+fn sign_tc3(params: &SigningParams) -> String {
+    let date = chrono::DateTime::from_timestamp(params.timestamp, 0)
+        .unwrap_or_default()
+        .format("%Y-%m-%d")
+        .to_string();
+    let scope = format!("{}/{}/tc3_request", date, params.service);
+
+    let string_to_sign = format!(
+        "TC3-HMAC-SHA256\n{}\n{}\n{}",
+        params.timestamp,
+        scope,
+        sha256_hex(canonical_request(params).as_bytes())
+    );
+
+    let key = signing_key(params.secret_key, &date, params.service);
+    let signature = hex_encode(&hmac_sha256(&key, string_to_sign.as_bytes()));
+
+    format!(
+        "TC3-HMAC-SHA256 Credential={}/{}, SignedHeaders=content-type;host, Signature={}",
+        params.secret_id, scope, signature
+    )
 }
 
-// Base64 编码
-fn base64_encode(data: &[u8]) -> String {
-    use base64::Engine;
-    base64::engine::general_purpose::STANDARD.encode(data)
+/// 派生签名密钥
+fn signing_key(secret_key: &str, date: &str, service: &str) -> Vec<u8> {
+    let k_date = hmac_sha256(format!("TC3{}", secret_key).as_bytes(), date.as_bytes());
+    let k_service = hmac_sha256(&k_date, service.as_bytes());
+    hmac_sha256(&k_service, b"tc3_request")
+}
+
+/// 将 `Response.Error.Code` 映射为提供商错误
+fn parse_error(code: &str, message: &str) -> AppError {
+    let detail = format!("{}: {}", code, message);
+    let error = if code.starts_with("AuthFailure") || code.starts_with("UnauthorizedOperation") {
+        ProviderError::AuthenticationFailed(detail)
+    } else if code.starts_with("RequestLimitExceeded") || code.starts_with("LimitExceeded") {
+        ProviderError::RateLimitExceeded
+    } else if code == "ResourceNotFound.NoDataOfRecord"
+        || code == "InvalidParameter.RecordIdInvalid"
+    {
+        ProviderError::RecordNotFound(detail)
+    } else if code.contains("DomainNotExist")
+        || code == "InvalidParameter.DomainInvalid"
+        || code == "ResourceNotFound.NoDataOfDomain"
+    {
+        ProviderError::DomainNotFound(detail)
+    } else {
+        ProviderError::ApiError(detail)
+    };
+    AppError::Provider(error)
+}
+
+fn parse_record_id(record_id: &str) -> Result<u64> {
+    record_id.parse().map_err(|_| {
+        AppError::Provider(ProviderError::InvalidConfig(format!(
+            "无效的记录 ID: {}",
+            record_id
+        )))
+    })
+}
+
+/// 未内置的记录类型 (如 PTR、HTTPS) 以 `Other` 保留
+fn parse_record_type(record_type: &str) -> DNSRecordType {
+    record_type
+        .parse()
+        .unwrap_or_else(|_| DNSRecordType::Other(record_type.to_string()))
+}
+
+/// DNSPod 的记录值: MX 的优先级在 MX 字段中, SRV 的优先级、权重和端口写在记录值中
+fn record_value(record_type: &DNSRecordType, content: &RecordContent) -> String {
+    match record_type {
+        DNSRecordType::MX => content.content.clone(),
+        _ => content.to_rdata(record_type),
+    }
+}
+
+/// 转换为通用记录
+fn to_dns_record(record: TencentRecord) -> DNSRecord {
+    let record_type = parse_record_type(&record.record_type);
+    let content = RecordContent::parse(&record_type, &record.value);
+    let priority = match record_type {
+        DNSRecordType::MX => record.mx,
+        _ => content.priority,
+    };
+
+    DNSRecord {
+        id: record.record_id.to_string(),
+        name: record.name,
+        record_type,
+        content: content.content,
+        ttl: record.ttl,
+        proxied: None,
+        priority,
+        weight: content.weight,
+        port: content.port,
+        comment: record.remark.filter(|remark| !remark.is_empty()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::providers::test_support::{paginate, PagedResponder};
+    use wiremock::matchers::{body_partial_json, header, method};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    /// 腾讯云文档中的签名示例 (CVM DescribeInstances, 中文已转义为 \uXXXX)
+    fn doc_example(payload: &str) -> SigningParams<'_> {
+        SigningParams {
+            secret_id: "AKIDz8krbsJ5yKBZQpn74WFkmLPx3EXAMPLE",
+            secret_key: "Gu5t9xGARNpq86cd98joQYCN3EXAMPLE",
+            service: "cvm",
+            host: "cvm.tencentcloudapi.com",
+            payload,
+            timestamp: 1551113065,
+        }
+    }
+
+    const DOC_PAYLOAD: &str =
+        r#"{"Limit": 1, "Filters": [{"Values": ["\u672a\u547d\u540d"], "Name": "instance-name"}]}"#;
+
+    fn mock_provider(server: &MockServer) -> TencentProvider {
+        let mut provider = TencentProvider::with_endpoint(server.uri());
+        provider.secret_id = Some("id".to_string());
+        provider.secret_key = Some("key".to_string());
+        provider
+    }
+
+    fn action(name: &'static str) -> wiremock::matchers::HeaderExactMatcher {
+        header("X-TC-Action", name)
+    }
+
+    #[test]
+    fn test_tc3_doc_example() {
+        let params = doc_example(DOC_PAYLOAD);
+
+        assert_eq!(
+            sha256_hex(DOC_PAYLOAD.as_bytes()),
+            "35e9c5b0e3ae67532d3c9f17ead6c90222632e5b1ff7f6e89887f1398934f064"
+        );
+        assert_eq!(
+            sha256_hex(canonical_request(&params).as_bytes()),
+            "5ffe6a04c0664d6b969fab9a13bdab201d63ee709638e2749d62a09ca18d7031"
+        );
+        assert_eq!(
+            sign_tc3(&params),
+            "TC3-HMAC-SHA256 Credential=AKIDz8krbsJ5yKBZQpn74WFkmLPx3EXAMPLE/2019-02-25/cvm/tc3_request, \
+             SignedHeaders=content-type;host, \
+             Signature=72e494ea809ad7a8c8f7a4507b9bddcbaa8e581f516e8da2f66e2c5a96525168"
+        );
+    }
+
+    #[test]
+    fn test_parse_error() {
+        let kind = |code: &str| match parse_error(code, "message") {
+            AppError::Provider(error) => error,
+            other => panic!("应该返回提供商错误: {:?}", other),
+        };
+
+        assert!(matches!(
+            kind("AuthFailure.SignatureFailure"),
+            ProviderError::AuthenticationFailed(_)
+        ));
+        assert!(matches!(
+            kind("RequestLimitExceeded"),
+            ProviderError::RateLimitExceeded
+        ));
+        assert!(matches!(
+            kind("ResourceNotFound.NoDataOfRecord"),
+            ProviderError::RecordNotFound(_)
+        ));
+        assert!(matches!(
+            kind("InvalidParameterValue.DomainNotExists"),
+            ProviderError::DomainNotFound(_)
+        ));
+        assert!(matches!(
+            kind("InvalidParameter.SubdomainInvalid"),
+            ProviderError::ApiError(_)
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_list_records_reads_all_pages() {
+        let server = MockServer::start().await;
+
+        let pages = paginate(7000, PAGE_SIZE, |i| {
+            json!({
+                "RecordId": i,
+                "Name": format!("host{}", i),
+                "Type": "A",
+                "Value": "1.1.1.1",
+                "TTL": 600,
+                "Line": "默认"
+            })
+        });
+        let pages = pages
+            .into_iter()
+            .map(|records| {
+                json!({
+                    "Response": {
+                        "RecordCountInfo": { "TotalCount": 7000 },
+                        "RecordList": records
+                    }
+                })
+            })
+            .collect();
+        Mock::given(method("POST"))
+            .and(action("DescribeRecordList"))
+            .and(header("X-TC-Version", API_VERSION))
+            .respond_with(PagedResponder::offset("Offset", "Limit", pages))
+            .expect(3)
+            .mount(&server)
+            .await;
+
+        let provider = mock_provider(&server);
+        let records = provider.list_records("example.com").await.unwrap();
+
+        assert_eq!(records.len(), 7000);
+        assert_eq!(records[6999].id, "6999");
+    }
+
+    #[tokio::test]
+    async fn test_get_record_without_records() {
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(action("DescribeRecordList"))
+            .and(body_partial_json(
+                json!({ "Subdomain": "www", "RecordType": "AAAA" }),
+            ))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+                "Response": {
+                    "Error": {
+                        "Code": "ResourceNotFound.NoDataOfRecord",
+                        "Message": "记录列表为空。"
+                    },
+                    "RequestId": "1"
+                }
+            })))
+            .mount(&server)
+            .await;
+
+        let provider = mock_provider(&server);
+        let record = provider
+            .get_record("example.com", "www", DNSRecordType::AAAA)
+            .await
+            .unwrap();
+        assert!(record.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_update_a_record_uses_dynamic_dns() {
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(action("DescribeRecord"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+                "Response": {
+                    "RecordInfo": {
+                        "Id": 42,
+                        "SubDomain": "www",
+                        "RecordType": "A",
+                        "RecordLine": "默认",
+                        "Value": "1.1.1.1",
+                        "TTL": 600,
+                        "MX": 0
+                    }
+                }
+            })))
+            .mount(&server)
+            .await;
+        Mock::given(method("POST"))
+            .and(action("ModifyDynamicDNS"))
+            .and(body_partial_json(json!({
+                "Domain": "example.com",
+                "SubDomain": "www",
+                "RecordId": 42,
+                "RecordLine": "默认",
+                "Value": "2.2.2.2"
+            })))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .set_body_json(json!({ "Response": { "RecordId": 42, "RequestId": "2" } })),
+            )
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let provider = mock_provider(&server);
+        let result = provider
+            .update_record("example.com", "42", "2.2.2.2")
+            .await
+            .unwrap();
+
+        assert!(result.success);
+        assert_eq!(result.record_id, "42");
+        assert_eq!(result.old_ip, "1.1.1.1");
+        assert_eq!(result.new_ip, "2.2.2.2");
+    }
+
+    #[tokio::test]
+    async fn test_update_a_record_with_ttl_uses_modify_record() {
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(action("DescribeRecord"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+                "Response": {
+                    "RecordInfo": {
+                        "Id": 42,
+                        "SubDomain": "www",
+                        "RecordType": "A",
+                        "RecordLine": "电信",
+                        "Value": "1.1.1.1",
+                        "TTL": 600,
+                        "MX": 0,
+                        "Remark": "home"
+                    }
+                }
+            })))
+            .mount(&server)
+            .await;
+        Mock::given(method("POST"))
+            .and(action("ModifyRecord"))
+            .and(body_partial_json(json!({
+                "RecordId": 42,
+                "RecordType": "A",
+                "RecordLine": "电信",
+                "Value": "2.2.2.2",
+                "TTL": 120,
+                "Remark": "home"
+            })))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .set_body_json(json!({ "Response": { "RecordId": 42, "RequestId": "2" } })),
+            )
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let provider = mock_provider(&server);
+        let spec = RecordSpec {
+            ttl: Some(120),
+            ..Default::default()
+        };
+        let result = provider
+            .update_record_with("example.com", "42", "2.2.2.2", &spec)
+            .await
+            .unwrap();
+        assert_eq!(result.old_ip, "1.1.1.1");
+    }
+
+    #[tokio::test]
+    async fn test_create_record_with_spec() {
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(action("CreateRecord"))
+            .and(body_partial_json(json!({
+                "SubDomain": "@",
+                "RecordType": "MX",
+                "RecordLine": "默认",
+                "TTL": 3600,
+                "MX": 10,
+                "Remark": "mail"
+            })))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .set_body_json(json!({ "Response": { "RecordId": 7, "RequestId": "3" } })),
+            )
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let provider = mock_provider(&server);
+        let spec = RecordSpec {
+            ttl: Some(3600),
+            priority: Some(10),
+            comment: Some("mail".to_string()),
+            ..Default::default()
+        };
+        let record = provider
+            .create_record_with(
+                "example.com",
+                "",
+                DNSRecordType::MX,
+                "mx.example.com",
+                &spec,
+            )
+            .await
+            .unwrap();
+        assert_eq!(record.id, "7");
+        assert_eq!(record.ttl, 3600);
+        assert_eq!(record.priority, Some(10));
+    }
+
+    #[tokio::test]
+    async fn test_update_srv_record_keeps_structure() {
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(action("DescribeRecord"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+                "Response": {
+                    "RecordInfo": {
+                        "Id": 9,
+                        "SubDomain": "_sip._tcp",
+                        "RecordType": "SRV",
+                        "RecordLine": "默认",
+                        "Value": "10 5 5060 sip.example.com.",
+                        "TTL": 600,
+                        "MX": 0
+                    },
+                    "RequestId": "1"
+                }
+            })))
+            .mount(&server)
+            .await;
+        Mock::given(method("POST"))
+            .and(action("ModifyRecord"))
+            .and(body_partial_json(json!({
+                "RecordType": "SRV",
+                "Value": "20 5 5061 sip.example.com.",
+            })))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .set_body_json(json!({ "Response": { "RecordId": 9, "RequestId": "2" } })),
+            )
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let provider = mock_provider(&server);
+        let result = provider
+            .update_record("example.com", "9", "20 5 5061 sip.example.com.")
+            .await
+            .unwrap();
+        assert_eq!(result.old_ip, "10 5 5060 sip.example.com.");
+
+        let record = to_dns_record(TencentRecord {
+            record_id: 10,
+            name: "@".to_string(),
+            record_type: "HTTPS".to_string(),
+            value: "1 . alpn=h2".to_string(),
+            ttl: 600,
+            mx: Some(0),
+            remark: None,
+        });
+        assert_eq!(
+            record.record_type,
+            DNSRecordType::Other("HTTPS".to_string())
+        );
+        assert_eq!(record.priority, None);
+    }
 }
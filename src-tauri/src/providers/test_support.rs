@@ -0,0 +1,300 @@
+// 提供商测试的公共工具
+use crate::error::{AppError, Result};
+use crate::providers::provider_trait::{
+    full_record_name, Credentials, DNSProvider, DNSRecord, DNSRecordType, ProviderCapabilities,
+    ProviderError, RecordSpec, UpdateResult,
+};
+use async_trait::async_trait;
+use std::collections::HashMap;
+use std::sync::Mutex;
+use wiremock::{Request, Respond, ResponseTemplate};
+
+/// 读取调用方目录下 `fixtures/<name>.json` 测试数据
+macro_rules! fixture {
+    ($name:literal) => {
+        serde_json::from_str::<serde_json::Value>(include_str!(concat!(
+            "fixtures/",
+            $name,
+            ".json"
+        )))
+        .unwrap()
+    };
+}
+pub(crate) use fixture;
+
+/// 生成 `mock_provider(server)`: 指向模拟服务器并设置 API 令牌
+macro_rules! mock_provider {
+    ($provider:ty, $token:literal) => {
+        fn mock_provider(server: &wiremock::MockServer) -> $provider {
+            let mut provider = <$provider>::with_endpoint(server.uri());
+            provider.api_token = Some($token.to_string());
+            provider
+        }
+    };
+}
+pub(crate) use mock_provider;
+
+/// 请求中分页参数的形式
+enum PageParam {
+    /// 页码, 例如 `page=2` (first 为第一页的页码)
+    Number { name: &'static str, first: usize },
+    /// 偏移量 + 每页数量, 例如 `offset=100&length=100` 或 `{"Offset": 100, "Limit": 100}`
+    Offset {
+        offset: &'static str,
+        limit: &'static str,
+    },
+}
+
+/// 按请求中的分页参数返回对应页的 JSON 响应, 用于验证提供商读取了全部分页
+///
+/// 分页参数可以在查询字符串中, 也可以在 JSON 请求体的顶层字段中
+pub struct PagedResponder {
+    param: PageParam,
+    pages: Vec<serde_json::Value>,
+}
+
+impl PagedResponder {
+    /// 使用页码参数分页
+    pub fn page_number(name: &'static str, first: usize, pages: Vec<serde_json::Value>) -> Self {
+        Self {
+            param: PageParam::Number { name, first },
+            pages,
+        }
+    }
+
+    /// 使用偏移量参数分页
+    pub fn offset(
+        offset: &'static str,
+        limit: &'static str,
+        pages: Vec<serde_json::Value>,
+    ) -> Self {
+        Self {
+            param: PageParam::Offset { offset, limit },
+            pages,
+        }
+    }
+
+    fn page_index(&self, request: &Request) -> Option<usize> {
+        let query: HashMap<String, String> = request.url.query_pairs().into_owned().collect();
+        let body: serde_json::Value =
+            serde_json::from_slice(&request.body).unwrap_or(serde_json::Value::Null);
+        let number = |name: &str| {
+            query
+                .get(name)
+                .and_then(|v| v.parse::<usize>().ok())
+                .or_else(|| body[name].as_u64().map(|v| v as usize))
+        };
+
+        match self.param {
+            PageParam::Number { name, first } => number(name).unwrap_or(first).checked_sub(first),
+            PageParam::Offset { offset, limit } => {
+                let limit = number(limit).filter(|l| *l > 0)?;
+                Some(number(offset).unwrap_or(0) / limit)
+            }
+        }
+    }
+}
+
+impl Respond for PagedResponder {
+    fn respond(&self, request: &Request) -> ResponseTemplate {
+        match self.page_index(request).and_then(|i| self.pages.get(i)) {
+            Some(page) => ResponseTemplate::new(200).set_body_json(page),
+            None => ResponseTemplate::new(400).set_body_string(format!(
+                "分页参数超出范围: {}",
+                request.url.query().unwrap_or_default()
+            )),
+        }
+    }
+}
+
+/// 生成 `count` 条测试记录并按 `page_size` 切分为多页
+pub fn paginate<T>(count: usize, page_size: usize, record: impl Fn(usize) -> T) -> Vec<Vec<T>> {
+    let records: Vec<T> = (0..count).map(record).collect();
+    let mut pages = Vec::new();
+    let mut records = records.into_iter().peekable();
+    while records.peek().is_some() {
+        pages.push(records.by_ref().take(page_size).collect());
+    }
+    pages
+}
+
+/// 内存中的提供商, 用于服务层测试 (记录 ID 为递增的序号)
+#[derive(Default)]
+pub struct FakeProvider {
+    records: Mutex<Vec<DNSRecord>>,
+    next_id: Mutex<usize>,
+    /// 下一次读取记录时返回的网络错误
+    failure: Mutex<Option<String>>,
+}
+
+impl FakeProvider {
+    pub fn new(records: Vec<DNSRecord>) -> Self {
+        Self {
+            next_id: Mutex::new(records.len() + 1),
+            records: Mutex::new(records),
+            failure: Mutex::new(None),
+        }
+    }
+
+    /// 当前的全部记录
+    pub fn records(&self) -> Vec<DNSRecord> {
+        self.records.lock().unwrap().clone()
+    }
+
+    /// 让下一次读取记录失败, 模拟网络错误
+    pub fn fail_next(&self, message: &str) {
+        *self.failure.lock().unwrap() = Some(message.to_string());
+    }
+
+    /// 直接修改远端记录, 模拟在提供商控制台中的改动
+    pub fn edit(&self, record_id: &str, edit: impl FnOnce(&mut DNSRecord)) {
+        let mut records = self.records.lock().unwrap();
+        edit(records.iter_mut().find(|r| r.id == record_id).unwrap());
+    }
+}
+
+/// 测试用的记录 (TTL 300, 未开启代理)
+pub fn record(id: &str, name: &str, record_type: DNSRecordType, content: &str) -> DNSRecord {
+    DNSRecord {
+        id: id.to_string(),
+        name: name.to_string(),
+        record_type,
+        content: content.to_string(),
+        ttl: 300,
+        proxied: Some(false),
+        priority: None,
+        weight: None,
+        port: None,
+        comment: None,
+    }
+}
+
+#[async_trait]
+impl DNSProvider for FakeProvider {
+    fn provider_id(&self) -> &'static str {
+        "fake"
+    }
+
+    fn provider_name(&self) -> &'static str {
+        "Fake"
+    }
+
+    async fn initialize(&mut self, _credentials: &Credentials) -> Result<()> {
+        Ok(())
+    }
+
+    async fn list_records(&self, _domain: &str) -> Result<Vec<DNSRecord>> {
+        if let Some(message) = self.failure.lock().unwrap().take() {
+            return Err(AppError::Provider(ProviderError::NetworkError(message)));
+        }
+        Ok(self.records())
+    }
+
+    async fn update_record(
+        &self,
+        domain: &str,
+        record_id: &str,
+        new_content: &str,
+    ) -> Result<UpdateResult> {
+        self.update_record_with(domain, record_id, new_content, &RecordSpec::default())
+            .await
+    }
+
+    async fn update_record_with(
+        &self,
+        _domain: &str,
+        record_id: &str,
+        new_content: &str,
+        spec: &RecordSpec,
+    ) -> Result<UpdateResult> {
+        let mut records = self.records.lock().unwrap();
+        let record = records
+            .iter_mut()
+            .find(|r| r.id == record_id)
+            .ok_or_else(|| AppError::Provider(ProviderError::RecordNotFound(record_id.into())))?;
+
+        let old_ip = std::mem::replace(&mut record.content, new_content.to_string());
+        record.ttl = spec.ttl.unwrap_or(record.ttl);
+        record.proxied = spec.proxied.or(record.proxied);
+        record.comment = spec.comment.clone().or(record.comment.take());
+        record.priority = spec.priority.or(record.priority);
+
+        Ok(UpdateResult {
+            success: true,
+            record_id: record_id.to_string(),
+            old_ip,
+            new_ip: new_content.to_string(),
+            message: "更新成功".to_string(),
+        })
+    }
+
+    async fn create_record(
+        &self,
+        domain: &str,
+        record_name: &str,
+        record_type: DNSRecordType,
+        content: &str,
+    ) -> Result<DNSRecord> {
+        self.create_record_with(
+            domain,
+            record_name,
+            record_type,
+            content,
+            &RecordSpec::default(),
+        )
+        .await
+    }
+
+    async fn create_record_with(
+        &self,
+        domain: &str,
+        record_name: &str,
+        record_type: DNSRecordType,
+        content: &str,
+        spec: &RecordSpec,
+    ) -> Result<DNSRecord> {
+        let id = {
+            let mut next_id = self.next_id.lock().unwrap();
+            *next_id += 1;
+            (*next_id - 1).to_string()
+        };
+        let created = DNSRecord {
+            ttl: spec.ttl.unwrap_or(300),
+            proxied: spec.proxied.or(Some(false)),
+            priority: spec.priority,
+            comment: spec.comment.clone(),
+            ..record(
+                &id,
+                &full_record_name(domain, record_name),
+                record_type,
+                content,
+            )
+        };
+        self.records.lock().unwrap().push(created.clone());
+        Ok(created)
+    }
+
+    async fn delete_record(&self, _domain: &str, record_id: &str) -> Result<()> {
+        let mut records = self.records.lock().unwrap();
+        let index = records
+            .iter()
+            .position(|r| r.id == record_id)
+            .ok_or_else(|| AppError::Provider(ProviderError::RecordNotFound(record_id.into())))?;
+        records.remove(index);
+        Ok(())
+    }
+
+    async fn test_connection(&self) -> Result<bool> {
+        Ok(true)
+    }
+
+    fn capabilities(&self) -> ProviderCapabilities {
+        ProviderCapabilities {
+            proxy: true,
+            ttl: true,
+            line: false,
+            priority: true,
+            comment: true,
+        }
+    }
+}
@@ -0,0 +1,765 @@
+use crate::error::{AppError, Result};
+use crate::providers::provider_trait::{
+    full_record_name, Credentials, DNSProvider, DNSRecord, DNSRecordType, ProviderError,
+    UpdateResult,
+};
+use async_trait::async_trait;
+use regex::Regex;
+use reqwest::header::{HeaderName, HeaderValue, CONTENT_TYPE};
+use reqwest::{Client, Method};
+use serde_json::Value;
+use std::collections::HashMap;
+use std::sync::RwLock;
+
+/// 错误信息中保留的响应内容长度
+const BODY_SNIPPET_LEN: usize = 200;
+
+/// 通用 Webhook 提供商
+///
+/// 请求的 URL、方法、请求头和请求体均为模板, 支持以下变量:
+/// `{ip}`、`{domain}`、`{subdomain}`、`{fqdn}`、`{record_type}`。
+/// Webhook 无法查询记录, 因此只在内存中保存最近一次成功推送的记录,
+/// 供更新服务判断 IP 是否变化。
+pub struct WebhookProvider {
+    config: Option<WebhookConfig>,
+    /// 最近一次成功推送的记录, 键为 `subdomain|TYPE`
+    sent: RwLock<HashMap<String, DNSRecord>>,
+    client: Client,
+}
+
+/// 从 `Credentials::extra` 解析出的 Webhook 配置
+#[derive(Debug)]
+struct WebhookConfig {
+    url: String,
+    method: Method,
+    headers: Vec<(HeaderName, String)>,
+    body: Option<String>,
+    rules: SuccessRules,
+}
+
+/// 判定请求成功的规则, 所有已配置的规则都满足时才视为成功
+#[derive(Debug)]
+struct SuccessRules {
+    /// 允许的状态码范围 (闭区间), 默认 200-299
+    status: Vec<(u16, u16)>,
+    body_regex: Option<Regex>,
+    json_path: Option<String>,
+    /// JSON 路径的期望值, 未设置时要求该值为真值
+    json_value: Option<String>,
+}
+
+/// 模板变量
+struct TemplateVars<'a> {
+    ip: &'a str,
+    domain: &'a str,
+    subdomain: &'a str,
+    fqdn: &'a str,
+    record_type: &'a str,
+}
+
+impl WebhookProvider {
+    pub fn new() -> Self {
+        Self {
+            config: None,
+            sent: RwLock::new(HashMap::new()),
+            client: Client::builder()
+                .timeout(std::time::Duration::from_secs(30))
+                .build()
+                .unwrap(),
+        }
+    }
+
+    fn config(&self) -> Result<&WebhookConfig> {
+        self.config.as_ref().ok_or_else(|| {
+            AppError::Provider(ProviderError::InvalidConfig(
+                "Webhook 尚未配置 url".to_string(),
+            ))
+        })
+    }
+
+    /// 渲染模板并发送请求, 按成功规则检查响应
+    async fn send(&self, vars: &TemplateVars<'_>) -> Result<String> {
+        let config = self.config()?;
+
+        let url = render(&config.url, vars, true);
+        let mut request = self.client.request(config.method.clone(), &url);
+
+        let mut has_content_type = false;
+        for (name, template) in &config.headers {
+            let value = HeaderValue::from_str(&render(template, vars, false)).map_err(|e| {
+                AppError::Provider(ProviderError::InvalidConfig(format!(
+                    "无效的请求头 {}: {}",
+                    name, e
+                )))
+            })?;
+            has_content_type |= name == CONTENT_TYPE;
+            request = request.header(name.clone(), value);
+        }
+
+        if let Some(template) = &config.body {
+            let body = render(template, vars, false);
+            // 未指定类型时, JSON 格式的请求体按 application/json 发送
+            if !has_content_type && serde_json::from_str::<Value>(&body).is_ok() {
+                request = request.header(CONTENT_TYPE, "application/json");
+            }
+            request = request.body(body);
+        }
+
+        let response = request
+            .send()
+            .await
+            .map_err(|e| AppError::Provider(ProviderError::NetworkError(e.to_string())))?;
+        let status = response.status().as_u16();
+        let text = response
+            .text()
+            .await
+            .map_err(|e| AppError::Provider(ProviderError::NetworkError(e.to_string())))?;
+
+        config.rules.check(status, &text)?;
+        Ok(text)
+    }
+
+    /// 推送记录并保存到内存
+    async fn push(
+        &self,
+        domain: &str,
+        record_name: &str,
+        record_type: &str,
+        content: &str,
+    ) -> Result<DNSRecord> {
+        let subdomain = match record_name {
+            "" => "@",
+            name => name,
+        };
+        let fqdn = full_record_name(domain, record_name);
+
+        self.send(&TemplateVars {
+            ip: content,
+            domain,
+            subdomain,
+            fqdn: &fqdn,
+            record_type,
+        })
+        .await?;
+
+        let id = record_id(subdomain, record_type);
+        let record = DNSRecord {
+            id: id.clone(),
+            name: fqdn,
+            record_type: record_type.parse()?,
+            content: content.to_string(),
+            ttl: 0,
+            proxied: None,
+            priority: None,
+            weight: None,
+            port: None,
+            comment: None,
+        };
+        self.sent.write().unwrap().insert(id, record.clone());
+        Ok(record)
+    }
+}
+
+impl Default for WebhookProvider {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[async_trait]
+impl DNSProvider for WebhookProvider {
+    fn provider_id(&self) -> &'static str {
+        "webhook"
+    }
+
+    fn provider_name(&self) -> &'static str {
+        "Webhook (自定义 HTTP)"
+    }
+
+    async fn initialize(&mut self, credentials: &Credentials) -> Result<()> {
+        self.config = Some(WebhookConfig::from_extra(&credentials.extra)?);
+        self.sent.write().unwrap().clear();
+        Ok(())
+    }
+
+    async fn list_records(&self, domain: &str) -> Result<Vec<DNSRecord>> {
+        let suffix = domain.trim_end_matches('.').to_lowercase();
+        Ok(self
+            .sent
+            .read()
+            .unwrap()
+            .values()
+            .filter(|r| {
+                let name = r.name.to_lowercase();
+                name == suffix || name.ends_with(&format!(".{}", suffix))
+            })
+            .cloned()
+            .collect())
+    }
+
+    async fn get_record(
+        &self,
+        _domain: &str,
+        record_name: &str,
+        record_type: DNSRecordType,
+    ) -> Result<Option<DNSRecord>> {
+        let subdomain = match record_name {
+            "" => "@",
+            name => name,
+        };
+        let id = record_id(subdomain, &record_type.to_string());
+        Ok(self.sent.read().unwrap().get(&id).cloned())
+    }
+
+    async fn update_record(
+        &self,
+        domain: &str,
+        record_id: &str,
+        new_content: &str,
+    ) -> Result<UpdateResult> {
+        let (subdomain, record_type) = record_id.rsplit_once('|').ok_or_else(|| {
+            AppError::Provider(ProviderError::RecordNotFound(record_id.to_string()))
+        })?;
+        let old_ip = self
+            .sent
+            .read()
+            .unwrap()
+            .get(record_id)
+            .map(|r| r.content.clone())
+            .unwrap_or_default();
+
+        let record = self
+            .push(domain, subdomain, record_type, new_content)
+            .await?;
+
+        Ok(UpdateResult {
+            success: true,
+            record_id: record.id,
+            old_ip,
+            new_ip: new_content.to_string(),
+            message: "更新成功".to_string(),
+        })
+    }
+
+    async fn create_record(
+        &self,
+        domain: &str,
+        record_name: &str,
+        record_type: DNSRecordType,
+        content: &str,
+    ) -> Result<DNSRecord> {
+        self.push(domain, record_name, &record_type.to_string(), content)
+            .await
+    }
+
+    async fn delete_record(&self, _domain: &str, _record_id: &str) -> Result<()> {
+        // Webhook 只能推送新值, 无法删除记录
+        Err(AppError::Provider(ProviderError::Unknown(
+            "Webhook 不支持删除记录".to_string(),
+        )))
+    }
+
+    async fn test_connection(&self) -> Result<bool> {
+        // 任何请求都会触发一次真实推送, 这里只检查配置
+        self.config()?;
+        Ok(true)
+    }
+
+    fn supported_record_types(&self) -> Vec<DNSRecordType> {
+        vec![DNSRecordType::A, DNSRecordType::AAAA]
+    }
+}
+
+impl WebhookConfig {
+    /// 解析配置字段:
+    /// `url` (必填)、`method`、`headers`、`body`、`success_status`、
+    /// `success_body_regex`、`success_json_path`、`success_json_value`
+    fn from_extra(extra: &Value) -> Result<Self> {
+        let field = |key: &str| {
+            extra
+                .get(key)
+                .and_then(|v| match v {
+                    Value::String(s) => Some(s.trim().to_string()),
+                    Value::Number(n) => Some(n.to_string()),
+                    Value::Bool(b) => Some(b.to_string()),
+                    _ => None,
+                })
+                .filter(|v| !v.is_empty())
+        };
+
+        let url = field("url").ok_or_else(|| invalid_config("Webhook 需要提供 url"))?;
+        if !url.starts_with("http://") && !url.starts_with("https://") {
+            return Err(invalid_config(&format!(
+                "url 必须以 http:// 或 https:// 开头: {}",
+                url
+            )));
+        }
+
+        let method = match field("method") {
+            Some(method) => Method::from_bytes(method.to_uppercase().as_bytes())
+                .map_err(|_| invalid_config(&format!("无效的请求方法: {}", method)))?,
+            None => Method::GET,
+        };
+
+        let headers = match extra.get("headers") {
+            Some(headers) => parse_headers(headers)?,
+            None => Vec::new(),
+        };
+
+        let status = match field("success_status") {
+            Some(rule) => parse_status_rule(&rule)?,
+            None => vec![(200, 299)],
+        };
+
+        let body_regex = field("success_body_regex")
+            .map(|pattern| {
+                Regex::new(&pattern)
+                    .map_err(|e| invalid_config(&format!("无效的正则表达式: {}", e)))
+            })
+            .transpose()?;
+
+        Ok(Self {
+            url,
+            method,
+            headers,
+            // 请求体保留首尾空白
+            body: extra
+                .get("body")
+                .and_then(|v| v.as_str())
+                .filter(|v| !v.is_empty())
+                .map(|v| v.to_string()),
+            rules: SuccessRules {
+                status,
+                body_regex,
+                json_path: field("success_json_path"),
+                json_value: field("success_json_value"),
+            },
+        })
+    }
+}
+
+impl SuccessRules {
+    /// 检查响应是否满足成功规则
+    fn check(&self, status: u16, body: &str) -> Result<()> {
+        if !self
+            .status
+            .iter()
+            .any(|(min, max)| (*min..=*max).contains(&status))
+        {
+            let message = format!("HTTP {}: {}", status, snippet(body));
+            return Err(AppError::Provider(match status {
+                401 | 403 => ProviderError::AuthenticationFailed(message),
+                429 => ProviderError::RateLimitExceeded,
+                _ => ProviderError::ApiError(message),
+            }));
+        }
+
+        if let Some(regex) = &self.body_regex {
+            if !regex.is_match(body) {
+                return Err(AppError::Provider(ProviderError::ApiError(format!(
+                    "响应内容不匹配 {}: {}",
+                    regex.as_str(),
+                    snippet(body)
+                ))));
+            }
+        }
+
+        if let Some(path) = &self.json_path {
+            let json: Value = serde_json::from_str(body).map_err(|e| {
+                AppError::Provider(ProviderError::ParseError(format!(
+                    "响应不是有效的 JSON: {}",
+                    e
+                )))
+            })?;
+            let value = json_lookup(&json, path);
+            let matched = match (&value, &self.json_value) {
+                (Some(Value::String(s)), Some(expected)) => s == expected,
+                (Some(v), Some(expected)) => v.to_string() == *expected,
+                (Some(v), None) => is_truthy(v),
+                (None, _) => false,
+            };
+            if !matched {
+                return Err(AppError::Provider(ProviderError::ApiError(format!(
+                    "响应字段 {} 不满足成功条件: {}",
+                    path,
+                    value
+                        .map(|v| v.to_string())
+                        .unwrap_or_else(|| "不存在".to_string())
+                ))));
+            }
+        }
+
+        Ok(())
+    }
+}
+
+fn invalid_config(message: &str) -> AppError {
+    AppError::Provider(ProviderError::InvalidConfig(message.to_string()))
+}
+
+fn record_id(subdomain: &str, record_type: &str) -> String {
+    format!("{}|{}", subdomain, record_type)
+}
+
+/// 替换模板变量, URL 中的变量值需要进行百分号编码
+fn render(template: &str, vars: &TemplateVars, encode: bool) -> String {
+    let value = |v: &str| {
+        if encode {
+            urlencoding::encode(v).into_owned()
+        } else {
+            v.to_string()
+        }
+    };
+
+    template
+        .replace("{ip}", &value(vars.ip))
+        .replace("{domain}", &value(vars.domain))
+        .replace("{subdomain}", &value(vars.subdomain))
+        .replace("{fqdn}", &value(vars.fqdn))
+        .replace("{record_type}", &value(vars.record_type))
+}
+
+/// 解析请求头配置
+///
+/// 支持 JSON 对象, 或每行一个 `Name: value` 的文本
+fn parse_headers(value: &Value) -> Result<Vec<(HeaderName, String)>> {
+    let pairs: Vec<(String, String)> = match value {
+        Value::Object(map) => map
+            .iter()
+            .map(|(k, v)| {
+                let v = match v {
+                    Value::String(s) => s.clone(),
+                    other => other.to_string(),
+                };
+                (k.clone(), v)
+            })
+            .collect(),
+        Value::String(s) if s.trim_start().starts_with('{') => {
+            let parsed: Value = serde_json::from_str(s)
+                .map_err(|e| invalid_config(&format!("headers 不是有效的 JSON: {}", e)))?;
+            return parse_headers(&parsed);
+        }
+        Value::String(s) => s
+            .lines()
+            .map(str::trim)
+            .filter(|line| !line.is_empty())
+            .map(|line| {
+                line.split_once(':')
+                    .map(|(k, v)| (k.trim().to_string(), v.trim().to_string()))
+                    .ok_or_else(|| invalid_config(&format!("无效的请求头: {}", line)))
+            })
+            .collect::<Result<_>>()?,
+        Value::Null => Vec::new(),
+        _ => return Err(invalid_config("headers 必须是对象或文本")),
+    };
+
+    pairs
+        .into_iter()
+        .map(|(name, value)| {
+            HeaderName::from_bytes(name.as_bytes())
+                .map(|name| (name, value))
+                .map_err(|_| invalid_config(&format!("无效的请求头名称: {}", name)))
+        })
+        .collect()
+}
+
+/// 解析状态码规则, 例如 `200`、`200,204`、`2xx`、`200-299`
+fn parse_status_rule(rule: &str) -> Result<Vec<(u16, u16)>> {
+    let error = || invalid_config(&format!("无效的状态码规则: {}", rule));
+
+    rule.split(',')
+        .map(str::trim)
+        .filter(|part| !part.is_empty())
+        .map(|part| {
+            let lower = part.to_lowercase();
+            if let Some(class) = lower.strip_suffix("xx") {
+                let class = match class.as_bytes() {
+                    [digit @ b'1'..=b'5'] => u16::from(digit - b'0'),
+                    _ => return Err(error()),
+                };
+                Ok((class * 100, class * 100 + 99))
+            } else if let Some((min, max)) = part.split_once('-') {
+                let min = min.trim().parse().map_err(|_| error())?;
+                let max = max.trim().parse().map_err(|_| error())?;
+                Ok((min, max))
+            } else {
+                let code = part.parse().map_err(|_| error())?;
+                Ok((code, code))
+            }
+        })
+        .collect::<Result<Vec<_>>>()
+        .and_then(|rules| {
+            if rules.is_empty() {
+                Err(error())
+            } else {
+                Ok(rules)
+            }
+        })
+}
+
+/// 按 `$.data.items[0].status` 或 `data.items.0.status` 形式的路径取值
+fn json_lookup<'a>(json: &'a Value, path: &str) -> Option<&'a Value> {
+    let path = path
+        .trim()
+        .trim_start_matches('$')
+        .replace('[', ".")
+        .replace(']', "");
+
+    path.split('.')
+        .filter(|segment| !segment.is_empty())
+        .try_fold(json, |value, segment| match value {
+            Value::Array(items) => segment.parse::<usize>().ok().and_then(|i| items.get(i)),
+            Value::Object(map) => map.get(segment),
+            _ => None,
+        })
+}
+
+fn is_truthy(value: &Value) -> bool {
+    match value {
+        Value::Null => false,
+        Value::Bool(b) => *b,
+        Value::Number(n) => n.as_f64().is_some_and(|n| n != 0.0),
+        Value::String(s) => !s.is_empty(),
+        Value::Array(items) => !items.is_empty(),
+        Value::Object(map) => !map.is_empty(),
+    }
+}
+
+fn snippet(body: &str) -> String {
+    body.chars().take(BODY_SNIPPET_LEN).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+    use wiremock::matchers::{body_json, header, method, path, query_param};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    fn provider(extra: Value) -> WebhookProvider {
+        let mut provider = WebhookProvider::new();
+        provider.config = Some(WebhookConfig::from_extra(&extra).unwrap());
+        provider
+    }
+
+    #[test]
+    fn test_render_template() {
+        let vars = TemplateVars {
+            ip: "2001:db8::1",
+            domain: "example.com",
+            subdomain: "home",
+            fqdn: "home.example.com",
+            record_type: "AAAA",
+        };
+
+        assert_eq!(
+            render(
+                "https://api/update?h={fqdn}&ip={ip}&t={record_type}",
+                &vars,
+                true
+            ),
+            "https://api/update?h=home.example.com&ip=2001%3Adb8%3A%3A1&t=AAAA"
+        );
+        assert_eq!(
+            render(
+                r#"{"name":"{subdomain}.{domain}","ip":"{ip}"}"#,
+                &vars,
+                false
+            ),
+            r#"{"name":"home.example.com","ip":"2001:db8::1"}"#
+        );
+    }
+
+    #[test]
+    fn test_parse_status_rule() {
+        assert_eq!(parse_status_rule("200").unwrap(), vec![(200, 200)]);
+        assert_eq!(
+            parse_status_rule("2xx, 304").unwrap(),
+            vec![(200, 299), (304, 304)]
+        );
+        assert_eq!(parse_status_rule("200-204").unwrap(), vec![(200, 204)]);
+        assert!(parse_status_rule("ok").is_err());
+        assert!(parse_status_rule("700xx").is_err());
+        assert!(parse_status_rule("6xx").is_err());
+        assert!(parse_status_rule("").is_err());
+    }
+
+    #[test]
+    fn test_json_lookup() {
+        let json = json!({ "data": { "items": [{ "status": "ok" }] }, "code": 0 });
+
+        assert_eq!(
+            json_lookup(&json, "$.data.items[0].status"),
+            Some(&json!("ok"))
+        );
+        assert_eq!(
+            json_lookup(&json, "data.items.0.status"),
+            Some(&json!("ok"))
+        );
+        assert_eq!(json_lookup(&json, "code"), Some(&json!(0)));
+        assert_eq!(json_lookup(&json, "data.missing"), None);
+    }
+
+    #[test]
+    fn test_config_from_extra() {
+        let config = WebhookConfig::from_extra(&json!({
+            "url": "https://example.com/{ip}",
+            "method": "post",
+            "headers": "Authorization: Bearer token\nX-Domain: {fqdn}",
+            "success_status": 200,
+        }))
+        .unwrap();
+        assert_eq!(config.method, Method::POST);
+        assert_eq!(config.headers.len(), 2);
+        assert_eq!(config.headers[0].0, "authorization");
+        assert_eq!(config.headers[1].1, "{fqdn}");
+        assert_eq!(config.rules.status, vec![(200, 200)]);
+
+        // 凭证存储中的请求头为 JSON 文本
+        let config = WebhookConfig::from_extra(&json!({
+            "url": "https://example.com",
+            "headers": r#"{"X-Token": "abc"}"#,
+        }))
+        .unwrap();
+        assert_eq!(config.headers[0].1, "abc");
+
+        assert!(WebhookConfig::from_extra(&json!({})).is_err());
+        assert!(WebhookConfig::from_extra(&json!({ "url": "ftp://example.com" })).is_err());
+        assert!(WebhookConfig::from_extra(&json!({
+            "url": "https://example.com",
+            "success_body_regex": "(",
+        }))
+        .is_err());
+    }
+
+    #[test]
+    fn test_success_rules() {
+        let rules = SuccessRules {
+            status: vec![(200, 299)],
+            body_regex: Some(Regex::new("^(good|nochg)").unwrap()),
+            json_path: None,
+            json_value: None,
+        };
+        assert!(rules.check(200, "good 1.2.3.4").is_ok());
+        assert!(matches!(
+            rules.check(200, "badauth"),
+            Err(AppError::Provider(ProviderError::ApiError(_)))
+        ));
+        assert!(matches!(
+            rules.check(401, "good"),
+            Err(AppError::Provider(ProviderError::AuthenticationFailed(_)))
+        ));
+        assert!(matches!(
+            rules.check(429, "good"),
+            Err(AppError::Provider(ProviderError::RateLimitExceeded))
+        ));
+
+        let rules = SuccessRules {
+            status: vec![(200, 299)],
+            body_regex: None,
+            json_path: Some("result.code".to_string()),
+            json_value: Some("0".to_string()),
+        };
+        assert!(rules.check(200, r#"{"result":{"code":0}}"#).is_ok());
+        assert!(rules.check(200, r#"{"result":{"code":1}}"#).is_err());
+        assert!(matches!(
+            rules.check(200, "not json"),
+            Err(AppError::Provider(ProviderError::ParseError(_)))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_create_and_update_record() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/update"))
+            .and(query_param("hostname", "home.example.com"))
+            .and(query_param("myip", "1.2.3.4"))
+            .respond_with(ResponseTemplate::new(200).set_body_string("good 1.2.3.4"))
+            .expect(1)
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/update"))
+            .and(query_param("myip", "5.6.7.8"))
+            .respond_with(ResponseTemplate::new(200).set_body_string("good 5.6.7.8"))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let provider = provider(json!({
+            "url": format!("{}/update?hostname={{fqdn}}&myip={{ip}}", server.uri()),
+            "success_body_regex": "^good",
+        }));
+
+        assert!(provider
+            .get_record("example.com", "home", DNSRecordType::A)
+            .await
+            .unwrap()
+            .is_none());
+
+        let created = provider
+            .create_record("example.com", "home", DNSRecordType::A, "1.2.3.4")
+            .await
+            .unwrap();
+        assert_eq!(created.id, "home|A");
+        assert_eq!(created.name, "home.example.com");
+
+        // 推送成功后可以读取到最近的值
+        let record = provider
+            .get_record("example.com", "home", DNSRecordType::A)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(record.content, "1.2.3.4");
+
+        let result = provider
+            .update_record("example.com", &record.id, "5.6.7.8")
+            .await
+            .unwrap();
+        assert_eq!(result.old_ip, "1.2.3.4");
+        assert_eq!(result.new_ip, "5.6.7.8");
+        assert_eq!(provider.list_records("example.com").await.unwrap().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_post_json_body_with_headers() {
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/hook"))
+            .and(header("authorization", "Bearer secret"))
+            .and(header("content-type", "application/json"))
+            .and(body_json(json!({
+                "domain": "example.com",
+                "name": "@",
+                "type": "AAAA",
+                "value": "2001:db8::1"
+            })))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({ "ok": false })))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let provider = provider(json!({
+            "url": format!("{}/hook", server.uri()),
+            "method": "POST",
+            "headers": { "Authorization": "Bearer secret" },
+            "body": r#"{"domain":"{domain}","name":"{subdomain}","type":"{record_type}","value":"{ip}"}"#,
+            "success_json_path": "$.ok",
+        }));
+
+        // 状态码为 200 但 JSON 字段不满足条件, 不应记录为已推送
+        let result = provider
+            .create_record("example.com", "", DNSRecordType::AAAA, "2001:db8::1")
+            .await;
+        assert!(matches!(
+            result,
+            Err(AppError::Provider(ProviderError::ApiError(_)))
+        ));
+        assert!(provider
+            .get_record("example.com", "@", DNSRecordType::AAAA)
+            .await
+            .unwrap()
+            .is_none());
+    }
+}
@@ -1,23 +1,397 @@
-// DNS 更新服务 - Phase 4 实现
-use crate::error::Result;
+use crate::error::{AppError, Result};
+use crate::models::Domain;
+use crate::providers::{
+    Credentials, DNSProvider, DNSRecord, DNSRecordType, ProviderFactory, RecordSpec, UpdateResult,
+};
+use crate::storage::secure_store::CredentialManager;
+use crate::storage::Database;
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+/// IP 未变化时检查远端记录选项是否被修改的间隔
+const DRIFT_CHECK_INTERVAL: Duration = Duration::from_secs(60 * 60);
+
+/// 已初始化的提供商 (凭证指纹, 提供商实例)
+type CachedProvider = (String, Arc<dyn DNSProvider>);
 
 /// DNS 更新服务
-pub struct DNSUpdaterService;
+pub struct DNSUpdaterService {
+    db: Arc<Database>,
+    credentials: Arc<CredentialManager>,
+    /// 按域名 ID 复用已初始化的提供商, 保留提供商内部的缓存 (例如 Zone ID)
+    providers: Mutex<HashMap<String, CachedProvider>>,
+    /// 按 "域名 ID:记录类型" 记录上次读取远端记录的时间
+    checked: Mutex<HashMap<String, Instant>>,
+}
 
 impl DNSUpdaterService {
-    pub fn new() -> Self {
-        Self
+    pub fn new(db: Arc<Database>, credentials: Arc<CredentialManager>) -> Self {
+        Self {
+            db,
+            credentials,
+            providers: Mutex::new(HashMap::new()),
+            checked: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// 更新域名的 DNS 记录
+    ///
+    /// 记录存在时更新其内容, 不存在时创建新记录; 域名配置的 TTL、代理与备注随之提交,
+    /// 与远端记录不一致时同样会更新
+    pub async fn update_domain(&self, domain_id: &str, new_ip: &str) -> Result<UpdateResult> {
+        let domain = self.db.get_domain(domain_id).await?;
+        let record_type: DNSRecordType = domain.record_type.parse()?;
+        self.update_record(&domain, record_type, new_ip).await
+    }
+
+    /// 更新域名指定类型的 DNS 记录 (用于双栈域名分别更新 A 和 AAAA)
+    pub async fn update_domain_record(
+        &self,
+        domain_id: &str,
+        record_type: DNSRecordType,
+        new_ip: &str,
+    ) -> Result<UpdateResult> {
+        let domain = self.db.get_domain(domain_id).await?;
+        self.update_record(&domain, record_type, new_ip).await
     }
 
-    /// 更新域名的 DNS 记录 (待实现)
-    pub async fn update_domain(&self, _domain_id: &str, _new_ip: &str) -> Result<()> {
-        // TODO: Phase 4 实现
-        Ok(())
+    /// IP 未变化时是否需要读取远端记录, 检查 TTL、代理与备注是否在提供商处被修改
+    ///
+    /// 只有配置了这些选项的域名才需要检查, 每个记录在进程启动后首次同步时检查一次,
+    /// 之后每隔 [`DRIFT_CHECK_INTERVAL`] 检查一次
+    pub fn drift_check_due(&self, domain: &Domain, record_type: &DNSRecordType) -> bool {
+        domain.has_record_options()
+            && self
+                .checked
+                .lock()
+                .unwrap()
+                .get(&check_key(&domain.id, record_type))
+                .is_none_or(|checked| checked.elapsed() >= DRIFT_CHECK_INTERVAL)
+    }
+
+    /// 校正被远端修改的记录, 远端记录与本地配置一致时返回 None
+    pub async fn correct_drift(
+        &self,
+        domain_id: &str,
+        record_type: DNSRecordType,
+        ip: &str,
+    ) -> Result<Option<UpdateResult>> {
+        let domain = self.db.get_domain(domain_id).await?;
+        let (result, changed) = self.sync_remote(&domain, record_type, ip).await?;
+        Ok(Some(result).filter(|_| changed))
+    }
+
+    /// 删除域名指定类型的 DNS 记录, 返回被删除的记录 (记录不存在时返回 None)
+    pub async fn delete_domain_record(
+        &self,
+        domain_id: &str,
+        record_type: DNSRecordType,
+    ) -> Result<Option<DNSRecord>> {
+        let domain = self.db.get_domain(domain_id).await?;
+        let provider = self.connect(&domain).await?;
+
+        let result = async {
+            let record = provider
+                .get_record(&domain.name, &domain.subdomain, record_type.clone())
+                .await?;
+
+            if let Some(record) = &record {
+                tracing::info!(
+                    "删除 {} 的 {} 记录: {}",
+                    domain.full_domain(),
+                    record_type,
+                    record.content
+                );
+                provider.delete_record(&domain.name, &record.id).await?;
+            }
+
+            Ok(record)
+        }
+        .await;
+
+        if result.is_err() {
+            self.forget(&domain.id);
+        }
+        result
+    }
+
+    async fn update_record(
+        &self,
+        domain: &Domain,
+        record_type: DNSRecordType,
+        new_ip: &str,
+    ) -> Result<UpdateResult> {
+        let (result, _) = self.sync_remote(domain, record_type, new_ip).await?;
+        Ok(result)
+    }
+
+    /// 使远端记录与本地一致, 返回更新结果以及是否修改了远端记录
+    async fn sync_remote(
+        &self,
+        domain: &Domain,
+        record_type: DNSRecordType,
+        new_ip: &str,
+    ) -> Result<(UpdateResult, bool)> {
+        let provider = self.connect(domain).await?;
+        let key = check_key(&domain.id, &record_type);
+        let result = self
+            .apply_update(provider.as_ref(), domain, record_type, new_ip)
+            .await;
+
+        // 出错时丢弃提供商, 下次重新初始化 (缓存的 Zone 等可能已失效)
+        match result {
+            Ok(_) => {
+                self.checked.lock().unwrap().insert(key, Instant::now());
+            }
+            Err(_) => self.forget(&domain.id),
+        }
+        result
+    }
+
+    /// 记录存在时更新, 不存在时创建
+    async fn apply_update(
+        &self,
+        provider: &dyn DNSProvider,
+        domain: &Domain,
+        record_type: DNSRecordType,
+        new_ip: &str,
+    ) -> Result<(UpdateResult, bool)> {
+        let spec = domain.record_spec();
+        let record = provider
+            .get_record(&domain.name, &domain.subdomain, record_type.clone())
+            .await?;
+
+        match record {
+            Some(record) if record.content == new_ip => {
+                let drifted = drifted_options(&record, &spec);
+                if !drifted.is_empty() {
+                    tracing::info!(
+                        "校正 {} 的 {} 记录选项: {}",
+                        domain.full_domain(),
+                        record_type,
+                        drifted.join(", ")
+                    );
+                    let mut result = provider
+                        .update_record_with(&domain.name, &record.id, new_ip, &spec)
+                        .await?;
+                    result.message = format!("已校正记录选项: {}", drifted.join(", "));
+                    return Ok((result, true));
+                }
+
+                tracing::debug!("{} 的记录已是最新 ({})", domain.full_domain(), new_ip);
+                let result = UpdateResult {
+                    success: true,
+                    record_id: record.id,
+                    old_ip: record.content,
+                    new_ip: new_ip.to_string(),
+                    message: "记录已是最新".to_string(),
+                };
+                Ok((result, false))
+            }
+            Some(record) => {
+                tracing::info!(
+                    "更新 {} 的 {} 记录: {} -> {}",
+                    domain.full_domain(),
+                    record_type,
+                    record.content,
+                    new_ip
+                );
+                let result = provider
+                    .update_record_with(&domain.name, &record.id, new_ip, &spec)
+                    .await?;
+                Ok((result, true))
+            }
+            None => {
+                tracing::info!(
+                    "创建 {} 的 {} 记录: {}",
+                    domain.full_domain(),
+                    record_type,
+                    new_ip
+                );
+                let created = provider
+                    .create_record_with(&domain.name, &domain.subdomain, record_type, new_ip, &spec)
+                    .await?;
+                let result = UpdateResult {
+                    success: true,
+                    record_id: created.id,
+                    old_ip: String::new(),
+                    new_ip: created.content,
+                    message: "记录已创建".to_string(),
+                };
+                Ok((result, true))
+            }
+        }
+    }
+
+    /// 获取域名对应的已初始化提供商, 凭证未变化时复用之前的实例
+    pub async fn connect(&self, domain: &Domain) -> Result<Arc<dyn DNSProvider>> {
+        let credentials = self.resolve_credentials(domain)?;
+        let fingerprint = credentials_fingerprint(domain, &credentials)?;
+
+        if let Some((cached, provider)) = self.providers.lock().unwrap().get(&domain.id) {
+            if *cached == fingerprint {
+                return Ok(Arc::clone(provider));
+            }
+        }
+
+        let mut provider = ProviderFactory::create(&domain.provider)?;
+        provider.initialize(&credentials).await?;
+        let provider: Arc<dyn DNSProvider> = Arc::from(provider);
+
+        self.providers
+            .lock()
+            .unwrap()
+            .insert(domain.id.clone(), (fingerprint, Arc::clone(&provider)));
+        Ok(provider)
     }
+
+    /// 丢弃缓存的提供商实例
+    pub(crate) fn forget(&self, domain_id: &str) {
+        self.providers.lock().unwrap().remove(domain_id);
+    }
+
+    /// 为域名指定已初始化的提供商 (仅用于测试)
+    #[cfg(test)]
+    pub(crate) fn use_provider(&self, domain: &Domain, provider: Arc<dyn DNSProvider>) {
+        let credentials = self.resolve_credentials(domain).unwrap();
+        let fingerprint = credentials_fingerprint(domain, &credentials).unwrap();
+        self.providers
+            .lock()
+            .unwrap()
+            .insert(domain.id.clone(), (fingerprint, provider));
+    }
+
+    /// 是否缓存了域名的提供商实例 (仅用于测试)
+    #[cfg(test)]
+    pub(crate) fn has_provider(&self, domain_id: &str) -> bool {
+        self.providers.lock().unwrap().contains_key(domain_id)
+    }
+
+    fn resolve_credentials(&self, domain: &Domain) -> Result<Credentials> {
+        domain_credentials(&self.credentials, domain)
+    }
+}
+
+/// 获取域名的凭证 (优先使用域名专属凭证, 其次使用提供商凭证)
+pub fn domain_credentials(store: &CredentialManager, domain: &Domain) -> Result<Credentials> {
+    let credentials = match store.get_credentials_for_domain(&domain.provider, &domain.id)? {
+        Some(credentials) => Some(credentials),
+        None => store.get_credentials(&domain.provider)?,
+    };
+
+    credentials
+        .map(|c| c.to_provider_credentials())
+        .ok_or_else(|| {
+            AppError::CredentialStore(format!(
+                "未找到 {} 的 {} 凭证",
+                domain.full_domain(),
+                domain.provider
+            ))
+        })
+}
+
+/// 远端记录检查时间的键
+fn check_key(domain_id: &str, record_type: &DNSRecordType) -> String {
+    format!("{}:{}", domain_id, record_type)
+}
+
+/// 提供商与凭证的指纹, 凭证变化后需要重新初始化提供商
+fn credentials_fingerprint(domain: &Domain, credentials: &Credentials) -> Result<String> {
+    Ok(format!(
+        "{}:{}",
+        domain.provider,
+        serde_json::to_string(credentials)?
+    ))
 }
 
-impl Default for DNSUpdaterService {
-    fn default() -> Self {
-        Self::new()
+/// 远端记录与配置不一致的选项, 未配置的选项不参与比较
+fn drifted_options(record: &DNSRecord, spec: &RecordSpec) -> Vec<&'static str> {
+    let mut drifted = Vec::new();
+    // 代理记录的 TTL 由提供商自动管理 (Cloudflare 固定为 Auto)
+    if spec.ttl.is_some_and(|ttl| ttl != record.ttl) && record.proxied != Some(true) {
+        drifted.push("ttl");
+    }
+    if spec
+        .proxied
+        .is_some_and(|proxied| proxied != record.proxied.unwrap_or(false))
+    {
+        drifted.push("proxied");
+    }
+    if spec.comment.is_some() && spec.comment != record.comment {
+        drifted.push("comment");
+    }
+    drifted
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::CreateDomain;
+
+    #[tokio::test]
+    async fn test_update_without_credentials() {
+        let db = Arc::new(Database::new("sqlite::memory:").await.unwrap());
+        let domain = db
+            .create_domain(CreateDomain {
+                name: "example.com".to_string(),
+                provider: "cloudflare".to_string(),
+                subdomain: "www".to_string(),
+                record_type: "A".to_string(),
+                update_interval: 300,
+                enabled: true,
+                dual_stack: false,
+                remove_aaaa_on_ipv6_loss: false,
+                ttl: None,
+                proxied: None,
+                comment: None,
+            })
+            .await
+            .unwrap();
+
+        let updater = DNSUpdaterService::new(db, Arc::new(CredentialManager::memory()));
+        let result = updater.update_domain(&domain.id, "1.2.3.4").await;
+
+        assert!(matches!(result, Err(AppError::CredentialStore(_))));
+    }
+
+    #[test]
+    fn test_drifted_options() {
+        let record = DNSRecord {
+            id: "1".to_string(),
+            name: "www.example.com".to_string(),
+            record_type: DNSRecordType::A,
+            content: "1.2.3.4".to_string(),
+            ttl: 1,
+            proxied: Some(false),
+            priority: None,
+            weight: None,
+            port: None,
+            comment: None,
+        };
+
+        // 未配置的选项不参与比较
+        assert!(drifted_options(&record, &RecordSpec::default()).is_empty());
+
+        let spec = RecordSpec {
+            ttl: Some(300),
+            proxied: Some(false),
+            comment: Some("home".to_string()),
+            ..Default::default()
+        };
+        assert_eq!(drifted_options(&record, &spec), vec!["ttl", "comment"]);
+
+        // 开启代理后 TTL 由提供商管理
+        let spec = RecordSpec {
+            ttl: Some(300),
+            proxied: Some(true),
+            ..Default::default()
+        };
+        let proxied = DNSRecord {
+            proxied: Some(true),
+            ..record.clone()
+        };
+        assert_eq!(drifted_options(&record, &spec), vec!["ttl", "proxied"]);
+        assert!(drifted_options(&proxied, &spec).is_empty());
     }
 }
@@ -97,9 +97,7 @@ impl ApiDetectionMethod {
             return false;
         }
         // 每个部分应该是数字且在 0-255 范围内
-        parts.iter().all(|part| {
-            part.parse::<u8>().is_ok()
-        })
+        parts.iter().all(|part| part.parse::<u8>().is_ok())
     }
 
     /// 验证是否为有效的 IPv6 地址
@@ -180,23 +178,25 @@ impl DnsDetectionMethod {
         // 使用 trust-dns 客户端
         use trust_dns_resolver::config::{ResolverConfig, ResolverOpts};
         use trust_dns_resolver::TokioAsyncResolver;
-        
 
-        let resolver = TokioAsyncResolver::tokio(ResolverConfig::default(), ResolverOpts::default());
+        let resolver =
+            TokioAsyncResolver::tokio(ResolverConfig::default(), ResolverOpts::default());
 
         if record_type == "A" {
-            let lookup = resolver.ipv4_lookup(hostname).await.map_err(|e| {
-                AppError::IPDetection(format!("DNS 解析失败: {}", e))
-            })?;
+            let lookup = resolver
+                .ipv4_lookup(hostname)
+                .await
+                .map_err(|e| AppError::IPDetection(format!("DNS 解析失败: {}", e)))?;
             if let Some(addr) = lookup.iter().next() {
                 Ok(addr.to_string())
             } else {
                 Err(AppError::IPDetection("DNS 解析返回空结果".to_string()))
             }
         } else {
-            let lookup = resolver.ipv6_lookup(hostname).await.map_err(|e| {
-                AppError::IPDetection(format!("DNS 解析失败: {}", e))
-            })?;
+            let lookup = resolver
+                .ipv6_lookup(hostname)
+                .await
+                .map_err(|e| AppError::IPDetection(format!("DNS 解析失败: {}", e)))?;
             if let Some(addr) = lookup.iter().next() {
                 Ok(addr.to_string())
             } else {
@@ -229,12 +229,13 @@ impl IPDetectionMethod for InterfaceDetectionMethod {
     }
 }
 
-/// IP 缓存
+/// IP 缓存 (IPv4 和 IPv6 分别记录检测时间)
 #[derive(Debug, Clone)]
 struct IPCache {
     ipv4: Option<String>,
     ipv6: Option<String>,
-    timestamp: i64,
+    ipv4_timestamp: i64,
+    ipv6_timestamp: i64,
     ttl: i64, // 秒
 }
 
@@ -243,20 +244,38 @@ impl IPCache {
         Self {
             ipv4: None,
             ipv6: None,
-            timestamp: 0,
+            ipv4_timestamp: 0,
+            ipv6_timestamp: 0,
             ttl,
         }
     }
 
-    fn is_valid(&self) -> bool {
+    /// 获取未过期的缓存地址
+    fn get(&self, ipv6: bool) -> Option<String> {
         let now = chrono::Utc::now().timestamp();
-        (now - self.timestamp) < self.ttl
+        let (ip, timestamp) = if ipv6 {
+            (&self.ipv6, self.ipv6_timestamp)
+        } else {
+            (&self.ipv4, self.ipv4_timestamp)
+        };
+
+        if (now - timestamp) < self.ttl {
+            ip.clone()
+        } else {
+            None
+        }
     }
 
-    fn update(&mut self, ipv4: Option<String>, ipv6: Option<String>) {
-        self.ipv4 = ipv4;
-        self.ipv6 = ipv6;
-        self.timestamp = chrono::Utc::now().timestamp();
+    /// 更新单个地址族的缓存, 不影响另一个地址族
+    fn set(&mut self, ipv6: bool, ip: String) {
+        let now = chrono::Utc::now().timestamp();
+        if ipv6 {
+            self.ipv6 = Some(ip);
+            self.ipv6_timestamp = now;
+        } else {
+            self.ipv4 = Some(ip);
+            self.ipv4_timestamp = now;
+        }
     }
 }
 
@@ -284,21 +303,18 @@ impl IPDetectorService {
         }
     }
 
-    /// 检测 IP (仅 IPv4)
+    /// 检测单个地址族的 IP
     pub async fn detect_ip(&self, prefer_ipv6: bool) -> Result<IPInfo> {
         // 检查缓存
         {
             let cache = self.cache.read().await;
-            if cache.is_valid() {
-                let ipv4 = cache.ipv4.clone();
-                let ipv6 = cache.ipv6.clone();
-
-                if prefer_ipv6 && ipv6.is_some() {
-                    tracing::info!("使用缓存的 IPv6: {:?}", ipv6);
-                    return Ok(IPInfo::new(None, ipv6, "cache".to_string()));
-                } else if ipv4.is_some() {
-                    tracing::info!("使用缓存的 IPv4: {:?}", ipv4);
-                    return Ok(IPInfo::new(ipv4, None, "cache".to_string()));
+            if let Some(ip) = cache.get(prefer_ipv6) {
+                if prefer_ipv6 {
+                    tracing::info!("使用缓存的 IPv6: {}", ip);
+                    return Ok(IPInfo::new(None, Some(ip), "cache".to_string()));
+                } else {
+                    tracing::info!("使用缓存的 IPv4: {}", ip);
+                    return Ok(IPInfo::new(Some(ip), None, "cache".to_string()));
                 }
             }
         }
@@ -316,18 +332,14 @@ impl IPDetectorService {
             if let Some(ip) = result {
                 tracing::info!("成功检测到 IP: {} (使用 {})", ip, method.method_name());
 
+                // 更新缓存
+                let mut cache = self.cache.write().await;
+                cache.set(prefer_ipv6, ip.clone());
+
                 let ipv4 = if prefer_ipv6 { None } else { Some(ip.clone()) };
                 let ipv6 = if prefer_ipv6 { Some(ip) } else { None };
 
-                let ip_info = IPInfo::new(
-                    ipv4.clone(),
-                    ipv6.clone(),
-                    method.method_name().to_string(),
-                );
-
-                // 更新缓存
-                let mut cache = self.cache.write().await;
-                cache.update(ipv4, ipv6);
+                let ip_info = IPInfo::new(ipv4, ipv6, method.method_name().to_string());
 
                 return Ok(ip_info);
             }
@@ -371,9 +383,8 @@ impl IPDetectorService {
     /// 清除缓存
     pub async fn clear_cache(&self) {
         let mut cache = self.cache.write().await;
-        cache.ipv4 = None;
-        cache.ipv6 = None;
-        cache.timestamp = 0;
+        let ttl = cache.ttl;
+        *cache = IPCache::new(ttl);
         tracing::info!("IP 缓存已清除");
     }
 }
@@ -418,6 +429,22 @@ mod tests {
         detector.clear_cache().await;
     }
 
+    #[test]
+    fn test_cache_keeps_families_separate() {
+        let mut cache = IPCache::new(60);
+        cache.set(false, "1.2.3.4".to_string());
+        assert_eq!(cache.get(false), Some("1.2.3.4".to_string()));
+        assert_eq!(cache.get(true), None);
+
+        cache.set(true, "2001:db8::1".to_string());
+        assert_eq!(cache.get(false), Some("1.2.3.4".to_string()));
+        assert_eq!(cache.get(true), Some("2001:db8::1".to_string()));
+
+        let mut expired = IPCache::new(0);
+        expired.set(false, "1.2.3.4".to_string());
+        assert_eq!(expired.get(false), None);
+    }
+
     #[test]
     fn test_ipv4_validation() {
         // 测试有效的 IPv4 地址
@@ -428,7 +455,9 @@ mod tests {
 
         // 测试 IPv6 地址应该被拒绝
         assert!(!ApiDetectionMethod::is_valid_ipv4("2001:4860:4860::8888"));
-        assert!(!ApiDetectionMethod::is_valid_ipv4("240e:337:b5:b470:4eaa:10f8:f6af:fdc8"));
+        assert!(!ApiDetectionMethod::is_valid_ipv4(
+            "240e:337:b5:b470:4eaa:10f8:f6af:fdc8"
+        ));
         assert!(!ApiDetectionMethod::is_valid_ipv4("fe80::1"));
 
         // 测试无效格式
@@ -440,7 +469,9 @@ mod tests {
     fn test_ipv6_validation() {
         // 测试有效的 IPv6 地址
         assert!(ApiDetectionMethod::is_valid_ipv6("2001:4860:4860::8888"));
-        assert!(ApiDetectionMethod::is_valid_ipv6("240e:337:b5:b470:4eaa:10f8:f6af:fdc8"));
+        assert!(ApiDetectionMethod::is_valid_ipv6(
+            "240e:337:b5:b470:4eaa:10f8:f6af:fdc8"
+        ));
         assert!(ApiDetectionMethod::is_valid_ipv6("fe80::1"));
         assert!(ApiDetectionMethod::is_valid_ipv6("::1"));
 
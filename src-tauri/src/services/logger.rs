@@ -8,6 +8,12 @@ pub struct Logger {
     _db: Option<Database>,
 }
 
+impl Default for Logger {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl Logger {
     /// 初始化日志系统
     pub fn init() -> Result<(), Box<dyn std::error::Error>> {
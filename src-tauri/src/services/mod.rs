@@ -2,7 +2,9 @@ pub mod dns_updater;
 pub mod ip_detector;
 pub mod logger;
 pub mod scheduler;
+pub mod zone_editor;
 
 pub use dns_updater::*;
 pub use ip_detector::*;
 pub use logger::*;
+pub use zone_editor::*;
@@ -1,5 +1,6 @@
-use crate::error::Result;
-use crate::models::Domain;
+use crate::error::{AppError, Result};
+use crate::models::{Domain, IPInfo, LogLevel, UpdateHistory};
+use crate::providers::{DNSRecordType, ProviderError, UpdateResult};
 use crate::services::{DNSUpdaterService, IPDetectorService};
 use crate::storage::Database;
 use std::collections::HashMap;
@@ -25,12 +26,12 @@ pub struct SchedulerService {
 }
 
 impl SchedulerService {
-    pub fn new(db: Arc<Database>) -> Self {
+    pub fn new(db: Arc<Database>, dns_updater: Arc<DNSUpdaterService>) -> Self {
         Self {
+            dns_updater,
             db,
             tasks: Arc::new(RwLock::new(HashMap::new())),
             ip_detector: Arc::new(IPDetectorService::new()),
-            dns_updater: Arc::new(DNSUpdaterService::new()),
             running: Arc::new(RwLock::new(false)),
         }
     }
@@ -116,67 +117,46 @@ impl SchedulerService {
                     continue;
                 }
 
-                // 检测 IP
-                let ip_info = match ip_detector.detect_ipv4().await {
-                    Ok(info) => info,
+                if domain_families(&domain).is_empty() {
+                    tracing::warn!(
+                        "域名 {} 的 {} 记录不支持自动更新",
+                        domain.full_domain(),
+                        domain.record_type
+                    );
+                    continue;
+                }
+
+                // 同一主机的 A 和 AAAA 记录由更新间隔较短的任务在一次检测中统一更新
+                let domains = match db.get_domains().await {
+                    Ok(domains) => domains,
                     Err(e) => {
-                        tracing::error!("域名 {} IP 检测失败: {}", domain.name, e);
+                        tracing::error!("获取域名列表失败: {}", e);
                         continue;
                     }
                 };
+                let group = host_group(&domains, &domain);
+                let leader = group_leader(&group);
+                if leader.id != domain.id {
+                    tracing::debug!(
+                        "域名 {} 的 {} 记录随 {} 记录一起更新",
+                        domain.full_domain(),
+                        domain.record_type,
+                        leader.record_type
+                    );
+                    continue;
+                }
 
-                let new_ip = match ip_info.ipv4 {
-                    Some(ip) => ip,
-                    None => {
-                        tracing::warn!("域名 {} 未检测到 IP 地址", domain.name);
+                // 检测 IP
+                let ip_info = match detect_for_domains(&ip_detector, &group).await {
+                    Ok(info) => info,
+                    Err(e) => {
+                        tracing::error!("域名 {} IP 检测失败: {}", domain.name, e);
                         continue;
                     }
                 };
 
-                // 检查 IP 是否变化
-                if let Some(current_ip) = &domain.current_ip {
-                    if current_ip == &new_ip {
-                        tracing::debug!("域名 {} IP 未变化 ({})", domain.name, new_ip);
-                        continue;
-                    }
-                }
-
-                // 更新 DNS
-                let current_ip = domain.current_ip.unwrap_or_else(|| "None".to_string());
-                tracing::info!(
-                    "域名 {} IP 变化: {} -> {}, 开始更新 DNS",
-                    domain.name,
-                    current_ip,
-                    new_ip
-                );
-
-                if let Err(e) = dns_updater.update_domain(&domain_id_for_spawn, &new_ip).await {
-                    tracing::error!("域名 {} DNS 更新失败: {}", domain.name, e);
-
-                    // 记录失败历史
-                    let _ = db
-                        .add_update_history(crate::models::UpdateHistory::failed(
-                            domain_id_for_spawn.clone(),
-                            e.to_string(),
-                        ))
-                        .await;
-                } else {
-                    tracing::info!("域名 {} DNS 更新成功", domain.name);
-
-                    // 更新数据库中的 IP
-                    let _ = db.update_domain_ip(&domain_id_for_spawn, &new_ip).await;
-
-                    // 记录成功历史
-                    let _ = db
-                        .add_update_history(crate::models::UpdateHistory::success(
-                            domain_id_for_spawn.clone(),
-                            Some(current_ip),
-                            new_ip.clone(),
-                        ))
-                        .await;
-
-                    // 发送事件到前端
-                    // TODO: 实现 Tauri 事件发送
+                for domain in group {
+                    sync_domain(&db, &dns_updater, domain, &ip_info).await;
                 }
             }
 
@@ -249,9 +229,297 @@ pub struct SchedulerStatus {
     pub active_tasks: u32,
 }
 
+/// 记录对应的地址族
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AddressFamily {
+    V4,
+    V6,
+}
+
+impl AddressFamily {
+    /// 对应的 DNS 记录类型
+    pub fn record_type(self) -> DNSRecordType {
+        match self {
+            AddressFamily::V4 => DNSRecordType::A,
+            AddressFamily::V6 => DNSRecordType::AAAA,
+        }
+    }
+
+    /// 从检测结果中取出该地址族的地址
+    pub fn address(self, ip_info: &IPInfo) -> Option<String> {
+        match self {
+            AddressFamily::V4 => ip_info.ipv4.clone(),
+            AddressFamily::V6 => ip_info.ipv6.clone(),
+        }
+    }
+}
+
+/// 根据记录类型确定需要检测的地址族 (非 A/AAAA 记录返回 None)
+pub fn address_family(record_type: &str) -> Option<AddressFamily> {
+    match record_type.to_uppercase().as_str() {
+        "A" => Some(AddressFamily::V4),
+        "AAAA" => Some(AddressFamily::V6),
+        _ => None,
+    }
+}
+
+/// 域名需要维护的地址族 (双栈域名同时维护 IPv4 和 IPv6)
+pub fn domain_families(domain: &Domain) -> Vec<AddressFamily> {
+    if domain.dual_stack {
+        vec![AddressFamily::V4, AddressFamily::V6]
+    } else {
+        address_family(&domain.record_type).into_iter().collect()
+    }
+}
+
+/// 按域名的记录类型检测 IP, 同时需要 IPv4 和 IPv6 时只做一次 `detect_all`
+pub async fn detect_for_domains(
+    ip_detector: &IPDetectorService,
+    domains: &[Domain],
+) -> Result<IPInfo> {
+    let families: Vec<_> = domains.iter().flat_map(domain_families).collect();
+    let needs_v4 = families.contains(&AddressFamily::V4);
+    let needs_v6 = families.contains(&AddressFamily::V6);
+
+    match (needs_v4, needs_v6) {
+        (true, true) => ip_detector.detect_all().await,
+        (false, true) => ip_detector.detect_ipv6().await,
+        _ => ip_detector.detect_ipv4().await,
+    }
+}
+
+/// 获取与指定域名属于同一主机的已启用 A/AAAA 记录 (A 记录排在最前)
+///
+/// 双栈域名自身已包含两个地址族, 不与其他域名合并
+fn host_group(domains: &[Domain], domain: &Domain) -> Vec<Domain> {
+    if domain.dual_stack {
+        return vec![domain.clone()];
+    }
+
+    let mut group: Vec<Domain> = domains
+        .iter()
+        .filter(|d| {
+            d.id != domain.id
+                && d.enabled
+                && !d.dual_stack
+                && d.provider == domain.provider
+                && d.name == domain.name
+                && d.subdomain == domain.subdomain
+                && address_family(&d.record_type).is_some()
+                && address_family(&d.record_type) != address_family(&domain.record_type)
+        })
+        .cloned()
+        .collect();
+    group.push(domain.clone());
+    group.sort_by_key(|d| address_family(&d.record_type) != Some(AddressFamily::V4));
+    group
+}
+
+/// 负责更新整组记录的域名: 更新间隔最短者, 间隔相同时为 A 记录
+///
+/// 整组按该域名的间隔更新, 另一条记录较长的间隔不再单独生效
+fn group_leader(group: &[Domain]) -> &Domain {
+    group
+        .iter()
+        .min_by_key(|d| {
+            (
+                d.update_interval,
+                address_family(&d.record_type) != Some(AddressFamily::V4),
+            )
+        })
+        .expect("host_group 至少包含域名自身")
+}
+
+/// 更新域名需要维护的所有记录
+async fn sync_domain(
+    db: &Database,
+    dns_updater: &DNSUpdaterService,
+    domain: Domain,
+    ip_info: &IPInfo,
+) {
+    for family in domain_families(&domain) {
+        if let Err(e) = sync_record(db, dns_updater, &domain, family, ip_info, false).await {
+            tracing::error!("域名 {} DNS 更新失败: {}", domain.name, e);
+        }
+    }
+}
+
+/// 同步域名某一地址族的记录, 并记录更新历史
+///
+/// 无需更新时返回 None; `force` 为 true 时即使 IP 未变化也会请求提供商。
+/// IP 未变化但配置了 TTL、代理或备注时, 定期读取远端记录并校正被修改的选项。
+/// 双栈域名检测不到 IPv6 时, 若开启了 `remove_aaaa_on_ipv6_loss` 则删除 AAAA 记录,
+/// 此时返回结果的 `new_ip` 为空
+pub async fn sync_record(
+    db: &Database,
+    dns_updater: &DNSUpdaterService,
+    domain: &Domain,
+    family: AddressFamily,
+    ip_info: &IPInfo,
+    force: bool,
+) -> Result<Option<UpdateResult>> {
+    let current_ip = match (domain.dual_stack, family) {
+        (false, _) => domain.current_ip.clone(),
+        (true, AddressFamily::V4) => domain.current_ipv4.clone(),
+        (true, AddressFamily::V6) => domain.current_ipv6.clone(),
+    };
+
+    let new_ip = match family.address(ip_info) {
+        Some(ip) => ip,
+        None if domain.dual_stack => {
+            if family == AddressFamily::V6 && domain.remove_aaaa_on_ipv6_loss {
+                return remove_ipv6_record(db, dns_updater, domain, current_ip).await;
+            }
+            tracing::warn!(
+                "域名 {} 未检测到 {} 记录所需的 IP 地址, 跳过",
+                domain.full_domain(),
+                family.record_type()
+            );
+            return Ok(None);
+        }
+        None => {
+            return Err(AppError::IPDetection(format!(
+                "未检测到 {} 记录所需的 IP 地址",
+                family.record_type()
+            )));
+        }
+    };
+
+    // 检查 IP 是否变化; 配置修改后需同步一次记录选项
+    let record_type = family.record_type();
+    let unchanged =
+        !force && current_ip.as_deref() == Some(new_ip.as_str()) && !domain.modified_since_sync();
+    let outcome = if unchanged {
+        // IP 未变化时定期读取远端记录, 校正在提供商处被修改的 TTL、代理与备注
+        if !dns_updater.drift_check_due(domain, &record_type) {
+            tracing::debug!(
+                "域名 {} 的 {} 记录 IP 未变化 ({})",
+                domain.name,
+                record_type,
+                new_ip
+            );
+            return Ok(None);
+        }
+
+        tracing::debug!(
+            "检查域名 {} 的 {} 记录是否在提供商处被修改",
+            domain.full_domain(),
+            record_type
+        );
+        match dns_updater
+            .correct_drift(&domain.id, record_type, &new_ip)
+            .await
+            .transpose()
+        {
+            Some(outcome) => outcome,
+            None => return Ok(None),
+        }
+    } else {
+        tracing::info!(
+            "域名 {} 的 {} 记录 IP 变化: {} -> {}, 开始更新 DNS",
+            domain.name,
+            record_type,
+            current_ip.as_deref().unwrap_or("None"),
+            new_ip
+        );
+        dns_updater
+            .update_domain_record(&domain.id, record_type, &new_ip)
+            .await
+    };
+
+    let error = match outcome {
+        Ok(result) if result.success => {
+            tracing::info!("域名 {} DNS 更新成功: {}", domain.name, result.message);
+
+            // 更新数据库中的 IP
+            if domain.dual_stack {
+                db.update_domain_family_ip(
+                    &domain.id,
+                    family == AddressFamily::V6,
+                    Some(&result.new_ip),
+                )
+                .await?;
+            } else {
+                db.update_domain_ip(&domain.id, &result.new_ip).await?;
+            }
+
+            // 记录成功历史 (使用提供商返回的旧值)
+            let old_ip = Some(result.old_ip.clone()).filter(|ip| !ip.is_empty());
+            let _ = db
+                .add_update_history(UpdateHistory::success(
+                    domain.id.clone(),
+                    old_ip,
+                    result.new_ip.clone(),
+                ))
+                .await;
+
+            // 发送事件到前端
+            // TODO: 实现 Tauri 事件发送
+
+            return Ok(Some(result));
+        }
+        Ok(result) => AppError::Provider(ProviderError::ApiError(result.message)),
+        Err(e) => e,
+    };
+
+    // 记录失败历史
+    let _ = db
+        .add_update_history(UpdateHistory::failed(domain.id.clone(), error.to_string()))
+        .await;
+    Err(error)
+}
+
+/// IPv6 不可用时删除双栈域名的 AAAA 记录
+///
+/// 本地没有记录 IPv6 地址时 (例如刚导入的域名) 远端仍可能残留 AAAA 记录, 因此总是查询提供商
+async fn remove_ipv6_record(
+    db: &Database,
+    dns_updater: &DNSUpdaterService,
+    domain: &Domain,
+    current_ip: Option<String>,
+) -> Result<Option<UpdateResult>> {
+    let record = match dns_updater
+        .delete_domain_record(&domain.id, DNSRecordType::AAAA)
+        .await
+    {
+        Ok(record) => record,
+        Err(e) => {
+            let _ = db
+                .add_update_history(UpdateHistory::failed(domain.id.clone(), e.to_string()))
+                .await;
+            return Err(e);
+        }
+    };
+
+    if current_ip.is_some() {
+        db.update_domain_family_ip(&domain.id, true, None).await?;
+    }
+
+    // 远端已经没有 AAAA 记录
+    let Some(record) = record else {
+        return Ok(None);
+    };
+
+    let message = format!("IPv6 不可用, 已删除 {} 的 AAAA 记录", domain.full_domain());
+    tracing::info!("{}", message);
+    let context = serde_json::json!({ "domain_id": domain.id, "old_ip": record.content });
+    let _ = db
+        .add_log(LogLevel::Info, message.clone(), Some(context.to_string()))
+        .await;
+
+    Ok(Some(UpdateResult {
+        success: true,
+        record_id: record.id,
+        old_ip: record.content,
+        new_ip: String::new(),
+        message,
+    }))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::storage::secure_store::CredentialManager;
 
     #[test]
     fn test_status() {
@@ -263,4 +531,277 @@ mod tests {
         assert!(status.running);
         assert_eq!(status.active_tasks, 5);
     }
+
+    fn domain(id: &str, subdomain: &str, record_type: &str) -> Domain {
+        Domain {
+            id: id.to_string(),
+            name: "example.com".to_string(),
+            provider: "cloudflare".to_string(),
+            subdomain: subdomain.to_string(),
+            record_type: record_type.to_string(),
+            current_ip: None,
+            last_updated: None,
+            update_interval: 300,
+            enabled: true,
+            dual_stack: false,
+            remove_aaaa_on_ipv6_loss: false,
+            current_ipv4: None,
+            current_ipv6: None,
+            ttl: None,
+            proxied: None,
+            comment: None,
+            created_at: 0,
+            updated_at: 0,
+        }
+    }
+
+    #[test]
+    fn test_address_family() {
+        let ip_info = IPInfo::new(
+            Some("1.2.3.4".to_string()),
+            Some("2001:db8::1".to_string()),
+            "combined".to_string(),
+        );
+
+        let v4 = address_family("A").unwrap();
+        let v6 = address_family("aaaa").unwrap();
+        assert_eq!(v4.address(&ip_info), Some("1.2.3.4".to_string()));
+        assert_eq!(v6.address(&ip_info), Some("2001:db8::1".to_string()));
+        assert_eq!(address_family("CNAME"), None);
+
+        let mut dual = domain("1", "home", "A");
+        assert_eq!(domain_families(&dual), vec![AddressFamily::V4]);
+        dual.dual_stack = true;
+        assert_eq!(
+            domain_families(&dual),
+            vec![AddressFamily::V4, AddressFamily::V6]
+        );
+    }
+
+    #[test]
+    fn test_host_group() {
+        let a = domain("1", "www", "A");
+        let aaaa = domain("2", "www", "AAAA");
+        let other = domain("3", "api", "AAAA");
+        let mut disabled = domain("4", "www", "A");
+        disabled.enabled = false;
+        let domains = vec![a.clone(), aaaa.clone(), other.clone(), disabled];
+
+        // A 记录负责同一主机的 AAAA 记录
+        let ids: Vec<_> = host_group(&domains, &aaaa)
+            .into_iter()
+            .map(|d| d.id)
+            .collect();
+        assert_eq!(ids, vec!["1", "2"]);
+        let ids: Vec<_> = host_group(&domains, &a).into_iter().map(|d| d.id).collect();
+        assert_eq!(ids, vec!["1", "2"]);
+
+        // 没有对应 A 记录的 AAAA 记录单独更新
+        let ids: Vec<_> = host_group(&domains, &other)
+            .into_iter()
+            .map(|d| d.id)
+            .collect();
+        assert_eq!(ids, vec!["3"]);
+    }
+
+    #[test]
+    fn test_group_leader_uses_shorter_interval() {
+        let mut a = domain("1", "www", "A");
+        let mut aaaa = domain("2", "www", "AAAA");
+
+        // 间隔相同时由 A 记录的任务负责
+        let group = host_group(&[a.clone(), aaaa.clone()], &a);
+        assert_eq!(group_leader(&group).id, "1");
+
+        // AAAA 记录的间隔更短时由它的任务负责, 整组按较短的间隔更新
+        a.update_interval = 600;
+        aaaa.update_interval = 60;
+        let domains = vec![a.clone(), aaaa.clone()];
+        assert_eq!(group_leader(&host_group(&domains, &a)).id, "2");
+        assert_eq!(group_leader(&host_group(&domains, &aaaa)).id, "2");
+    }
+
+    #[tokio::test]
+    async fn test_dual_stack_tracks_families_separately() {
+        let db = Arc::new(Database::new("sqlite::memory:").await.unwrap());
+        let domain = db
+            .create_domain(crate::models::CreateDomain {
+                name: "example.com".to_string(),
+                provider: "cloudflare".to_string(),
+                subdomain: "home".to_string(),
+                record_type: "A".to_string(),
+                update_interval: 300,
+                enabled: true,
+                dual_stack: true,
+                remove_aaaa_on_ipv6_loss: false,
+                ttl: None,
+                proxied: None,
+                comment: None,
+            })
+            .await
+            .unwrap();
+
+        db.update_domain_family_ip(&domain.id, true, Some("2001:db8::1"))
+            .await
+            .unwrap();
+        let domain = db.get_domain(&domain.id).await.unwrap();
+        assert_eq!(domain.current_ipv6.as_deref(), Some("2001:db8::1"));
+        assert_eq!(domain.current_ip.as_deref(), Some("2001:db8::1"));
+
+        db.update_domain_family_ip(&domain.id, false, Some("1.2.3.4"))
+            .await
+            .unwrap();
+        db.update_domain_family_ip(&domain.id, true, None)
+            .await
+            .unwrap();
+        let domain = db.get_domain(&domain.id).await.unwrap();
+        assert_eq!(domain.current_ipv4.as_deref(), Some("1.2.3.4"));
+        assert_eq!(domain.current_ipv6, None);
+        assert_eq!(domain.current_ip.as_deref(), Some("1.2.3.4"));
+
+        // 未检测到 IPv6 且未开启删除时跳过 AAAA 记录, 不访问提供商
+        let updater =
+            DNSUpdaterService::new(Arc::clone(&db), Arc::new(CredentialManager::memory()));
+        let ip_info = IPInfo::new(Some("1.2.3.4".to_string()), None, "api".to_string());
+        let result = sync_record(&db, &updater, &domain, AddressFamily::V6, &ip_info, false)
+            .await
+            .unwrap();
+        assert!(result.is_none());
+    }
+
+    /// 创建域名并让更新服务使用内存中的提供商
+    async fn with_fake_provider(
+        create: crate::models::CreateDomain,
+        records: Vec<crate::providers::DNSRecord>,
+    ) -> (
+        Arc<Database>,
+        DNSUpdaterService,
+        Domain,
+        Arc<crate::providers::test_support::FakeProvider>,
+    ) {
+        let db = Arc::new(Database::new("sqlite::memory:").await.unwrap());
+        let domain = db.create_domain(create).await.unwrap();
+
+        let credentials = CredentialManager::memory();
+        credentials
+            .store_credentials(
+                &domain.provider,
+                &crate::models::Credentials {
+                    provider_id: crate::models::ProviderType::Cloudflare,
+                    api_key: Some("token".to_string()),
+                    api_secret: None,
+                    access_key: None,
+                    region: None,
+                    extra: Default::default(),
+                },
+            )
+            .unwrap();
+
+        let provider = Arc::new(crate::providers::test_support::FakeProvider::new(records));
+        let updater = DNSUpdaterService::new(Arc::clone(&db), Arc::new(credentials));
+        updater.use_provider(&domain, provider.clone());
+        (db, updater, domain, provider)
+    }
+
+    #[tokio::test]
+    async fn test_stale_aaaa_removed_without_local_ipv6() {
+        use crate::providers::test_support::record;
+
+        let (db, updater, domain, provider) = with_fake_provider(
+            crate::models::CreateDomain {
+                name: "example.com".to_string(),
+                provider: "cloudflare".to_string(),
+                subdomain: "home".to_string(),
+                record_type: "A".to_string(),
+                update_interval: 300,
+                enabled: true,
+                dual_stack: true,
+                remove_aaaa_on_ipv6_loss: true,
+                ttl: None,
+                proxied: None,
+                comment: None,
+            },
+            vec![
+                record("1", "home.example.com", DNSRecordType::A, "1.2.3.4"),
+                record("2", "home.example.com", DNSRecordType::AAAA, "2001:db8::1"),
+            ],
+        )
+        .await;
+
+        // 刚导入的域名本地没有 IPv6 地址, 远端残留的 AAAA 记录仍需删除
+        assert_eq!(domain.current_ipv6, None);
+        let ip_info = IPInfo::new(Some("1.2.3.4".to_string()), None, "api".to_string());
+        let result = sync_record(&db, &updater, &domain, AddressFamily::V6, &ip_info, false)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(result.record_id, "2");
+        assert_eq!(result.old_ip, "2001:db8::1");
+        let ids: Vec<_> = provider.records().into_iter().map(|r| r.id).collect();
+        assert_eq!(ids, vec!["1"]);
+
+        // 远端已经没有 AAAA 记录时不再删除
+        let result = sync_record(&db, &updater, &domain, AddressFamily::V6, &ip_info, false)
+            .await
+            .unwrap();
+        assert!(result.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_remote_option_drift_is_corrected() {
+        use crate::providers::test_support::record;
+
+        let (db, updater, domain, provider) = with_fake_provider(
+            crate::models::CreateDomain {
+                name: "example.com".to_string(),
+                provider: "cloudflare".to_string(),
+                subdomain: "www".to_string(),
+                record_type: "A".to_string(),
+                update_interval: 300,
+                enabled: true,
+                dual_stack: false,
+                remove_aaaa_on_ipv6_loss: false,
+                ttl: Some(600),
+                proxied: None,
+                comment: None,
+            },
+            vec![crate::providers::DNSRecord {
+                ttl: 600,
+                ..record("1", "www.example.com", DNSRecordType::A, "1.2.3.4")
+            }],
+        )
+        .await;
+
+        // 上次同步之后本地配置没有修改, IP 也没有变化
+        db.update_domain_ip(&domain.id, "1.2.3.4").await.unwrap();
+        sqlx::query("UPDATE domains SET updated_at = updated_at - 60")
+            .execute(db.pool())
+            .await
+            .unwrap();
+        let domain = db.get_domain(&domain.id).await.unwrap();
+        assert!(!domain.modified_since_sync());
+
+        // 只在提供商控制台修改了 TTL
+        provider.edit("1", |r| r.ttl = 120);
+
+        let ip_info = IPInfo::new(Some("1.2.3.4".to_string()), None, "api".to_string());
+        let result = sync_record(&db, &updater, &domain, AddressFamily::V4, &ip_info, false)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(result.message, "已校正记录选项: ttl");
+        assert_eq!(provider.records()[0].ttl, 600);
+        assert_eq!(
+            db.get_domain_history(&domain.id, 10).await.unwrap().len(),
+            1
+        );
+
+        // 检查间隔内不再读取远端记录
+        provider.edit("1", |r| r.ttl = 120);
+        let result = sync_record(&db, &updater, &domain, AddressFamily::V4, &ip_info, false)
+            .await
+            .unwrap();
+        assert!(result.is_none());
+        assert_eq!(provider.records()[0].ttl, 120);
+    }
 }
@@ -0,0 +1,682 @@
+use crate::error::{AppError, Result};
+use crate::models::{CreateZoneRecord, Domain, LogLevel, UpdateZoneRecord};
+use crate::providers::registry::{self, ProviderDescriptor};
+use crate::providers::{
+    full_record_name, CaaData, DNSProvider, DNSRecord, DNSRecordType, ProviderError, RecordContent,
+    RecordSpec, UpdateResult,
+};
+use crate::services::DNSUpdaterService;
+use crate::storage::Database;
+use std::net::{Ipv4Addr, Ipv6Addr};
+use std::sync::Arc;
+
+/// 区域记录编辑服务
+///
+/// 查看和修改已配置域名所在区域中的任意记录, 每次修改 (无论成功与否) 都写入审计日志
+pub struct ZoneEditorService {
+    db: Arc<Database>,
+    /// 与调度器共用已初始化的提供商
+    dns_updater: Arc<DNSUpdaterService>,
+}
+
+/// 记录修改操作
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum ZoneAction {
+    Create,
+    Update,
+    Delete,
+}
+
+/// 审计日志中的一次记录修改
+///
+/// 更新和删除在读取到记录之前失败时, `name` 为区域名称, `record_type` 为 None
+#[derive(Debug)]
+struct RecordChange {
+    action: ZoneAction,
+    record_id: Option<String>,
+    name: String,
+    record_type: Option<DNSRecordType>,
+    old: Option<String>,
+    new: Option<String>,
+}
+
+impl ZoneEditorService {
+    pub fn new(db: Arc<Database>, dns_updater: Arc<DNSUpdaterService>) -> Self {
+        Self { db, dns_updater }
+    }
+
+    /// 列出域名所在区域的全部记录
+    pub async fn list_records(&self, domain_id: &str) -> Result<Vec<DNSRecord>> {
+        let domain = self.db.get_domain(domain_id).await?;
+        let result = async {
+            let provider = self.connect(&domain).await?;
+            provider.list_records(&domain.name).await
+        }
+        .await;
+        self.release_on_error(&domain, &result);
+        result
+    }
+
+    /// 在域名所在区域中创建记录
+    pub async fn create_record(
+        &self,
+        domain_id: &str,
+        record: CreateZoneRecord,
+    ) -> Result<DNSRecord> {
+        let domain = self.db.get_domain(domain_id).await?;
+        let descriptor = descriptor(&domain)?;
+        let record_type: DNSRecordType = record.record_type.parse()?;
+        let name = match record.name.trim() {
+            "" => "@",
+            name => name,
+        };
+        let spec = record.record_spec();
+
+        if !descriptor.record_types.contains(&record_type) {
+            return Err(AppError::Validation(format!(
+                "{} 不支持 {} 记录",
+                descriptor.name, record_type
+            )));
+        }
+        validate_name(name)?;
+        validate_record(&record_type, &record.content, &spec, descriptor)?;
+
+        let result = async {
+            let provider = self.connect(&domain).await?;
+            provider
+                .create_record_with(
+                    &domain.name,
+                    name,
+                    record_type.clone(),
+                    record.content.trim(),
+                    &spec,
+                )
+                .await
+        }
+        .await;
+
+        let mut change = RecordChange {
+            action: ZoneAction::Create,
+            record_id: None,
+            name: full_record_name(&domain.name, name),
+            record_type: Some(record_type),
+            old: None,
+            new: Some(record.content.trim().to_string()),
+        };
+        if let Ok(created) = &result {
+            change.record_id = Some(created.id.clone());
+            change.new = Some(created.rdata());
+        }
+        self.finish(&domain, &change, result).await
+    }
+
+    /// 更新区域中的记录内容和选项
+    ///
+    /// 需要先读取记录才能按类型校验, 因此连接、查找和校验失败同样写入审计日志
+    pub async fn update_record(
+        &self,
+        domain_id: &str,
+        record_id: &str,
+        record: UpdateZoneRecord,
+    ) -> Result<UpdateResult> {
+        let domain = self.db.get_domain(domain_id).await?;
+        let descriptor = descriptor(&domain)?;
+        let spec = record.record_spec();
+        let mut change = RecordChange {
+            action: ZoneAction::Update,
+            record_id: Some(record_id.to_string()),
+            name: domain.name.clone(),
+            record_type: None,
+            old: None,
+            new: Some(record.content.trim().to_string()),
+        };
+
+        let result = async {
+            let provider = self.connect(&domain).await?;
+            let current = find_record(provider.as_ref(), &domain, record_id).await?;
+            change.name = current.name.clone();
+            change.record_type = Some(current.record_type.clone());
+            change.old = Some(current.rdata());
+            validate_record(&current.record_type, &record.content, &spec, descriptor)?;
+
+            // 未指定优先级时沿用原值, 审计日志记录提交后的完整记录数据
+            let mut content =
+                RecordContent::from_input(&current.record_type, &record.content, &spec)?;
+            content.priority = content.priority.or(current.priority);
+            change.new = Some(content.to_rdata(&current.record_type));
+
+            provider
+                .update_record_with(&domain.name, record_id, record.content.trim(), &spec)
+                .await
+        }
+        .await;
+        self.finish(&domain, &change, result).await
+    }
+
+    /// 删除区域中的记录, 返回被删除的记录
+    pub async fn delete_record(&self, domain_id: &str, record_id: &str) -> Result<DNSRecord> {
+        let domain = self.db.get_domain(domain_id).await?;
+        let mut change = RecordChange {
+            action: ZoneAction::Delete,
+            record_id: Some(record_id.to_string()),
+            name: domain.name.clone(),
+            record_type: None,
+            old: None,
+            new: None,
+        };
+
+        let result = async {
+            let provider = self.connect(&domain).await?;
+            let current = find_record(provider.as_ref(), &domain, record_id).await?;
+            change.name = current.name.clone();
+            change.record_type = Some(current.record_type.clone());
+            change.old = Some(current.rdata());
+            if current.record_type == DNSRecordType::SOA {
+                return Err(soa_is_read_only());
+            }
+
+            provider.delete_record(&domain.name, record_id).await?;
+            Ok(current)
+        }
+        .await;
+        self.finish(&domain, &change, result).await
+    }
+
+    /// 获取域名对应的已初始化提供商
+    async fn connect(&self, domain: &Domain) -> Result<Arc<dyn DNSProvider>> {
+        self.dns_updater.connect(domain).await
+    }
+
+    /// 写入审计日志并返回操作结果
+    async fn finish<T>(
+        &self,
+        domain: &Domain,
+        change: &RecordChange,
+        result: Result<T>,
+    ) -> Result<T> {
+        self.release_on_error(domain, &result);
+        self.audit(domain, change, &result).await;
+        result
+    }
+
+    /// 提供商出错 (认证失败、网络错误等) 时丢弃缓存的实例, 下次重新初始化
+    fn release_on_error<T>(&self, domain: &Domain, result: &Result<T>) {
+        if let Err(AppError::Provider(e)) = result {
+            if !matches!(e, ProviderError::RecordNotFound(_)) {
+                self.dns_updater.forget(&domain.id);
+            }
+        }
+    }
+
+    /// 写入审计日志, 写入失败不影响操作结果
+    async fn audit<T>(&self, domain: &Domain, change: &RecordChange, result: &Result<T>) {
+        let (level, message) = match result {
+            Ok(_) => (LogLevel::Info, change.summary()),
+            Err(e) => (
+                LogLevel::Error,
+                format!("{}, 失败: {}", change.summary(), e),
+            ),
+        };
+        let context = serde_json::json!({
+            "domain_id": domain.id,
+            "provider": domain.provider,
+            "action": change.action.as_str(),
+            "record_id": change.record_id,
+            "name": change.name,
+            "record_type": change.record_type,
+            "old": change.old,
+            "new": change.new,
+        });
+
+        if let Err(e) = self
+            .db
+            .add_log(level, message, Some(context.to_string()))
+            .await
+        {
+            tracing::warn!("写入审计日志失败: {}", e);
+        }
+    }
+}
+
+impl ZoneAction {
+    fn as_str(&self) -> &'static str {
+        match self {
+            ZoneAction::Create => "create",
+            ZoneAction::Update => "update",
+            ZoneAction::Delete => "delete",
+        }
+    }
+}
+
+impl RecordChange {
+    fn summary(&self) -> String {
+        let value = |v: &Option<String>| v.clone().unwrap_or_default();
+        // 未读取到记录时只能给出记录 ID
+        let record = match &self.record_type {
+            Some(record_type) => format!("{} 的 {} 记录", self.name, record_type),
+            None => format!("{} 中的记录 {}", self.name, value(&self.record_id)),
+        };
+        match self.action {
+            ZoneAction::Create => format!("创建 {}: {}", record, value(&self.new)),
+            ZoneAction::Update => match &self.old {
+                Some(old) => format!("更新 {}: {} -> {}", record, old, value(&self.new)),
+                None => format!("更新 {}: {}", record, value(&self.new)),
+            },
+            ZoneAction::Delete => match &self.old {
+                Some(old) => format!("删除 {}: {}", record, old),
+                None => format!("删除 {}", record),
+            },
+        }
+    }
+}
+
+fn descriptor(domain: &Domain) -> Result<&'static ProviderDescriptor> {
+    registry::find(&domain.provider)
+        .ok_or_else(|| AppError::Custom(format!("未知的提供商: {}", domain.provider)))
+}
+
+/// 在区域中按 ID 查找记录
+async fn find_record(
+    provider: &dyn DNSProvider,
+    domain: &Domain,
+    record_id: &str,
+) -> Result<DNSRecord> {
+    provider
+        .list_records(&domain.name)
+        .await?
+        .into_iter()
+        .find(|r| r.id == record_id)
+        .ok_or_else(|| AppError::Provider(ProviderError::RecordNotFound(record_id.to_string())))
+}
+
+fn soa_is_read_only() -> AppError {
+    AppError::Validation("SOA 记录由提供商管理, 不能修改".to_string())
+}
+
+/// 校验相对于区域的记录名称, `@` 表示区域根, 通配符 `*` 只能是最左侧的标签
+fn validate_name(name: &str) -> Result<()> {
+    let valid = name == "@"
+        || (name.len() <= 253
+            && name
+                .split('.')
+                .enumerate()
+                .all(|(i, label)| (i == 0 && label == "*") || is_label(label)));
+
+    if valid {
+        Ok(())
+    } else {
+        Err(AppError::Validation(format!("无效的记录名称: {}", name)))
+    }
+}
+
+/// 按记录类型校验记录内容, 并检查选项是否被提供商支持
+fn validate_record(
+    record_type: &DNSRecordType,
+    content: &str,
+    spec: &RecordSpec,
+    descriptor: &ProviderDescriptor,
+) -> Result<()> {
+    if *record_type == DNSRecordType::SOA {
+        return Err(soa_is_read_only());
+    }
+    if spec.ttl == Some(0) {
+        return Err(AppError::Validation("TTL 必须大于 0".to_string()));
+    }
+    spec.ensure_supported(&descriptor.capabilities)?;
+
+    let content = content.trim();
+    if content.is_empty() {
+        return Err(AppError::Validation("记录内容不能为空".to_string()));
+    }
+    // 同时检查优先级是否适用于该类型以及 SRV/CAA 的格式
+    let parsed = RecordContent::from_input(record_type, content, spec)?;
+
+    let valid = match record_type {
+        DNSRecordType::A => content.parse::<Ipv4Addr>().is_ok(),
+        DNSRecordType::AAAA => content.parse::<Ipv6Addr>().is_ok(),
+        DNSRecordType::CNAME | DNSRecordType::NS => is_hostname(content),
+        // 目标为 "." 表示不提供该服务 (RFC 7505 / RFC 2782)
+        DNSRecordType::MX | DNSRecordType::SRV => {
+            parsed.content == "." || is_hostname(&parsed.content)
+        }
+        DNSRecordType::CAA => {
+            let caa = CaaData::parse(&parsed.content)?;
+            caa.tag.chars().all(|c| c.is_ascii_alphanumeric())
+        }
+        _ => true,
+    };
+
+    if valid {
+        Ok(())
+    } else {
+        Err(AppError::Validation(format!(
+            "无效的 {} 记录内容: {}",
+            record_type, content
+        )))
+    }
+}
+
+/// 主机名, 允许以点结尾
+fn is_hostname(name: &str) -> bool {
+    let name = name.strip_suffix('.').unwrap_or(name);
+    !name.is_empty() && name.len() <= 253 && name.split('.').all(is_label)
+}
+
+/// 单个标签: 1-63 个字母、数字、连字符或下划线 (用于 `_sip._tcp` 等服务名称), 不以连字符开头或结尾
+fn is_label(label: &str) -> bool {
+    (1..=63).contains(&label.len())
+        && label
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+        && !label.starts_with('-')
+        && !label.ends_with('-')
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::CreateDomain;
+    use crate::storage::secure_store::CredentialManager;
+
+    fn cloudflare() -> &'static ProviderDescriptor {
+        registry::find("cloudflare").unwrap()
+    }
+
+    fn priority(priority: u16) -> RecordSpec {
+        RecordSpec {
+            priority: Some(priority),
+            ..Default::default()
+        }
+    }
+
+    async fn setup() -> (Arc<Database>, ZoneEditorService, Domain) {
+        let (db, _, editor, domain) = setup_with(CredentialManager::memory()).await;
+        (db, editor, domain)
+    }
+
+    async fn setup_with(
+        credentials: CredentialManager,
+    ) -> (
+        Arc<Database>,
+        Arc<DNSUpdaterService>,
+        ZoneEditorService,
+        Domain,
+    ) {
+        let db = Arc::new(Database::new("sqlite::memory:").await.unwrap());
+        let domain = db
+            .create_domain(CreateDomain {
+                name: "example.com".to_string(),
+                provider: "cloudflare".to_string(),
+                subdomain: "www".to_string(),
+                record_type: "A".to_string(),
+                update_interval: 300,
+                enabled: true,
+                dual_stack: false,
+                remove_aaaa_on_ipv6_loss: false,
+                ttl: None,
+                proxied: None,
+                comment: None,
+            })
+            .await
+            .unwrap();
+        let updater = Arc::new(DNSUpdaterService::new(
+            Arc::clone(&db),
+            Arc::new(credentials),
+        ));
+        let editor = ZoneEditorService::new(Arc::clone(&db), Arc::clone(&updater));
+        (db, updater, editor, domain)
+    }
+
+    fn cloudflare_credentials() -> CredentialManager {
+        let credentials = CredentialManager::memory();
+        credentials
+            .store_credentials(
+                "cloudflare",
+                &crate::models::Credentials {
+                    provider_id: crate::models::ProviderType::Cloudflare,
+                    api_key: Some("token".to_string()),
+                    api_secret: None,
+                    access_key: None,
+                    region: None,
+                    extra: Default::default(),
+                },
+            )
+            .unwrap();
+        credentials
+    }
+
+    fn record(name: &str, record_type: &str, content: &str) -> CreateZoneRecord {
+        CreateZoneRecord {
+            name: name.to_string(),
+            record_type: record_type.to_string(),
+            content: content.to_string(),
+            ttl: None,
+            proxied: None,
+            priority: None,
+            comment: None,
+        }
+    }
+
+    #[test]
+    fn test_validate_name() {
+        for name in ["@", "www", "*.dev", "_sip._tcp", "a-b.c"] {
+            assert!(validate_name(name).is_ok(), "{}", name);
+        }
+        for name in ["", "www..a", "dev.*", "-www", "www ", "例子"] {
+            assert!(validate_name(name).is_err(), "{}", name);
+        }
+    }
+
+    #[test]
+    fn test_validate_record() {
+        let check = |record_type: &str, content: &str, spec: &RecordSpec| {
+            validate_record(&record_type.parse().unwrap(), content, spec, cloudflare())
+        };
+        let none = RecordSpec::default();
+
+        assert!(check("A", "203.0.113.1", &none).is_ok());
+        assert!(check("A", "2001:db8::1", &none).is_err());
+        assert!(check("AAAA", "2001:db8::1", &none).is_ok());
+        assert!(check("CNAME", "target.example.com.", &none).is_ok());
+        assert!(check("CNAME", "https://example.com", &none).is_err());
+        assert!(check("MX", "10 mail.example.com", &none).is_ok());
+        assert!(check("MX", "mail.example.com", &priority(5)).is_ok());
+        assert!(check("MX", "0 .", &none).is_ok());
+        assert!(check("MX", "ten mail.example.com", &none).is_err());
+        assert!(check("SRV", "10 5 5060 sip.example.com", &none).is_ok());
+        assert!(check("SRV", "5060 sip.example.com", &none).is_err());
+        assert!(check("CAA", "0 issue letsencrypt.org", &none).is_ok());
+        assert!(check("CAA", "0 is-sue letsencrypt.org", &none).is_err());
+        assert!(check("CAA", "issue letsencrypt.org", &none).is_err());
+        assert!(check("TXT", "v=spf1 -all", &none).is_ok());
+        assert!(check("TXT", "  ", &none).is_err());
+
+        // 优先级只适用于 MX/SRV, SOA 只读, TTL 必须大于 0
+        assert!(check("A", "203.0.113.1", &priority(10)).is_err());
+        assert!(check("SOA", "ns1.example.com. admin 1 2 3 4 5", &none).is_err());
+        let zero_ttl = RecordSpec {
+            ttl: Some(0),
+            ..Default::default()
+        };
+        assert!(check("A", "203.0.113.1", &zero_ttl).is_err());
+
+        // 提供商不支持的选项
+        let porkbun = registry::find("porkbun").unwrap();
+        let proxied = RecordSpec {
+            proxied: Some(true),
+            ..Default::default()
+        };
+        assert!(check("A", "203.0.113.1", &proxied).is_ok());
+        assert!(validate_record(&DNSRecordType::A, "203.0.113.1", &proxied, porkbun).is_err());
+    }
+
+    #[test]
+    fn test_change_summary() {
+        let change = RecordChange {
+            action: ZoneAction::Update,
+            record_id: Some("1".to_string()),
+            name: "example.com".to_string(),
+            record_type: Some(DNSRecordType::MX),
+            old: Some("10 mail.example.com".to_string()),
+            new: Some("20 mail.example.com".to_string()),
+        };
+        assert_eq!(
+            change.summary(),
+            "更新 example.com 的 MX 记录: 10 mail.example.com -> 20 mail.example.com"
+        );
+
+        // 未读取到记录时只能给出记录 ID
+        let change = RecordChange {
+            action: ZoneAction::Delete,
+            record_id: Some("7".to_string()),
+            name: "example.com".to_string(),
+            record_type: None,
+            old: None,
+            new: None,
+        };
+        assert_eq!(change.summary(), "删除 example.com 中的记录 7");
+    }
+
+    #[tokio::test]
+    async fn test_failed_create_is_audited() {
+        let (db, editor, domain) = setup().await;
+
+        let result = editor
+            .create_record(&domain.id, record("@", "MX", "10 mail.example.com"))
+            .await;
+        assert!(matches!(result, Err(AppError::CredentialStore(_))));
+
+        let logs = db.get_logs(None, 10, 0).await.unwrap();
+        assert_eq!(logs.len(), 1);
+        assert_eq!(logs[0].level, "error");
+        assert!(logs[0]
+            .message
+            .starts_with("创建 example.com 的 MX 记录: 10 mail.example.com, 失败: "));
+
+        let context: serde_json::Value =
+            serde_json::from_str(logs[0].context.as_deref().unwrap()).unwrap();
+        assert_eq!(context["domain_id"], domain.id.as_str());
+        assert_eq!(context["action"], "create");
+        assert_eq!(context["record_type"], "MX");
+        assert_eq!(context["new"], "10 mail.example.com");
+        assert!(context["old"].is_null());
+    }
+
+    #[tokio::test]
+    async fn test_edits_use_cached_provider() {
+        use crate::providers::test_support::{record, FakeProvider};
+
+        let (db, updater, editor, domain) = setup_with(cloudflare_credentials()).await;
+
+        // 提供商已由更新服务初始化, 编辑记录时直接复用
+        let provider = Arc::new(FakeProvider::new(vec![
+            record("1", "example.com", DNSRecordType::TXT, "a"),
+            record("2", "example.com", DNSRecordType::TXT, "b"),
+        ]));
+        updater.use_provider(&domain, provider.clone());
+
+        let deleted = editor.delete_record(&domain.id, "2").await.unwrap();
+        assert_eq!(deleted.content, "b");
+        let ids: Vec<_> = provider.records().into_iter().map(|r| r.id).collect();
+        assert_eq!(ids, vec!["1"]);
+
+        let logs = db.get_logs(None, 10, 0).await.unwrap();
+        assert_eq!(logs[0].message, "删除 example.com 的 TXT 记录: b");
+    }
+
+    #[tokio::test]
+    async fn test_failed_connect_is_audited() {
+        // 没有凭证, 连接提供商失败
+        let (db, editor, domain) = setup().await;
+
+        let update = UpdateZoneRecord {
+            content: "203.0.113.2".to_string(),
+            ttl: None,
+            proxied: None,
+            priority: None,
+            comment: None,
+        };
+        let result = editor.update_record(&domain.id, "1", update).await;
+        assert!(matches!(result, Err(AppError::CredentialStore(_))));
+        let result = editor.delete_record(&domain.id, "1").await;
+        assert!(matches!(result, Err(AppError::CredentialStore(_))));
+
+        let logs = db.get_logs(None, 10, 0).await.unwrap();
+        let messages: Vec<_> = logs.iter().map(|l| l.message.as_str()).collect();
+        assert_eq!(logs.len(), 2, "{:?}", messages);
+        assert!(logs.iter().all(|l| l.level == "error"));
+        assert!(messages
+            .iter()
+            .any(|m| m.starts_with("更新 example.com 中的记录 1: 203.0.113.2, 失败: ")));
+        assert!(messages
+            .iter()
+            .any(|m| m.starts_with("删除 example.com 中的记录 1, 失败: ")));
+    }
+
+    #[tokio::test]
+    async fn test_failed_lookup_is_audited_and_releases_provider() {
+        use crate::providers::test_support::{record, FakeProvider};
+
+        let (db, updater, editor, domain) = setup_with(cloudflare_credentials()).await;
+        let provider = Arc::new(FakeProvider::new(vec![record(
+            "1",
+            "example.com",
+            DNSRecordType::TXT,
+            "a",
+        )]));
+        updater.use_provider(&domain, provider.clone());
+
+        // 记录不存在: 写入审计日志, 保留缓存的提供商
+        let update = UpdateZoneRecord {
+            content: "b".to_string(),
+            ttl: None,
+            proxied: None,
+            priority: None,
+            comment: None,
+        };
+        let result = editor.update_record(&domain.id, "9", update).await;
+        assert!(matches!(
+            result,
+            Err(AppError::Provider(ProviderError::RecordNotFound(_)))
+        ));
+        let result = editor.delete_record(&domain.id, "9").await;
+        assert!(matches!(
+            result,
+            Err(AppError::Provider(ProviderError::RecordNotFound(_)))
+        ));
+        assert!(updater.has_provider(&domain.id));
+
+        // 提供商出错: 写入审计日志, 丢弃缓存的提供商
+        provider.fail_next("connection reset");
+        let result = editor.delete_record(&domain.id, "1").await;
+        assert!(matches!(result, Err(AppError::Provider(_))));
+        assert!(!updater.has_provider(&domain.id));
+        assert_eq!(provider.records().len(), 1);
+
+        let logs = db.get_logs(None, 10, 0).await.unwrap();
+        assert_eq!(logs.len(), 3);
+        assert!(logs.iter().all(|l| l.level == "error"));
+        assert!(logs.iter().any(|l| l
+            .message
+            .starts_with("更新 example.com 中的记录 9: b, 失败: ")));
+        assert!(logs.iter().any(|l| l.message.contains("connection reset")));
+    }
+
+    #[tokio::test]
+    async fn test_invalid_record_is_rejected_before_provider() {
+        let (db, editor, domain) = setup().await;
+
+        // 校验失败时不请求提供商, 也不写入审计日志
+        for (name, record_type, content) in [
+            ("www", "A", "not-an-ip"),
+            ("bad name", "A", "203.0.113.1"),
+            ("@", "SOA", "ns1.example.com. admin 1 2 3 4 5"),
+        ] {
+            let result = editor
+                .create_record(&domain.id, record(name, record_type, content))
+                .await;
+            assert!(matches!(result, Err(AppError::Validation(_))), "{}", name);
+        }
+        assert!(db.get_logs(None, 10, 0).await.unwrap().is_empty());
+    }
+}
@@ -1,6 +1,7 @@
 use crate::error::{AppError, Result};
 use crate::models::{CreateDomain, Domain, LogEntry, LogLevel, UpdateDomain, UpdateHistory};
 use sqlx::{Row, SqlitePool};
+use std::path::Path;
 
 /// 数据库管理器
 pub struct Database {
@@ -20,10 +21,53 @@ impl Database {
         Ok(db)
     }
 
+    /// 打开 (必要时创建) 指定路径的数据库文件
+    pub async fn open(path: &Path) -> Result<Self> {
+        // 确保数据库目录存在
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+
+        // SQLx SQLite 需要使用 file:// URI 格式并指定 mode=rwc
+        // mode=rwc: read-write-create mode
+        let database_url = format!(
+            "file://{}?mode=rwc",
+            path.to_str()
+                .ok_or_else(|| AppError::Custom("无效的数据库路径".to_string()))?
+        );
+        tracing::info!("使用数据库: {}", database_url);
+
+        Self::new(&database_url).await
+    }
+
     /// 运行数据库迁移
+    ///
+    /// 初始迁移可重复执行; 之后的迁移按版本号顺序执行一次, 版本记录在 `PRAGMA user_version` 中
     async fn migrate(&self) -> Result<()> {
         let migration_sql = include_str!("../../migrations/001_initial.sql");
         sqlx::query(migration_sql).execute(&self.pool).await?;
+
+        let migrations: &[(i64, &str)] = &[
+            (2, include_str!("../../migrations/002_dual_stack.sql")),
+            (3, include_str!("../../migrations/003_record_options.sql")),
+        ];
+
+        let version: i64 = sqlx::query_scalar("PRAGMA user_version")
+            .fetch_one(&self.pool)
+            .await?;
+        for (target, sql) in migrations {
+            if *target <= version {
+                continue;
+            }
+            let mut tx = self.pool.begin().await?;
+            sqlx::query(sql).execute(&mut *tx).await?;
+            sqlx::query(&format!("PRAGMA user_version = {}", target))
+                .execute(&mut *tx)
+                .await?;
+            tx.commit().await?;
+            tracing::info!("数据库已迁移到版本 {}", target);
+        }
+
         tracing::info!("数据库迁移完成");
         Ok(())
     }
@@ -41,7 +85,8 @@ impl Database {
             r#"
             SELECT id, name, provider, subdomain, record_type,
                    current_ip, last_updated, update_interval, enabled,
-                   created_at, updated_at
+                   dual_stack, remove_aaaa_on_ipv6_loss, current_ipv4, current_ipv6,
+                   ttl, proxied, comment, created_at, updated_at
             FROM domains
             ORDER BY created_at DESC
             "#,
@@ -58,7 +103,8 @@ impl Database {
             r#"
             SELECT id, name, provider, subdomain, record_type,
                    current_ip, last_updated, update_interval, enabled,
-                   created_at, updated_at
+                   dual_stack, remove_aaaa_on_ipv6_loss, current_ipv4, current_ipv6,
+                   ttl, proxied, comment, created_at, updated_at
             FROM domains
             WHERE id = ?1
             "#,
@@ -73,14 +119,17 @@ impl Database {
 
     /// 创建新域名
     pub async fn create_domain(&self, create: CreateDomain) -> Result<Domain> {
-        let domain = Domain::new(create);
+        let mut domain = Domain::new(create);
+        domain.validate()?;
 
         sqlx::query(
             r#"
             INSERT INTO domains (id, name, provider, subdomain, record_type,
                                current_ip, update_interval, enabled,
+                               dual_stack, remove_aaaa_on_ipv6_loss,
+                               ttl, proxied, comment,
                                created_at, updated_at)
-            VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10)
+            VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, ?14, ?15)
             "#,
         )
         .bind(&domain.id)
@@ -91,6 +140,11 @@ impl Database {
         .bind(&domain.current_ip)
         .bind(domain.update_interval)
         .bind(domain.enabled)
+        .bind(domain.dual_stack)
+        .bind(domain.remove_aaaa_on_ipv6_loss)
+        .bind(domain.ttl)
+        .bind(domain.proxied)
+        .bind(&domain.comment)
         .bind(domain.created_at)
         .bind(domain.updated_at)
         .execute(&self.pool)
@@ -103,33 +157,28 @@ impl Database {
     /// 更新域名
     pub async fn update_domain(&self, id: &str, update: UpdateDomain) -> Result<Domain> {
         let mut domain = self.get_domain(id).await?;
-
-        if let Some(name) = update.name {
-            domain.name = name;
-        }
-        if let Some(subdomain) = update.subdomain {
-            domain.subdomain = subdomain;
-        }
-        if let Some(interval) = update.update_interval {
-            domain.update_interval = interval;
-        }
-        if let Some(enabled) = update.enabled {
-            domain.enabled = enabled;
-        }
-        domain.updated_at = chrono::Utc::now().timestamp();
+        domain.apply(update);
+        domain.validate()?;
 
         sqlx::query(
             r#"
             UPDATE domains
             SET name = ?1, subdomain = ?2, update_interval = ?3,
-                enabled = ?4, updated_at = ?5
-            WHERE id = ?6
+                enabled = ?4, dual_stack = ?5, remove_aaaa_on_ipv6_loss = ?6,
+                ttl = ?7, proxied = ?8, comment = ?9,
+                updated_at = ?10
+            WHERE id = ?11
             "#,
         )
         .bind(&domain.name)
         .bind(&domain.subdomain)
         .bind(domain.update_interval)
         .bind(domain.enabled)
+        .bind(domain.dual_stack)
+        .bind(domain.remove_aaaa_on_ipv6_loss)
+        .bind(domain.ttl)
+        .bind(domain.proxied)
+        .bind(&domain.comment)
         .bind(domain.updated_at)
         .bind(id)
         .execute(&self.pool)
@@ -158,7 +207,45 @@ impl Database {
     pub async fn update_domain_ip(&self, id: &str, new_ip: &str) -> Result<()> {
         let now = chrono::Utc::now().timestamp();
 
-        sqlx::query("UPDATE domains SET current_ip = ?1, last_updated = ?2 WHERE id = ?3")
+        sqlx::query(
+            r#"
+            UPDATE domains
+            SET current_ip = ?1,
+                current_ipv4 = CASE WHEN record_type = 'A' THEN ?1 ELSE current_ipv4 END,
+                current_ipv6 = CASE WHEN record_type = 'AAAA' THEN ?1 ELSE current_ipv6 END,
+                last_updated = ?2
+            WHERE id = ?3
+            "#,
+        )
+        .bind(new_ip)
+        .bind(now)
+        .bind(id)
+        .execute(&self.pool)
+        .await?;
+
+        Ok(())
+    }
+
+    /// 更新双栈域名某一地址族的当前 IP (`None` 表示记录已删除)
+    ///
+    /// `current_ip` 保持为 IPv4 地址, 没有 IPv4 时使用 IPv6 地址
+    pub async fn update_domain_family_ip(
+        &self,
+        id: &str,
+        ipv6: bool,
+        new_ip: Option<&str>,
+    ) -> Result<()> {
+        let now = chrono::Utc::now().timestamp();
+        // SET 中的表达式读取的是更新前的值
+        let sql = if ipv6 {
+            "UPDATE domains SET current_ipv6 = ?1, current_ip = COALESCE(current_ipv4, ?1),
+                    last_updated = ?2 WHERE id = ?3"
+        } else {
+            "UPDATE domains SET current_ipv4 = ?1, current_ip = COALESCE(?1, current_ipv6),
+                    last_updated = ?2 WHERE id = ?3"
+        };
+
+        sqlx::query(sql)
             .bind(new_ip)
             .bind(now)
             .bind(id)
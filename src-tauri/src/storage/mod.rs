@@ -1,5 +1,6 @@
 pub mod config_store;
 pub mod database;
 pub mod secure_store;
+pub mod yaml_config;
 
 pub use database::*;
@@ -1,6 +1,25 @@
 use crate::error::{AppError, Result};
 use crate::models::Credentials;
+use base64::Engine;
+use ring::aead::{Aad, LessSafeKey, Nonce, UnboundKey, AES_256_GCM, NONCE_LEN};
+use ring::rand::{SecureRandom, SystemRandom};
+use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
+use std::num::NonZeroU32;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+
+/// 凭证文件口令环境变量
+pub const PASSPHRASE_ENV: &str = "DDNS_CREDENTIAL_PASSPHRASE";
+
+/// 凭证文件口令所在文件的环境变量 (例如 Docker secrets)
+pub const PASSPHRASE_FILE_ENV: &str = "DDNS_CREDENTIAL_PASSPHRASE_FILE";
+
+/// PBKDF2 迭代次数
+const PBKDF2_ITERATIONS: u32 = 100_000;
+
+/// 加密附加数据
+const CREDENTIALS_AAD: &[u8] = b"ddns-credentials-v1";
 
 /// 凭证存储接口
 pub trait SecureCredentialStore: Send + Sync {
@@ -71,6 +90,227 @@ impl SecureCredentialStore for MemoryCredentialStore {
     }
 }
 
+/// 加密文件凭证存储
+///
+/// 凭证以 AES-256-GCM 加密后写入单个文件, 密钥由口令经 PBKDF2-HMAC-SHA256 派生
+pub struct FileCredentialStore {
+    path: PathBuf,
+    key: LessSafeKey,
+    salt: Vec<u8>,
+    rng: SystemRandom,
+    credentials: Mutex<HashMap<String, Credentials>>,
+}
+
+/// 加密文件格式
+#[derive(Debug, Serialize, Deserialize)]
+struct EncryptedFile {
+    version: u32,
+    salt: String,
+    nonce: String,
+    ciphertext: String,
+}
+
+impl FileCredentialStore {
+    /// 打开加密凭证文件, 文件不存在时在首次写入时创建
+    pub fn open(path: impl Into<PathBuf>, passphrase: &str) -> Result<Self> {
+        let path = path.into();
+        let rng = SystemRandom::new();
+
+        if !path.exists() {
+            let mut salt = vec![0u8; 16];
+            rng.fill(&mut salt)
+                .map_err(|_| AppError::CredentialStore("生成随机盐失败".to_string()))?;
+
+            return Ok(Self {
+                key: derive_key(passphrase, &salt)?,
+                path,
+                salt,
+                rng,
+                credentials: Mutex::new(HashMap::new()),
+            });
+        }
+
+        let content = std::fs::read_to_string(&path)?;
+        let file: EncryptedFile = serde_json::from_str(&content)?;
+        if file.version != 1 {
+            return Err(AppError::CredentialStore(format!(
+                "不支持的凭证文件版本: {}",
+                file.version
+            )));
+        }
+
+        let salt = base64_decode(&file.salt)?;
+        let nonce = base64_decode(&file.nonce)?;
+        let mut data = base64_decode(&file.ciphertext)?;
+        let key = derive_key(passphrase, &salt)?;
+
+        let nonce = Nonce::try_assume_unique_for_key(&nonce)
+            .map_err(|_| AppError::CredentialStore("凭证文件 nonce 无效".to_string()))?;
+        let plaintext = key
+            .open_in_place(nonce, Aad::from(CREDENTIALS_AAD), &mut data)
+            .map_err(|_| {
+                AppError::CredentialStore("凭证文件解密失败, 口令错误或文件已损坏".to_string())
+            })?;
+        let credentials = serde_json::from_slice(plaintext)?;
+
+        Ok(Self {
+            path,
+            key,
+            salt,
+            rng,
+            credentials: Mutex::new(credentials),
+        })
+    }
+
+    /// 在配置目录下打开默认凭证文件
+    ///
+    /// 口令读取 `DDNS_CREDENTIAL_PASSPHRASE` 或 `DDNS_CREDENTIAL_PASSPHRASE_FILE`,
+    /// 两者都未设置时拒绝打开, 不会在磁盘上保存可解密凭证的密钥
+    pub fn open_default(config_dir: &Path) -> Result<Self> {
+        let passphrase = passphrase_from_env()?.ok_or_else(|| {
+            AppError::CredentialStore(format!(
+                "未设置凭证加密口令, 请设置 {} 或 {}",
+                PASSPHRASE_ENV, PASSPHRASE_FILE_ENV
+            ))
+        })?;
+
+        Self::open(config_dir.join("credentials.enc"), &passphrase)
+    }
+
+    /// 加密并写入全部凭证
+    fn persist(&self, credentials: &HashMap<String, Credentials>) -> Result<()> {
+        let mut nonce = [0u8; NONCE_LEN];
+        self.rng
+            .fill(&mut nonce)
+            .map_err(|_| AppError::CredentialStore("生成随机 nonce 失败".to_string()))?;
+
+        let mut data = serde_json::to_vec(credentials)?;
+        self.key
+            .seal_in_place_append_tag(
+                Nonce::assume_unique_for_key(nonce),
+                Aad::from(CREDENTIALS_AAD),
+                &mut data,
+            )
+            .map_err(|_| AppError::CredentialStore("加密凭证失败".to_string()))?;
+
+        let file = EncryptedFile {
+            version: 1,
+            salt: base64_encode(&self.salt),
+            nonce: base64_encode(&nonce),
+            ciphertext: base64_encode(&data),
+        };
+
+        if let Some(parent) = self.path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        // 先写临时文件再重命名, 避免写入中断导致文件损坏
+        let tmp_path = self.path.with_extension("tmp");
+        write_private(&tmp_path, serde_json::to_string_pretty(&file)?.as_bytes())?;
+        std::fs::rename(&tmp_path, &self.path)?;
+
+        Ok(())
+    }
+
+    fn lock(&self) -> Result<std::sync::MutexGuard<'_, HashMap<String, Credentials>>> {
+        self.credentials
+            .lock()
+            .map_err(|e| AppError::CredentialStore(format!("锁定失败: {}", e)))
+    }
+}
+
+impl SecureCredentialStore for FileCredentialStore {
+    fn store_credentials(&self, provider_id: &str, credentials: &Credentials) -> Result<()> {
+        let mut creds = self.lock()?;
+        creds.insert(provider_id.to_string(), credentials.clone());
+        self.persist(&creds)?;
+        tracing::info!("存储凭证: {}", provider_id);
+        Ok(())
+    }
+
+    fn get_credentials(&self, provider_id: &str) -> Result<Option<Credentials>> {
+        Ok(self.lock()?.get(provider_id).cloned())
+    }
+
+    fn delete_credentials(&self, provider_id: &str) -> Result<()> {
+        let mut creds = self.lock()?;
+        if creds.remove(provider_id).is_some() {
+            self.persist(&creds)?;
+            tracing::info!("删除凭证: {}", provider_id);
+        }
+        Ok(())
+    }
+
+    fn list_providers(&self) -> Result<Vec<String>> {
+        Ok(self.lock()?.keys().cloned().collect())
+    }
+}
+
+/// 由口令派生 AES-256 密钥
+fn derive_key(passphrase: &str, salt: &[u8]) -> Result<LessSafeKey> {
+    let mut key = [0u8; 32];
+    ring::pbkdf2::derive(
+        ring::pbkdf2::PBKDF2_HMAC_SHA256,
+        NonZeroU32::new(PBKDF2_ITERATIONS).expect("迭代次数不能为 0"),
+        salt,
+        passphrase.as_bytes(),
+        &mut key,
+    );
+
+    let key = UnboundKey::new(&AES_256_GCM, &key)
+        .map_err(|_| AppError::CredentialStore("创建密钥失败".to_string()))?;
+    Ok(LessSafeKey::new(key))
+}
+
+/// 从环境变量读取凭证加密口令, 未设置时返回 None
+pub fn passphrase_from_env() -> Result<Option<String>> {
+    if let Ok(passphrase) = std::env::var(PASSPHRASE_ENV) {
+        if !passphrase.is_empty() {
+            return Ok(Some(passphrase));
+        }
+    }
+
+    match std::env::var_os(PASSPHRASE_FILE_ENV).filter(|v| !v.is_empty()) {
+        Some(path) => {
+            let passphrase = std::fs::read_to_string(&path).map_err(|e| {
+                AppError::CredentialStore(format!(
+                    "读取口令文件 {} 失败: {}",
+                    Path::new(&path).display(),
+                    e
+                ))
+            })?;
+            let passphrase = passphrase.trim_end_matches(['\r', '\n']);
+            if passphrase.is_empty() {
+                return Err(AppError::CredentialStore("口令文件为空".to_string()));
+            }
+            Ok(Some(passphrase.to_string()))
+        }
+        None => Ok(None),
+    }
+}
+
+/// 写入仅当前用户可读写的文件
+fn write_private(path: &Path, data: &[u8]) -> Result<()> {
+    std::fs::write(path, data)?;
+
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        std::fs::set_permissions(path, std::fs::Permissions::from_mode(0o600))?;
+    }
+
+    Ok(())
+}
+
+fn base64_encode(data: &[u8]) -> String {
+    base64::engine::general_purpose::STANDARD.encode(data)
+}
+
+fn base64_decode(data: &str) -> Result<Vec<u8>> {
+    base64::engine::general_purpose::STANDARD
+        .decode(data)
+        .map_err(|e| AppError::CredentialStore(format!("凭证文件格式错误: {}", e)))
+}
+
 /// 凭证管理器
 pub struct CredentialManager {
     store: Box<dyn SecureCredentialStore>,
@@ -87,8 +327,16 @@ impl CredentialManager {
         Self::new(Box::new(MemoryCredentialStore::new()))
     }
 
+    /// 创建加密文件存储的凭证管理器
+    pub fn file(config_dir: &Path) -> Result<Self> {
+        Ok(Self::new(Box::new(FileCredentialStore::open_default(
+            config_dir,
+        )?)))
+    }
+
     /// 存储凭证
     pub fn store_credentials(&self, provider_id: &str, credentials: &Credentials) -> Result<()> {
+        ensure_provider_matches(provider_id, credentials)?;
         self.store.store_credentials(provider_id, credentials)
     }
 
@@ -125,9 +373,29 @@ impl CredentialManager {
         domain_id: &str,
         credentials: &Credentials,
     ) -> Result<()> {
+        ensure_provider_matches(provider_id, credentials)?;
         let key = format!("{}@{}", provider_id, domain_id);
         self.store.store_credentials(&key, credentials)
     }
+
+    /// 删除域名凭证
+    pub fn delete_credentials_for_domain(&self, provider_id: &str, domain_id: &str) -> Result<()> {
+        let key = format!("{}@{}", provider_id, domain_id);
+        self.store.delete_credentials(&key)
+    }
+}
+
+/// 凭证中的提供商必须与存储键的提供商一致
+fn ensure_provider_matches(provider_id: &str, credentials: &Credentials) -> Result<()> {
+    if credentials.provider_id.as_str() == provider_id {
+        Ok(())
+    } else {
+        Err(AppError::CredentialStore(format!(
+            "凭证属于提供商 {}, 不能保存为 {} 的凭证",
+            credentials.provider_id.as_str(),
+            provider_id
+        )))
+    }
 }
 
 #[cfg(test)]
@@ -160,4 +428,67 @@ mod tests {
         let retrieved = store.get_credentials("test").unwrap();
         assert!(retrieved.is_none());
     }
+
+    #[test]
+    fn test_file_store() {
+        let dir = std::env::temp_dir().join(format!("ddns-test-{}", uuid::Uuid::new_v4()));
+        let path = dir.join("credentials.enc");
+        let credentials = Credentials {
+            provider_id: ProviderType::Cloudflare,
+            api_key: Some("secret_token".to_string()),
+            api_secret: None,
+            access_key: None,
+            region: None,
+            extra: Default::default(),
+        };
+
+        // 存储凭证并确认文件中没有明文
+        let store = FileCredentialStore::open(&path, "passphrase").unwrap();
+        store
+            .store_credentials("cloudflare@1", &credentials)
+            .unwrap();
+        let content = std::fs::read_to_string(&path).unwrap();
+        assert!(!content.contains("secret_token"));
+
+        // 重新打开后可以读取
+        let store = FileCredentialStore::open(&path, "passphrase").unwrap();
+        let retrieved = store.get_credentials("cloudflare@1").unwrap().unwrap();
+        assert_eq!(retrieved.api_key, Some("secret_token".to_string()));
+
+        // 错误的口令无法解密
+        assert!(FileCredentialStore::open(&path, "wrong").is_err());
+
+        // 删除后持久化
+        store.delete_credentials("cloudflare@1").unwrap();
+        let store = FileCredentialStore::open(&path, "passphrase").unwrap();
+        assert!(store.get_credentials("cloudflare@1").unwrap().is_none());
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_manager_rejects_mismatched_provider() {
+        let manager = CredentialManager::memory();
+        let credentials = Credentials {
+            provider_id: ProviderType::Cloudflare,
+            api_key: Some("token".to_string()),
+            api_secret: None,
+            access_key: None,
+            region: None,
+            extra: Default::default(),
+        };
+
+        assert!(manager.store_credentials("aliyun", &credentials).is_err());
+        assert!(manager
+            .store_credentials_for_domain("aliyun", "1", &credentials)
+            .is_err());
+        assert!(manager.list_providers().unwrap().is_empty());
+
+        manager
+            .store_credentials("cloudflare", &credentials)
+            .unwrap();
+        manager
+            .store_credentials_for_domain("cloudflare", "1", &credentials)
+            .unwrap();
+    }
 }
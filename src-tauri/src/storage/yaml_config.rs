@@ -0,0 +1,719 @@
+use crate::error::{AppError, Result};
+use crate::models::{
+    AppConfig, CreateDomain, Credentials, Domain, ProviderType, RecordType, UpdateDomain,
+};
+use crate::providers::{registry, ProviderFactory};
+use crate::storage::secure_store::CredentialManager;
+use crate::storage::Database;
+use serde::Deserialize;
+use std::collections::HashMap;
+use std::path::Path;
+
+/// 支持的 IP 检测方法
+const IP_DETECTION_METHODS: &[&str] = &["auto", "api", "dns", "interface"];
+
+/// 支持的日志级别
+const LOG_LEVELS: &[&str] = &["error", "warn", "info", "debug"];
+
+/// YAML 配置文件 (参见 docker/config.example.yml)
+#[derive(Debug, Clone, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct YamlConfig {
+    #[serde(default)]
+    pub domains: Vec<DomainEntry>,
+    #[serde(default)]
+    pub settings: SettingsSection,
+    #[serde(default)]
+    pub logging: Option<LoggingSection>,
+}
+
+/// 域名配置项
+#[derive(Debug, Clone, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct DomainEntry {
+    pub name: String,
+    pub provider: String,
+    #[serde(default)]
+    pub subdomain: Option<String>,
+    #[serde(default)]
+    pub record_type: Option<String>,
+    #[serde(default)]
+    pub enabled: Option<bool>,
+    #[serde(default)]
+    pub update_interval: Option<i64>,
+    #[serde(default)]
+    pub dual_stack: Option<bool>,
+    #[serde(default)]
+    pub remove_aaaa_on_ipv6_loss: Option<bool>,
+    #[serde(default)]
+    pub ttl: Option<u32>,
+    #[serde(default)]
+    pub proxied: Option<bool>,
+    #[serde(default)]
+    pub comment: Option<String>,
+    #[serde(default)]
+    pub credentials: HashMap<String, String>,
+}
+
+/// 应用设置
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct SettingsSection {
+    pub ip_detection_method: Option<String>,
+    pub default_update_interval: Option<i64>,
+    pub log_level: Option<String>,
+    pub enable_notifications: Option<bool>,
+    pub auto_start: Option<bool>,
+}
+
+/// 日志配置
+#[derive(Debug, Clone, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct LoggingSection {
+    pub level: Option<String>,
+    pub file: Option<String>,
+    pub max_size: Option<String>,
+    pub max_backups: Option<u32>,
+    pub max_age: Option<u32>,
+}
+
+/// 同步结果
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ReconcileSummary {
+    pub created: usize,
+    pub updated: usize,
+    pub disabled: usize,
+}
+
+impl YamlConfig {
+    /// 从文件加载并校验配置
+    pub fn load(path: &Path) -> Result<Self> {
+        let content = std::fs::read_to_string(path)?;
+        Self::parse(&content).map_err(|e| match e {
+            AppError::Validation(msg) => {
+                AppError::Validation(format!("{}: {}", path.display(), msg))
+            }
+            other => other,
+        })
+    }
+
+    /// 解析并校验 YAML 内容
+    pub fn parse(content: &str) -> Result<Self> {
+        let config: YamlConfig = serde_yaml::from_str(content)
+            .map_err(|e| AppError::Validation(format!("配置文件格式错误: {}", e)))?;
+        config.validate()?;
+        Ok(config)
+    }
+
+    /// 校验配置, 错误信息中包含出错的键
+    fn validate(&self) -> Result<()> {
+        let providers = ProviderFactory::list_providers();
+        let mut seen: HashMap<(String, String, String, String), usize> = HashMap::new();
+
+        for (index, entry) in self.domains.iter().enumerate() {
+            let key = |field: &str| format!("domains[{}].{}", index, field);
+
+            if entry.name.trim().is_empty() {
+                return Err(invalid(&key("name"), "不能为空"));
+            }
+            if !providers.contains(&entry.provider.as_str()) {
+                return Err(invalid(
+                    &key("provider"),
+                    &format!(
+                        "未知的提供商 '{}', 可选值: {}",
+                        entry.provider,
+                        providers.join(", ")
+                    ),
+                ));
+            }
+            let record_type: RecordType = entry.record_type().parse().map_err(|e| match e {
+                AppError::Validation(message) => invalid(&key("record_type"), &message),
+                e => e,
+            })?;
+            let dual_stack = entry.dual_stack.unwrap_or(false);
+            if dual_stack && record_type != RecordType::A {
+                return Err(invalid(&key("record_type"), "双栈模式下必须为 A 或省略"));
+            }
+            if entry.remove_aaaa_on_ipv6_loss == Some(true) && !dual_stack {
+                return Err(invalid(
+                    &key("remove_aaaa_on_ipv6_loss"),
+                    "仅在 dual_stack 为 true 时有效",
+                ));
+            }
+            if let Some(interval) = entry.update_interval {
+                if interval <= 0 {
+                    return Err(invalid(&key("update_interval"), "必须大于 0"));
+                }
+            }
+            if entry.ttl == Some(0) {
+                return Err(invalid(&key("ttl"), "必须大于 0"));
+            }
+            if let Some(descriptor) = registry::find(&entry.provider) {
+                let capabilities = &descriptor.capabilities;
+                let unsupported = [
+                    ("ttl", entry.ttl.is_some() && !capabilities.ttl),
+                    (
+                        "proxied",
+                        entry.proxied == Some(true) && !capabilities.proxy,
+                    ),
+                    ("comment", entry.comment.is_some() && !capabilities.comment),
+                ];
+                if let Some((field, _)) = unsupported.iter().find(|(_, unsupported)| *unsupported) {
+                    return Err(invalid(
+                        &key(field),
+                        &format!("提供商 {} 不支持该选项", entry.provider),
+                    ));
+                }
+            }
+            if entry.credentials.is_empty() {
+                return Err(invalid(&key("credentials"), "不能为空"));
+            }
+            if let Some((name, _)) = entry.credentials.iter().find(|(_, v)| v.trim().is_empty()) {
+                return Err(invalid(&key(&format!("credentials.{}", name)), "不能为空"));
+            }
+
+            let identity = entry.identity();
+            if let Some(first) = seen.insert(identity, index) {
+                return Err(invalid(
+                    &format!("domains[{}]", index),
+                    &format!("与 domains[{}] 重复", first),
+                ));
+            }
+        }
+
+        if let Some(method) = &self.settings.ip_detection_method {
+            if !IP_DETECTION_METHODS.contains(&method.as_str()) {
+                return Err(invalid(
+                    "settings.ip_detection_method",
+                    &format!(
+                        "不支持的检测方法 '{}', 可选值: {}",
+                        method,
+                        IP_DETECTION_METHODS.join(", ")
+                    ),
+                ));
+            }
+        }
+        if let Some(interval) = self.settings.default_update_interval {
+            if interval <= 0 {
+                return Err(invalid("settings.default_update_interval", "必须大于 0"));
+            }
+        }
+        if let Some(level) = &self.settings.log_level {
+            validate_log_level("settings.log_level", level)?;
+        }
+
+        if let Some(logging) = &self.logging {
+            if let Some(level) = &logging.level {
+                validate_log_level("logging.level", level)?;
+            }
+            if let Some(size) = &logging.max_size {
+                if parse_size(size).is_none() {
+                    return Err(invalid(
+                        "logging.max_size",
+                        &format!("无效的大小 '{}', 例如 10M", size),
+                    ));
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// 转换为应用配置
+    pub fn app_config(&self) -> AppConfig {
+        let defaults = AppConfig::default();
+        let settings = &self.settings;
+
+        AppConfig {
+            ip_detection_method: settings
+                .ip_detection_method
+                .clone()
+                .unwrap_or(defaults.ip_detection_method),
+            default_update_interval: settings
+                .default_update_interval
+                .unwrap_or(defaults.default_update_interval),
+            log_level: settings
+                .log_level
+                .clone()
+                .or_else(|| self.logging.as_ref().and_then(|l| l.level.clone()))
+                .unwrap_or(defaults.log_level),
+            enable_notifications: settings
+                .enable_notifications
+                .unwrap_or(defaults.enable_notifications),
+            auto_start: settings.auto_start.unwrap_or(defaults.auto_start),
+        }
+    }
+
+    /// 转换为域名创建参数和对应凭证
+    pub fn domains(&self) -> Vec<(CreateDomain, Credentials)> {
+        let default_interval = self.app_config().default_update_interval;
+
+        self.domains
+            .iter()
+            .map(|entry| {
+                let create = CreateDomain {
+                    name: entry.name.trim().to_string(),
+                    provider: entry.provider.clone(),
+                    subdomain: entry.subdomain(),
+                    record_type: entry.record_type(),
+                    update_interval: entry.update_interval.unwrap_or(default_interval),
+                    enabled: entry.enabled.unwrap_or(true),
+                    dual_stack: entry.dual_stack.unwrap_or(false),
+                    remove_aaaa_on_ipv6_loss: entry.remove_aaaa_on_ipv6_loss.unwrap_or(false),
+                    ttl: entry.ttl,
+                    proxied: entry.proxied,
+                    comment: entry.comment.clone(),
+                };
+                (create, entry.credentials())
+            })
+            .collect()
+    }
+
+    /// 将配置文件同步到数据库: 新增条目, 更新变更, 禁用已移除的域名
+    pub async fn reconcile(
+        &self,
+        db: &Database,
+        credential_manager: &CredentialManager,
+    ) -> Result<ReconcileSummary> {
+        let mut summary = ReconcileSummary::default();
+        let mut existing = db.get_domains().await?;
+        let mut matched_ids = Vec::new();
+
+        for (create, credentials) in self.domains() {
+            let position = existing.iter().position(|d| same_domain(d, &create));
+
+            let domain = match position {
+                Some(position) => {
+                    let domain = existing.remove(position);
+                    if domain.update_interval != create.update_interval
+                        || domain.enabled != create.enabled
+                        || domain.dual_stack != create.dual_stack
+                        || domain.remove_aaaa_on_ipv6_loss != create.remove_aaaa_on_ipv6_loss
+                        || domain.ttl != create.ttl
+                        || domain.proxied != create.proxied
+                        || domain.comment != create.comment
+                    {
+                        summary.updated += 1;
+                        db.update_domain(
+                            &domain.id,
+                            UpdateDomain {
+                                name: None,
+                                subdomain: None,
+                                update_interval: Some(create.update_interval),
+                                enabled: Some(create.enabled),
+                                dual_stack: Some(create.dual_stack),
+                                remove_aaaa_on_ipv6_loss: Some(create.remove_aaaa_on_ipv6_loss),
+                                ttl: Some(create.ttl),
+                                proxied: Some(create.proxied),
+                                comment: Some(create.comment),
+                            },
+                        )
+                        .await?
+                    } else {
+                        domain
+                    }
+                }
+                None => {
+                    summary.created += 1;
+                    db.create_domain(create).await?
+                }
+            };
+
+            credential_manager.store_credentials_for_domain(
+                &domain.provider,
+                &domain.id,
+                &credentials,
+            )?;
+            matched_ids.push(domain.id);
+        }
+
+        for domain in existing {
+            if domain.enabled && !matched_ids.contains(&domain.id) {
+                summary.disabled += 1;
+                db.update_domain(
+                    &domain.id,
+                    UpdateDomain {
+                        name: None,
+                        subdomain: None,
+                        update_interval: None,
+                        enabled: Some(false),
+                        dual_stack: None,
+                        remove_aaaa_on_ipv6_loss: None,
+                        ttl: None,
+                        proxied: None,
+                        comment: None,
+                    },
+                )
+                .await?;
+                tracing::info!("配置文件中已移除, 禁用域名: {}", domain.full_domain());
+            }
+        }
+
+        let config = self.app_config();
+        let settings = [
+            ("ip_detection_method", config.ip_detection_method),
+            (
+                "default_update_interval",
+                config.default_update_interval.to_string(),
+            ),
+            ("log_level", config.log_level),
+            (
+                "enable_notifications",
+                config.enable_notifications.to_string(),
+            ),
+            ("auto_start", config.auto_start.to_string()),
+        ];
+        for (key, value) in settings {
+            db.set_setting(key, &value).await?;
+        }
+
+        tracing::info!(
+            "配置文件同步完成: 新增 {}, 更新 {}, 禁用 {}",
+            summary.created,
+            summary.updated,
+            summary.disabled
+        );
+        Ok(summary)
+    }
+}
+
+impl DomainEntry {
+    fn subdomain(&self) -> String {
+        match self.subdomain.as_deref().map(str::trim) {
+            None | Some("") => "@".to_string(),
+            Some(subdomain) => subdomain.to_string(),
+        }
+    }
+
+    fn record_type(&self) -> String {
+        self.record_type
+            .as_deref()
+            .unwrap_or("A")
+            .trim()
+            .to_uppercase()
+    }
+
+    fn identity(&self) -> (String, String, String, String) {
+        (
+            self.provider.clone(),
+            self.name.trim().to_lowercase(),
+            self.subdomain().to_lowercase(),
+            self.record_type(),
+        )
+    }
+
+    /// 转换为凭证, `api_token` 视为 `api_key`, 其余字段放入 extra
+    fn credentials(&self) -> Credentials {
+        let mut extra = self.credentials.clone();
+        let api_key = extra
+            .remove("api_key")
+            .or_else(|| extra.remove("api_token"));
+
+        Credentials {
+            // 提供商已在 validate 中校验
+            provider_id: ProviderType::from_str(&self.provider).unwrap_or(ProviderType::Cloudflare),
+            api_key,
+            api_secret: extra.remove("api_secret"),
+            access_key: extra.remove("access_key"),
+            region: extra.remove("region"),
+            extra,
+        }
+    }
+}
+
+/// 判断数据库中的域名是否对应配置项
+fn same_domain(domain: &Domain, create: &CreateDomain) -> bool {
+    let normalize = |s: &str| match s {
+        "" => "@".to_string(),
+        s => s.to_lowercase(),
+    };
+
+    domain.provider == create.provider
+        && domain.name.eq_ignore_ascii_case(&create.name)
+        && normalize(&domain.subdomain) == normalize(&create.subdomain)
+        && domain.record_type.eq_ignore_ascii_case(&create.record_type)
+}
+
+fn invalid(key: &str, message: &str) -> AppError {
+    AppError::Validation(format!("{}: {}", key, message))
+}
+
+fn validate_log_level(key: &str, level: &str) -> Result<()> {
+    if LOG_LEVELS.contains(&level) {
+        Ok(())
+    } else {
+        Err(invalid(
+            key,
+            &format!(
+                "不支持的日志级别 '{}', 可选值: {}",
+                level,
+                LOG_LEVELS.join(", ")
+            ),
+        ))
+    }
+}
+
+/// 解析大小字符串 (例如 10M, 512K, 1G), 返回字节数
+fn parse_size(size: &str) -> Option<u64> {
+    let size = size.trim().to_uppercase();
+    let (number, unit) = match size.find(|c: char| !c.is_ascii_digit()) {
+        Some(index) => size.split_at(index),
+        None => (size.as_str(), ""),
+    };
+    let number: u64 = number.parse().ok()?;
+    let multiplier = match unit.trim_end_matches('B') {
+        "" => 1,
+        "K" => 1024,
+        "M" => 1024 * 1024,
+        "G" => 1024 * 1024 * 1024,
+        _ => return None,
+    };
+    number.checked_mul(multiplier)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn error_message(content: &str) -> String {
+        match YamlConfig::parse(content) {
+            Err(AppError::Validation(msg)) => msg,
+            other => panic!("应该返回验证错误: {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_parse_example_config() {
+        let config = YamlConfig::parse(include_str!("../../../docker/config.example.yml")).unwrap();
+
+        let domains = config.domains();
+        assert_eq!(domains.len(), 1);
+        let (create, credentials) = &domains[0];
+        assert_eq!(create.name, "example.com");
+        assert_eq!(create.provider, "cloudflare");
+        assert_eq!(create.subdomain, "www");
+        assert_eq!(create.update_interval, 300);
+        assert_eq!(
+            credentials.api_key.as_deref(),
+            Some("your-cloudflare-api-token")
+        );
+
+        let app_config = config.app_config();
+        assert_eq!(app_config.ip_detection_method, "auto");
+        assert!(!app_config.enable_notifications);
+    }
+
+    #[test]
+    fn test_validation_names_key() {
+        let msg = error_message(
+            r#"
+domains:
+  - name: example.com
+    provider: cloudflare
+    credentials: { api_token: x }
+  - name: example.com
+    provider: godaddy
+    credentials: { api_token: x }
+"#,
+        );
+        assert!(msg.starts_with("domains[1].provider"), "{}", msg);
+
+        let msg = error_message(
+            r#"
+domains:
+  - name: example.com
+    provider: aliyun
+    record_type: MX
+    credentials: { access_key_id: x, access_key_secret: y }
+"#,
+        );
+        assert!(msg.starts_with("domains[0].record_type"), "{}", msg);
+
+        let msg = error_message(
+            r#"
+domains:
+  - name: example.com
+    provider: cloudflare
+    remove_aaaa_on_ipv6_loss: true
+    credentials: { api_token: x }
+"#,
+        );
+        assert!(
+            msg.starts_with("domains[0].remove_aaaa_on_ipv6_loss"),
+            "{}",
+            msg
+        );
+
+        let msg = error_message(
+            r#"
+domains:
+  - name: example.com
+    provider: aliyun
+    proxied: true
+    credentials: { access_key_id: x, access_key_secret: y }
+"#,
+        );
+        assert!(msg.starts_with("domains[0].proxied"), "{}", msg);
+
+        let msg = error_message(
+            r#"
+domains:
+  - name: example.com
+    provider: cloudflare
+    ttl: 0
+    credentials: { api_token: x }
+"#,
+        );
+        assert!(msg.starts_with("domains[0].ttl"), "{}", msg);
+
+        let msg = error_message("logging:\n  max_size: 99999999999G\n");
+        assert!(msg.starts_with("logging.max_size"), "{}", msg);
+
+        let msg = error_message("settings:\n  log_level: verbose\n");
+        assert!(msg.starts_with("settings.log_level"), "{}", msg);
+
+        let msg = error_message("settings:\n  log_levle: info\n");
+        assert!(msg.contains("log_levle"), "{}", msg);
+    }
+
+    #[test]
+    fn test_aliyun_credentials_go_to_extra() {
+        let config = YamlConfig::parse(
+            r#"
+domains:
+  - name: example.com
+    provider: aliyun
+    subdomain: home
+    credentials:
+      access_key_id: id
+      access_key_secret: secret
+"#,
+        )
+        .unwrap();
+
+        let (_, credentials) = &config.domains()[0];
+        let provider_credentials = credentials.to_provider_credentials();
+        assert_eq!(provider_credentials.extra["access_key_id"], "id");
+        assert_eq!(provider_credentials.extra["access_key_secret"], "secret");
+    }
+
+    #[tokio::test]
+    async fn test_aliyun_region_reaches_provider() {
+        use crate::providers::aliyun::AliyunProvider;
+        use crate::providers::DNSProvider;
+        use wiremock::matchers::{method, query_param};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let db = Database::new("sqlite::memory:").await.unwrap();
+        let credentials = CredentialManager::memory();
+        let config = YamlConfig::parse(
+            r#"
+domains:
+  - name: example.com
+    provider: aliyun
+    subdomain: home
+    credentials:
+      access_key_id: id
+      access_key_secret: secret
+      region: cn-shanghai
+"#,
+        )
+        .unwrap();
+        config.reconcile(&db, &credentials).await.unwrap();
+
+        // 写入凭证存储后再读出, 与调度器使用的凭证一致
+        let domain = db.get_domains().await.unwrap().remove(0);
+        let stored =
+            crate::services::dns_updater::domain_credentials(&credentials, &domain).unwrap();
+        assert_eq!(stored.region.as_deref(), Some("cn-shanghai"));
+
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(query_param("Action", "DescribeDomains"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "RequestId": "req",
+                "TotalCount": 0
+            })))
+            .mount(&server)
+            .await;
+        let mut provider = AliyunProvider::with_endpoint(server.uri());
+        provider.initialize(&stored).await.unwrap();
+        assert_eq!(provider.region(), Some("cn-shanghai"));
+    }
+
+    #[tokio::test]
+    async fn test_reconcile() {
+        let db = Database::new("sqlite::memory:").await.unwrap();
+        let credentials = CredentialManager::memory();
+
+        let removed = db
+            .create_domain(CreateDomain {
+                name: "old.org".to_string(),
+                provider: "cloudflare".to_string(),
+                subdomain: "@".to_string(),
+                record_type: "A".to_string(),
+                update_interval: 300,
+                enabled: true,
+                dual_stack: false,
+                remove_aaaa_on_ipv6_loss: false,
+                ttl: None,
+                proxied: None,
+                comment: None,
+            })
+            .await
+            .unwrap();
+
+        let config = YamlConfig::parse(
+            r#"
+domains:
+  - name: example.com
+    provider: cloudflare
+    subdomain: www
+    credentials: { api_token: token }
+"#,
+        )
+        .unwrap();
+        let summary = config.reconcile(&db, &credentials).await.unwrap();
+        assert_eq!(
+            summary,
+            ReconcileSummary {
+                created: 1,
+                updated: 0,
+                disabled: 1
+            }
+        );
+        assert!(!db.get_domain(&removed.id).await.unwrap().enabled);
+
+        let config = YamlConfig::parse(
+            r#"
+domains:
+  - name: example.com
+    provider: cloudflare
+    subdomain: www
+    update_interval: 60
+    ttl: 120
+    proxied: true
+    credentials: { api_token: token }
+"#,
+        )
+        .unwrap();
+        let summary = config.reconcile(&db, &credentials).await.unwrap();
+        assert_eq!(summary.created, 0);
+        assert_eq!(summary.updated, 1);
+
+        let domain = db
+            .get_domains()
+            .await
+            .unwrap()
+            .into_iter()
+            .find(|d| d.name == "example.com")
+            .unwrap();
+        assert_eq!(domain.update_interval, 60);
+        assert_eq!(domain.ttl, Some(120));
+        assert_eq!(domain.proxied, Some(true));
+        assert!(credentials
+            .get_credentials_for_domain("cloudflare", &domain.id)
+            .unwrap()
+            .is_some());
+    }
+}
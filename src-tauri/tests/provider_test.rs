@@ -22,6 +22,24 @@ fn test_list_providers() {
     assert!(providers.contains(&"aliyun"));
     assert!(providers.contains(&"tencent"));
     assert!(providers.contains(&"aws"));
+    assert!(providers.contains(&"huawei"));
+    assert!(providers.contains(&"baidu"));
+    assert!(providers.contains(&"jdcloud"));
+    assert!(providers.contains(&"rfc2136"));
+    assert!(providers.contains(&"webhook"));
+    assert!(providers.contains(&"dyndns2"));
+    assert!(providers.contains(&"duckdns"));
+    assert!(providers.contains(&"dynu"));
+    assert!(providers.contains(&"freedns"));
+    assert!(providers.contains(&"gandi"));
+    assert!(providers.contains(&"digitalocean"));
+    assert!(providers.contains(&"linode"));
+    assert!(providers.contains(&"hetzner"));
+    assert!(providers.contains(&"porkbun"));
+    assert!(providers.contains(&"namecheap"));
+    assert!(providers.contains(&"namecom"));
+    assert!(providers.contains(&"azure"));
+    assert!(providers.contains(&"gcp"));
 }
 
 #[test]
@@ -86,6 +104,144 @@ fn test_aws_provider_creation() {
     assert!(provider.provider_name().contains("AWS"));
 }
 
+#[test]
+fn test_huawei_provider_creation() {
+    use ddns_lib::providers::huawei::HuaweiProvider;
+
+    let provider = HuaweiProvider::new();
+    assert_eq!(provider.provider_id(), "huawei");
+    assert!(provider.provider_name().contains("华为"));
+}
+
+#[test]
+fn test_baidu_provider_creation() {
+    use ddns_lib::providers::baidu::BaiduProvider;
+
+    let provider = BaiduProvider::new();
+    assert_eq!(provider.provider_id(), "baidu");
+    assert!(provider.provider_name().contains("百度"));
+}
+
+#[test]
+fn test_jdcloud_provider_creation() {
+    use ddns_lib::providers::jdcloud::JdcloudProvider;
+
+    let provider = JdcloudProvider::new();
+    assert_eq!(provider.provider_id(), "jdcloud");
+    assert!(provider.provider_name().contains("京东"));
+}
+
+#[test]
+fn test_webhook_provider_creation() {
+    use ddns_lib::providers::webhook::WebhookProvider;
+
+    let provider = WebhookProvider::new();
+    assert_eq!(provider.provider_id(), "webhook");
+    assert!(provider.provider_name().contains("Webhook"));
+}
+
+#[test]
+fn test_dyndns2_provider_creation() {
+    use ddns_lib::providers::dyndns2::DynDns2Provider;
+
+    let provider = DynDns2Provider::new();
+    assert_eq!(provider.provider_id(), "dyndns2");
+    assert!(provider.provider_name().contains("DynDNS2"));
+}
+
+#[test]
+fn test_free_ddns_providers_record_types() {
+    use ddns_lib::providers::duckdns::DuckDnsProvider;
+    use ddns_lib::providers::dynu::DynuProvider;
+    use ddns_lib::providers::freedns::FreeDnsProvider;
+
+    let duckdns = DuckDnsProvider::new();
+    assert_eq!(duckdns.provider_id(), "duckdns");
+    assert!(duckdns
+        .supported_record_types()
+        .contains(&DNSRecordType::TXT));
+
+    let dynu = DynuProvider::new();
+    assert_eq!(dynu.provider_id(), "dynu");
+    assert!(!dynu
+        .supported_record_types()
+        .contains(&DNSRecordType::CNAME));
+
+    let freedns = FreeDnsProvider::new();
+    assert_eq!(freedns.provider_id(), "freedns");
+    assert_eq!(
+        freedns.supported_record_types(),
+        vec![DNSRecordType::A, DNSRecordType::AAAA]
+    );
+}
+
+#[test]
+fn test_bearer_token_providers_in_factory() {
+    for (id, name) in [
+        ("gandi", "Gandi"),
+        ("digitalocean", "DigitalOcean"),
+        ("linode", "Linode"),
+        ("hetzner", "Hetzner"),
+    ] {
+        let provider = ProviderFactory::create(id).unwrap();
+        assert_eq!(provider.provider_id(), id);
+        assert!(provider.provider_name().contains(name));
+        assert!(provider
+            .supported_record_types()
+            .contains(&DNSRecordType::AAAA));
+    }
+}
+
+#[test]
+fn test_registrar_providers_in_factory() {
+    for (id, name) in [
+        ("porkbun", "Porkbun"),
+        ("namecheap", "Namecheap"),
+        ("namecom", "Name.com"),
+    ] {
+        let provider = ProviderFactory::create(id).unwrap();
+        assert_eq!(provider.provider_id(), id);
+        assert_eq!(provider.provider_name(), name);
+        assert!(provider
+            .supported_record_types()
+            .contains(&DNSRecordType::MX));
+    }
+}
+
+#[tokio::test]
+async fn test_cloud_providers_require_credentials() {
+    for (id, name) in [("azure", "Azure DNS"), ("gcp", "Google Cloud DNS")] {
+        let mut provider = ProviderFactory::create(id).unwrap();
+        assert_eq!(provider.provider_id(), id);
+        assert_eq!(provider.provider_name(), name);
+
+        // 缺少凭证时在请求令牌之前报错
+        let result = provider.initialize(&Credentials::default()).await;
+        assert!(result.is_err());
+    }
+}
+
+#[test]
+fn test_provider_descriptors() {
+    use ddns_lib::models::ProviderType;
+
+    let descriptors = ProviderFactory::descriptors();
+    assert_eq!(descriptors.len(), ProviderFactory::list_providers().len());
+
+    for descriptor in descriptors {
+        // 每个注册的提供商都能在配置中引用
+        let provider_type = ProviderType::from_str(descriptor.id).unwrap();
+        assert_eq!(provider_type.as_str(), descriptor.id);
+        assert!(ProviderFactory::create(descriptor.id).is_ok());
+    }
+
+    let aliyun = descriptors.iter().find(|d| d.id == "aliyun").unwrap();
+    let required: Vec<_> = aliyun.required_fields().map(|f| f.key).collect();
+    assert_eq!(required, vec!["access_key_id", "access_key_secret"]);
+    assert!(aliyun.capabilities.line);
+    assert!(!aliyun.capabilities.proxy);
+}
+
 #[tokio::test]
 async fn test_provider_initialization() {
     use ddns_lib::providers::cloudflare::CloudflareProvider;
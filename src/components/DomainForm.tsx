@@ -1,5 +1,5 @@
 import { useState, useEffect } from 'react';
-import { Domain, ProviderType } from '../types';
+import { Domain, ProviderDescriptor, ProviderType } from '../types';
 import { api } from '../lib/api';
 
 interface DomainFormProps {
@@ -19,6 +19,11 @@ export function DomainForm({ domain, open, onClose, onSuccess }: DomainFormProps
     record_type: 'A' | 'AAAA' | 'CNAME';
     update_interval: number;
     enabled: boolean;
+    dual_stack: boolean;
+    remove_aaaa_on_ipv6_loss: boolean;
+    ttl: string;
+    proxied: boolean;
+    comment: string;
   }>({
     name: '',
     provider: 'cloudflare',
@@ -26,68 +31,32 @@ export function DomainForm({ domain, open, onClose, onSuccess }: DomainFormProps
     record_type: 'A',
     update_interval: 300,
     enabled: true,
+    dual_stack: false,
+    remove_aaaa_on_ipv6_loss: false,
+    ttl: '',
+    proxied: false,
+    comment: '',
   });
 
   const [credentials, setCredentials] = useState<Record<string, string>>({});
   const [loading, setLoading] = useState(false);
   const [error, setError] = useState<string | null>(null);
 
-  // 提供商配置字段
-  const providerFields: Record<
-    ProviderType,
-    { name: string; fields: Array<{ key: string; label: string; type?: string }> }
-  > = {
-    cloudflare: {
-      name: 'Cloudflare',
-      fields: [
-        { key: 'api_token', label: 'API Token' },
-        { key: 'account_email', label: 'Account Email (可选)' },
-      ],
-    },
-    aliyun: {
-      name: '阿里云',
-      fields: [
-        { key: 'access_key_id', label: 'Access Key ID' },
-        { key: 'access_key_secret', label: 'Access Key Secret' },
-      ],
-    },
-    tencent: {
-      name: '腾讯云',
-      fields: [
-        { key: 'secret_id', label: 'Secret ID' },
-        { key: 'secret_key', label: 'Secret Key' },
-      ],
-    },
-    aws: {
-      name: 'AWS Route53',
-      fields: [
-        { key: 'access_key_id', label: 'Access Key ID' },
-        { key: 'secret_access_key', label: 'Secret Access Key' },
-        { key: 'region', label: 'Region (如: us-east-1)' },
-      ],
-    },
-    huawei: {
-      name: '华为云',
-      fields: [
-        { key: 'access_key', label: 'Access Key' },
-        { key: 'secret_key', label: 'Secret Key' },
-      ],
-    },
-    baidu: {
-      name: '百度云',
-      fields: [
-        { key: 'access_key_id', label: 'Access Key ID' },
-        { key: 'secret_access_key', label: 'Secret Access Key' },
-      ],
-    },
-    jdcloud: {
-      name: '京东云',
-      fields: [
-        { key: 'access_key', label: 'Access Key' },
-        { key: 'secret_key', label: 'Secret Key' },
-      ],
-    },
-  };
+  // 提供商描述 (名称与凭证字段由后端注册表提供)
+  const [descriptors, setDescriptors] = useState<ProviderDescriptor[]>([]);
+
+  useEffect(() => {
+    if (!open || descriptors.length > 0) return;
+    api
+      .listProviderDescriptors()
+      .then(setDescriptors)
+      .catch((err) => {
+        console.error('Failed to load providers:', err);
+        setError(String(err));
+      });
+  }, [open, descriptors.length]);
+
+  const descriptor = descriptors.find((d) => d.id === formData.provider);
 
   useEffect(() => {
     if (domain) {
@@ -98,6 +67,11 @@ export function DomainForm({ domain, open, onClose, onSuccess }: DomainFormProps
         record_type: domain.record_type,
         update_interval: domain.update_interval,
         enabled: domain.enabled,
+        dual_stack: domain.dual_stack,
+        remove_aaaa_on_ipv6_loss: domain.remove_aaaa_on_ipv6_loss,
+        ttl: domain.ttl?.toString() ?? '',
+        proxied: domain.proxied ?? false,
+        comment: domain.comment ?? '',
       });
     } else {
       setFormData({
@@ -107,6 +81,11 @@ export function DomainForm({ domain, open, onClose, onSuccess }: DomainFormProps
         record_type: 'A',
         update_interval: 300,
         enabled: true,
+        dual_stack: false,
+        remove_aaaa_on_ipv6_loss: false,
+        ttl: '',
+        proxied: false,
+        comment: '',
       });
     }
     setCredentials({});
@@ -127,18 +106,28 @@ export function DomainForm({ domain, open, onClose, onSuccess }: DomainFormProps
         throw new Error('请输入子域名');
       }
 
-      const fields = providerFields[formData.provider];
-      const missingFields = fields.fields.filter((field) => !credentials[field.key]?.trim());
+      const missingFields = (descriptor?.credential_fields ?? []).filter(
+        (field) => field.required && !credentials[field.key]?.trim()
+      );
 
       if (missingFields.length > 0) {
         throw new Error(`请填写 ${missingFields.map((f) => f.label).join(', ')}`);
       }
 
-      // 准备域名数据
+      const capabilities = descriptor?.capabilities;
+      const ttl = formData.ttl.trim() ? parseInt(formData.ttl) : null;
+      if (ttl !== null && !(ttl > 0)) {
+        throw new Error('TTL 必须为正整数');
+      }
+
+      // 准备域名数据, 提供商不支持的记录选项提交为 null (使用默认值)
       const domainData = {
         ...formData,
         name: formData.name.trim(),
         subdomain: formData.subdomain.trim(),
+        ttl: capabilities?.ttl ? ttl : null,
+        proxied: capabilities?.proxy ? formData.proxied : null,
+        comment: capabilities?.comment && formData.comment.trim() ? formData.comment.trim() : null,
       };
 
       if (isEdit) {
@@ -232,9 +221,9 @@ export function DomainForm({ domain, open, onClose, onSuccess }: DomainFormProps
                     className="w-full px-3 py-2 border border-gray-300 rounded-md focus:outline-none focus:ring-2 focus:ring-blue-500"
                     disabled={loading}
                   >
-                    {Object.entries(providerFields).map(([key, { name }]) => (
-                      <option key={key} value={key}>
-                        {name}
+                    {descriptors.map((d) => (
+                      <option key={d.id} value={d.id}>
+                        {d.name}
                       </option>
                     ))}
                   </select>
@@ -253,7 +242,7 @@ export function DomainForm({ domain, open, onClose, onSuccess }: DomainFormProps
                       })
                     }
                     className="w-full px-3 py-2 border border-gray-300 rounded-md focus:outline-none focus:ring-2 focus:ring-blue-500"
-                    disabled={loading}
+                    disabled={loading || formData.dual_stack}
                   >
                     <option value="A">A (IPv4)</option>
                     <option value="AAAA">AAAA (IPv6)</option>
@@ -295,31 +284,129 @@ export function DomainForm({ domain, open, onClose, onSuccess }: DomainFormProps
                 </div>
               </div>
 
-              {/* 提供商凭证 */}
-              <div className="border-t pt-4">
-                <h3 className="text-sm font-semibold text-gray-900 mb-3">
-                  {providerFields[formData.provider].name} 凭证
-                </h3>
-                <div className="space-y-3">
-                  {providerFields[formData.provider].fields.map((field) => (
-                    <div key={field.key}>
+              <div className="grid grid-cols-2 gap-4">
+                <label className="flex items-center cursor-pointer">
+                  <input
+                    type="checkbox"
+                    checked={formData.dual_stack}
+                    onChange={(e) =>
+                      setFormData({
+                        ...formData,
+                        dual_stack: e.target.checked,
+                        record_type: e.target.checked ? 'A' : formData.record_type,
+                        remove_aaaa_on_ipv6_loss: e.target.checked && formData.remove_aaaa_on_ipv6_loss,
+                      })
+                    }
+                    className="w-4 h-4 text-blue-600 border-gray-300 rounded focus:ring-blue-500"
+                    disabled={loading}
+                  />
+                  <span className="ml-2 text-sm font-medium text-gray-700">双栈 (同时更新 A 和 AAAA)</span>
+                </label>
+
+                <label className="flex items-center cursor-pointer">
+                  <input
+                    type="checkbox"
+                    checked={formData.remove_aaaa_on_ipv6_loss}
+                    onChange={(e) => setFormData({ ...formData, remove_aaaa_on_ipv6_loss: e.target.checked })}
+                    className="w-4 h-4 text-blue-600 border-gray-300 rounded focus:ring-blue-500"
+                    disabled={loading || !formData.dual_stack}
+                  />
+                  <span className="ml-2 text-sm font-medium text-gray-700">IPv6 不可用时删除 AAAA 记录</span>
+                </label>
+              </div>
+
+              {/* 记录选项 (按提供商能力显示) */}
+              {descriptor && (descriptor.capabilities.ttl || descriptor.capabilities.proxy || descriptor.capabilities.comment) && (
+                <div className="grid grid-cols-2 gap-4">
+                  {descriptor.capabilities.ttl && (
+                    <div>
                       <label className="block text-sm font-medium text-gray-700 mb-1">
-                        {field.label}{' '}
-                        {!field.label.includes('可选') && <span className="text-red-500">*</span>}
+                        TTL (秒) <span className="text-gray-400">(可选)</span>
                       </label>
                       <input
-                        type={field.type || 'text'}
-                        value={credentials[field.key] || ''}
-                        onChange={(e) =>
-                          setCredentials({ ...credentials, [field.key]: e.target.value })
-                        }
+                        type="number"
+                        min="1"
+                        value={formData.ttl}
+                        onChange={(e) => setFormData({ ...formData, ttl: e.target.value })}
+                        placeholder="提供商默认"
                         className="w-full px-3 py-2 border border-gray-300 rounded-md focus:outline-none focus:ring-2 focus:ring-blue-500"
                         disabled={loading}
                       />
                     </div>
-                  ))}
+                  )}
+
+                  {descriptor.capabilities.comment && (
+                    <div>
+                      <label className="block text-sm font-medium text-gray-700 mb-1">
+                        备注 <span className="text-gray-400">(可选)</span>
+                      </label>
+                      <input
+                        type="text"
+                        value={formData.comment}
+                        onChange={(e) => setFormData({ ...formData, comment: e.target.value })}
+                        className="w-full px-3 py-2 border border-gray-300 rounded-md focus:outline-none focus:ring-2 focus:ring-blue-500"
+                        disabled={loading}
+                      />
+                    </div>
+                  )}
+
+                  {descriptor.capabilities.proxy && (
+                    <label className="flex items-center cursor-pointer">
+                      <input
+                        type="checkbox"
+                        checked={formData.proxied}
+                        onChange={(e) => setFormData({ ...formData, proxied: e.target.checked })}
+                        className="w-4 h-4 text-blue-600 border-gray-300 rounded focus:ring-blue-500"
+                        disabled={loading}
+                      />
+                      <span className="ml-2 text-sm font-medium text-gray-700">开启代理 (橙色云)</span>
+                    </label>
+                  )}
                 </div>
-              </div>
+              )}
+
+              {/* 提供商凭证 */}
+              {descriptor && (
+                <div className="border-t pt-4">
+                  <h3 className="text-sm font-semibold text-gray-900 mb-3">{descriptor.name} 凭证</h3>
+                  <div className="space-y-3">
+                    {descriptor.credential_fields.map((field) => (
+                      <div key={field.key}>
+                        <label className="block text-sm font-medium text-gray-700 mb-1">
+                          {field.label}{' '}
+                          {field.required ? (
+                            <span className="text-red-500">*</span>
+                          ) : (
+                            <span className="text-gray-400">(可选)</span>
+                          )}
+                        </label>
+                        {field.type === 'textarea' ? (
+                          <textarea
+                            rows={4}
+                            value={credentials[field.key] || ''}
+                            onChange={(e) =>
+                              setCredentials({ ...credentials, [field.key]: e.target.value })
+                            }
+                            className="w-full px-3 py-2 border border-gray-300 rounded-md font-mono text-xs focus:outline-none focus:ring-2 focus:ring-blue-500"
+                            disabled={loading}
+                          />
+                        ) : (
+                          <input
+                            type={field.type}
+                            value={credentials[field.key] || ''}
+                            onChange={(e) =>
+                              setCredentials({ ...credentials, [field.key]: e.target.value })
+                            }
+                            className="w-full px-3 py-2 border border-gray-300 rounded-md focus:outline-none focus:ring-2 focus:ring-blue-500"
+                            disabled={loading}
+                          />
+                        )}
+                        {field.help && <p className="text-xs text-gray-500 mt-1">{field.help}</p>}
+                      </div>
+                    ))}
+                  </div>
+                </div>
+              )}
             </div>
 
             <div className="flex justify-end gap-3 mt-6 pt-4 border-t">
@@ -67,6 +67,21 @@ export function DomainList({ domains, onDomainsChange, onEdit }: DomainListProps
       huawei: 'bg-red-100 text-red-800',
       baidu: 'bg-purple-100 text-purple-800',
       jdcloud: 'bg-pink-100 text-pink-800',
+      rfc2136: 'bg-gray-100 text-gray-800',
+      webhook: 'bg-teal-100 text-teal-800',
+      dyndns2: 'bg-indigo-100 text-indigo-800',
+      duckdns: 'bg-amber-100 text-amber-800',
+      dynu: 'bg-sky-100 text-sky-800',
+      freedns: 'bg-lime-100 text-lime-800',
+      gandi: 'bg-emerald-100 text-emerald-800',
+      digitalocean: 'bg-blue-100 text-blue-800',
+      linode: 'bg-green-100 text-green-800',
+      hetzner: 'bg-red-100 text-red-800',
+      porkbun: 'bg-pink-100 text-pink-800',
+      namecheap: 'bg-orange-100 text-orange-800',
+      namecom: 'bg-sky-100 text-sky-800',
+      azure: 'bg-blue-100 text-blue-800',
+      gcp: 'bg-yellow-100 text-yellow-800',
     };
 
     const names: Record<string, string> = {
@@ -77,6 +92,21 @@ export function DomainList({ domains, onDomainsChange, onEdit }: DomainListProps
       huawei: '华为云',
       baidu: '百度云',
       jdcloud: '京东云',
+      rfc2136: 'RFC 2136',
+      webhook: 'Webhook',
+      dyndns2: 'DynDNS2',
+      duckdns: 'DuckDNS',
+      dynu: 'Dynu',
+      freedns: 'FreeDNS',
+      gandi: 'Gandi',
+      digitalocean: 'DigitalOcean',
+      linode: 'Linode',
+      hetzner: 'Hetzner',
+      porkbun: 'Porkbun',
+      namecheap: 'Namecheap',
+      namecom: 'Name.com',
+      azure: 'Azure DNS',
+      gcp: 'Google Cloud DNS',
     };
 
     return (
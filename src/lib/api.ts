@@ -1,5 +1,16 @@
 import { invoke } from '@tauri-apps/api/core';
-import type { Domain, DomainInput, IPInfo, LogEntry } from '../types';
+import type {
+  DNSRecord,
+  Domain,
+  DomainInput,
+  IPInfo,
+  LogEntry,
+  ProviderCredentials,
+  ProviderDescriptor,
+  UpdateResult,
+  ZoneRecordInput,
+  ZoneRecordUpdate,
+} from '../types';
 
 // Tauri IPC 命令封装
 export const api = {
@@ -10,6 +21,28 @@ export const api = {
     invoke('update_domain', { id, updates }),
   deleteDomain: (id: string): Promise<void> => invoke('delete_domain', { id }),
 
+  // 凭证相关命令 (传入 domainId 时为域名专属凭证)
+  setCredentials: (providerId: string, credentials: ProviderCredentials, domainId?: string): Promise<void> =>
+    invoke('set_credentials', { providerId, domainId, credentials }),
+  deleteCredentials: (providerId: string, domainId?: string): Promise<void> =>
+    invoke('delete_credentials', { providerId, domainId }),
+  hasCredentials: (providerId: string, domainId?: string): Promise<boolean> =>
+    invoke('has_credentials', { providerId, domainId }),
+  testCredentials: (providerId: string, credentials?: ProviderCredentials, domainId?: string): Promise<boolean> =>
+    invoke('test_credentials', { providerId, domainId, credentials }),
+
+  // 提供商相关命令
+  listProviderDescriptors: (): Promise<ProviderDescriptor[]> => invoke('list_provider_descriptors'),
+
+  // 区域记录相关命令 (修改会写入审计日志)
+  listZoneRecords: (domainId: string): Promise<DNSRecord[]> => invoke('list_zone_records', { domainId }),
+  createZoneRecord: (domainId: string, record: ZoneRecordInput): Promise<DNSRecord> =>
+    invoke('create_zone_record', { domainId, record }),
+  updateZoneRecord: (domainId: string, recordId: string, record: ZoneRecordUpdate): Promise<UpdateResult> =>
+    invoke('update_zone_record', { domainId, recordId, record }),
+  deleteZoneRecord: (domainId: string, recordId: string): Promise<DNSRecord> =>
+    invoke('delete_zone_record', { domainId, recordId }),
+
   // IP 检测相关命令
   detectIP: (method?: string): Promise<IPInfo> => invoke('detect_ip', { method }),
   detectIPv4: (): Promise<IPInfo> => invoke('detect_ip', { method: 'ipv4' }),
@@ -9,6 +9,13 @@ export interface Domain {
   last_updated: string | null;
   update_interval: number;
   enabled: boolean;
+  dual_stack: boolean; // 同时维护 A 和 AAAA 记录
+  remove_aaaa_on_ipv6_loss: boolean; // IPv6 不可用时删除 AAAA 记录
+  current_ipv4: string | null;
+  current_ipv6: string | null;
+  ttl: number | null; // 记录 TTL (秒), null 表示使用提供商默认值
+  proxied: boolean | null; // Cloudflare 代理
+  comment: string | null; // 记录备注
   created_at: string;
   updated_at: string;
 }
@@ -22,7 +29,7 @@ export function getFullDomain(domain: { name: string; subdomain: string }): stri
 }
 
 // DNS 提供商类型
-export type ProviderType = 'aliyun' | 'cloudflare' | 'tencent' | 'aws' | 'huawei' | 'baidu' | 'jdcloud';
+export type ProviderType = 'aliyun' | 'cloudflare' | 'tencent' | 'aws' | 'huawei' | 'baidu' | 'jdcloud' | 'rfc2136' | 'webhook' | 'dyndns2' | 'duckdns' | 'dynu' | 'freedns' | 'gandi' | 'digitalocean' | 'linode' | 'hetzner' | 'porkbun' | 'namecheap' | 'namecom' | 'azure' | 'gcp';
 
 // 提供商凭证
 export interface ProviderCredentials {
@@ -34,6 +41,70 @@ export interface ProviderCredentials {
   extra?: Record<string, string>;
 }
 
+// 提供商凭证字段 (来自后端注册表)
+export interface CredentialField {
+  key: string;
+  label: string;
+  type: 'text' | 'password' | 'textarea';
+  required: boolean;
+  help: string | null;
+}
+
+// 提供商能力
+export interface ProviderCapabilities {
+  proxy: boolean;
+  ttl: boolean;
+  line: boolean;
+  priority: boolean;
+  comment: boolean;
+}
+
+// 提供商描述
+export interface ProviderDescriptor {
+  id: ProviderType;
+  name: string;
+  credential_fields: CredentialField[];
+  record_types: string[];
+  capabilities: ProviderCapabilities;
+}
+
+// 区域中的 DNS 记录 (类型可能是 SRV、CAA 或 PTR 等任意类型)
+export interface DNSRecord {
+  id: string;
+  name: string; // 完整名称
+  record_type: string;
+  content: string; // MX/SRV 只包含目标主机
+  ttl: number;
+  proxied: boolean | null;
+  priority: number | null; // MX/SRV 优先级
+  weight: number | null; // SRV 权重
+  port: number | null; // SRV 端口
+  comment: string | null;
+}
+
+// 创建区域记录的输入, content 为区域文件格式 (如 `10 5 5060 sip.example.com`)
+export interface ZoneRecordInput {
+  name: string; // 相对名称, 根域名为 @
+  record_type: string;
+  content: string;
+  ttl?: number | null;
+  proxied?: boolean | null;
+  priority?: number | null;
+  comment?: string | null;
+}
+
+// 更新区域记录的输入, 名称和类型不可修改
+export type ZoneRecordUpdate = Omit<ZoneRecordInput, 'name' | 'record_type'>;
+
+// DNS 更新结果
+export interface UpdateResult {
+  success: boolean;
+  record_id: string;
+  old_ip: string;
+  new_ip: string;
+  message: string;
+}
+
 // IP 信息
 export interface IPInfo {
   ipv4: string | null;
@@ -71,4 +142,7 @@ export type ThemeMode = 'light' | 'dark' | 'auto';
 export type AppliedTheme = 'light' | 'dark';
 
 // 域名输入类型(用于创建/编辑)
-export type DomainInput = Omit<Domain, 'id' | 'current_ip' | 'last_updated' | 'created_at' | 'updated_at'>;
+export type DomainInput = Omit<
+  Domain,
+  'id' | 'current_ip' | 'current_ipv4' | 'current_ipv6' | 'last_updated' | 'created_at' | 'updated_at'
+>;